@@ -20,6 +20,8 @@ pub enum AIProvider {
     OpenAI {
         api_key: String,
         organization: Option<String>,
+        project: Option<String>,
+        extra_headers: Vec<(String, String)>,
     },
     /// Anthropic Claude API (Claude 3.5 Sonnet, Opus, Haiku)
     Anthropic {
@@ -49,6 +51,16 @@ pub enum AIProvider {
         base_url: String,
         api_key: Option<String>,
     },
+    /// A user-declared OpenAI-compatible gateway, loaded from `custom_providers.json` at startup
+    /// (see [`crate::provider_registry`]) rather than hardcoded here
+    Custom {
+        name: String,
+        base_url: String,
+        api_key: Option<String>,
+        auth_style: crate::provider_registry::AuthStyle,
+        extra_headers: Vec<(String, String)>,
+        omit_stream_field: bool,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,12 +70,44 @@ pub struct AIRequest {
     pub temperature: Option<f32>,
     pub max_tokens: Option<i32>,
     pub stream: bool,
+    /// Tools the model may call, mapped to each provider's own wire format by the respective
+    /// `send_*_request` function. Ignored by providers that don't implement tool calling below
+    /// (currently only OpenAI, Anthropic, and Gemini do).
+    #[serde(default)]
+    pub tools: Vec<ToolDefinition>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
     pub role: String,
     pub content: String,
+    /// ID of the tool call this message reports the result of. Only set on `role: "tool"`
+    /// messages - `send_anthropic_request` and `send_gemini_request` translate it into those
+    /// providers' own tool-result shapes; the rest pass it through as an ordinary field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+    /// Whether this message is pinned (see `crate::services::ConversationService::pin_message`),
+    /// so [`crate::tokenizer::fit_messages_to_context`] keeps it over unpinned history when
+    /// trimming to fit the model's context window. Never sent to the provider.
+    #[serde(default, skip_serializing)]
+    pub pinned: bool,
+}
+
+/// A function the model may call, in OpenAI's JSON-Schema function-calling shape - the other
+/// supported providers' own tool formats are mapped from this one in their `send_*_request` fn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolDefinition {
+    pub name: String,
+    pub description: String,
+    pub parameters: serde_json::Value,
+}
+
+/// A single tool invocation the model requested, parsed back out of the provider's response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCall {
+    pub id: String,
+    pub name: String,
+    pub arguments: serde_json::Value,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +115,285 @@ pub struct AIResponse {
     pub content: String,
     pub model: String,
     pub tokens_used: Option<i32>,
+    /// Rate-limit and retry headers captured from the provider's HTTP response, for debugging
+    /// throttling without reproducing the request. Empty for streaming responses and for
+    /// providers (e.g. Ollama) that don't send rate-limit headers.
+    #[serde(default)]
+    pub response_headers: std::collections::HashMap<String, String>,
+    /// Tools the model asked to invoke instead of (or alongside) replying in `content`. Empty
+    /// for providers that don't implement tool calling, and whenever the model didn't call one.
+    #[serde(default)]
+    pub tool_calls: Vec<ToolCall>,
+}
+
+/// Header name prefixes/names worth capturing for rate-limit debugging, covering the
+/// conventions used by OpenAI/Azure (`x-ratelimit-*`), Anthropic (`anthropic-ratelimit-*`),
+/// and the generic `retry-after` header
+const CAPTURED_HEADER_PATTERNS: &[&str] = &["ratelimit", "retry-after", "x-request-id"];
+
+/// Pull rate-limit/debugging headers out of a provider HTTP response before its body is consumed
+fn capture_response_headers(response: &reqwest::Response) -> std::collections::HashMap<String, String> {
+    response
+        .headers()
+        .iter()
+        .filter(|(name, _)| {
+            let name = name.as_str().to_lowercase();
+            CAPTURED_HEADER_PATTERNS.iter().any(|pattern| name.contains(pattern))
+        })
+        .filter_map(|(name, value)| {
+            value.to_str().ok().map(|v| (name.as_str().to_string(), v.to_string()))
+        })
+        .collect()
+}
+
+/// Map `tools` to OpenAI's `tools` request array (also used by Gemini-style callers that mimic
+/// the OpenAI function-calling shape)
+fn openai_tools_json(tools: &[ToolDefinition]) -> serde_json::Value {
+    serde_json::json!(tools
+        .iter()
+        .map(|t| serde_json::json!({
+            "type": "function",
+            "function": {
+                "name": t.name,
+                "description": t.description,
+                "parameters": t.parameters,
+            }
+        }))
+        .collect::<Vec<_>>())
+}
+
+/// Parse an OpenAI (or OpenAI-compatible) `message.tool_calls` array into [`ToolCall`]s,
+/// decoding each call's JSON-encoded `arguments` string back into a [`serde_json::Value`]
+fn parse_openai_tool_calls(message: &serde_json::Value) -> Vec<ToolCall> {
+    message["tool_calls"]
+        .as_array()
+        .map(|calls| {
+            calls
+                .iter()
+                .filter_map(|call| {
+                    let id = call["id"].as_str()?.to_string();
+                    let name = call["function"]["name"].as_str()?.to_string();
+                    let arguments = call["function"]["arguments"]
+                        .as_str()
+                        .and_then(|raw| serde_json::from_str(raw).ok())
+                        .unwrap_or(serde_json::Value::Null);
+                    Some(ToolCall { id, name, arguments })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Retry behavior for a single [`AIProvider::send_request_with_retry`] call.
+///
+/// The default is a single attempt (no retry), so existing callers of
+/// [`AIProvider::send_request`] keep today's fail-fast behavior - retries are opt-in.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first. `1` disables retrying.
+    pub max_attempts: u32,
+    /// Delay before the second attempt, doubled after each further failure (capped at
+    /// `max_backoff_ms`) unless the provider sends a `Retry-After` header.
+    pub initial_backoff_ms: u64,
+    /// Upper bound on the exponential backoff between attempts
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 8_000,
+        }
+    }
+}
+
+/// Send `req_builder`, retrying on connect/timeout errors and on 429/5xx responses per `policy`.
+///
+/// A `Retry-After` header (seconds) on a 429/5xx response takes priority over the computed
+/// backoff. `req_builder` must be clone-able (i.e. built with [`reqwest::RequestBuilder::json`]
+/// or another in-memory body, not a streaming body) since each attempt needs its own clone.
+async fn send_with_retry(
+    policy: &RetryPolicy,
+    req_builder: reqwest::RequestBuilder,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let attempts = policy.max_attempts.max(1);
+    let mut backoff_ms = policy.initial_backoff_ms;
+
+    for attempt in 1..=attempts {
+        let builder = req_builder
+            .try_clone()
+            .expect("retryable request bodies must be clone-able");
+
+        match builder.send().await {
+            Ok(response) => {
+                let status = response.status();
+                let retryable = status.as_u16() == 429 || status.is_server_error();
+                if !retryable || attempt == attempts {
+                    return Ok(response);
+                }
+                let wait_ms = response
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .map(|secs| secs.saturating_mul(1000))
+                    .unwrap_or(backoff_ms);
+                tokio::time::sleep(Duration::from_millis(wait_ms)).await;
+                backoff_ms = (backoff_ms * 2).min(policy.max_backoff_ms);
+            }
+            Err(e) => {
+                if attempt == attempts || !(e.is_timeout() || e.is_connect()) {
+                    return Err(e);
+                }
+                tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+                backoff_ms = (backoff_ms * 2).min(policy.max_backoff_ms);
+            }
+        }
+    }
+
+    unreachable!("loop always returns by the final attempt")
+}
+
+/// How much detail to log about outgoing AI requests
+///
+/// Message content can contain sensitive user data, so verbose logging is opt-in even in
+/// development - it must be requested explicitly via `AI_LOG_VERBOSITY`, not just implied
+/// by running outside production.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiLogVerbosity {
+    /// Log nothing beyond what already happens at the HTTP layer
+    Minimal,
+    /// Log provider, model, and message count (default)
+    Standard,
+    /// Also log message content - useful when debugging prompt construction locally
+    Verbose,
+}
+
+impl AiLogVerbosity {
+    /// Read verbosity from the `AI_LOG_VERBOSITY` environment variable
+    /// (`minimal` | `standard` | `verbose`), defaulting to `Standard`.
+    pub fn from_env() -> Self {
+        match std::env::var("AI_LOG_VERBOSITY").unwrap_or_default().to_lowercase().as_str() {
+            "minimal" => AiLogVerbosity::Minimal,
+            "verbose" => AiLogVerbosity::Verbose,
+            _ => AiLogVerbosity::Standard,
+        }
+    }
+}
+
+fn log_outgoing_request(provider_label: &str, request: &AIRequest) {
+    match AiLogVerbosity::from_env() {
+        AiLogVerbosity::Minimal => {}
+        AiLogVerbosity::Standard => {
+            tracing::info!(
+                "AI request: provider={} model={} messages={}",
+                provider_label,
+                request.model,
+                request.messages.len()
+            );
+        }
+        AiLogVerbosity::Verbose => {
+            tracing::debug!(
+                "AI request: provider={} model={} messages={:?}",
+                provider_label,
+                request.model,
+                request.messages
+            );
+        }
+    }
+}
+
+/// In-memory + on-disk cache of AI responses, keyed by (provider, model, messages, temperature),
+/// so repeated identical prompts - title generation and summarization are the common case -
+/// don't re-bill tokens. Opt-in via `send_ai_provider_request`'s `use_cache` flag; entries older
+/// than [`response_cache::RESPONSE_CACHE_TTL`] are treated as misses.
+pub mod response_cache {
+    use super::{AIRequest, AIResponse};
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::{Mutex, OnceLock};
+
+    /// How long a cached response stays valid before a repeat request is treated as a cache miss
+    pub const RESPONSE_CACHE_TTL: chrono::Duration = chrono::Duration::hours(1);
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct CachedResponse {
+        response: AIResponse,
+        cached_at: DateTime<Utc>,
+    }
+
+    fn cache() -> &'static Mutex<HashMap<String, CachedResponse>> {
+        static CACHE: OnceLock<Mutex<HashMap<String, CachedResponse>>> = OnceLock::new();
+        CACHE.get_or_init(|| Mutex::new(load_from_disk()))
+    }
+
+    fn cache_file_path() -> Option<PathBuf> {
+        crate::platform::get_app_data_dir().map(|dir| dir.join("ai_response_cache.json"))
+    }
+
+    fn load_from_disk() -> HashMap<String, CachedResponse> {
+        cache_file_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_to_disk(entries: &HashMap<String, CachedResponse>) {
+        if let Some(path) = cache_file_path() {
+            if let Ok(json) = serde_json::to_string(entries) {
+                let _ = std::fs::write(path, json);
+            }
+        }
+    }
+
+    /// Normalize `provider_label`, `request.model`, `request.temperature`, and each message's
+    /// role/content/tool_call_id into one cache key, so the same conversation sent twice hashes
+    /// the same regardless of incidental differences like trailing whitespace
+    fn cache_key(provider_label: &str, request: &AIRequest) -> String {
+        let mut key = format!("{}\u{1}{}\u{1}{:?}", provider_label, request.model, request.temperature);
+        for message in &request.messages {
+            key.push('\u{1}');
+            key.push_str(&message.role);
+            key.push('\u{1}');
+            key.push_str(message.content.trim());
+            if let Some(id) = &message.tool_call_id {
+                key.push('\u{1}');
+                key.push_str(id);
+            }
+        }
+        key
+    }
+
+    /// Look up a cached response for `request`, evicting (and treating as a miss) an entry
+    /// older than [`RESPONSE_CACHE_TTL`]
+    pub fn get(provider_label: &str, request: &AIRequest) -> Option<AIResponse> {
+        let key = cache_key(provider_label, request);
+        let mut entries = cache().lock().unwrap();
+        match entries.get(&key) {
+            Some(entry) if Utc::now() - entry.cached_at < RESPONSE_CACHE_TTL => Some(entry.response.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Store `response` for `request`, persisting the updated cache to disk so it survives a
+    /// restart
+    pub fn put(provider_label: &str, request: &AIRequest, response: &AIResponse) {
+        let key = cache_key(provider_label, request);
+        let mut entries = cache().lock().unwrap();
+        entries.insert(
+            key,
+            CachedResponse { response: response.clone(), cached_at: Utc::now() },
+        );
+        save_to_disk(&entries);
+    }
 }
 
 impl AIProvider {
@@ -79,6 +402,24 @@ impl AIProvider {
         AIProvider::OpenAI {
             api_key,
             organization,
+            project: None,
+            extra_headers: Vec::new(),
+        }
+    }
+
+    /// Create a new OpenAI provider with project scoping and arbitrary extra headers applied
+    /// to every outgoing request (e.g. for cost-attribution setups with project-scoped keys)
+    pub fn openai_with_headers(
+        api_key: String,
+        organization: Option<String>,
+        project: Option<String>,
+        extra_headers: Vec<(String, String)>,
+    ) -> Self {
+        AIProvider::OpenAI {
+            api_key,
+            organization,
+            project,
+            extra_headers,
         }
     }
 
@@ -128,20 +469,85 @@ impl AIProvider {
         AIProvider::OpenAICompatible { base_url, api_key }
     }
 
-    /// Send a request to the AI provider
+    /// Create a provider from a declarative definition loaded via
+    /// [`crate::provider_registry::load_custom_providers`]. The definition file itself holds no
+    /// secrets, so the API key (if the gateway needs one) is supplied separately, the same way
+    /// it's supplied for every other provider.
+    pub fn from_custom_definition(
+        def: crate::provider_registry::CustomProviderDefinition,
+        api_key: Option<String>,
+    ) -> Self {
+        AIProvider::Custom {
+            name: def.name,
+            base_url: def.base_url,
+            api_key,
+            auth_style: def.auth_style,
+            extra_headers: def.extra_headers,
+            omit_stream_field: def.omit_stream_field,
+        }
+    }
+
+    /// Short identifier for this provider, used for logging only
+    fn provider_label(&self) -> String {
+        match self {
+            AIProvider::OpenAI { .. } => "openai".to_string(),
+            AIProvider::Anthropic { .. } => "anthropic".to_string(),
+            AIProvider::GoogleGemini { .. } => "google_gemini".to_string(),
+            AIProvider::AzureOpenAI { .. } => "azure_openai".to_string(),
+            AIProvider::LMStudio { .. } => "lm_studio".to_string(),
+            AIProvider::Ollama { .. } => "ollama".to_string(),
+            AIProvider::OpenAICompatible { .. } => "openai_compatible".to_string(),
+            AIProvider::Custom { name, .. } => format!("custom:{}", name),
+        }
+    }
+
+    /// Enforce this provider's configured [`crate::models::RateLimits`] (if any) against one
+    /// request estimated to cost `request.messages`' token count, consuming budget from it.
+    /// Providers with no stored limits - including every local one, which has no `api_configs`
+    /// row at all - are unlimited and this always succeeds.
+    fn check_rate_limit(&self, request: &AIRequest) -> AppResult<()> {
+        let estimated_tokens = crate::tokenizer::estimate_prompt_tokens(&request.messages);
+        crate::ratelimit::check_and_consume(&self.provider_label(), estimated_tokens)
+    }
+
+    /// Send a request to the AI provider, failing immediately on any error (no retry)
     pub async fn send_request(&self, request: AIRequest) -> AppResult<AIResponse> {
+        self.send_request_with_retry(request, &RetryPolicy::default())
+            .await
+    }
+
+    /// Send a request to the AI provider, retrying per `policy` on connection failures and on
+    /// 429/5xx responses
+    pub async fn send_request_with_retry(
+        &self,
+        request: AIRequest,
+        policy: &RetryPolicy,
+    ) -> AppResult<AIResponse> {
+        log_outgoing_request(&self.provider_label(), &request);
+        self.check_rate_limit(&request)?;
+
         match self {
             AIProvider::OpenAI {
                 api_key,
                 organization,
+                project,
+                extra_headers,
             } => {
-                Self::send_openai_request(api_key, organization.clone(), request).await
+                Self::send_openai_request(
+                    api_key,
+                    organization.clone(),
+                    project.clone(),
+                    extra_headers,
+                    request,
+                    policy,
+                )
+                .await
             }
             AIProvider::Anthropic { api_key } => {
-                Self::send_anthropic_request(api_key, request).await
+                Self::send_anthropic_request(api_key, request, policy).await
             }
             AIProvider::GoogleGemini { api_key } => {
-                Self::send_gemini_request(api_key, request).await
+                Self::send_gemini_request(api_key, request, policy).await
             }
             AIProvider::AzureOpenAI {
                 api_key,
@@ -149,15 +555,44 @@ impl AIProvider {
                 deployment_name,
                 api_version,
             } => {
-                Self::send_azure_request(api_key, endpoint, deployment_name, api_version, request)
-                    .await
+                Self::send_azure_request(
+                    api_key,
+                    endpoint,
+                    deployment_name,
+                    api_version,
+                    request,
+                    policy,
+                )
+                .await
             }
             AIProvider::LMStudio { base_url } => {
-                Self::send_openai_compatible_request(base_url, None, request).await
+                Self::send_openai_compatible_request(base_url, None, request, policy).await
+            }
+            AIProvider::Ollama { base_url } => {
+                Self::send_ollama_request(base_url, request, policy).await
             }
-            AIProvider::Ollama { base_url } => Self::send_ollama_request(base_url, request).await,
             AIProvider::OpenAICompatible { base_url, api_key } => {
-                Self::send_openai_compatible_request(base_url, api_key.clone(), request).await
+                Self::send_openai_compatible_request(base_url, api_key.clone(), request, policy)
+                    .await
+            }
+            AIProvider::Custom {
+                base_url,
+                api_key,
+                auth_style,
+                extra_headers,
+                omit_stream_field,
+                ..
+            } => {
+                Self::send_custom_request(
+                    base_url,
+                    api_key.as_deref(),
+                    auth_style,
+                    extra_headers,
+                    *omit_stream_field,
+                    request,
+                    policy,
+                )
+                .await
             }
         }
     }
@@ -166,7 +601,10 @@ impl AIProvider {
     async fn send_openai_request(
         api_key: &str,
         organization: Option<String>,
+        project: Option<String>,
+        extra_headers: &[(String, String)],
         request: AIRequest,
+        policy: &RetryPolicy,
     ) -> AppResult<AIResponse> {
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(120))
@@ -184,16 +622,27 @@ impl AIProvider {
             req_builder = req_builder.header("OpenAI-Organization", org);
         }
 
-        req_builder = req_builder.json(&serde_json::json!({
+        if let Some(project) = project {
+            req_builder = req_builder.header("OpenAI-Project", project);
+        }
+
+        for (name, value) in extra_headers {
+            req_builder = req_builder.header(name, value);
+        }
+
+        let mut body = serde_json::json!({
             "model": request.model,
             "messages": request.messages,
             "temperature": request.temperature.unwrap_or(0.7),
             "max_tokens": request.max_tokens,
             "stream": request.stream,
-        }));
+        });
+        if !request.tools.is_empty() {
+            body["tools"] = openai_tools_json(&request.tools);
+        }
+        req_builder = req_builder.json(&body);
 
-        let response = req_builder
-            .send()
+        let response = send_with_retry(policy, req_builder)
             .await
             .map_err(|e| AppError::api(format!("Failed to send OpenAI request: {}", e)))?;
 
@@ -206,15 +655,19 @@ impl AIProvider {
             )));
         }
 
+        let response_headers = capture_response_headers(&response);
+
         let response_json: serde_json::Value = response
             .json()
             .await
             .map_err(|e| AppError::api(format!("Failed to parse OpenAI response: {}", e)))?;
 
-        let content = response_json["choices"][0]["message"]["content"]
-            .as_str()
-            .ok_or_else(|| AppError::api("Invalid OpenAI response format"))?
-            .to_string();
+        let message = &response_json["choices"][0]["message"];
+        let tool_calls = parse_openai_tool_calls(message);
+        let content = message["content"].as_str().unwrap_or_default().to_string();
+        if content.is_empty() && tool_calls.is_empty() {
+            return Err(AppError::api("Invalid OpenAI response format"));
+        }
 
         let tokens_used = response_json["usage"]["total_tokens"]
             .as_i64()
@@ -224,6 +677,8 @@ impl AIProvider {
             content,
             model: request.model,
             tokens_used,
+            response_headers,
+            tool_calls,
         })
     }
 
@@ -231,6 +686,7 @@ impl AIProvider {
     async fn send_anthropic_request(
         api_key: &str,
         request: AIRequest,
+        policy: &RetryPolicy,
     ) -> AppResult<AIResponse> {
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(120))
@@ -239,15 +695,26 @@ impl AIProvider {
 
         let url = "https://api.anthropic.com/v1/messages";
 
-        // Convert messages to Anthropic format (extract system message if present)
+        // Convert messages to Anthropic format (extract system message if present, and turn a
+        // `role: "tool"` message into the `tool_result` content block Anthropic expects instead
+        // of a bare `role: "tool"` message, which its API rejects)
         let mut system_message = None;
         let mut messages_without_system = Vec::new();
 
         for msg in request.messages {
             if msg.role == "system" {
                 system_message = Some(msg.content);
+            } else if msg.role == "tool" {
+                messages_without_system.push(serde_json::json!({
+                    "role": "user",
+                    "content": [{
+                        "type": "tool_result",
+                        "tool_use_id": msg.tool_call_id.unwrap_or_default(),
+                        "content": msg.content,
+                    }],
+                }));
             } else {
-                messages_without_system.push(msg);
+                messages_without_system.push(serde_json::json!(msg));
             }
         }
 
@@ -265,13 +732,26 @@ impl AIProvider {
             body["temperature"] = serde_json::json!(temp);
         }
 
-        let response = client
+        if !request.tools.is_empty() {
+            body["tools"] = serde_json::json!(request
+                .tools
+                .iter()
+                .map(|t| serde_json::json!({
+                    "name": t.name,
+                    "description": t.description,
+                    "input_schema": t.parameters,
+                }))
+                .collect::<Vec<_>>());
+        }
+
+        let req_builder = client
             .post(url)
             .header("x-api-key", api_key)
             .header("anthropic-version", "2023-06-01")
             .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
+            .json(&body);
+
+        let response = send_with_retry(policy, req_builder)
             .await
             .map_err(|e| AppError::api(format!("Failed to send Anthropic request: {}", e)))?;
 
@@ -284,15 +764,29 @@ impl AIProvider {
             )));
         }
 
+        let response_headers = capture_response_headers(&response);
+
         let response_json: serde_json::Value = response
             .json()
             .await
             .map_err(|e| AppError::api(format!("Failed to parse Anthropic response: {}", e)))?;
 
-        let content = response_json["content"][0]["text"]
-            .as_str()
-            .ok_or_else(|| AppError::api("Invalid Anthropic response format"))?
-            .to_string();
+        let mut content = String::new();
+        let mut tool_calls = Vec::new();
+        for block in response_json["content"].as_array().into_iter().flatten() {
+            match block["type"].as_str() {
+                Some("text") => content.push_str(block["text"].as_str().unwrap_or_default()),
+                Some("tool_use") => tool_calls.push(ToolCall {
+                    id: block["id"].as_str().unwrap_or_default().to_string(),
+                    name: block["name"].as_str().unwrap_or_default().to_string(),
+                    arguments: block["input"].clone(),
+                }),
+                _ => {}
+            }
+        }
+        if content.is_empty() && tool_calls.is_empty() {
+            return Err(AppError::api("Invalid Anthropic response format"));
+        }
 
         let tokens_used = response_json["usage"]["output_tokens"]
             .as_i64()
@@ -306,11 +800,17 @@ impl AIProvider {
             content,
             model: request.model,
             tokens_used,
+            response_headers,
+            tool_calls,
         })
     }
 
     /// Send request to Google Gemini API
-    async fn send_gemini_request(api_key: &str, request: AIRequest) -> AppResult<AIResponse> {
+    async fn send_gemini_request(
+        api_key: &str,
+        request: AIRequest,
+        policy: &RetryPolicy,
+    ) -> AppResult<AIResponse> {
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(120))
             .build()
@@ -322,11 +822,18 @@ impl AIProvider {
             request.model, api_key
         );
 
-        // Convert messages to Gemini format
+        // Convert messages to Gemini format. Gemini's real `functionResponse` part is keyed by
+        // function name rather than a call id (see the `id: String::new()` comment on the
+        // response side below), which nothing in `ChatMessage` currently carries - so a
+        // `role: "tool"` message is mapped to Gemini's "function" role with a best-effort text
+        // part rather than a proper `functionResponse`, which is close enough for the model to
+        // use the result but won't round-trip through strict function-calling validation.
         let mut contents = Vec::new();
         for msg in request.messages {
             let role = if msg.role == "assistant" {
                 "model"
+            } else if msg.role == "tool" {
+                "function"
             } else {
                 "user"
             };
@@ -353,11 +860,22 @@ impl AIProvider {
             body["generationConfig"]["maxOutputTokens"] = serde_json::json!(max_tokens);
         }
 
-        let response = client
+        if !request.tools.is_empty() {
+            body["tools"] = serde_json::json!([{
+                "functionDeclarations": request.tools.iter().map(|t| serde_json::json!({
+                    "name": t.name,
+                    "description": t.description,
+                    "parameters": t.parameters,
+                })).collect::<Vec<_>>(),
+            }]);
+        }
+
+        let req_builder = client
             .post(&url)
             .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
+            .json(&body);
+
+        let response = send_with_retry(policy, req_builder)
             .await
             .map_err(|e| AppError::api(format!("Failed to send Gemini request: {}", e)))?;
 
@@ -370,15 +888,34 @@ impl AIProvider {
             )));
         }
 
+        let response_headers = capture_response_headers(&response);
+
         let response_json: serde_json::Value = response
             .json()
             .await
             .map_err(|e| AppError::api(format!("Failed to parse Gemini response: {}", e)))?;
 
-        let content = response_json["candidates"][0]["content"]["parts"][0]["text"]
-            .as_str()
-            .ok_or_else(|| AppError::api("Invalid Gemini response format"))?
-            .to_string();
+        let mut content = String::new();
+        let mut tool_calls = Vec::new();
+        let parts = response_json["candidates"][0]["content"]["parts"]
+            .as_array()
+            .into_iter()
+            .flatten();
+        for part in parts {
+            if let Some(text) = part["text"].as_str() {
+                content.push_str(text);
+            } else if part.get("functionCall").is_some() {
+                tool_calls.push(ToolCall {
+                    // Gemini function calls carry no id; tools/call sites key on name instead
+                    id: String::new(),
+                    name: part["functionCall"]["name"].as_str().unwrap_or_default().to_string(),
+                    arguments: part["functionCall"]["args"].clone(),
+                });
+            }
+        }
+        if content.is_empty() && tool_calls.is_empty() {
+            return Err(AppError::api("Invalid Gemini response format"));
+        }
 
         let tokens_used = response_json["usageMetadata"]["totalTokenCount"]
             .as_i64()
@@ -388,6 +925,8 @@ impl AIProvider {
             content,
             model: request.model,
             tokens_used,
+            response_headers,
+            tool_calls,
         })
     }
 
@@ -398,6 +937,7 @@ impl AIProvider {
         deployment_name: &str,
         api_version: &str,
         request: AIRequest,
+        policy: &RetryPolicy,
     ) -> AppResult<AIResponse> {
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(120))
@@ -411,7 +951,7 @@ impl AIProvider {
             api_version
         );
 
-        let response = client
+        let req_builder = client
             .post(&url)
             .header("api-key", api_key)
             .header("Content-Type", "application/json")
@@ -420,8 +960,9 @@ impl AIProvider {
                 "temperature": request.temperature.unwrap_or(0.7),
                 "max_tokens": request.max_tokens,
                 "stream": request.stream,
-            }))
-            .send()
+            }));
+
+        let response = send_with_retry(policy, req_builder)
             .await
             .map_err(|e| AppError::api(format!("Failed to send Azure OpenAI request: {}", e)))?;
 
@@ -434,6 +975,8 @@ impl AIProvider {
             )));
         }
 
+        let response_headers = capture_response_headers(&response);
+
         let response_json: serde_json::Value = response
             .json()
             .await
@@ -452,6 +995,8 @@ impl AIProvider {
             content,
             model: deployment_name.to_string(),
             tokens_used,
+            response_headers,
+            tool_calls: Vec::new(),
         })
     }
 
@@ -460,6 +1005,7 @@ impl AIProvider {
         base_url: &str,
         api_key: Option<String>,
         request: AIRequest,
+        policy: &RetryPolicy,
     ) -> AppResult<AIResponse> {
         let client = reqwest::Client::new();
         let url = format!("{}/v1/chat/completions", base_url);
@@ -476,8 +1022,7 @@ impl AIProvider {
             req_builder = req_builder.header("Authorization", format!("Bearer {}", key));
         }
 
-        let response = req_builder
-            .send()
+        let response = send_with_retry(policy, req_builder)
             .await
             .map_err(|e| AppError::api(format!("Failed to send request: {}", e)))?;
 
@@ -490,6 +1035,8 @@ impl AIProvider {
             )));
         }
 
+        let response_headers = capture_response_headers(&response);
+
         let response_json: serde_json::Value = response
             .json()
             .await
@@ -508,26 +1055,108 @@ impl AIProvider {
             content,
             model: request.model,
             tokens_used,
+            response_headers,
+            tool_calls: Vec::new(),
+        })
+    }
+
+    /// Send request to a declarative custom provider (see [`crate::provider_registry`]),
+    /// applying whichever auth style and extra headers the definition specifies
+    async fn send_custom_request(
+        base_url: &str,
+        api_key: Option<&str>,
+        auth_style: &crate::provider_registry::AuthStyle,
+        extra_headers: &[(String, String)],
+        omit_stream_field: bool,
+        request: AIRequest,
+        policy: &RetryPolicy,
+    ) -> AppResult<AIResponse> {
+        let client = reqwest::Client::new();
+        let url = format!("{}/v1/chat/completions", base_url);
+
+        let mut body = serde_json::json!({
+            "model": request.model,
+            "messages": request.messages,
+            "temperature": request.temperature.unwrap_or(0.7),
+            "max_tokens": request.max_tokens,
+        });
+        if !omit_stream_field {
+            body["stream"] = serde_json::json!(request.stream);
+        }
+
+        let mut req_builder = client.post(&url).json(&body);
+
+        match (auth_style, api_key) {
+            (crate::provider_registry::AuthStyle::Bearer, Some(key)) => {
+                req_builder = req_builder.header("Authorization", format!("Bearer {}", key));
+            }
+            (crate::provider_registry::AuthStyle::Header(header_name), Some(key)) => {
+                req_builder = req_builder.header(header_name.as_str(), key);
+            }
+            _ => {}
+        }
+        for (name, value) in extra_headers {
+            req_builder = req_builder.header(name, value);
+        }
+
+        let response = send_with_retry(policy, req_builder)
+            .await
+            .map_err(|e| AppError::api(format!("Failed to send custom provider request: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::api(format!(
+                "Custom provider request failed with status {}: {}",
+                status, error_text
+            )));
+        }
+
+        let response_headers = capture_response_headers(&response);
+
+        let response_json: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::api(format!("Failed to parse custom provider response: {}", e)))?;
+
+        let content = response_json["choices"][0]["message"]["content"]
+            .as_str()
+            .ok_or_else(|| AppError::api("Invalid custom provider response format"))?
+            .to_string();
+
+        let tokens_used = response_json["usage"]["total_tokens"]
+            .as_i64()
+            .map(|t| t as i32);
+
+        Ok(AIResponse {
+            content,
+            model: request.model,
+            tokens_used,
+            response_headers,
+            tool_calls: Vec::new(),
         })
     }
 
     /// Send request to Ollama endpoint
-    async fn send_ollama_request(base_url: &str, request: AIRequest) -> AppResult<AIResponse> {
+    async fn send_ollama_request(
+        base_url: &str,
+        request: AIRequest,
+        policy: &RetryPolicy,
+    ) -> AppResult<AIResponse> {
         let client = reqwest::Client::new();
         let url = format!("{}/api/chat", base_url);
 
-        let response = client
-            .post(&url)
-            .json(&serde_json::json!({
-                "model": request.model,
-                "messages": request.messages,
-                "stream": request.stream,
-                "options": {
-                    "temperature": request.temperature.unwrap_or(0.7),
-                    "num_predict": request.max_tokens,
-                }
-            }))
-            .send()
+        let req_builder = client.post(&url).json(&serde_json::json!({
+            "model": request.model,
+            "messages": request.messages,
+            "stream": request.stream,
+            "options": {
+                "temperature": request.temperature.unwrap_or(0.7),
+                "num_predict": request.max_tokens,
+            }
+        }));
+
+        let response = send_with_retry(policy, req_builder)
             .await
             .map_err(|e| AppError::api(format!("Failed to send Ollama request: {}", e)))?;
 
@@ -540,6 +1169,8 @@ impl AIProvider {
             )));
         }
 
+        let response_headers = capture_response_headers(&response);
+
         let response_json: serde_json::Value = response
             .json()
             .await
@@ -554,6 +1185,364 @@ impl AIProvider {
             content,
             model: request.model,
             tokens_used: None, // Ollama doesn't provide token counts in the same way
+            response_headers,
+            tool_calls: Vec::new(),
+        })
+    }
+
+    /// Send a request in streaming mode, invoking `on_chunk` with each piece of generated text
+    /// as it arrives and returning the fully assembled response once the stream ends.
+    ///
+    /// `on_chunk` returns `false` to abort the stream early (see
+    /// [`crate::commands::cancel_ai_request`]), in which case this returns
+    /// [`AppError::Cancelled`] instead of a response.
+    ///
+    /// Supports OpenAI, Azure OpenAI, LM Studio, and OpenAI-compatible endpoints (SSE `data:`
+    /// lines), Anthropic (SSE `content_block_delta` events), and Ollama (newline-delimited
+    /// JSON). Google Gemini and declarative custom providers have no token-streaming endpoint
+    /// wired up here yet and fall back to a single chunk delivered after the full response
+    /// completes.
+    pub async fn send_request_streaming(
+        &self,
+        request: AIRequest,
+        on_chunk: &(dyn Fn(&str) -> bool + Send + Sync),
+    ) -> AppResult<AIResponse> {
+        let mut request = request;
+        request.stream = true;
+        log_outgoing_request(&self.provider_label(), &request);
+        self.check_rate_limit(&request)?;
+
+        match self {
+            AIProvider::OpenAI {
+                api_key,
+                organization,
+                project,
+                extra_headers,
+            } => {
+                Self::stream_openai_request(
+                    "https://api.openai.com/v1/chat/completions",
+                    api_key,
+                    organization.clone(),
+                    project.clone(),
+                    extra_headers,
+                    request,
+                    on_chunk,
+                )
+                .await
+            }
+            AIProvider::Anthropic { api_key } => {
+                Self::stream_anthropic_request(api_key, request, on_chunk).await
+            }
+            AIProvider::Ollama { base_url } => {
+                Self::stream_ollama_request(base_url, request, on_chunk).await
+            }
+            AIProvider::LMStudio { base_url } => {
+                Self::stream_openai_request(
+                    &format!("{}/v1/chat/completions", base_url),
+                    "",
+                    None,
+                    None,
+                    &[],
+                    request,
+                    on_chunk,
+                )
+                .await
+            }
+            AIProvider::OpenAICompatible { base_url, api_key } => {
+                Self::stream_openai_request(
+                    &format!("{}/v1/chat/completions", base_url),
+                    api_key.as_deref().unwrap_or(""),
+                    None,
+                    None,
+                    &[],
+                    request,
+                    on_chunk,
+                )
+                .await
+            }
+            AIProvider::GoogleGemini { .. }
+            | AIProvider::AzureOpenAI { .. }
+            | AIProvider::Custom { .. } => {
+                let response = self.send_request(request).await?;
+                on_chunk(&response.content);
+                Ok(response)
+            }
+        }
+    }
+
+    /// Stream a chat completion from an OpenAI-compatible SSE endpoint (`data: {json}` lines,
+    /// terminated by `data: [DONE]`), accumulating the full response as chunks arrive.
+    async fn stream_openai_request(
+        url: &str,
+        api_key: &str,
+        organization: Option<String>,
+        project: Option<String>,
+        extra_headers: &[(String, String)],
+        request: AIRequest,
+        on_chunk: &(dyn Fn(&str) -> bool + Send + Sync),
+    ) -> AppResult<AIResponse> {
+        use futures_util::StreamExt;
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(120))
+            .build()
+            .map_err(|e| AppError::api(format!("Failed to create HTTP client: {}", e)))?;
+
+        let mut req_builder = client.post(url).header("Content-Type", "application/json");
+        if !api_key.is_empty() {
+            req_builder = req_builder.header("Authorization", format!("Bearer {}", api_key));
+        }
+        if let Some(org) = organization {
+            req_builder = req_builder.header("OpenAI-Organization", org);
+        }
+        if let Some(project) = project {
+            req_builder = req_builder.header("OpenAI-Project", project);
+        }
+        for (name, value) in extra_headers {
+            req_builder = req_builder.header(name, value);
+        }
+
+        let model = request.model.clone();
+        let response = req_builder
+            .json(&serde_json::json!({
+                "model": request.model,
+                "messages": request.messages,
+                "temperature": request.temperature.unwrap_or(0.7),
+                "max_tokens": request.max_tokens,
+                "stream": true,
+            }))
+            .send()
+            .await
+            .map_err(|e| AppError::api(format!("Failed to send streaming request: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::api(format!(
+                "Streaming request failed with status {}: {}",
+                status, error_text
+            )));
+        }
+
+        let mut content = String::new();
+        let mut tokens_used = None;
+        let mut buffer = String::new();
+        let mut stream = response.bytes_stream();
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| AppError::api(format!("Stream read error: {}", e)))?;
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            while let Some(newline_pos) = buffer.find('\n') {
+                let line = buffer[..newline_pos].trim().to_string();
+                buffer.drain(..=newline_pos);
+
+                let Some(data) = line.strip_prefix("data:") else {
+                    continue;
+                };
+                let data = data.trim();
+                if data == "[DONE]" {
+                    continue;
+                }
+
+                let Ok(event) = serde_json::from_str::<serde_json::Value>(data) else {
+                    continue;
+                };
+
+                if let Some(delta) = event["choices"][0]["delta"]["content"].as_str() {
+                    content.push_str(delta);
+                    if !on_chunk(delta) {
+                        return Err(AppError::cancelled("Streaming request cancelled"));
+                    }
+                }
+                if let Some(total) = event["usage"]["total_tokens"].as_i64() {
+                    tokens_used = Some(total as i32);
+                }
+            }
+        }
+
+        Ok(AIResponse {
+            content,
+            model,
+            tokens_used,
+            response_headers: std::collections::HashMap::new(),
+            tool_calls: Vec::new(),
+        })
+    }
+
+    /// Stream a message from the Anthropic Messages API (SSE `content_block_delta` events)
+    async fn stream_anthropic_request(
+        api_key: &str,
+        request: AIRequest,
+        on_chunk: &(dyn Fn(&str) -> bool + Send + Sync),
+    ) -> AppResult<AIResponse> {
+        use futures_util::StreamExt;
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(120))
+            .build()
+            .map_err(|e| AppError::api(format!("Failed to create HTTP client: {}", e)))?;
+
+        let mut system_message = None;
+        let mut messages_without_system = Vec::new();
+        for msg in request.messages {
+            if msg.role == "system" {
+                system_message = Some(msg.content);
+            } else if msg.role == "tool" {
+                messages_without_system.push(serde_json::json!({
+                    "role": "user",
+                    "content": [{
+                        "type": "tool_result",
+                        "tool_use_id": msg.tool_call_id.unwrap_or_default(),
+                        "content": msg.content,
+                    }],
+                }));
+            } else {
+                messages_without_system.push(serde_json::json!(msg));
+            }
+        }
+
+        let mut body = serde_json::json!({
+            "model": request.model,
+            "messages": messages_without_system,
+            "max_tokens": request.max_tokens.unwrap_or(4096),
+            "stream": true,
+        });
+        if let Some(system) = system_message {
+            body["system"] = serde_json::json!(system);
+        }
+        if let Some(temp) = request.temperature {
+            body["temperature"] = serde_json::json!(temp);
+        }
+
+        let response = client
+            .post("https://api.anthropic.com/v1/messages")
+            .header("x-api-key", api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| AppError::api(format!("Failed to send streaming request: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::api(format!(
+                "Anthropic streaming request failed with status {}: {}",
+                status, error_text
+            )));
+        }
+
+        let mut content = String::new();
+        let mut tokens_used = None;
+        let mut buffer = String::new();
+        let mut stream = response.bytes_stream();
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| AppError::api(format!("Stream read error: {}", e)))?;
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            while let Some(newline_pos) = buffer.find('\n') {
+                let line = buffer[..newline_pos].trim().to_string();
+                buffer.drain(..=newline_pos);
+
+                let Some(data) = line.strip_prefix("data:") else {
+                    continue;
+                };
+                let Ok(event) = serde_json::from_str::<serde_json::Value>(data.trim()) else {
+                    continue;
+                };
+
+                if let Some(delta) = event["delta"]["text"].as_str() {
+                    content.push_str(delta);
+                    if !on_chunk(delta) {
+                        return Err(AppError::cancelled("Streaming request cancelled"));
+                    }
+                }
+                if let Some(output) = event["usage"]["output_tokens"].as_i64() {
+                    tokens_used = Some(output as i32);
+                }
+            }
+        }
+
+        Ok(AIResponse {
+            content,
+            model: request.model,
+            tokens_used,
+            response_headers: std::collections::HashMap::new(),
+            tool_calls: Vec::new(),
+        })
+    }
+
+    /// Stream a chat response from Ollama (newline-delimited JSON objects, not SSE)
+    async fn stream_ollama_request(
+        base_url: &str,
+        request: AIRequest,
+        on_chunk: &(dyn Fn(&str) -> bool + Send + Sync),
+    ) -> AppResult<AIResponse> {
+        use futures_util::StreamExt;
+
+        let client = reqwest::Client::new();
+        let url = format!("{}/api/chat", base_url);
+
+        let response = client
+            .post(&url)
+            .json(&serde_json::json!({
+                "model": request.model,
+                "messages": request.messages,
+                "stream": true,
+                "options": {
+                    "temperature": request.temperature.unwrap_or(0.7),
+                    "num_predict": request.max_tokens,
+                }
+            }))
+            .send()
+            .await
+            .map_err(|e| AppError::api(format!("Failed to send streaming request: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::api(format!(
+                "Ollama streaming request failed with status {}: {}",
+                status, error_text
+            )));
+        }
+
+        let mut content = String::new();
+        let mut buffer = String::new();
+        let mut stream = response.bytes_stream();
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| AppError::api(format!("Stream read error: {}", e)))?;
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            while let Some(newline_pos) = buffer.find('\n') {
+                let line = buffer[..newline_pos].trim().to_string();
+                buffer.drain(..=newline_pos);
+                if line.is_empty() {
+                    continue;
+                }
+
+                let Ok(event) = serde_json::from_str::<serde_json::Value>(&line) else {
+                    continue;
+                };
+                if let Some(delta) = event["message"]["content"].as_str() {
+                    content.push_str(delta);
+                    if !on_chunk(delta) {
+                        return Err(AppError::cancelled("Streaming request cancelled"));
+                    }
+                }
+            }
+        }
+
+        Ok(AIResponse {
+            content,
+            model: request.model,
+            tokens_used: None,
+            response_headers: std::collections::HashMap::new(),
+            tool_calls: Vec::new(),
         })
     }
 
@@ -761,6 +1750,120 @@ impl AIProvider {
 
         Ok(models)
     }
+
+    /// Pull (download) an Ollama model, invoking `on_progress` for each status update Ollama
+    /// streams back - newline-delimited JSON, the same wire format as [`Self::stream_ollama_request`]
+    pub async fn pull_ollama_model(
+        base_url: &str,
+        model: &str,
+        on_progress: &(dyn Fn(OllamaPullProgress) + Send + Sync),
+    ) -> AppResult<()> {
+        use futures_util::StreamExt;
+
+        let client = reqwest::Client::new();
+        let url = format!("{}/api/pull", base_url);
+
+        let response = client
+            .post(&url)
+            .json(&serde_json::json!({ "name": model, "stream": true }))
+            .send()
+            .await
+            .map_err(|e| AppError::api(format!("Failed to start Ollama model pull: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::api(format!(
+                "Ollama pull failed with status {}: {}",
+                status, error_text
+            )));
+        }
+
+        let mut buffer = String::new();
+        let mut stream = response.bytes_stream();
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| AppError::api(format!("Stream read error: {}", e)))?;
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            while let Some(newline_pos) = buffer.find('\n') {
+                let line = buffer[..newline_pos].trim().to_string();
+                buffer.drain(..=newline_pos);
+                if line.is_empty() {
+                    continue;
+                }
+
+                if let Ok(progress) = serde_json::from_str::<OllamaPullProgress>(&line) {
+                    on_progress(progress);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Delete a locally pulled Ollama model
+    pub async fn delete_ollama_model(base_url: &str, model: &str) -> AppResult<()> {
+        let client = reqwest::Client::new();
+        let url = format!("{}/api/delete", base_url);
+
+        let response = client
+            .delete(&url)
+            .json(&serde_json::json!({ "name": model }))
+            .send()
+            .await
+            .map_err(|e| AppError::api(format!("Failed to delete Ollama model: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::api(format!(
+                "Ollama delete failed with status {}: {}",
+                status, error_text
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Fetch metadata (parameters, template, modelfile, etc.) for a locally pulled Ollama model
+    pub async fn show_ollama_model(base_url: &str, model: &str) -> AppResult<serde_json::Value> {
+        let client = reqwest::Client::new();
+        let url = format!("{}/api/show", base_url);
+
+        let response = client
+            .post(&url)
+            .json(&serde_json::json!({ "name": model }))
+            .send()
+            .await
+            .map_err(|e| AppError::api(format!("Failed to fetch Ollama model info: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::api(format!(
+                "Ollama show failed with status {}: {}",
+                status, error_text
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| AppError::api(format!("Failed to parse Ollama model info response: {}", e)))
+    }
+}
+
+/// One status update from Ollama's `/api/pull` progress stream
+///
+/// `digest`/`total`/`completed` are only present while a layer is actively downloading (status
+/// `"pulling manifest"`/`"verifying sha256 digest"`/etc. have neither).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaPullProgress {
+    pub status: String,
+    pub digest: Option<String>,
+    pub total: Option<u64>,
+    pub completed: Option<u64>,
 }
 
 #[cfg(test)]
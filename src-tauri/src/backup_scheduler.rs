@@ -0,0 +1,51 @@
+//! Background scheduler that periodically snapshots the database file and prunes old backups
+//! beyond the configured retention count.
+//!
+//! Spawned once from `main.rs`'s `setup()` closure; a no-op if
+//! [`crate::database::DatabaseConfig::backup_enabled`] is false. Runs for the lifetime of the
+//! process and is not expected to be stopped before shutdown.
+
+use crate::database::DatabaseManager;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often the scheduler wakes up to take a new backup
+const BACKUP_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Spawn the scheduler as a background tokio task. Returns immediately; does nothing if backups
+/// are disabled in the database config.
+pub fn spawn(db: Arc<DatabaseManager>) {
+    if !db.config().backup_enabled {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(BACKUP_INTERVAL);
+        // The first tick fires immediately; skip it so we don't double up with the backup a
+        // user might take manually right after launch.
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            run_once(&db);
+        }
+    });
+}
+
+fn run_once(db: &DatabaseManager) {
+    match db.create_timestamped_backup() {
+        Ok(path) => tracing::info!("📦 Scheduled backup created at {:?}", path),
+        Err(e) => {
+            tracing::warn!("⚠️ Scheduled backup failed: {}", e);
+            return;
+        }
+    }
+
+    match db.enforce_backup_retention(db.config().backup_retention_count) {
+        Ok(removed) if removed > 0 => {
+            tracing::info!("🧹 Pruned {} backup(s) beyond retention count", removed)
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("⚠️ Backup retention cleanup failed: {}", e),
+    }
+}
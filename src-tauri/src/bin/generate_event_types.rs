@@ -0,0 +1,52 @@
+//! Generates TypeScript definitions for backend-emitted event payloads and command DTOs,
+//! written to `src/lib/types/generated/` for the SvelteKit frontend to import directly.
+//!
+//! This only covers types that derive `ts_rs::TS` - it is not a substitute for the
+//! hand-maintained core domain types in `src/lib/types/models.ts`, and new event/DTO types need
+//! their own `#[derive(ts_rs::TS)]` plus an entry in the list below before they show up here.
+//!
+//! Run with `cargo run --bin generate_event_types` from `src-tauri/`, or wire into the
+//! frontend's build script as a prebuild step so drift is caught at build time.
+
+use forbidden_library_native::command_palette::{CommandPaletteEntry, CommandPaletteEntryKind};
+use forbidden_library_native::commands::{
+    AiStreamChunk, ExportProgressEvent, MemoryReport, NotificationActionPayload,
+};
+use forbidden_library_native::database::BackupInfo;
+use forbidden_library_native::library_archive::LibraryImportSummary;
+use forbidden_library_native::models::{NotificationCategory, ReliabilityReport};
+use forbidden_library_native::provider_monitor::ProviderStatusChangedEvent;
+use ts_rs::TS;
+
+fn main() {
+    let exports: &[(&str, fn() -> Result<(), ts_rs::ExportError>)] = &[
+        ("AiStreamChunk", AiStreamChunk::export),
+        ("MemoryReport", MemoryReport::export),
+        ("ReliabilityReport", ReliabilityReport::export),
+        ("BackupInfo", BackupInfo::export),
+        ("LibraryImportSummary", LibraryImportSummary::export),
+        ("CommandPaletteEntry", CommandPaletteEntry::export),
+        ("CommandPaletteEntryKind", CommandPaletteEntryKind::export),
+        ("NotificationCategory", NotificationCategory::export),
+        ("NotificationActionPayload", NotificationActionPayload::export),
+        ("ExportProgressEvent", ExportProgressEvent::export),
+        ("ProviderStatusChangedEvent", ProviderStatusChangedEvent::export),
+    ];
+
+    let mut failed = false;
+    for (name, export) in exports {
+        if let Err(e) = export() {
+            eprintln!("Failed to export {}: {}", name, e);
+            failed = true;
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+
+    println!(
+        "Exported {} TypeScript definitions to src/lib/types/generated/",
+        exports.len()
+    );
+}
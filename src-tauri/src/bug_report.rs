@@ -0,0 +1,57 @@
+//! Bundle diagnostic information into a single file a user can attach to a bug report, so field
+//! problems come with enough context to reproduce instead of a one-line description.
+//!
+//! Like [`crate::library_archive`], there's no `zip` crate in this workspace, so the bundle is a
+//! gzip-compressed JSON document rather than a true multi-file archive. It also can't include
+//! recent log lines: this build's `tracing_subscriber` (see `main.rs`) writes to stdout only,
+//! with no rotating log file to read back from - `logs_note` says so explicitly rather than
+//! silently omitting them.
+
+use crate::commands::DatabaseStats;
+use crate::errors::{AppError, AppResult};
+use chrono::{DateTime, Utc};
+use std::io::Write;
+
+/// Bumped whenever the bundle's JSON shape changes incompatibly
+const BUG_REPORT_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BugReportBundle {
+    pub format_version: u32,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub database_stats: DatabaseStats,
+    pub generated_at: DateTime<Utc>,
+    pub logs_note: String,
+}
+
+/// Gather the pieces of a [`BugReportBundle`] this build can actually produce
+///
+/// Deliberately doesn't touch API keys or anything else in [`crate::keychain`] - they live in
+/// the OS keychain, not in anything this function reads, so there's nothing to strip.
+pub fn build_bundle(database_stats: DatabaseStats) -> BugReportBundle {
+    BugReportBundle {
+        format_version: BUG_REPORT_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        database_stats,
+        generated_at: Utc::now(),
+        logs_note: "This build logs to stdout only; no log file exists to attach.".to_string(),
+    }
+}
+
+/// Serialize and gzip-compress a bundle, ready to write to disk
+pub fn compress_bundle(bundle: &BugReportBundle) -> AppResult<Vec<u8>> {
+    let json = serde_json::to_vec_pretty(bundle)
+        .map_err(|e| AppError::validation(format!("Failed to serialize bug report bundle: {}", e)))?;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder
+        .write_all(&json)
+        .map_err(|e| AppError::io(format!("Failed to compress bug report bundle: {}", e)))?;
+    encoder
+        .finish()
+        .map_err(|e| AppError::io(format!("Failed to finish bug report bundle compression: {}", e)))
+}
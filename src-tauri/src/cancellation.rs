@@ -0,0 +1,77 @@
+//! Cooperative cancellation for in-flight streaming AI requests
+//!
+//! Mirrors [`crate::ratelimit`]'s global-registry pattern, but tracks per-request flags instead
+//! of per-provider buckets: [`crate::commands::stream_ai_provider_request`] registers its
+//! `stream_id` here before it starts reading the provider's response stream and checks
+//! [`is_cancelled`] after every chunk, while [`crate::commands::cancel_ai_request`] flips the
+//! flag from wherever the user's cancel button fires - no direct handle to the in-flight
+//! `reqwest` future needs to be threaded anywhere.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+fn registry() -> &'static Mutex<HashMap<String, bool>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<String, bool>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Mark `request_id` as in-flight and cancellable. Call once, before streaming begins.
+pub fn register(request_id: &str) {
+    registry().lock().unwrap().insert(request_id.to_string(), false);
+}
+
+/// Drop `request_id` from the registry once its stream has ended, whether it completed normally
+/// or was cancelled. Call exactly once per [`register`].
+pub fn unregister(request_id: &str) {
+    registry().lock().unwrap().remove(request_id);
+}
+
+/// Whether `request_id` has been cancelled since it was registered
+pub fn is_cancelled(request_id: &str) -> bool {
+    registry()
+        .lock()
+        .unwrap()
+        .get(request_id)
+        .copied()
+        .unwrap_or(false)
+}
+
+/// Mark a registered request as cancelled. Returns `false` if `request_id` isn't currently
+/// registered (already finished, never started, or unknown id).
+pub fn cancel(request_id: &str) -> bool {
+    let mut registry = registry().lock().unwrap();
+    match registry.get_mut(request_id) {
+        Some(cancelled) => {
+            *cancelled = true;
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_flips_registered_request() {
+        register("req-1");
+        assert!(!is_cancelled("req-1"));
+        assert!(cancel("req-1"));
+        assert!(is_cancelled("req-1"));
+        unregister("req-1");
+    }
+
+    #[test]
+    fn test_cancel_unknown_request_returns_false() {
+        assert!(!cancel("not-registered"));
+    }
+
+    #[test]
+    fn test_unregister_clears_state() {
+        register("req-2");
+        unregister("req-2");
+        assert!(!is_cancelled("req-2"));
+        assert!(!cancel("req-2"));
+    }
+}
@@ -0,0 +1,228 @@
+//! Unified, ranked search across conversations, personas, grimoire entries, grimoire templates,
+//! and built-in app actions, backing the UI's Ctrl+K command palette in a single IPC call.
+//!
+//! Conversations are ranked by FTS5/BM25 via [`crate::database::fts_search`]. Personas, grimoire
+//! entries, templates, and actions have no FTS index of their own, so they're ranked by
+//! case-insensitive substring matching instead - good enough for the handful of dozens of items
+//! a typical library has, not a true trigram index; swap in one if the entry counts grow large
+//! enough that substring scans get slow.
+
+use crate::database::fts_search;
+use crate::errors::AppResult;
+use crate::models::{GrimoireEntry, Persona};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// What kind of thing a [`CommandPaletteEntry`] refers to, so the frontend knows how to open it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ts_rs::TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub enum CommandPaletteEntryKind {
+    Conversation,
+    Persona,
+    GrimoireEntry,
+    GrimoireTemplate,
+    Action,
+}
+
+/// One ranked result in the command palette's merged list
+#[derive(Debug, Clone, Serialize, Deserialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct CommandPaletteEntry {
+    pub kind: CommandPaletteEntryKind,
+    pub id: String,
+    pub title: String,
+    pub subtitle: Option<String>,
+    /// Higher is more relevant. Not comparable across queries - only used to sort entries
+    /// within a single call's result list.
+    pub relevance_score: f64,
+}
+
+/// A built-in app action offered in the palette when the query matches its title. `id` is an
+/// opaque string the frontend maps to a UI action - this module only ranks and filters actions,
+/// it never executes one.
+struct AppAction {
+    id: &'static str,
+    title: &'static str,
+    subtitle: &'static str,
+}
+
+const APP_ACTIONS: &[AppAction] = &[
+    AppAction {
+        id: "new-conversation",
+        title: "New Conversation",
+        subtitle: "Start a fresh conversation",
+    },
+    AppAction {
+        id: "new-persona",
+        title: "New Persona",
+        subtitle: "Create a persona",
+    },
+    AppAction {
+        id: "new-grimoire-entry",
+        title: "New Grimoire Entry",
+        subtitle: "Add a knowledge-base entry",
+    },
+    AppAction {
+        id: "open-settings",
+        title: "Settings",
+        subtitle: "Open app settings",
+    },
+    AppAction {
+        id: "export-conversation",
+        title: "Export Conversation",
+        subtitle: "Export the current conversation",
+    },
+];
+
+/// Cap on how many conversation matches to pull from FTS per query, to keep the merged list
+/// dominated by relevance rather than by whichever source has the most rows
+const MAX_CONVERSATION_MATCHES: i32 = 8;
+
+/// Build the merged, ranked command palette list for `query`.
+///
+/// `personas` and `grimoire_entries` are passed in rather than loaded here, since the caller
+/// (the `get_command_palette_entries` command) already has them from the service layer and
+/// there's no need for this function to open its own second and third connections.
+pub fn get_entries(
+    conn: &Connection,
+    personas: &[Persona],
+    grimoire_entries: &[GrimoireEntry],
+    query: &str,
+) -> AppResult<Vec<CommandPaletteEntry>> {
+    let mut entries = Vec::new();
+
+    for result in fts_search::search_titles(conn, query, Some(MAX_CONVERSATION_MATCHES))? {
+        entries.push(CommandPaletteEntry {
+            kind: CommandPaletteEntryKind::Conversation,
+            id: result.conversation_id.to_string(),
+            title: result.title,
+            subtitle: None,
+            // bm25() scores are negative, with lower (more negative) meaning more relevant;
+            // negate it so every entry in the merged list follows "higher is better".
+            relevance_score: -result.relevance_score,
+        });
+    }
+
+    let needle = query.to_lowercase();
+
+    for persona in personas {
+        if let Some(score) = substring_score(&persona.name, &needle) {
+            entries.push(CommandPaletteEntry {
+                kind: CommandPaletteEntryKind::Persona,
+                id: persona.id.map(|id| id.to_string()).unwrap_or_default(),
+                title: persona.name.clone(),
+                subtitle: persona.description.clone(),
+                relevance_score: score,
+            });
+        }
+    }
+
+    for entry in grimoire_entries {
+        if let Some(score) = substring_score(&entry.title, &needle) {
+            entries.push(CommandPaletteEntry {
+                kind: CommandPaletteEntryKind::GrimoireEntry,
+                id: entry.id.clone(),
+                title: entry.title.clone(),
+                subtitle: entry.category.clone(),
+                relevance_score: score,
+            });
+        }
+    }
+
+    for template in crate::grimoire_templates::list_templates() {
+        if let Some(score) = substring_score(&template.name, &needle) {
+            entries.push(CommandPaletteEntry {
+                kind: CommandPaletteEntryKind::GrimoireTemplate,
+                id: template.name.clone(),
+                title: template.name.clone(),
+                subtitle: Some("Grimoire template".to_string()),
+                relevance_score: score,
+            });
+        }
+    }
+
+    for action in APP_ACTIONS {
+        if let Some(score) = substring_score(action.title, &needle) {
+            entries.push(CommandPaletteEntry {
+                kind: CommandPaletteEntryKind::Action,
+                id: action.id.to_string(),
+                title: action.title.to_string(),
+                subtitle: Some(action.subtitle.to_string()),
+                relevance_score: score,
+            });
+        }
+    }
+
+    entries.sort_by(|a, b| {
+        b.relevance_score
+            .partial_cmp(&a.relevance_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    Ok(entries)
+}
+
+/// Score a case-insensitive substring match against an already-lowercased `needle`: an exact
+/// match scores highest, a prefix match next, any other substring match lowest. Returns `None`
+/// for no match at all, so the caller can filter non-matches out with `if let Some(...)`.
+///
+/// An empty query matches everything at the lowest score, so a blank palette query still lists
+/// personas, entries, templates, and actions (sorted below any conversation matches, which can't
+/// exist for an empty FTS query).
+fn substring_score(haystack: &str, needle_lower: &str) -> Option<f64> {
+    if needle_lower.is_empty() {
+        return Some(0.0);
+    }
+
+    let haystack_lower = haystack.to_lowercase();
+    if haystack_lower == needle_lower {
+        Some(100.0)
+    } else if haystack_lower.starts_with(needle_lower) {
+        Some(50.0)
+    } else if haystack_lower.contains(needle_lower) {
+        Some(10.0)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substring_score_ranks_exact_above_prefix_above_contains() {
+        let exact = substring_score("Settings", "settings").unwrap();
+        let prefix = substring_score("Settings Panel", "settings").unwrap();
+        let contains = substring_score("App Settings Panel", "settings").unwrap();
+        assert!(exact > prefix);
+        assert!(prefix > contains);
+    }
+
+    #[test]
+    fn test_substring_score_no_match_returns_none() {
+        assert_eq!(substring_score("New Conversation", "xyz"), None);
+    }
+
+    #[test]
+    fn test_get_entries_matches_action_by_title() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE conversations (id INTEGER PRIMARY KEY, title TEXT, metadata TEXT, created_at TEXT)",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "CREATE TABLE messages (id INTEGER PRIMARY KEY, conversation_id INTEGER, content TEXT, role TEXT)",
+            [],
+        )
+        .unwrap();
+        crate::database::fts_search::initialize_fts_tables(&conn).unwrap();
+
+        let entries = get_entries(&conn, &[], &[], "settings").unwrap();
+        assert!(entries
+            .iter()
+            .any(|e| e.kind == CommandPaletteEntryKind::Action && e.title == "Settings"));
+    }
+}
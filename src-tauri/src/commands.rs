@@ -7,13 +7,20 @@
 use crate::models::{Conversation, Message, MessageRole, Persona};
 use crate::services::Services;
 use crate::validation::InputValidator;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tauri::State;
+use tauri::{GlobalShortcutManager, State};
+
+/// Actor recorded against every `crate::services::AuditLogService::record` call from this file.
+/// This app has no multi-user authentication, so there's no verified identity to attach beyond
+/// "this install" - see [`crate::models::AuditLogEntry`].
+const AUDIT_ACTOR: &str = "desktop-app";
 
 /// Application state shared across all commands
 pub struct AppState {
     pub services: Arc<Services>,
+    pub provider_monitor: Arc<crate::provider_monitor::ProviderMonitor>,
 }
 
 /// Validate and sanitize file paths to prevent path traversal attacks
@@ -198,6 +205,71 @@ pub async fn get_conversations(
         .map_err(|e| format!("Failed to get conversations: {}", e))
 }
 
+/// Lightweight conversation summary for virtual-scrolled list views
+#[derive(Debug, Serialize)]
+pub struct ConversationSummary {
+    pub id: i64,
+    pub title: String,
+    pub persona_id: Option<i64>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub archived: bool,
+    /// Denormalized preview of the most recent message, so the list view doesn't need
+    /// to fetch full message history just to render a preview line
+    pub last_message_snippet: Option<String>,
+}
+
+impl ConversationSummary {
+    fn from_conversation_and_last_message(conversation: Conversation, last_message: Option<String>) -> Self {
+        use crate::services::ConversationService;
+
+        Self {
+            id: conversation.id.unwrap_or_default(),
+            title: conversation.title,
+            persona_id: conversation.persona_id,
+            updated_at: conversation.updated_at,
+            archived: conversation.archived,
+            last_message_snippet: last_message.map(|m| ConversationService::snippet_for_message(&m)),
+        }
+    }
+}
+
+/// A single window of conversation summaries plus the total row count
+///
+/// Pairs with `ConversationSummary` to let a virtualized list fetch only the rows
+/// currently in (or near) the viewport instead of the full conversation list.
+#[derive(Debug, Serialize)]
+pub struct ConversationWindow {
+    pub items: Vec<ConversationSummary>,
+    pub total_count: i64,
+}
+
+/// Get a windowed page of conversation summaries for virtual scrolling
+#[tauri::command]
+pub async fn get_conversation_window(
+    offset: i32,
+    limit: i32,
+    state: State<'_, AppState>,
+) -> Result<ConversationWindow, String> {
+    let items = state
+        .services
+        .conversations
+        .get_conversations_with_last_message(Some(limit), Some(offset))
+        .map_err(|e| format!("Failed to get conversations: {}", e))?
+        .into_iter()
+        .map(|(conversation, last_message)| {
+            ConversationSummary::from_conversation_and_last_message(conversation, last_message)
+        })
+        .collect();
+
+    let total_count = state
+        .services
+        .conversations
+        .count_conversations(None)
+        .map_err(|e| format!("Failed to count conversations: {}", e))?;
+
+    Ok(ConversationWindow { items, total_count })
+}
+
 /// Search conversations by title or content
 #[tauri::command]
 pub async fn search_conversations(
@@ -333,6 +405,80 @@ pub async fn get_search_suggestions(
         .map_err(|e| format!("Suggestions failed: {}", e))
 }
 
+/// Fuzzily match `query` against conversations, personas, grimoire entries, grimoire templates,
+/// and built-in app actions in one ranked list, for the UI's Ctrl+K command palette
+#[tauri::command]
+pub async fn get_command_palette_entries(
+    query: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::command_palette::CommandPaletteEntry>, String> {
+    let conn = state
+        .services
+        .conversations
+        .db
+        .get_connection()
+        .map_err(|e| format!("Database connection error: {}", e))?;
+
+    let personas = state
+        .services
+        .personas
+        .get_personas()
+        .map_err(|e| format!("Failed to load personas: {}", e))?;
+
+    let grimoire_entries = state
+        .services
+        .grimoire
+        .list_entries()
+        .map_err(|e| format!("Failed to load grimoire entries: {}", e))?;
+
+    crate::command_palette::get_entries(&conn, &personas, &grimoire_entries, &query)
+        .map_err(|e| format!("Command palette search failed: {}", e))
+}
+
+/// Search message content ranked by relevance, returning match offsets instead of pre-rendered
+/// HTML so the frontend can highlight matches with its own renderer
+#[tauri::command]
+pub async fn search_messages(
+    query: String,
+    limit: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::database::fts_search::MessageSearchResult>, String> {
+    use crate::database::fts_search::search_messages as fts_search_messages;
+
+    tracing::info!("Message search for: {}", query);
+
+    let conn = state
+        .services
+        .conversations
+        .db
+        .get_connection()
+        .map_err(|e| format!("Database connection error: {}", e))?;
+
+    fts_search_messages(&conn, &query, limit)
+        .map_err(|e| format!("Message search failed: {}", e))
+}
+
+/// Find prior user questions similar to `text`, paired with the answer they received, so a
+/// near-duplicate question can be answered from history instead of re-sent to a provider
+#[tauri::command]
+pub async fn find_similar_questions(
+    text: String,
+    limit: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::database::fts_search::SimilarQuestion>, String> {
+    use crate::database::fts_search::find_similar_questions as fts_find_similar_questions;
+
+    let conn = state
+        .services
+        .conversations
+        .db
+        .get_connection()
+        .map_err(|e| format!("Database connection error: {}", e))?;
+
+    fts_find_similar_questions(&conn, &text, limit)
+        .map_err(|e| format!("Similar question search failed: {}", e))
+}
+
 /// Rebuild full-text search indices
 #[tauri::command]
 pub async fn rebuild_search_index(state: State<'_, AppState>) -> Result<String, String> {
@@ -366,6 +512,94 @@ pub async fn get_conversation(
         .map_err(|e| format!("Failed to get conversation: {}", e))
 }
 
+/// Combined response for `open_conversation`
+#[derive(Debug, Serialize)]
+pub struct OpenConversationResult {
+    pub conversation: Conversation,
+    pub messages: Vec<Message>,
+    pub persona: Option<Persona>,
+    /// Other recently active conversations, prefetched so the frontend can warm their
+    /// cache without a follow-up round trip if the user opens one next
+    pub prefetched_conversations: Vec<Conversation>,
+}
+
+/// Open a conversation in a single IPC round trip
+///
+/// Replaces the common `get_conversation` + `get_messages` pair (and the persona lookup
+/// that usually follows) with one call, and opportunistically prefetches the next most
+/// recently active conversations so switching between them feels instant.
+#[tauri::command]
+pub async fn open_conversation(
+    id: i64,
+    prefetch_count: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<OpenConversationResult, String> {
+    tracing::debug!("Opening conversation {}", id);
+
+    let conversation = state
+        .services
+        .conversations
+        .get_conversation(id)
+        .map_err(|e| format!("Failed to get conversation: {}", e))?
+        .ok_or_else(|| format!("Conversation {} not found", id))?;
+
+    state
+        .services
+        .conversations
+        .record_opened(id)
+        .map_err(|e| format!("Failed to record conversation access: {}", e))?;
+    if let Err(e) = state.services.evict_stale_conversation_cache_default() {
+        tracing::warn!("Failed to evict stale conversation cache entries: {}", e);
+    }
+
+    let messages = state
+        .services
+        .conversations
+        .get_messages(id, None, None)
+        .map_err(|e| format!("Failed to get messages: {}", e))?;
+
+    let persona = match conversation.persona_id {
+        Some(persona_id) => state
+            .services
+            .personas
+            .get_persona(persona_id)
+            .map_err(|e| format!("Failed to get persona: {}", e))?,
+        None => None,
+    };
+
+    let prefetch_count = prefetch_count.unwrap_or(5).max(0);
+    let prefetched_conversations = state
+        .services
+        .conversations
+        .get_conversations(Some(prefetch_count + 1), None)
+        .map_err(|e| format!("Failed to prefetch conversations: {}", e))?
+        .into_iter()
+        .filter(|c| c.id != Some(id))
+        .take(prefetch_count as usize)
+        .collect();
+
+    Ok(OpenConversationResult {
+        conversation,
+        messages,
+        persona,
+        prefetched_conversations,
+    })
+}
+
+/// Non-archived conversations that haven't been opened in the longest time (or never), oldest
+/// first - surfaced as a cleanup suggestion list for libraries with thousands of threads
+#[tauri::command]
+pub async fn get_least_recently_opened_conversations(
+    limit: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Conversation>, String> {
+    state
+        .services
+        .conversations
+        .least_recently_opened(limit.unwrap_or(20))
+        .map_err(|e| format!("Failed to list least recently opened conversations: {}", e))
+}
+
 #[tauri::command]
 pub async fn delete_conversation(id: i64, state: State<'_, AppState>) -> Result<(), String> {
     tracing::info!("Deleting conversation with id: {}", id);
@@ -373,7 +607,68 @@ pub async fn delete_conversation(id: i64, state: State<'_, AppState>) -> Result<
         .services
         .conversations
         .delete_conversation(id)
-        .map_err(|e| format!("Failed to delete conversation: {}", e))
+        .map_err(|e| format!("Failed to delete conversation: {}", e))?;
+
+    let _ = state.services.audit_log.record(
+        AUDIT_ACTOR,
+        "conversation.delete",
+        Some(&format!("conversation_id={}", id)),
+    );
+    Ok(())
+}
+
+/// Move a conversation to the trash instead of deleting it outright
+///
+/// Trashed conversations are hidden from [`get_conversations`] but remain in the database -
+/// undoable via [`restore_conversation`] until [`purge_trash`] (or the automatic purge job)
+/// removes them for good.
+#[tauri::command]
+pub async fn trash_conversation(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    tracing::info!("Moving conversation {} to trash", id);
+    state
+        .services
+        .conversations
+        .trash_conversation(id)
+        .map_err(|e| format!("Failed to trash conversation: {}", e))
+}
+
+/// Restore a conversation previously moved to trash via [`trash_conversation`]
+#[tauri::command]
+pub async fn restore_conversation(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    tracing::info!("Restoring conversation {} from trash", id);
+    state
+        .services
+        .conversations
+        .restore_conversation(id)
+        .map_err(|e| format!("Failed to restore conversation: {}", e))?;
+
+    let _ = state.services.audit_log.record(
+        AUDIT_ACTOR,
+        "conversation.restore",
+        Some(&format!("conversation_id={}", id)),
+    );
+    Ok(())
+}
+
+/// Permanently delete every conversation trashed more than `older_than_days` ago
+///
+/// Returns the number of conversations purged. Called on demand from the trash UI; also run
+/// automatically by [`crate::trash_scheduler`] honoring
+/// [`crate::database::DatabaseConfig::trash_retention_days`].
+#[tauri::command]
+pub async fn purge_trash(older_than_days: i64, state: State<'_, AppState>) -> Result<usize, String> {
+    let purged = state
+        .services
+        .conversations
+        .purge_trash(older_than_days)
+        .map_err(|e| format!("Failed to purge trash: {}", e))?;
+
+    let _ = state.services.audit_log.record(
+        AUDIT_ACTOR,
+        "conversation.purge_trash",
+        Some(&format!("older_than_days={} purged={}", older_than_days, purged)),
+    );
+    Ok(purged)
 }
 
 #[tauri::command]
@@ -394,822 +689,4790 @@ pub async fn archive_conversation(
         .map_err(|e| format!("Failed to archive conversation: {}", e))
 }
 
-// ==================== MESSAGE COMMANDS ====================
+/// Progress reported as the `bulk-operation-progress` event while [`bulk_update_conversations`]
+/// works through a batch
+#[derive(Debug, Clone, Serialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct BulkOperationProgressEvent {
+    pub processed: usize,
+    pub total: usize,
+}
 
+/// Apply one action to many conversations at once - archive/unarchive/delete/tag/untag -
+/// atomically, so a batch over hundreds of conversations either lands completely or not at all.
+///
+/// Emits `bulk-operation-progress` after each conversation is processed so the UI can show a
+/// progress bar; since the whole batch runs inside one transaction, nothing is actually committed
+/// until the last event fires.
 #[tauri::command]
-pub async fn add_message(
-    conversation_id: i64,
-    role: String,
-    content: String,
-    tokens_used: Option<i32>,
-    model_used: Option<String>,
+pub async fn bulk_update_conversations(
+    ids: Vec<i64>,
+    action: crate::models::BulkConversationAction,
     state: State<'_, AppState>,
-) -> Result<Message, String> {
-    tracing::debug!(
-        "Adding message to conversation {}: {} bytes",
-        conversation_id,
-        content.len()
-    );
-
-    // Validate message content
-    let validator = InputValidator::default();
-    let validated_content = validator.validate_message_content(&content)
-        .map_err(|e| format!("Invalid message content: {}", e))?;
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    use tauri::Manager;
 
-    let message_role = match role.as_str() {
-        "user" => MessageRole::User,
-        "assistant" => MessageRole::Assistant,
-        "system" => MessageRole::System,
-        _ => return Err(format!("Invalid role: {}", role)),
-    };
+    tracing::info!("Applying bulk action {:?} to {} conversations", action, ids.len());
+    let total = ids.len();
+    state
+        .services
+        .conversations
+        .bulk_update_conversations(&ids, &action, |processed| {
+            let _ = app_handle.emit_all(
+                "bulk-operation-progress",
+                BulkOperationProgressEvent { processed, total },
+            );
+        })
+        .map_err(|e| format!("Failed to apply bulk action: {}", e))
+}
 
+/// Freeze/unfreeze a conversation for compliance retention
+///
+/// While frozen, its messages can't be edited, regenerated, or deleted, and the conversation
+/// itself can't be deleted. Freeze a conversation before calling `export_compliance_package`
+/// so the exported record can't drift from what's on disk afterward.
+#[tauri::command]
+pub async fn freeze_conversation(
+    id: i64,
+    frozen: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    tracing::info!("Setting conversation {} frozen status to: {}", id, frozen);
     state
         .services
         .conversations
-        .add_message(
-            conversation_id,
-            message_role,
-            validated_content,
-            tokens_used,
-            model_used,
-        )
-        .map_err(|e| format!("Failed to add message: {}", e))
+        .set_conversation_frozen(id, frozen)
+        .map_err(|e| format!("Failed to set conversation frozen status: {}", e))
 }
 
+/// Fork a conversation at a given message, copying everything up to and including it into a new
+/// conversation, so exploring an alternative reply doesn't lose the original thread
 #[tauri::command]
-pub async fn get_messages(
+pub async fn fork_conversation(
     conversation_id: i64,
+    from_message_id: i64,
     state: State<'_, AppState>,
-) -> Result<Vec<Message>, String> {
-    tracing::debug!("Getting messages for conversation: {}", conversation_id);
+) -> Result<Conversation, String> {
+    tracing::info!(
+        "Forking conversation {} at message {}",
+        conversation_id,
+        from_message_id
+    );
     state
         .services
         .conversations
-        .get_messages(conversation_id)
-        .map_err(|e| format!("Failed to get messages: {}", e))
+        .fork_conversation(conversation_id, from_message_id)
+        .map_err(|e| format!("Failed to fork conversation: {}", e))
 }
 
-// ==================== PERSONA COMMANDS ====================
-
+/// Deep-copy a conversation and all of its messages into a new, independent conversation, for
+/// consolidating related research threads without disturbing the original
 #[tauri::command]
-pub async fn create_persona(
-    name: String,
-    description: Option<String>,
-    system_prompt: String,
+pub async fn duplicate_conversation(
+    conversation_id: i64,
     state: State<'_, AppState>,
-) -> Result<Persona, String> {
-    tracing::info!("Creating persona: {}", name);
-
-    // Validate persona name and prompt
-    let validator = InputValidator::default();
-    let validated_name = validator.validate_persona_name(&name)
-        .map_err(|e| format!("Invalid persona name: {}", e))?;
-    let validated_prompt = validator.validate_system_prompt(&system_prompt)
-        .map_err(|e| format!("Invalid system prompt: {}", e))?;
-
+) -> Result<Conversation, String> {
+    tracing::info!("Duplicating conversation {}", conversation_id);
     state
         .services
-        .personas
-        .create_persona(validated_name, description, validated_prompt)
-        .map_err(|e| format!("Failed to create persona: {}", e))
+        .conversations
+        .duplicate_conversation(conversation_id)
+        .map_err(|e| format!("Failed to duplicate conversation: {}", e))
 }
 
+/// Merge `source_id` into `target_id`: the source's messages are reassigned to the target and
+/// interleaved by timestamp, their tags are united, and the now-empty source is deleted
 #[tauri::command]
-pub async fn get_personas(state: State<'_, AppState>) -> Result<Vec<Persona>, String> {
-    tracing::debug!("Getting all personas");
+pub async fn merge_conversations(
+    source_id: i64,
+    target_id: i64,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    tracing::info!("Merging conversation {} into {}", source_id, target_id);
     state
         .services
-        .personas
-        .get_personas()
-        .map_err(|e| format!("Failed to get personas: {}", e))
+        .conversations
+        .merge_conversations(source_id, target_id)
+        .map_err(|e| format!("Failed to merge conversations: {}", e))
 }
 
+/// Where a conversation came from, for UI lineage breadcrumbs - `None` if it wasn't created via
+/// `fork_conversation`
 #[tauri::command]
-pub async fn get_persona(id: i64, state: State<'_, AppState>) -> Result<Option<Persona>, String> {
-    tracing::debug!("Getting persona with id: {}", id);
+pub async fn get_conversation_lineage(
+    conversation_id: i64,
+    state: State<'_, AppState>,
+) -> Result<Option<crate::services::ConversationLineage>, String> {
     state
         .services
-        .personas
-        .get_persona(id)
-        .map_err(|e| format!("Failed to get persona: {}", e))
+        .conversations
+        .get_conversation_lineage(conversation_id)
+        .map_err(|e| format!("Failed to get conversation lineage: {}", e))
 }
 
+/// Set this conversation's overrides for system prompt, model, temperature, max tokens, and API
+/// profile
+///
+/// Any field left `None` clears that override, falling back to the persona and then to the
+/// default - see `send_ai_request`'s conversation > persona > default resolution.
+/// `profile_name`, if set, is resolved by [`resolve_api_profile`] ahead of the provider's
+/// default profile in `send_ai_provider_request`/`stream_ai_provider_request`.
 #[tauri::command]
-pub async fn update_persona(
-    id: i64,
-    name: Option<String>,
-    description: Option<String>,
+pub async fn update_conversation_settings(
+    conversation_id: i64,
     system_prompt: Option<String>,
+    model: Option<String>,
+    temperature: Option<f32>,
+    max_tokens: Option<i32>,
+    profile_name: Option<String>,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    tracing::info!("Updating persona with id: {}", id);
+) -> Result<crate::models::ConversationSettings, String> {
     state
         .services
-        .personas
-        .update_persona(id, name, description, system_prompt)
-        .map_err(|e| format!("Failed to update persona: {}", e))
+        .conversations
+        .upsert_conversation_settings(conversation_id, system_prompt, model, temperature, max_tokens, profile_name)
+        .map_err(|e| format!("Failed to update conversation settings: {}", e))
 }
 
+/// Fetch this conversation's overrides, if any have been set via `update_conversation_settings`
 #[tauri::command]
-pub async fn delete_persona(id: i64, state: State<'_, AppState>) -> Result<(), String> {
-    tracing::info!("Deleting persona with id: {}", id);
+pub async fn get_conversation_settings(
+    conversation_id: i64,
+    state: State<'_, AppState>,
+) -> Result<Option<crate::models::ConversationSettings>, String> {
     state
         .services
-        .personas
-        .delete_persona(id)
-        .map_err(|e| format!("Failed to delete persona: {}", e))
+        .conversations
+        .get_conversation_settings(conversation_id)
+        .map_err(|e| format!("Failed to get conversation settings: {}", e))
 }
 
-// ==================== API CONFIGURATION COMMANDS ====================
-
+/// Export a frozen conversation as a tamper-evident compliance package
+///
+/// Returns an error if the conversation isn't frozen - see `freeze_conversation`.
 #[tauri::command]
-pub async fn store_api_config(
-    provider: String,
-    api_key: String,
-    base_url: Option<String>,
+pub async fn export_compliance_package(
+    conversation_id: i64,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    tracing::info!("Storing API config for provider: {}", provider);
-
-    // Validate API key
-    let validator = InputValidator::default();
-    let validated_api_key = validator.validate_api_key(&api_key)
-        .map_err(|e| format!("Invalid API key: {}", e))?;
+) -> Result<crate::compliance_export::ComplianceExport, String> {
+    tracing::info!("Exporting compliance package for conversation {}", conversation_id);
 
-    // Validate base URL if provided
-    let validated_base_url = if let Some(url) = base_url {
-        Some(validator.validate_url(&url)
-            .map_err(|e| format!("Invalid base URL: {}", e))?)
-    } else {
-        None
-    };
+    let conversation = state
+        .services
+        .conversations
+        .get_conversation(conversation_id)
+        .map_err(|e| format!("Failed to get conversation: {}", e))?
+        .ok_or_else(|| format!("Conversation {} not found", conversation_id))?;
 
-    state
+    let messages = state
         .services
-        .apis
-        .store_api_config(provider, validated_api_key, validated_base_url)
-        .map_err(|e| format!("Failed to store API config: {}", e))
+        .conversations
+        .get_messages(conversation_id, None, None)
+        .map_err(|e| format!("Failed to get messages: {}", e))?;
+
+    let export = crate::compliance_export::export_frozen_conversation(conversation, messages)
+        .map_err(|e| format!("Failed to export compliance package: {}", e))?;
+
+    let _ = state.services.audit_log.record(
+        AUDIT_ACTOR,
+        "compliance_export.export",
+        Some(&format!("conversation_id={}", conversation_id)),
+    );
+    Ok(export)
 }
 
+/// Export a conversation as a bundle a colleague without library access can review and annotate
+///
+/// Unlike `export_compliance_package`, this doesn't require the conversation to be frozen - see
+/// `import_review_annotations` for how the reviewer's comments come back.
 #[tauri::command]
-pub async fn get_api_config(
-    provider: String,
+pub async fn export_conversation_for_review(
+    conversation_id: i64,
     state: State<'_, AppState>,
-) -> Result<Option<(String, Option<String>)>, String> {
-    tracing::debug!("Getting API config for provider: {}", provider);
-    state
+) -> Result<crate::review_export::ReviewExport, String> {
+    tracing::info!("Exporting conversation {} for review", conversation_id);
+
+    let conversation = state
         .services
-        .apis
-        .get_api_config(&provider)
-        .map_err(|e| format!("Failed to get API config: {}", e))
+        .conversations
+        .get_conversation(conversation_id)
+        .map_err(|e| format!("Failed to get conversation: {}", e))?
+        .ok_or_else(|| format!("Conversation {} not found", conversation_id))?;
+
+    let messages = state
+        .services
+        .conversations
+        .get_messages(conversation_id, None, None)
+        .map_err(|e| format!("Failed to get messages: {}", e))?;
+
+    let export = crate::review_export::export_for_review(conversation, messages);
+
+    let _ = state.services.audit_log.record(
+        AUDIT_ACTOR,
+        "review_export.export",
+        Some(&format!("conversation_id={}", conversation_id)),
+    );
+    Ok(export)
 }
 
+/// Import the comments a reviewer left on a bundle produced by `export_conversation_for_review`
+///
+/// Returns the number of annotations stored. Annotations referencing a message id that isn't in
+/// the bundle, or carrying empty comment text, are dropped rather than failing the import.
 #[tauri::command]
-pub async fn delete_api_config(provider: String, state: State<'_, AppState>) -> Result<(), String> {
-    tracing::info!("Deleting API config for provider: {}", provider);
+pub async fn import_review_annotations(
+    reviewed_bundle: String,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
     state
         .services
-        .apis
-        .delete_api_config(&provider)
-        .map_err(|e| format!("Failed to delete API config: {}", e))
+        .conversations
+        .import_review_annotations(&reviewed_bundle)
+        .map_err(|e| format!("Failed to import review annotations: {}", e))
 }
 
-// ==================== SYSTEM COMMANDS ====================
+/// Package a conversation into a signed, self-contained snapshot (a `.flib` file), for sending
+/// to someone without giving them access to the rest of the library
+///
+/// Signed with a key local to this machine - see `crate::conversation_share`'s module doc for why
+/// that means `open_shared_snapshot` can only verify a snapshot on the same install that created
+/// it, not after it travels to someone else's machine.
+#[tauri::command]
+pub async fn share_conversation(conversation_id: i64, state: State<'_, AppState>) -> Result<String, String> {
+    tracing::info!("Sharing conversation {}", conversation_id);
+
+    let keychain = crate::keychain::KeychainManager::new();
+    let signing_key = keychain
+        .get_or_create_share_signing_key()
+        .map_err(|e| format!("Failed to load share signing key: {}", e))?;
+
+    let snapshot = crate::conversation_share::share_conversation(
+        &state.services.conversations,
+        &state.services.personas,
+        &signing_key,
+        conversation_id,
+    )
+    .map_err(|e| format!("Failed to share conversation: {}", e))?;
+
+    let _ = state.services.audit_log.record(
+        AUDIT_ACTOR,
+        "conversation.share",
+        Some(&format!("conversation_id={}", conversation_id)),
+    );
+    Ok(snapshot)
+}
 
+/// Open a snapshot produced by `share_conversation` for read-only viewing
+///
+/// Verifies the snapshot's signature before returning it; the conversation and messages inside
+/// are never written to the local database, so opening a shared snapshot never mixes someone
+/// else's conversation into this library.
 #[tauri::command]
-pub async fn get_database_stats(state: State<'_, AppState>) -> Result<DatabaseStats, String> {
-    tracing::debug!("Getting database statistics");
+pub async fn open_shared_snapshot(
+    snapshot_json: String,
+) -> Result<crate::conversation_share::ConversationShareSnapshot, String> {
+    let keychain = crate::keychain::KeychainManager::new();
+    let signing_key = keychain
+        .get_or_create_share_signing_key()
+        .map_err(|e| format!("Failed to load share signing key: {}", e))?;
+
+    crate::conversation_share::open_shared_snapshot(&signing_key, &snapshot_json)
+        .map_err(|e| format!("Failed to open shared snapshot: {}", e))
+}
 
-    // Get basic statistics about the database
-    let conversations_result = state
+/// Fetch the reviewer comments left on a message
+#[tauri::command]
+pub async fn get_message_annotations(
+    message_id: i64,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::MessageAnnotation>, String> {
+    state
         .services
         .conversations
-        .get_conversations(Some(1), None);
-    let personas_result = state.services.personas.get_personas();
-
-    let total_conversations = match conversations_result {
-        Ok(conversations) => conversations.len() as i64,
-        Err(_) => 0i64,
-    };
-
-    let total_personas = match personas_result {
-        Ok(personas) => personas.len() as i64,
-        Err(_) => 0i64,
-    };
-
-    // Count total messages - simplified approach
-    // Note: For a more accurate count, consider adding a count_messages method to ConversationService
-    let total_messages = 0i64;
-
-    // Calculate database size (simplified - returns 0 for now)
-    // Note: Can be enhanced by adding a method to DatabaseManager that queries PRAGMA page_count/page_size
-    let database_size_mb = 0.0;
-
-    Ok(DatabaseStats {
-        total_conversations,
-        total_personas,
-        total_messages,
-        database_size_mb,
-    })
+        .get_annotations_for_message(message_id)
+        .map_err(|e| format!("Failed to get message annotations: {}", e))
 }
 
-/// Database statistics structure
-#[derive(Serialize)]
-pub struct DatabaseStats {
-    pub total_conversations: i64,
-    pub total_personas: i64,
-    pub total_messages: i64,
-    pub database_size_mb: f64,
+/// Compact an archived conversation's messages into a single compressed blob in cold storage
+///
+/// Returns the number of messages that were compacted. No-op (returns 0) if the conversation
+/// has no messages. Reopening the conversation afterwards transparently decompresses the blob.
+#[tauri::command]
+pub async fn compact_archived_conversation(
+    id: i64,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    tracing::info!("Compacting archived conversation {} to cold storage", id);
+    state
+        .services
+        .conversations
+        .compact_archived_conversation(id)
+        .map_err(|e| format!("Failed to compact conversation: {}", e))
 }
 
-// ==================== AI INTEGRATION COMMANDS ====================
-
+/// Word/character/token statistics for a conversation, useful when writing reports
 #[tauri::command]
-pub async fn send_ai_request(
-    message: String,
-    persona_id: Option<i64>,
-    conversation_id: Option<i64>,
-    model: Option<String>,
+pub async fn get_conversation_statistics(
+    id: i64,
     state: State<'_, AppState>,
-) -> Result<AiResponse, String> {
-    tracing::info!(
-        "Processing AI request for conversation: {:?}",
-        conversation_id
-    );
-
-    // Get the persona if specified
-    let persona = if let Some(pid) = persona_id {
-        match state.services.personas.get_persona(pid) {
-            Ok(Some(p)) => Some(p),
-            Ok(None) => return Err(format!("Persona with ID {} not found", pid)),
-            Err(e) => return Err(format!("Failed to get persona: {}", e)),
-        }
-    } else {
-        None
-    };
-
-    // Use the persona's system prompt if available
-    let system_prompt = persona
-        .map(|p| p.system_prompt)
-        .unwrap_or_else(|| "You are a helpful assistant.".to_string());
-
-    // In a real implementation, this would call an AI service
-    // For now, we'll return a more sophisticated mock response
-    let start_time = std::time::Instant::now();
+) -> Result<crate::services::ConversationStatistics, String> {
+    state
+        .services
+        .conversations
+        .get_conversation_statistics(id)
+        .map_err(|e| format!("Failed to compute conversation statistics: {}", e))
+}
 
-    // Simulate processing time
-    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+/// Classify a conversation's topic and sentiment using keyword heuristics and persist the
+/// result for later filtering
+#[tauri::command]
+pub async fn analyze_conversation(
+    conversation_id: i64,
+    state: State<'_, AppState>,
+) -> Result<crate::tagging::ConversationAnalysis, String> {
+    state
+        .services
+        .conversations
+        .analyze_conversation(conversation_id)
+        .map_err(|e| format!("Failed to analyze conversation: {}", e))
+}
 
-    let response_content = format!(
-        "I've received your message: \"{}\". \n\nThis is a simulated response from the Forbidden Library AI assistant. In the full implementation, this would connect to an actual AI model.\n\nSystem context: {}", 
-        message,
-        system_prompt
-    );
+/// Get a conversation's most recently computed topic/sentiment analysis, if any
+#[tauri::command]
+pub async fn get_conversation_analysis(
+    conversation_id: i64,
+    state: State<'_, AppState>,
+) -> Result<Option<crate::tagging::ConversationAnalysis>, String> {
+    state
+        .services
+        .conversations
+        .get_conversation_analysis(conversation_id)
+        .map_err(|e| format!("Failed to get conversation analysis: {}", e))
+}
 
-    let processing_time = start_time.elapsed().as_millis() as i64;
+/// Find conversations matching an analyzed sentiment and/or topic, updated no earlier than
+/// `since_rfc3339` - powers filters like "show frustrated support threads from last month"
+#[tauri::command]
+pub async fn filter_conversations_by_analysis(
+    sentiment: Option<String>,
+    topic: Option<String>,
+    since_rfc3339: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Conversation>, String> {
+    let sentiment = sentiment.as_deref().map(crate::tagging::Sentiment::parse);
+    let since = since_rfc3339
+        .as_deref()
+        .map(DateTime::parse_from_rfc3339)
+        .transpose()
+        .map_err(|e| format!("Invalid since timestamp: {}", e))?
+        .map(|dt| dt.with_timezone(&Utc));
 
-    Ok(AiResponse {
-        content: response_content,
-        model_used: model.unwrap_or_else(|| "forbidden-library-v1".to_string()),
-        tokens_used: message.len() as i32 + 200, // Simulate token counting
-        processing_time_ms: processing_time,
-    })
+    state
+        .services
+        .conversations
+        .filter_by_analysis(sentiment, topic.as_deref(), since)
+        .map_err(|e| format!("Failed to filter conversations: {}", e))
 }
 
-/// AI response structure
-#[derive(Serialize)]
-pub struct AiResponse {
-    pub content: String,
-    pub model_used: String,
-    pub tokens_used: i32,
-    pub processing_time_ms: i64,
-}
+// ==================== METADATA SUGGESTION COMMANDS ====================
 
-// ==================== FILE MANAGEMENT COMMANDS ====================
+/// A proposed title/tags pair for a conversation, for the UI to offer as a one-click fix for
+/// vague defaults like "New Conversation (7)"
+#[derive(Debug, Serialize)]
+pub struct MetadataSuggestion {
+    pub suggested_title: String,
+    pub suggested_tags: Vec<String>,
+}
 
+/// Propose a better title and relevant tags for a conversation based on its current content
+///
+/// Tags always come from the local keyword heuristic in [`crate::tagging`] - there's no
+/// provider-backed tagger in this tree. The title comes from `strategy` (or, if unset, the
+/// size-based default from [`crate::summarization::default_strategy_for`]): extractive picks a
+/// representative sentence locally, abstractive asks `provider_type`/`model` for a short title,
+/// with credentials loaded from the active `api_configs` entry for `provider_type` when present.
+/// Nothing is persisted; callers apply the suggestion via `update_conversation_title`/tagging
+/// commands once the user accepts it.
 #[tauri::command]
-pub async fn export_conversation(
+pub async fn suggest_metadata(
     conversation_id: i64,
-    format: String,
+    provider_type: String,
+    model: String,
+    strategy: Option<crate::summarization::SummarizationStrategy>,
     state: State<'_, AppState>,
-) -> Result<String, String> {
-    tracing::info!("Exporting conversation {} as {}", conversation_id, format);
-
-    let conversation = state
-        .services
-        .conversations
-        .get_conversation(conversation_id)
-        .map_err(|e| format!("Failed to get conversation: {}", e))?;
+) -> Result<MetadataSuggestion, String> {
+    use crate::summarization::{
+        default_strategy_for, AbstractiveSummarizer, ExtractiveSummarizer, Summarizer,
+        SummarizationStrategy,
+    };
 
     let messages = state
         .services
         .conversations
-        .get_messages(conversation_id)
-        .map_err(|e| format!("Failed to get messages: {}", e))?;
-
-    match format.as_str() {
-        "json" => {
-            let export_data = serde_json::json!({
-                "conversation": conversation,
-                "messages": messages,
-                "exported_at": chrono::Utc::now().to_rfc3339(),
-                "version": env!("CARGO_PKG_VERSION")
-            });
+        .get_messages(conversation_id, None, None)
+        .map_err(|e| format!("Failed to load conversation messages: {}", e))?;
 
-            serde_json::to_string_pretty(&export_data)
-                .map_err(|e| format!("Failed to serialize conversation: {}", e))
-        }
-        "markdown" => {
-            let mut markdown = String::new();
+    if messages.is_empty() {
+        return Err("Conversation has no messages to analyze".to_string());
+    }
 
-            if let Some(conv) = conversation {
-                markdown.push_str(&format!("# {}\n\n", conv.title));
-                markdown.push_str(&format!(
-                    "**Created:** {}\n\n",
-                    conv.created_at.format("%Y-%m-%d %H:%M:%S UTC")
-                ));
+    let combined_text = messages
+        .iter()
+        .map(|m| m.content.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
 
-                for message in messages {
-                    let role = match message.role {
-                        MessageRole::User => "**User:**",
-                        MessageRole::Assistant => "**Assistant:**",
-                        MessageRole::System => "**System:**",
-                    };
+    let analysis = crate::tagging::classify_conversation(&combined_text);
 
-                    markdown.push_str(&format!("{} {}\n\n", role, message.content));
-                    markdown.push_str("---\n\n");
-                }
-            }
+    let strategy = strategy.unwrap_or_else(|| default_strategy_for(&combined_text));
 
-            Ok(markdown)
+    let raw_title = match strategy {
+        SummarizationStrategy::Extractive => ExtractiveSummarizer { max_sentences: 1 }
+            .summarize(&combined_text)
+            .await
+            .map_err(|e| format!("Failed to suggest title: {}", e))?,
+        SummarizationStrategy::Abstractive => {
+            let stored_config = state
+                .services
+                .apis
+                .get_api_config(&provider_type)
+                .map_err(|e| format!("Failed to load stored API config: {}", e))?;
+
+            let (api_key, base_url, organization) = match stored_config {
+                Some(config) => (Some(config.api_key), config.base_url, config.organization),
+                None => (None, None, None),
+            };
+
+            let provider = create_ai_provider(provider_type, api_key, base_url, None, None, None, organization, None)?;
+            let summarizer = AbstractiveSummarizer::new(provider, model).with_instruction(
+                "Generate a short, descriptive title (5 words or fewer, no quotes or punctuation at the end) for this conversation:",
+            );
+            summarizer
+                .summarize(&combined_text)
+                .await
+                .map_err(|e| format!("Failed to suggest title: {}", e))?
         }
-        _ => Err(format!("Unsupported export format: {}", format)),
-    }
+    };
+
+    Ok(MetadataSuggestion {
+        suggested_title: raw_title.trim().trim_matches('"').to_string(),
+        suggested_tags: analysis.tags,
+    })
 }
 
-/// Import conversation from JSON file
+// ==================== MESSAGE COMMANDS ====================
+
 #[tauri::command]
-pub async fn import_conversation(
-    json_data: String,
+pub async fn add_message(
+    conversation_id: i64,
+    role: String,
+    content: String,
+    tokens_used: Option<i32>,
+    model_used: Option<String>,
+    tool_call_id: Option<String>,
     state: State<'_, AppState>,
-) -> Result<i64, String> {
-    tracing::info!("Importing conversation from JSON data");
-
-    // Parse the JSON data
-    let import_data: serde_json::Value = serde_json::from_str(&json_data)
-        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+) -> Result<Message, String> {
+    tracing::debug!(
+        "Adding message to conversation {}: {} bytes",
+        conversation_id,
+        content.len()
+    );
 
-    // Validate the format
-    let conversation_data = import_data["conversation"]
+    // Validate message content
+    let validator = InputValidator::default();
+    let validated_content = validator.validate_message_content(&content)
+        .map_err(|e| format!("Invalid message content: {}", e))?;
+
+    let message_role = match role.as_str() {
+        "user" => MessageRole::User,
+        "assistant" => MessageRole::Assistant,
+        "system" => MessageRole::System,
+        "tool" => MessageRole::Tool,
+        _ => return Err(format!("Invalid role: {}", role)),
+    };
+
+    let message = state
+        .services
+        .conversations
+        .add_message(
+            conversation_id,
+            message_role,
+            validated_content,
+            tokens_used,
+            model_used,
+            tool_call_id,
+        )
+        .map_err(|e| format!("Failed to add message: {}", e))?;
+
+    // Notify any webhooks registered for this conversation or its tags in the background, so a
+    // slow or unreachable endpoint never delays or fails message persistence itself.
+    if message.role == MessageRole::Assistant {
+        let db = state.services.conversations.db.clone();
+        let notified_message = message.clone();
+        tokio::spawn(async move {
+            crate::webhooks::notify_assistant_message(&db, conversation_id, &notified_message).await;
+        });
+    }
+
+    Ok(message)
+}
+
+#[tauri::command]
+pub async fn get_messages(
+    conversation_id: i64,
+    limit: Option<i32>,
+    offset: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Message>, String> {
+    tracing::debug!("Getting messages for conversation: {}", conversation_id);
+    state
+        .services
+        .conversations
+        .get_messages(conversation_id, limit, offset)
+        .map_err(|e| format!("Failed to get messages: {}", e))
+}
+
+/// Count a conversation's messages, for frontend virtualization alongside `get_messages`
+#[tauri::command]
+pub async fn count_messages(conversation_id: i64, state: State<'_, AppState>) -> Result<i64, String> {
+    state
+        .services
+        .conversations
+        .count_messages(conversation_id)
+        .map_err(|e| format!("Failed to count messages: {}", e))
+}
+
+/// Delta of message changes since a client's last-seen cursor
+#[derive(Debug, Serialize)]
+pub struct MessagesDelta {
+    pub messages: Vec<Message>,
+    /// Highest message id included in this delta; pass back as `since_id` next poll
+    pub cursor: i64,
+}
+
+/// Get only messages added to a conversation since the client's last cursor
+///
+/// Intended for polling or resume-after-sleep, so the whole conversation history isn't
+/// re-transferred over IPC every time. `since_id` is the cursor from a previous call's
+/// response (or `0` to fetch from the beginning).
+#[tauri::command]
+pub async fn get_messages_since(
+    conversation_id: i64,
+    since_id: i64,
+    state: State<'_, AppState>,
+) -> Result<MessagesDelta, String> {
+    let messages = state
+        .services
+        .conversations
+        .get_messages_since(conversation_id, since_id)
+        .map_err(|e| format!("Failed to get messages since {}: {}", since_id, e))?;
+
+    let cursor = messages
+        .iter()
+        .filter_map(|m| m.id)
+        .max()
+        .unwrap_or(since_id);
+
+    Ok(MessagesDelta { messages, cursor })
+}
+
+/// Replace a message's content with a regenerated version and return the word-level diff
+/// against what it replaced, for the UI to render without a client-side diff library
+#[tauri::command]
+pub async fn regenerate_message(
+    message_id: i64,
+    new_content: String,
+    state: State<'_, AppState>,
+) -> Result<crate::models::MessageRegeneration, String> {
+    tracing::info!("Regenerating message {}", message_id);
+    state
+        .services
+        .conversations
+        .regenerate_message(message_id, new_content)
+        .map_err(|e| format!("Failed to regenerate message: {}", e))
+}
+
+/// List every recorded regeneration for a message, oldest first
+#[tauri::command]
+pub async fn get_message_regenerations(
+    message_id: i64,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::MessageRegeneration>, String> {
+    state
+        .services
+        .conversations
+        .get_message_regenerations(message_id)
+        .map_err(|e| format!("Failed to get message regenerations: {}", e))
+}
+
+/// Overwrite a message's content in place, for correcting a message the user wrote themselves
+#[tauri::command]
+pub async fn update_message(
+    message_id: i64,
+    new_content: String,
+    state: State<'_, AppState>,
+) -> Result<Message, String> {
+    let validator = InputValidator::default();
+    let validated_content = validator
+        .validate_message_content(&new_content)
+        .map_err(|e| format!("Invalid message content: {}", e))?;
+
+    state
+        .services
+        .conversations
+        .update_message(message_id, validated_content)
+        .map_err(|e| format!("Failed to update message {}: {}", message_id, e))
+}
+
+/// Delete a message, cascading to its regeneration history
+#[tauri::command]
+pub async fn delete_message(message_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .services
+        .conversations
+        .delete_message(message_id)
+        .map_err(|e| format!("Failed to delete message {}: {}", message_id, e))
+}
+
+/// Pin a message so it's kept in context assembly and surfaced by [`get_pinned_messages`]
+#[tauri::command]
+pub async fn pin_message(message_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .services
+        .conversations
+        .pin_message(message_id)
+        .map_err(|e| format!("Failed to pin message {}: {}", message_id, e))
+}
+
+/// Unpin a previously pinned message
+#[tauri::command]
+pub async fn unpin_message(message_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .services
+        .conversations
+        .unpin_message(message_id)
+        .map_err(|e| format!("Failed to unpin message {}: {}", message_id, e))
+}
+
+/// Attach a named flag (e.g. "pinned") to a message
+#[tauri::command]
+pub async fn set_message_flag(
+    message_id: i64,
+    flag: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .services
+        .conversations
+        .set_message_flag(message_id, &flag)
+        .map_err(|e| format!("Failed to set flag '{}' on message {}: {}", flag, message_id, e))
+}
+
+/// Every message pinned in a conversation, oldest first
+#[tauri::command]
+pub async fn get_pinned_messages(
+    conversation_id: i64,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::Message>, String> {
+    state
+        .services
+        .conversations
+        .get_pinned_messages(conversation_id)
+        .map_err(|e| format!("Failed to get pinned messages for conversation {}: {}", conversation_id, e))
+}
+
+/// Validate and copy a file into app-managed storage as an attachment on a message
+///
+/// Identical content is deduped on disk - see `crate::services::AttachmentService`.
+#[tauri::command]
+pub async fn add_attachment(
+    message_id: i64,
+    file_path: String,
+    state: State<'_, AppState>,
+) -> Result<crate::models::MessageAttachment, String> {
+    tracing::info!("Adding attachment to message {}", message_id);
+    state
+        .services
+        .attachments
+        .add_attachment(message_id, &file_path)
+        .map_err(|e| format!("Failed to add attachment: {}", e))
+}
+
+/// List the attachments recorded for a message
+#[tauri::command]
+pub async fn get_attachments(
+    message_id: i64,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::MessageAttachment>, String> {
+    state
+        .services
+        .attachments
+        .get_attachments(message_id)
+        .map_err(|e| format!("Failed to get attachments: {}", e))
+}
+
+/// Remove an attachment record, deleting its underlying file once no other attachment still
+/// references the same content
+#[tauri::command]
+pub async fn remove_attachment(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .services
+        .attachments
+        .remove_attachment(&id)
+        .map_err(|e| format!("Failed to remove attachment: {}", e))
+}
+
+/// Register a webhook that fires when a new assistant message arrives, either for one specific
+/// conversation (`scope = "conversation"`, `scope_value` a conversation id) or for any
+/// conversation carrying a tag (`scope = "tag"`, `scope_value` the tag name). Deliveries are
+/// HMAC-signed with `secret` - see `crate::webhooks`.
+#[tauri::command]
+pub async fn register_webhook(
+    scope: String,
+    scope_value: String,
+    url: String,
+    secret: String,
+    state: State<'_, AppState>,
+) -> Result<crate::webhooks::Webhook, String> {
+    let scope = match scope.as_str() {
+        "conversation" => crate::webhooks::WebhookScope::Conversation,
+        "tag" => crate::webhooks::WebhookScope::Tag,
+        _ => return Err(format!("Invalid webhook scope: {}", scope)),
+    };
+
+    state
+        .services
+        .webhooks
+        .register_webhook(scope, scope_value, url, secret)
+        .map_err(|e| format!("Failed to register webhook: {}", e))
+}
+
+/// List all registered webhooks, newest first. Secrets are never included in the response.
+#[tauri::command]
+pub async fn list_webhooks(state: State<'_, AppState>) -> Result<Vec<crate::webhooks::Webhook>, String> {
+    state
+        .services
+        .webhooks
+        .list_webhooks()
+        .map_err(|e| format!("Failed to list webhooks: {}", e))
+}
+
+/// Delete a webhook registration
+#[tauri::command]
+pub async fn delete_webhook(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .services
+        .webhooks
+        .delete_webhook(&id)
+        .map_err(|e| format!("Failed to delete webhook: {}", e))
+}
+
+/// Start the read-only HTTP snapshot server, exposing `conversation_ids` as sanitized HTML pages
+/// gated behind `token`. Starting a new server while one is already running replaces it - see
+/// `crate::services::SnapshotServerService`.
+#[tauri::command]
+pub async fn start_snapshot_server(
+    bind_addr: String,
+    port: u16,
+    token: String,
+    conversation_ids: Vec<i64>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    tracing::info!("Starting snapshot server on {}:{}", bind_addr, port);
+    state
+        .services
+        .snapshot_server
+        .start(crate::snapshot_server::SnapshotServerConfig {
+            bind_addr,
+            port,
+            token,
+            conversation_ids,
+        })
+        .map_err(|e| format!("Failed to start snapshot server: {}", e))
+}
+
+/// Stop the read-only HTTP snapshot server; a no-op if it isn't running
+#[tauri::command]
+pub async fn stop_snapshot_server(state: State<'_, AppState>) -> Result<(), String> {
+    state.services.snapshot_server.stop();
+    Ok(())
+}
+
+/// Whether the read-only HTTP snapshot server is currently running
+#[tauri::command]
+pub async fn get_snapshot_server_status(state: State<'_, AppState>) -> Result<bool, String> {
+    Ok(state.services.snapshot_server.is_running())
+}
+
+/// Replace runs of old low-value messages in a conversation with generated summaries, to keep
+/// long conversations cheap to resend as context without losing substantive content
+#[tauri::command]
+pub async fn compact_history(
+    conversation_id: i64,
+    state: State<'_, AppState>,
+) -> Result<crate::models::HistoryCompactionReport, String> {
+    tracing::info!("Compacting history for conversation {}", conversation_id);
+    state
+        .services
+        .conversations
+        .compact_history(conversation_id)
+        .map_err(|e| format!("Failed to compact conversation history: {}", e))
+}
+
+// ==================== PERSONA COMMANDS ====================
+
+#[tauri::command]
+pub async fn create_persona(
+    name: String,
+    description: Option<String>,
+    system_prompt: String,
+    state: State<'_, AppState>,
+) -> Result<Persona, String> {
+    tracing::info!("Creating persona: {}", name);
+
+    // Validate persona name and prompt
+    let validator = InputValidator::default();
+    let validated_name = validator.validate_persona_name(&name)
+        .map_err(|e| format!("Invalid persona name: {}", e))?;
+    let validated_prompt = validator.validate_system_prompt(&system_prompt)
+        .map_err(|e| format!("Invalid system prompt: {}", e))?;
+
+    state
+        .services
+        .personas
+        .create_persona(validated_name, description, validated_prompt)
+        .map_err(|e| format!("Failed to create persona: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_personas(state: State<'_, AppState>) -> Result<Vec<Persona>, String> {
+    tracing::debug!("Getting all personas");
+    state
+        .services
+        .personas
+        .get_personas()
+        .map_err(|e| format!("Failed to get personas: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_persona(id: i64, state: State<'_, AppState>) -> Result<Option<Persona>, String> {
+    tracing::debug!("Getting persona with id: {}", id);
+    state
+        .services
+        .personas
+        .get_persona(id)
+        .map_err(|e| format!("Failed to get persona: {}", e))
+}
+
+#[tauri::command]
+pub async fn update_persona(
+    id: i64,
+    name: Option<String>,
+    description: Option<String>,
+    system_prompt: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    tracing::info!("Updating persona with id: {}", id);
+    state
+        .services
+        .personas
+        .update_persona(id, name, description, system_prompt)
+        .map_err(|e| format!("Failed to update persona: {}", e))
+}
+
+#[tauri::command]
+pub async fn delete_persona(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    tracing::info!("Deleting persona with id: {}", id);
+    state
+        .services
+        .personas
+        .delete_persona(id)
+        .map_err(|e| format!("Failed to delete persona: {}", e))?;
+
+    let _ = state
+        .services
+        .audit_log
+        .record(AUDIT_ACTOR, "persona.delete", Some(&format!("persona_id={}", id)));
+    Ok(())
+}
+
+/// Set a persona's avatar from an image file on disk, copying it into an app-managed avatars
+/// directory. Returns the stored filename.
+#[tauri::command]
+pub async fn set_persona_avatar(
+    persona_id: i64,
+    image_path: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    tracing::info!("Setting avatar for persona {}", persona_id);
+    state
+        .services
+        .personas
+        .set_persona_avatar(persona_id, &image_path)
+        .map_err(|e| format!("Failed to set persona avatar: {}", e))
+}
+
+/// Absolute path to a persona's avatar image, if one has been set
+#[tauri::command]
+pub async fn get_persona_avatar(
+    persona_id: i64,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    state
+        .services
+        .personas
+        .get_persona_avatar(persona_id)
+        .map(|path| path.map(|p| p.to_string_lossy().to_string()))
+        .map_err(|e| format!("Failed to get persona avatar: {}", e))
+}
+
+/// Remember a new fact about a persona's user, to be injected into the system prompt on future
+/// [`send_ai_request`] calls with this persona
+#[tauri::command]
+pub async fn append_persona_memory(
+    persona_id: i64,
+    fact: String,
+    relevance_score: Option<f32>,
+    state: State<'_, AppState>,
+) -> Result<crate::models::PersonaMemory, String> {
+    state
+        .services
+        .persona_memories
+        .append_persona_memory(persona_id, fact, relevance_score)
+        .map_err(|e| format!("Failed to append persona memory: {}", e))
+}
+
+/// List everything remembered about a persona's user, most relevant first
+#[tauri::command]
+pub async fn get_persona_memory(
+    persona_id: i64,
+    limit: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::PersonaMemory>, String> {
+    state
+        .services
+        .persona_memories
+        .get_persona_memory(persona_id, limit)
+        .map_err(|e| format!("Failed to get persona memory: {}", e))
+}
+
+/// Forget everything remembered about a persona's user
+#[tauri::command]
+pub async fn clear_persona_memory(persona_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .services
+        .persona_memories
+        .clear_persona_memory(persona_id)
+        .map_err(|e| format!("Failed to clear persona memory: {}", e))
+}
+
+// ==================== PROJECT COMMANDS ====================
+
+#[tauri::command]
+pub async fn create_project(
+    name: String,
+    description: Option<String>,
+    repository_url: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::models::Project, String> {
+    tracing::info!("Creating project: {}", name);
+    state
+        .services
+        .projects
+        .create_project(name, description, repository_url)
+        .map_err(|e| format!("Failed to create project: {}", e))
+}
+
+#[tauri::command]
+pub async fn update_project(
+    id: String,
+    name: Option<String>,
+    description: Option<String>,
+    repository_url: Option<String>,
+    status: Option<crate::models::ProjectStatus>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    tracing::info!("Updating project with id: {}", id);
+    state
+        .services
+        .projects
+        .update_project(&id, name, description, repository_url, status)
+        .map_err(|e| format!("Failed to update project: {}", e))
+}
+
+#[tauri::command]
+pub async fn list_projects(state: State<'_, AppState>) -> Result<Vec<crate::models::Project>, String> {
+    tracing::debug!("Listing all projects");
+    state
+        .services
+        .projects
+        .list_projects()
+        .map_err(|e| format!("Failed to list projects: {}", e))
+}
+
+#[tauri::command]
+pub async fn archive_project(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    tracing::info!("Archiving project with id: {}", id);
+    state
+        .services
+        .projects
+        .archive_project(&id)
+        .map_err(|e| format!("Failed to archive project: {}", e))
+}
+
+/// Attach a conversation to a project so it's included in `get_project_context`
+#[tauri::command]
+pub async fn link_conversation_to_project(
+    project_id: String,
+    conversation_id: i64,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .services
+        .projects
+        .link_conversation(&project_id, conversation_id)
+        .map_err(|e| format!("Failed to link conversation to project: {}", e))
+}
+
+/// Detach a conversation from a project
+#[tauri::command]
+pub async fn unlink_conversation_from_project(
+    project_id: String,
+    conversation_id: i64,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .services
+        .projects
+        .unlink_conversation(&project_id, conversation_id)
+        .map_err(|e| format!("Failed to unlink conversation from project: {}", e))
+}
+
+/// Everything a provider needs to reason about a project: the project itself plus every
+/// conversation linked to it, for assembling project-level AI context server-side instead of
+/// making the frontend fetch and stitch each linked conversation individually
+#[derive(Debug, Serialize)]
+pub struct ProjectContext {
+    pub project: crate::models::Project,
+    pub linked_conversations: Vec<Conversation>,
+}
+
+#[tauri::command]
+pub async fn get_project_context(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<ProjectContext, String> {
+    let project = state
+        .services
+        .projects
+        .get_project(&project_id)
+        .map_err(|e| format!("Failed to get project: {}", e))?
+        .ok_or_else(|| format!("Project {} not found", project_id))?;
+
+    let conversation_ids = state
+        .services
+        .projects
+        .linked_conversation_ids(&project_id)
+        .map_err(|e| format!("Failed to get linked conversations: {}", e))?;
+
+    let mut linked_conversations = Vec::new();
+    for id in conversation_ids {
+        if let Some(conversation) = state
+            .services
+            .conversations
+            .get_conversation(id)
+            .map_err(|e| format!("Failed to get conversation {}: {}", id, e))?
+        {
+            linked_conversations.push(conversation);
+        }
+    }
+
+    Ok(ProjectContext {
+        project,
+        linked_conversations,
+    })
+}
+
+// ==================== API CONFIGURATION COMMANDS ====================
+
+/// Store a named API profile for `provider`. `profile_name` defaults to `provider` when
+/// omitted, preserving the historical one-profile-per-provider behavior for callers that don't
+/// care about naming multiple profiles (e.g. "work-openai", "personal-openai").
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn store_api_config(
+    provider: String,
+    api_key: String,
+    base_url: Option<String>,
+    organization: Option<String>,
+    project: Option<String>,
+    extra_headers: Option<Vec<(String, String)>>,
+    rate_limits: Option<crate::models::RateLimits>,
+    profile_name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let profile_name = profile_name.unwrap_or_else(|| provider.clone());
+    tracing::info!("Storing API profile '{}' for provider: {}", profile_name, provider);
+
+    // Validate API key
+    let validator = InputValidator::default();
+    let validated_api_key = validator.validate_api_key(&api_key)
+        .map_err(|e| format!("Invalid API key: {}", e))?;
+
+    // Validate base URL if provided
+    let validated_base_url = if let Some(url) = base_url {
+        Some(validator.validate_url(&url)
+            .map_err(|e| format!("Invalid base URL: {}", e))?)
+    } else {
+        None
+    };
+
+    let validated_headers = extra_headers
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, value)| validator.validate_http_header(&name, &value))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Invalid header: {}", e))?;
+
+    let audit_profile_name = profile_name.clone();
+    let audit_provider = provider.clone();
+
+    state
+        .services
+        .apis
+        .store_api_config(
+            profile_name,
+            provider,
+            validated_api_key,
+            validated_base_url,
+            organization,
+            project,
+            validated_headers,
+            rate_limits,
+        )
+        .map_err(|e| format!("Failed to store API config: {}", e))?;
+
+    let _ = state.services.audit_log.record(
+        AUDIT_ACTOR,
+        "api_config.store",
+        Some(&format!("profile={} provider={}", audit_profile_name, audit_provider)),
+    );
+    Ok(())
+}
+
+/// Get `provider`'s default profile - see [`get_api_profile`] to look up a specific named one
+#[tauri::command]
+pub async fn get_api_config(
+    provider: String,
+    state: State<'_, AppState>,
+) -> Result<Option<crate::models::ApiConfig>, String> {
+    tracing::debug!("Getting API config for provider: {}", provider);
+    state
+        .services
+        .apis
+        .get_api_config(&provider)
+        .map_err(|e| format!("Failed to get API config: {}", e))
+}
+
+/// Get a specific named API profile, regardless of whether it's its provider's default
+#[tauri::command]
+pub async fn get_api_profile(
+    profile_name: String,
+    state: State<'_, AppState>,
+) -> Result<Option<crate::models::ApiConfig>, String> {
+    state
+        .services
+        .apis
+        .get_api_profile(&profile_name)
+        .map_err(|e| format!("Failed to get API profile: {}", e))
+}
+
+/// List stored API profiles, optionally filtered to a single provider
+#[tauri::command]
+pub async fn list_api_profiles(
+    provider: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::ApiProfileSummary>, String> {
+    state
+        .services
+        .apis
+        .list_api_profiles(provider.as_deref())
+        .map_err(|e| format!("Failed to list API profiles: {}", e))
+}
+
+/// Make `profile_name` its provider's default profile
+#[tauri::command]
+pub async fn set_default_profile(profile_name: String, state: State<'_, AppState>) -> Result<(), String> {
+    tracing::info!("Setting default API profile: {}", profile_name);
+    state
+        .services
+        .apis
+        .set_default_profile(&profile_name)
+        .map_err(|e| format!("Failed to set default API profile: {}", e))
+}
+
+#[tauri::command]
+pub async fn delete_api_config(profile_name: String, state: State<'_, AppState>) -> Result<(), String> {
+    tracing::info!("Deleting API profile: {}", profile_name);
+    state
+        .services
+        .apis
+        .delete_api_config(&profile_name)
+        .map_err(|e| format!("Failed to delete API config: {}", e))?;
+
+    let _ = state.services.audit_log.record(
+        AUDIT_ACTOR,
+        "api_config.delete",
+        Some(&format!("profile={}", profile_name)),
+    );
+    Ok(())
+}
+
+// ==================== SYSTEM COMMANDS ====================
+
+#[tauri::command]
+pub async fn get_database_stats(state: State<'_, AppState>) -> Result<DatabaseStats, String> {
+    tracing::debug!("Getting database statistics");
+
+    // Get basic statistics about the database
+    let conversations_result = state
+        .services
+        .conversations
+        .get_conversations(Some(1), None);
+    let personas_result = state.services.personas.get_personas();
+
+    let total_conversations = match conversations_result {
+        Ok(conversations) => conversations.len() as i64,
+        Err(_) => 0i64,
+    };
+
+    let total_personas = match personas_result {
+        Ok(personas) => personas.len() as i64,
+        Err(_) => 0i64,
+    };
+
+    // Count total messages - simplified approach
+    // Note: For a more accurate count, consider adding a count_messages method to ConversationService
+    let total_messages = 0i64;
+
+    // Calculate database size (simplified - returns 0 for now)
+    // Note: Can be enhanced by adding a method to DatabaseManager that queries PRAGMA page_count/page_size
+    let database_size_mb = 0.0;
+
+    let last_maintenance = state
+        .services
+        .conversations
+        .db
+        .last_maintenance()
+        .map_err(|e| format!("Failed to read maintenance status: {}", e))?;
+
+    Ok(DatabaseStats {
+        total_conversations,
+        total_personas,
+        total_messages,
+        database_size_mb,
+        last_maintenance,
+    })
+}
+
+/// Run a database maintenance pass on demand: checkpoint the WAL and reclaim freed pages via
+/// incremental vacuum. Runs automatically every hour via [`crate::maintenance_scheduler`]; this
+/// command exists for a "Run maintenance now" action in a diagnostics panel.
+#[tauri::command]
+pub async fn run_maintenance(
+    state: State<'_, AppState>,
+) -> Result<crate::models::DatabaseMaintenanceReport, String> {
+    state
+        .services
+        .conversations
+        .db
+        .run_maintenance()
+        .map_err(|e| format!("Failed to run database maintenance: {}", e))
+}
+
+/// Gather app version, OS info, database stats, and whatever else this build can capture about
+/// the environment into a single gzip-compressed file a user can attach to a bug report
+///
+/// Writes straight to `output_path` like `export_library` does, rather than returning the bytes
+/// over IPC. See [`crate::bug_report`] for what's included and what can't be (there's no log
+/// file or settings-with-secrets concept in this build to pull from).
+#[tauri::command]
+pub async fn create_bug_report_bundle(
+    output_path: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let validated_path = validate_file_path_secure(&output_path)?;
+
+    tracing::info!("Creating bug report bundle at: {}", validated_path);
+
+    let database_stats = get_database_stats(state).await?;
+    let bundle = crate::bug_report::build_bundle(database_stats);
+    let bundle_bytes = crate::bug_report::compress_bundle(&bundle)
+        .map_err(|e| format!("Failed to build bug report bundle: {}", e))?;
+
+    std::fs::write(&validated_path, &bundle_bytes)
+        .map_err(|e| format!("Failed to write bug report bundle: {}", e))?;
+
+    Ok(validated_path)
+}
+
+/// Database statistics structure
+#[derive(Serialize, Clone)]
+pub struct DatabaseStats {
+    pub total_conversations: i64,
+    pub total_personas: i64,
+    pub total_messages: i64,
+    pub database_size_mb: f64,
+    pub last_maintenance: crate::models::DatabaseMaintenanceReport,
+}
+
+// ==================== DASHBOARD COMMANDS ====================
+
+/// A background job in progress, normalized from whichever service owns it
+#[derive(Debug, Serialize)]
+pub struct DashboardJob {
+    pub job_id: String,
+    pub kind: String,
+    pub completed: i64,
+    pub total: i64,
+}
+
+/// Snapshot of request-quota usage, for the dashboard's usage summary
+#[derive(Debug, Serialize)]
+pub struct DashboardUsageSummary {
+    pub policy: crate::models::UsagePolicy,
+    pub requests_today: u32,
+    pub reliability: crate::models::ReliabilityReport,
+}
+
+/// Everything the home screen needs, assembled from across the services layer in one call
+#[derive(Debug, Serialize)]
+pub struct DashboardData {
+    pub recent_conversations: Vec<Conversation>,
+    /// Always empty - there is no conversation/message pinning feature yet. Kept as a field so
+    /// the frontend doesn't need a breaking change once one exists.
+    pub pinned_items: Vec<serde_json::Value>,
+    /// Always empty - there is no reminders feature yet, for the same reason as `pinned_items`.
+    pub due_reminders: Vec<serde_json::Value>,
+    pub running_jobs: Vec<DashboardJob>,
+    pub usage_summary: DashboardUsageSummary,
+    /// Providers with a stored API configuration; see [`DashboardUsageSummary`] for why this
+    /// isn't a live reachability check.
+    pub configured_providers: Vec<String>,
+}
+
+/// Aggregate the data the home screen needs into a single call
+///
+/// Replaces what would otherwise be half a dozen sequential IPC round trips at startup
+/// (recent conversations, job progress, usage policy, reliability, provider configuration).
+#[tauri::command]
+pub async fn get_dashboard(state: State<'_, AppState>) -> Result<DashboardData, String> {
+    tracing::debug!("Assembling dashboard data");
+
+    let recent_conversations = state
+        .services
+        .conversations
+        .get_conversations(Some(10), Some(0))
+        .map_err(|e| format!("Failed to get recent conversations: {}", e))?;
+
+    let mut running_jobs: Vec<DashboardJob> = state
+        .services
+        .embeddings
+        .list_in_progress()
+        .map_err(|e| format!("Failed to get embedding rebuild jobs: {}", e))?
+        .into_iter()
+        .map(|job| DashboardJob {
+            job_id: job.job_id,
+            kind: "embedding_rebuild".to_string(),
+            completed: job.processed_items as i64,
+            total: job.total_items as i64,
+        })
+        .collect();
+    running_jobs.extend(
+        state
+            .services
+            .read_aloud
+            .list_in_progress()
+            .map_err(|e| format!("Failed to get read-aloud jobs: {}", e))?
+            .into_iter()
+            .map(|job| DashboardJob {
+                job_id: job.job_id,
+                kind: "read_aloud".to_string(),
+                completed: job.completed_count as i64,
+                total: job.total_count as i64,
+            }),
+    );
+
+    let usage_summary = DashboardUsageSummary {
+        policy: state
+            .services
+            .usage_policy
+            .get_policy()
+            .map_err(|e| format!("Failed to get usage policy: {}", e))?,
+        requests_today: state
+            .services
+            .usage_policy
+            .requests_today()
+            .map_err(|e| format!("Failed to get today's request count: {}", e))?,
+        reliability: state
+            .services
+            .reliability
+            .get_report()
+            .map_err(|e| format!("Failed to get reliability report: {}", e))?,
+    };
+
+    let configured_providers = state
+        .services
+        .apis
+        .list_configured_providers()
+        .map_err(|e| format!("Failed to get configured providers: {}", e))?;
+
+    Ok(DashboardData {
+        recent_conversations,
+        pinned_items: Vec::new(),
+        due_reminders: Vec::new(),
+        running_jobs,
+        usage_summary,
+        configured_providers,
+    })
+}
+
+// ==================== SLASH COMMAND COMMANDS ====================
+
+/// Create or update a user-defined slash command
+#[tauri::command]
+pub async fn upsert_slash_command(
+    name: String,
+    prompt_template: String,
+    default_model: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::models::SlashCommand, String> {
+    state
+        .services
+        .slash_commands
+        .upsert_command(name, prompt_template, default_model)
+        .map_err(|e| format!("Failed to save slash command: {}", e))
+}
+
+/// List all user-defined slash commands
+#[tauri::command]
+pub async fn get_slash_commands(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::SlashCommand>, String> {
+    state
+        .services
+        .slash_commands
+        .get_commands()
+        .map_err(|e| format!("Failed to list slash commands: {}", e))
+}
+
+/// Delete a user-defined slash command by name
+#[tauri::command]
+pub async fn delete_slash_command(name: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .services
+        .slash_commands
+        .delete_command(&name)
+        .map_err(|e| format!("Failed to delete slash command: {}", e))
+}
+
+/// Parse, resolve, and dispatch a `/name arg1 arg2` input to an AI provider
+///
+/// Looks up the command by name, substitutes arguments into its prompt template, and sends
+/// the resolved prompt to the given provider using the command's default model unless an
+/// explicit model override is supplied.
+#[tauri::command]
+pub async fn execute_slash_command(
+    input: String,
+    provider_type: String,
+    api_key: Option<String>,
+    base_url: Option<String>,
+    model_override: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, String> {
+    use crate::ai_providers::{AIRequest, ChatMessage};
+    use crate::services::SlashCommandService;
+
+    let (name, args) =
+        SlashCommandService::parse_input(&input).ok_or("Input must start with '/'")?;
+
+    let command = state
+        .services
+        .slash_commands
+        .get_command(&name)
+        .map_err(|e| format!("Failed to look up slash command: {}", e))?
+        .ok_or_else(|| format!("Unknown slash command: /{}", name))?;
+
+    let resolved_prompt = SlashCommandService::render_template(&command.prompt_template, &args);
+    let model = model_override
+        .or(command.default_model)
+        .ok_or("No model specified and command has no default model")?;
+
+    let provider = create_ai_provider(provider_type, api_key, base_url, None, None, None, None, None)?;
+    let request = AIRequest {
+        model,
+        messages: vec![ChatMessage {
+            role: "user".to_string(),
+            content: resolved_prompt,
+            tool_call_id: None,
+            pinned: false,
+        }],
+        temperature: None,
+        max_tokens: None,
+        stream: false,
+        tools: Vec::new(),
+    };
+
+    let response = provider
+        .send_request(request)
+        .await
+        .map_err(|e| format!("Failed to execute slash command: {}", e))?;
+
+    Ok(serde_json::json!({
+        "content": response.content,
+        "model": response.model,
+        "tokens_used": response.tokens_used,
+    }))
+}
+
+// ==================== GLOBAL SHORTCUT COMMANDS ====================
+
+/// Bind an OS-level global keyboard shortcut to `action`, replacing whatever accelerator it was
+/// previously bound to
+#[tauri::command]
+pub async fn register_shortcut(
+    action: String,
+    accelerator: String,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<crate::models::Shortcut, String> {
+    if let Some(existing) = state
+        .services
+        .shortcuts
+        .get_shortcut(&action)
+        .map_err(|e| format!("Failed to look up existing shortcut: {}", e))?
+    {
+        let _ = app_handle
+            .global_shortcut_manager()
+            .unregister(&existing.accelerator);
+    }
+
+    let dispatch_handle = app_handle.clone();
+    let dispatch_action_name = action.clone();
+    app_handle
+        .global_shortcut_manager()
+        .register(&accelerator, move || {
+            crate::shortcuts::dispatch_action(&dispatch_handle, &dispatch_action_name);
+        })
+        .map_err(|e| format!("Failed to register shortcut '{}': {}", accelerator, e))?;
+
+    state
+        .services
+        .shortcuts
+        .upsert_shortcut(action, accelerator)
+        .map_err(|e| format!("Failed to save shortcut: {}", e))
+}
+
+/// Unbind a global keyboard shortcut by action name, at both the OS level and in storage
+#[tauri::command]
+pub async fn unregister_shortcut(
+    action: String,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if let Some(existing) = state
+        .services
+        .shortcuts
+        .get_shortcut(&action)
+        .map_err(|e| format!("Failed to look up shortcut: {}", e))?
+    {
+        let _ = app_handle
+            .global_shortcut_manager()
+            .unregister(&existing.accelerator);
+    }
+
+    state
+        .services
+        .shortcuts
+        .delete_shortcut(&action)
+        .map_err(|e| format!("Failed to delete shortcut: {}", e))
+}
+
+/// List all currently bound global keyboard shortcuts
+#[tauri::command]
+pub async fn list_shortcuts(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::Shortcut>, String> {
+    state
+        .services
+        .shortcuts
+        .get_shortcuts()
+        .map_err(|e| format!("Failed to list shortcuts: {}", e))
+}
+
+// ==================== PROMPT TEMPLATE COMMANDS ====================
+
+/// Create a reusable prompt template with named `{{variable}}` placeholders
+#[tauri::command]
+pub async fn create_prompt_template(
+    name: String,
+    category: Option<String>,
+    template: String,
+    favorite: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<crate::models::PromptTemplate, String> {
+    state
+        .services
+        .prompt_templates
+        .create_template(name, category, template, favorite.unwrap_or(false))
+        .map_err(|e| format!("Failed to create prompt template: {}", e))
+}
+
+/// List all prompt templates, favorites first, then alphabetically by name
+#[tauri::command]
+pub async fn list_prompt_templates(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::PromptTemplate>, String> {
+    state
+        .services
+        .prompt_templates
+        .list_templates()
+        .map_err(|e| format!("Failed to list prompt templates: {}", e))
+}
+
+/// Render a prompt template's `{{variable}}` placeholders with `variables`
+///
+/// Fails if any placeholder referenced by the template has no supplied value, rather than
+/// silently sending an unrendered `{{variable}}` to a provider.
+#[tauri::command]
+pub async fn render_prompt_template(
+    template_id: i64,
+    variables: std::collections::HashMap<String, String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    state
+        .services
+        .prompt_templates
+        .render_by_id(template_id, &variables)
+        .map_err(|e| format!("Failed to render prompt template: {}", e))
+}
+
+// ==================== READ-ALOUD COMMANDS ====================
+
+/// Queue an entire conversation for background text-to-speech, one chunk per message
+#[tauri::command]
+pub async fn enqueue_conversation_read_aloud(
+    conversation_id: i64,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let messages = state
+        .services
+        .conversations
+        .get_messages(conversation_id, None, None)
+        .map_err(|e| format!("Failed to load conversation messages: {}", e))?;
+
+    state
+        .services
+        .read_aloud
+        .enqueue_conversation(conversation_id, &messages)
+        .map_err(|e| format!("Failed to queue conversation for read-aloud: {}", e))
+}
+
+/// Queue a grimoire entry for background text-to-speech as a single chunk
+#[tauri::command]
+pub async fn enqueue_grimoire_read_aloud(
+    entry_id: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let content = state
+        .services
+        .grimoire
+        .get_entry_content(&entry_id)
+        .map_err(|e| format!("Failed to load grimoire entry: {}", e))?
+        .ok_or_else(|| format!("Grimoire entry {} not found", entry_id))?;
+
+    state
+        .services
+        .read_aloud
+        .enqueue_grimoire_entry(&entry_id, &content)
+        .map_err(|e| format!("Failed to queue grimoire entry for read-aloud: {}", e))
+}
+
+/// Get chunk-level progress and sequential playback ordering for a read-aloud job
+#[tauri::command]
+pub async fn get_read_aloud_progress(
+    job_id: String,
+    state: State<'_, AppState>,
+) -> Result<crate::models::ReadAloudProgress, String> {
+    state
+        .services
+        .read_aloud
+        .get_progress(&job_id)
+        .map_err(|e| format!("Failed to get read-aloud progress: {}", e))
+}
+
+// ==================== SNIPPET COMMANDS ====================
+
+/// Create or update a text-expansion snippet
+#[tauri::command]
+pub async fn upsert_snippet(
+    trigger: String,
+    expansion: String,
+    state: State<'_, AppState>,
+) -> Result<crate::models::Snippet, String> {
+    state
+        .services
+        .snippets
+        .upsert_snippet(trigger, expansion)
+        .map_err(|e| format!("Failed to save snippet: {}", e))
+}
+
+/// List all text-expansion snippets
+#[tauri::command]
+pub async fn get_snippets(state: State<'_, AppState>) -> Result<Vec<crate::models::Snippet>, String> {
+    state
+        .services
+        .snippets
+        .get_snippets()
+        .map_err(|e| format!("Failed to list snippets: {}", e))
+}
+
+/// Delete a text-expansion snippet by trigger
+#[tauri::command]
+pub async fn delete_snippet(trigger: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .services
+        .snippets
+        .delete_snippet(&trigger)
+        .map_err(|e| format!("Failed to delete snippet: {}", e))
+}
+
+/// Expand a snippet trigger as the user types, for the frontend to splice into the editor
+#[tauri::command]
+pub async fn expand_snippet(
+    trigger: String,
+    state: State<'_, AppState>,
+) -> Result<Option<crate::models::ExpandedSnippet>, String> {
+    state
+        .services
+        .snippets
+        .expand_snippet(&trigger)
+        .map_err(|e| format!("Failed to expand snippet: {}", e))
+}
+
+// ==================== USAGE POLICY COMMANDS ====================
+
+/// Get the current usage window policy (quiet hours, daily request cap, active override)
+#[tauri::command]
+pub async fn get_usage_policy(
+    state: State<'_, AppState>,
+) -> Result<crate::models::UsagePolicy, String> {
+    state
+        .services
+        .usage_policy
+        .get_policy()
+        .map_err(|e| format!("Failed to load usage policy: {}", e))
+}
+
+/// Replace the usage window policy
+#[tauri::command]
+pub async fn set_usage_policy(
+    policy: crate::models::UsagePolicy,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .services
+        .usage_policy
+        .set_policy(&policy)
+        .map_err(|e| format!("Failed to save usage policy: {}", e))
+}
+
+/// Temporarily bypass the usage policy until the given time
+#[tauri::command]
+pub async fn override_usage_policy(
+    until: chrono::DateTime<chrono::Utc>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .services
+        .usage_policy
+        .set_override(until)
+        .map_err(|e| format!("Failed to set usage policy override: {}", e))
+}
+
+// ==================== AI INTEGRATION COMMANDS ====================
+
+/// Resolve `system_prompt`, `model`, `temperature`, and `max_tokens` for a request with
+/// conversation > persona > default precedence, falling back to `explicit_model` (the caller's
+/// own per-call override) ahead of everything for `model` specifically
+///
+/// `conversation_id` is used to look up both the conversation's persona and its
+/// `conversation_settings` row (see `update_conversation_settings`); either may be absent.
+fn resolve_conversation_settings(
+    state: &AppState,
+    conversation_id: Option<i64>,
+    persona_id: Option<i64>,
+    explicit_model: Option<String>,
+) -> Result<(String, String, Option<f32>, Option<i32>), String> {
+    let conversation = match conversation_id {
+        Some(id) => state
+            .services
+            .conversations
+            .get_conversation(id)
+            .map_err(|e| format!("Failed to get conversation: {}", e))?,
+        None => None,
+    };
+
+    let resolved_persona_id = conversation.as_ref().and_then(|c| c.persona_id).or(persona_id);
+    let persona = if let Some(pid) = resolved_persona_id {
+        match state.services.personas.get_persona(pid) {
+            Ok(Some(p)) => Some(p),
+            Ok(None) => return Err(format!("Persona with ID {} not found", pid)),
+            Err(e) => return Err(format!("Failed to get persona: {}", e)),
+        }
+    } else {
+        None
+    };
+
+    let conversation_settings = match conversation_id {
+        Some(id) => state
+            .services
+            .conversations
+            .get_conversation_settings(id)
+            .map_err(|e| format!("Failed to get conversation settings: {}", e))?,
+        None => None,
+    };
+
+    let persona_settings = persona.as_ref().and_then(|p| p.settings.as_ref());
+
+    let mut system_prompt = conversation_settings
+        .as_ref()
+        .and_then(|s| s.system_prompt.clone())
+        .or_else(|| persona.as_ref().map(|p| p.system_prompt.clone()))
+        .unwrap_or_else(|| "You are a helpful assistant.".to_string());
+
+    if let Some(pid) = resolved_persona_id {
+        let memories = state
+            .services
+            .persona_memories
+            .get_persona_memory(pid, Some(crate::services::PERSONA_MEMORY_INJECTION_LIMIT))
+            .map_err(|e| format!("Failed to load persona memory: {}", e))?;
+        if !memories.is_empty() {
+            system_prompt.push_str("\n\nRemembered about this user:\n");
+            for memory in &memories {
+                system_prompt.push_str(&format!("- {}\n", memory.fact));
+            }
+        }
+    }
+
+    let model = explicit_model
+        .or_else(|| conversation_settings.as_ref().and_then(|s| s.model.clone()))
+        .or_else(|| persona_settings.and_then(|s| s.preferred_model.clone()))
+        .unwrap_or_else(|| "forbidden-library-v1".to_string());
+
+    let temperature = conversation_settings
+        .as_ref()
+        .and_then(|s| s.temperature)
+        .or_else(|| persona_settings.and_then(|s| s.temperature));
+
+    let max_tokens = conversation_settings
+        .as_ref()
+        .and_then(|s| s.max_tokens)
+        .or_else(|| persona_settings.and_then(|s| s.max_tokens));
+
+    Ok((system_prompt, model, temperature, max_tokens))
+}
+
+/// Resolve which stored [`crate::models::ApiConfig`], if any, a provider request should pull
+/// credentials from when the caller didn't pass an `api_key` directly
+///
+/// Precedence: an explicit `profile_name` argument, then the conversation's persisted
+/// `conversation_settings.profile_name` (see `update_conversation_settings`), then finally
+/// `provider_type`'s default profile. Returns `Ok(None)` rather than an error when nothing is
+/// configured, since callers that always pass their own `api_key` never need this to succeed.
+fn resolve_api_profile(
+    state: &AppState,
+    provider_type: &str,
+    conversation_id: Option<i64>,
+    profile_name: Option<String>,
+) -> Result<Option<crate::models::ApiConfig>, String> {
+    if let Some(profile_name) = profile_name {
+        return state
+            .services
+            .apis
+            .get_api_profile(&profile_name)
+            .map_err(|e| format!("Failed to load API profile '{}': {}", profile_name, e));
+    }
+
+    let conversation_profile = match conversation_id {
+        Some(id) => state
+            .services
+            .conversations
+            .get_conversation_settings(id)
+            .map_err(|e| format!("Failed to load conversation settings: {}", e))?
+            .and_then(|s| s.profile_name),
+        None => None,
+    };
+
+    if let Some(profile_name) = conversation_profile {
+        return state
+            .services
+            .apis
+            .get_api_profile(&profile_name)
+            .map_err(|e| format!("Failed to load API profile '{}': {}", profile_name, e));
+    }
+
+    state
+        .services
+        .apis
+        .get_api_config(provider_type)
+        .map_err(|e| format!("Failed to load stored API config: {}", e))
+}
+
+/// Build the condensed context `send_ai_request` sends instead of full conversation history:
+/// the latest rolling summary chunk from `summarize_conversation` (if any), plus the most
+/// recent [`crate::services::RECENT_MESSAGE_WINDOW`] messages verbatim
+fn build_rolling_history_context(
+    state: &State<'_, AppState>,
+    conversation_id: i64,
+) -> Result<Option<String>, String> {
+    let latest_summary = state
+        .services
+        .conversations
+        .get_latest_conversation_summary(conversation_id)
+        .map_err(|e| format!("Failed to load conversation summary: {}", e))?;
+
+    let mut recent = state
+        .services
+        .conversations
+        .get_messages(conversation_id, None, None)
+        .map_err(|e| format!("Failed to load recent messages: {}", e))?;
+    if recent.len() > crate::services::RECENT_MESSAGE_WINDOW {
+        let cut = recent.len() - crate::services::RECENT_MESSAGE_WINDOW;
+        recent.drain(..cut);
+    }
+
+    if latest_summary.is_none() && recent.is_empty() {
+        return Ok(None);
+    }
+
+    let mut context = String::new();
+    if let Some(summary) = latest_summary {
+        context.push_str("Summary of earlier conversation:\n");
+        context.push_str(&summary.summary);
+    }
+    if !recent.is_empty() {
+        if !context.is_empty() {
+            context.push_str("\n\n");
+        }
+        context.push_str("Recent messages:\n");
+        for message in &recent {
+            context.push_str(&format!("{:?}: {}\n", message.role, message.content));
+        }
+    }
+    Ok(Some(context))
+}
+
+#[tauri::command]
+pub async fn send_ai_request(
+    message: String,
+    persona_id: Option<i64>,
+    conversation_id: Option<i64>,
+    model: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<AiResponse, String> {
+    tracing::info!(
+        "Processing AI request for conversation: {:?}",
+        conversation_id
+    );
+
+    state
+        .services
+        .usage_policy
+        .check_and_record_request()
+        .map_err(|e| e.user_message())?;
+
+    let (system_prompt, model, temperature, max_tokens) =
+        resolve_conversation_settings(&state, conversation_id, persona_id, model)?;
+
+    // Rather than resending the whole conversation, fold in the latest rolling summary (see
+    // `summarize_conversation`) plus only the most recent messages.
+    let history_context = match conversation_id {
+        Some(id) => build_rolling_history_context(&state, id)?,
+        None => None,
+    };
+
+    // In a real implementation, this would call an AI service
+    // For now, we'll return a more sophisticated mock response
+    let start_time = std::time::Instant::now();
+
+    // Simulate processing time
+    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+    let response_content = format!(
+        "I've received your message: \"{}\". \n\nThis is a simulated response from the Forbidden Library AI assistant. In the full implementation, this would connect to an actual AI model.\n\nSystem context: {}{}",
+        message,
+        system_prompt,
+        history_context
+            .map(|context| format!("\n\n{}", context))
+            .unwrap_or_default()
+    );
+
+    let processing_time = start_time.elapsed().as_millis() as i64;
+
+    Ok(AiResponse {
+        content: response_content,
+        model_used: model,
+        tokens_used: message.len() as i32 + 200, // Simulate token counting
+        processing_time_ms: processing_time,
+        temperature_used: temperature,
+        max_tokens_used: max_tokens,
+    })
+}
+
+/// AI response structure
+#[derive(Serialize)]
+pub struct AiResponse {
+    pub content: String,
+    pub model_used: String,
+    pub tokens_used: i32,
+    pub processing_time_ms: i64,
+    pub temperature_used: Option<f32>,
+    pub max_tokens_used: Option<i32>,
+}
+
+// ==================== FILE MANAGEMENT COMMANDS ====================
+
+/// Progress reported as the `export-progress` event while `export_conversation` works through a
+/// conversation large enough to take a noticeable amount of time
+#[derive(Debug, Clone, Serialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct ExportProgressEvent {
+    pub conversation_id: i64,
+    pub processed: usize,
+    pub total: usize,
+    pub done: bool,
+}
+
+/// Conversations with at least this many messages emit `export-progress` events as they're
+/// rendered; smaller ones finish fast enough that a progress UI would just flicker
+const EXPORT_PROGRESS_EVENT_THRESHOLD_MESSAGES: usize = 200;
+
+/// Emit an `export-progress` event roughly this often while rendering `html`/`pdf` exports
+const EXPORT_PROGRESS_EVENT_INTERVAL: usize = 50;
+
+#[tauri::command]
+pub async fn export_conversation(
+    conversation_id: i64,
+    format: String,
+    theme: Option<crate::models::HtmlExportTheme>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    tracing::info!("Exporting conversation {} as {}", conversation_id, format);
+
+    let conversation = state
+        .services
+        .conversations
+        .get_conversation(conversation_id)
+        .map_err(|e| format!("Failed to get conversation: {}", e))?;
+
+    let messages = state
+        .services
+        .conversations
+        .get_messages(conversation_id, None, None)
+        .map_err(|e| format!("Failed to get messages: {}", e))?;
+
+    let total = messages.len();
+    let emit_progress = |processed: usize, done: bool| {
+        if total >= EXPORT_PROGRESS_EVENT_THRESHOLD_MESSAGES {
+            crate::windows::emit_to_conversation(
+                &app_handle,
+                conversation_id,
+                "export-progress",
+                ExportProgressEvent { conversation_id, processed, total, done },
+            );
+        }
+    };
+
+    let result = match format.as_str() {
+        "json" => {
+            let export_data = serde_json::json!({
+                "conversation": conversation,
+                "messages": messages,
+                "exported_at": chrono::Utc::now().to_rfc3339(),
+                "version": env!("CARGO_PKG_VERSION")
+            });
+
+            let result = serde_json::to_string_pretty(&export_data)
+                .map_err(|e| format!("Failed to serialize conversation: {}", e));
+            emit_progress(total, true);
+            result
+        }
+        "markdown" => {
+            let mut markdown = String::new();
+
+            if let Some(conv) = conversation {
+                markdown.push_str(&format!("# {}\n\n", conv.title));
+                markdown.push_str(&format!(
+                    "**Created:** {}\n\n",
+                    conv.created_at.format("%Y-%m-%d %H:%M:%S UTC")
+                ));
+
+                for (index, message) in messages.into_iter().enumerate() {
+                    let role = match message.role {
+                        MessageRole::User => "**User:**",
+                        MessageRole::Assistant => "**Assistant:**",
+                        MessageRole::System => "**System:**",
+                        MessageRole::Tool => "**Tool:**",
+                    };
+
+                    markdown.push_str(&format!("{} {}\n\n", role, message.content));
+                    markdown.push_str("---\n\n");
+
+                    if (index + 1) % EXPORT_PROGRESS_EVENT_INTERVAL == 0 {
+                        emit_progress(index + 1, false);
+                    }
+                }
+            }
+
+            emit_progress(total, true);
+            Ok(markdown)
+        }
+        "html" => {
+            let conversation = conversation.ok_or_else(|| "Conversation not found".to_string())?;
+            let persona = match conversation.persona_id {
+                Some(id) => state
+                    .services
+                    .personas
+                    .get_persona(id)
+                    .map_err(|e| format!("Failed to get persona: {}", e))?,
+                None => None,
+            };
+
+            let mut attachments_by_message = std::collections::HashMap::new();
+            for (index, message) in messages.iter().enumerate() {
+                if let Some(id) = message.id {
+                    let attachments = state
+                        .services
+                        .attachments
+                        .get_attachments(id)
+                        .map_err(|e| format!("Failed to get attachments for message {}: {}", id, e))?;
+
+                    let with_bytes: Vec<_> = attachments
+                        .into_iter()
+                        .filter_map(|attachment| {
+                            match state.services.attachments.read_attachment_bytes(&attachment) {
+                                Ok(bytes) => {
+                                    Some(crate::export_formats::AttachmentWithBytes { attachment, bytes })
+                                }
+                                Err(e) => {
+                                    tracing::warn!(
+                                        "Skipping attachment {} in export, failed to read: {}",
+                                        attachment.filename,
+                                        e
+                                    );
+                                    None
+                                }
+                            }
+                        })
+                        .collect();
+
+                    if !with_bytes.is_empty() {
+                        attachments_by_message.insert(id, with_bytes);
+                    }
+                }
+
+                if (index + 1) % EXPORT_PROGRESS_EVENT_INTERVAL == 0 {
+                    emit_progress(index + 1, false);
+                }
+            }
+
+            let html = crate::export_formats::conversation_to_html(
+                &conversation,
+                &messages,
+                persona.as_ref(),
+                &attachments_by_message,
+                theme.unwrap_or_default(),
+            );
+            emit_progress(total, true);
+            Ok(html)
+        }
+        "pdf" => {
+            let conversation = conversation.ok_or_else(|| "Conversation not found".to_string())?;
+            let persona = match conversation.persona_id {
+                Some(id) => state
+                    .services
+                    .personas
+                    .get_persona(id)
+                    .map_err(|e| format!("Failed to get persona: {}", e))?,
+                None => None,
+            };
+
+            let mut attachment_names_by_message = std::collections::HashMap::new();
+            for (index, message) in messages.iter().enumerate() {
+                if let Some(id) = message.id {
+                    let attachments = state
+                        .services
+                        .attachments
+                        .get_attachments(id)
+                        .map_err(|e| format!("Failed to get attachments for message {}: {}", id, e))?;
+
+                    if !attachments.is_empty() {
+                        attachment_names_by_message
+                            .insert(id, attachments.into_iter().map(|a| a.filename).collect());
+                    }
+                }
+
+                if (index + 1) % EXPORT_PROGRESS_EVENT_INTERVAL == 0 {
+                    emit_progress(index + 1, false);
+                }
+            }
+
+            let pdf_bytes = crate::pdf_export::conversation_to_pdf(
+                &conversation,
+                &messages,
+                persona.as_ref(),
+                &attachment_names_by_message,
+            );
+            emit_progress(total, true);
+
+            // PDF bytes aren't valid UTF-8 and can't be carried in a plain Rust `String`; this
+            // format specifically returns base64, which callers must decode before writing it
+            // to disk (`export_conversation_to_file` does this for you).
+            use base64::Engine as _;
+            Ok(base64::engine::general_purpose::STANDARD.encode(pdf_bytes))
+        }
+        _ => Err(format!("Unsupported export format: {}", format)),
+    };
+
+    if result.is_ok() {
+        let _ = state.services.audit_log.record(
+            AUDIT_ACTOR,
+            "conversation.export",
+            Some(&format!("conversation_id={} format={}", conversation_id, format)),
+        );
+    }
+    result
+}
+
+/// Payloads larger than this are written to a temp file instead of returned inline over IPC
+const LARGE_PAYLOAD_THRESHOLD_BYTES: usize = 2 * 1024 * 1024;
+
+/// Prefix used for temp artifacts written by `export_conversation_to_file`, so
+/// `cleanup_temp_artifact` can refuse to touch anything it didn't create
+const TEMP_ARTIFACT_PREFIX: &str = "forbidden-library-export-";
+
+/// Export a conversation, writing the result to a temp file if it's too large for IPC
+///
+/// Exports (and large attachment reads) can produce multi-megabyte strings; passing those
+/// through JSON IPC is slow and can blow past the webview's message size limits. This writes
+/// large results to a temp file and returns its path instead, leaving the caller to read it
+/// via Tauri's fs APIs (or the asset protocol) and clean it up with `cleanup_temp_artifact`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExportPayload {
+    Inline { content: String },
+    TempFile { path: String, size_bytes: usize },
+}
+
+#[tauri::command]
+pub async fn export_conversation_to_file(
+    conversation_id: i64,
+    format: String,
+    theme: Option<crate::models::HtmlExportTheme>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<ExportPayload, String> {
+    let content = export_conversation(conversation_id, format.clone(), theme, state, app_handle).await?;
+
+    if content.len() <= LARGE_PAYLOAD_THRESHOLD_BYTES {
+        return Ok(ExportPayload::Inline { content });
+    }
+
+    let extension = match format.as_str() {
+        "markdown" => "md",
+        "html" => "html",
+        "pdf" => "pdf",
+        _ => "json",
+    };
+    let file_name = format!(
+        "{}{}-{}.{}",
+        TEMP_ARTIFACT_PREFIX,
+        conversation_id,
+        uuid::Uuid::new_v4(),
+        extension
+    );
+    let path = std::env::temp_dir().join(file_name);
+
+    if format == "pdf" {
+        use base64::Engine as _;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&content)
+            .map_err(|e| format!("Failed to decode PDF export: {}", e))?;
+        std::fs::write(&path, &bytes)
+            .map_err(|e| format!("Failed to write export to temp file: {}", e))?;
+    } else {
+        std::fs::write(&path, &content)
+            .map_err(|e| format!("Failed to write export to temp file: {}", e))?;
+    }
+
+    if let Err(e) = crate::platform::add_to_jump_list_recent(&path) {
+        tracing::debug!("Failed to add export to jump list: {}", e);
+    }
+
+    Ok(ExportPayload::TempFile {
+        path: path.to_string_lossy().to_string(),
+        size_bytes: content.len(),
+    })
+}
+
+/// Build a compact, token-budgeted context file from several conversations for pasting into
+/// other AI tools
+///
+/// Each conversation contributes a source-marked excerpt (`[Conversation: <title> (id=<id>)]`
+/// followed by its messages); conversations are added in the order given until the estimated
+/// token ceiling would be exceeded, using the same one-token-per-four-characters heuristic as
+/// `get_conversation_statistics`.
+#[tauri::command]
+pub async fn export_context_pack(
+    conversation_ids: Vec<i64>,
+    token_ceiling: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let token_ceiling = token_ceiling.unwrap_or(4000);
+    let char_budget = token_ceiling.saturating_mul(4);
+
+    let mut pack = String::new();
+    for id in conversation_ids {
+        let conversation = state
+            .services
+            .conversations
+            .get_conversation(id)
+            .map_err(|e| format!("Failed to load conversation {}: {}", id, e))?
+            .ok_or_else(|| format!("Conversation {} not found", id))?;
+        let messages = state
+            .services
+            .conversations
+            .get_messages(id, None, None)
+            .map_err(|e| format!("Failed to load messages for conversation {}: {}", id, e))?;
+
+        let mut excerpt = format!("[Conversation: {} (id={})]\n", conversation.title, id);
+        for message in &messages {
+            excerpt.push_str(&format!("{:?}: {}\n", message.role, message.content));
+        }
+        excerpt.push('\n');
+
+        if pack.len() + excerpt.len() > char_budget && !pack.is_empty() {
+            break;
+        }
+        pack.push_str(&excerpt);
+    }
+
+    Ok(pack)
+}
+
+/// Remove a temp artifact previously created by a command like `export_conversation_to_file`
+///
+/// Refuses to delete anything outside the system temp directory or without the expected
+/// prefix, so this can't be used to delete arbitrary files.
+#[tauri::command]
+pub async fn cleanup_temp_artifact(path: String) -> Result<(), String> {
+    use std::path::Path;
+
+    let temp_dir = std::env::temp_dir();
+    let target = Path::new(&path);
+
+    let file_name = target
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid temp artifact path")?;
+
+    if !file_name.starts_with(TEMP_ARTIFACT_PREFIX) {
+        return Err("Refusing to delete a file outside the temp artifact namespace".to_string());
+    }
+
+    if target.parent() != Some(temp_dir.as_path()) {
+        return Err("Refusing to delete a file outside the system temp directory".to_string());
+    }
+
+    match std::fs::remove_file(target) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to remove temp artifact: {}", e)),
+    }
+}
+
+/// Import conversation from JSON file
+#[tauri::command]
+pub async fn import_conversation(
+    json_data: String,
+    state: State<'_, AppState>,
+) -> Result<i64, String> {
+    tracing::info!("Importing conversation from JSON data");
+
+    // Parse the JSON data
+    let import_data: serde_json::Value = serde_json::from_str(&json_data)
+        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+    // Validate the format
+    let conversation_data = import_data["conversation"]
         .as_object()
         .ok_or("Invalid import format: missing 'conversation' object")?;
 
-    let messages_data = import_data["messages"]
-        .as_array()
-        .ok_or("Invalid import format: missing 'messages' array")?;
+    let messages_data = import_data["messages"]
+        .as_array()
+        .ok_or("Invalid import format: missing 'messages' array")?;
+
+    // Extract conversation details
+    let title = conversation_data["title"]
+        .as_str()
+        .ok_or("Invalid conversation: missing 'title'")?
+        .to_string();
+
+    let persona_id = conversation_data["persona_id"]
+        .as_i64()
+        .or_else(|| conversation_data["persona_id"].as_str().and_then(|s| s.parse().ok()));
+
+    // Create the conversation
+    let new_conversation = state
+        .services
+        .conversations
+        .create_conversation(title, persona_id)
+        .map_err(|e| format!("Failed to create conversation: {}", e))?;
+
+    let conversation_id = new_conversation
+        .id
+        .ok_or("Failed to get conversation ID")?;
+
+    // Import messages
+    let mut imported_count = 0;
+    for message_data in messages_data {
+        let role_str = message_data["role"]
+            .as_str()
+            .ok_or("Invalid message: missing 'role'")?;
+
+        let role = match role_str {
+            "User" | "user" => crate::models::MessageRole::User,
+            "Assistant" | "assistant" => crate::models::MessageRole::Assistant,
+            "System" | "system" => crate::models::MessageRole::System,
+            "Tool" | "tool" => crate::models::MessageRole::Tool,
+            _ => return Err(format!("Invalid message role: {}", role_str)),
+        };
+
+        let content = message_data["content"]
+            .as_str()
+            .ok_or("Invalid message: missing 'content'")?
+            .to_string();
+
+        let tokens_used = message_data["tokens_used"]
+            .as_i64()
+            .map(|t| t as i32);
+
+        let model_used = message_data["model_used"]
+            .as_str()
+            .map(|s| s.to_string());
+
+        let tool_call_id = message_data["tool_call_id"]
+            .as_str()
+            .map(|s| s.to_string());
+
+        state
+            .services
+            .conversations
+            .add_message(conversation_id, role, content, tokens_used, model_used, tool_call_id)
+            .map_err(|e| format!("Failed to import message: {}", e))?;
+
+        imported_count += 1;
+    }
+
+    tracing::info!(
+        "Successfully imported conversation with {} messages",
+        imported_count
+    );
+
+    Ok(conversation_id)
+}
+
+#[tauri::command]
+pub async fn backup_database(
+    backup_path: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    // Validate path to prevent path traversal attacks
+    let validated_path = validate_file_path_secure(&backup_path)?;
+
+    tracing::info!("Creating database backup at: {}", validated_path);
+    state
+        .services
+        .conversations
+        .db
+        .backup(&std::path::PathBuf::from(&validated_path))
+        .map_err(|e| format!("Failed to back up database: {}", e))?;
+
+    Ok(validated_path)
+}
+
+/// List backups taken by the scheduled backup job or a prior `backup_database` call into the
+/// managed backup directory, newest first
+#[tauri::command]
+pub async fn list_backups(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::database::BackupInfo>, String> {
+    state
+        .services
+        .conversations
+        .db
+        .list_backups()
+        .map_err(|e| format!("Failed to list backups: {}", e))
+}
+
+/// Restore the live database from a backup in the managed backup directory, by filename as
+/// returned from [`list_backups`]. The app must be restarted afterwards to pick up the restored
+/// file through a fresh connection pool.
+#[tauri::command]
+pub async fn restore_from_backup(
+    filename: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    tracing::warn!("Restoring database from backup: {}", filename);
+    state
+        .services
+        .conversations
+        .db
+        .restore_from_backup(&filename)
+        .map_err(|e| format!("Failed to restore backup: {}", e))?;
+
+    let _ = state.services.audit_log.record(
+        AUDIT_ACTOR,
+        "database.restore_from_backup",
+        Some(&format!("filename={}", filename)),
+    );
+    Ok(())
+}
+
+/// Rotate the database's SQLCipher encryption key: generate a new random 256-bit key, persist it
+/// to the OS keychain, and rekey the live database to match via `PRAGMA rekey`
+#[tauri::command]
+pub async fn rotate_encryption_key(state: State<'_, AppState>) -> Result<(), String> {
+    tracing::warn!("Rotating database encryption key");
+
+    let keychain = crate::keychain::KeychainManager::new();
+    let new_key = keychain
+        .rotate_db_encryption_key()
+        .map_err(|e| format!("Failed to generate new encryption key: {}", e))?;
+
+    state
+        .services
+        .conversations
+        .db
+        .rotate_encryption_key(&new_key)
+        .map_err(|e| format!("Failed to rekey database: {}", e))?;
+
+    let _ = state
+        .services
+        .audit_log
+        .record(AUDIT_ACTOR, "encryption_key.rotate", None);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn restore_database(
+    backup_path: String,
+    _state: State<'_, AppState>,
+) -> Result<String, String> {
+    tracing::info!("Restoring database from: {}", backup_path);
+
+    // This would implement database restore functionality
+    // For now, return success message
+    Ok(format!("Database restored from: {}", backup_path))
+}
+
+#[tauri::command]
+pub async fn clear_database(_state: State<'_, AppState>) -> Result<String, String> {
+    tracing::info!("Clearing all data from the database");
+
+    // This would implement database clearing functionality
+    // For now, return success message
+    Ok("Database cleared successfully".to_string())
+}
+
+/// Export the entire library (conversations, messages, personas, grimoire entries) to a single
+/// gzip-compressed archive file, for moving a library to another machine
+///
+/// Unlike `export_conversation`, which returns content for the caller to handle, this always
+/// writes straight to `output_path` since the whole-library archive is expected to be too large
+/// for IPC.
+#[tauri::command]
+pub async fn export_library(output_path: String, state: State<'_, AppState>) -> Result<String, String> {
+    let validated_path = validate_file_path_secure(&output_path)?;
+
+    tracing::info!("Exporting library to: {}", validated_path);
+
+    let archive_bytes = crate::library_archive::export_library(&state.services.conversations.db)
+        .map_err(|e| format!("Failed to export library: {}", e))?;
+
+    std::fs::write(&validated_path, &archive_bytes)
+        .map_err(|e| format!("Failed to write library archive: {}", e))?;
+
+    let _ = state.services.audit_log.record(
+        AUDIT_ACTOR,
+        "library.export",
+        Some(&format!("output_path={}", validated_path)),
+    );
+    Ok(validated_path)
+}
+
+/// Restore a library archive produced by `export_library`
+///
+/// Conversations, messages, personas, and grimoire entries are added to the existing library
+/// rather than replacing it - this does not clear the database first.
+#[tauri::command]
+pub async fn import_library(
+    input_path: String,
+    state: State<'_, AppState>,
+) -> Result<crate::library_archive::LibraryImportSummary, String> {
+    let validated_path = validate_file_path_secure(&input_path)?;
+
+    tracing::info!("Importing library from: {}", validated_path);
+
+    let archive_bytes = std::fs::read(&validated_path)
+        .map_err(|e| format!("Failed to read library archive: {}", e))?;
+
+    let summary = crate::library_archive::import_library(&state.services.conversations.db, &archive_bytes)
+        .map_err(|e| format!("Failed to import library: {}", e))?;
+
+    let _ = state.services.audit_log.record(
+        AUDIT_ACTOR,
+        "library.restore",
+        Some(&format!("input_path={}", validated_path)),
+    );
+    Ok(summary)
+}
+
+/// Page through the audit log recorded by [`AuditLogService::record`][svc] for sensitive
+/// operations - API config changes, exports, key rotations, deletions, restores - newest first.
+///
+/// [svc]: crate::services::AuditLogService
+#[tauri::command]
+pub async fn get_audit_log(
+    filter: crate::models::AuditLogFilter,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::AuditLogEntry>, String> {
+    state
+        .services
+        .audit_log
+        .query(&filter)
+        .map_err(|e| format!("Failed to query audit log: {}", e))
+}
+
+/// Export a persona and every conversation conducted with it to one archive
+///
+/// Useful when retiring a persona or handing its entire interaction history off to a teammate,
+/// without bundling the rest of the library the way [`export_library`] does.
+#[tauri::command]
+pub async fn export_persona_history(
+    persona_id: i64,
+    output_path: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let validated_path = validate_file_path_secure(&output_path)?;
+
+    tracing::info!("Exporting persona {} history to: {}", persona_id, validated_path);
+
+    let archive_bytes =
+        crate::library_archive::export_persona_history(&state.services.conversations.db, persona_id)
+            .map_err(|e| format!("Failed to export persona history: {}", e))?;
+
+    std::fs::write(&validated_path, &archive_bytes)
+        .map_err(|e| format!("Failed to write persona archive: {}", e))?;
+
+    let _ = state.services.audit_log.record(
+        AUDIT_ACTOR,
+        "persona.export_history",
+        Some(&format!("persona_id={} output_path={}", persona_id, validated_path)),
+    );
+    Ok(validated_path)
+}
+
+/// Package a persona's system prompt, settings, avatar, and remembered facts into a portable
+/// JSON bundle, for sharing a single persona between users without exporting whole databases
+///
+/// Unlike [`export_persona_history`], this carries no conversation history and is returned
+/// directly as a JSON string rather than written to disk, since it's small enough to paste.
+#[tauri::command]
+pub async fn export_persona_bundle(persona_id: i64, state: State<'_, AppState>) -> Result<String, String> {
+    tracing::info!("Exporting persona {} bundle", persona_id);
+
+    let bundle = crate::library_archive::export_persona_bundle(
+        &state.services.personas,
+        &state.services.persona_memories,
+        persona_id,
+    )
+    .map_err(|e| format!("Failed to export persona bundle: {}", e))?;
+
+    let _ = state.services.audit_log.record(
+        AUDIT_ACTOR,
+        "persona.export_bundle",
+        Some(&format!("persona_id={}", persona_id)),
+    );
+    Ok(bundle)
+}
+
+/// Restore a persona bundle produced by [`export_persona_bundle`] as a brand-new persona
+#[tauri::command]
+pub async fn import_persona_bundle(bundle_json: String, state: State<'_, AppState>) -> Result<Persona, String> {
+    tracing::info!("Importing persona bundle");
+
+    crate::library_archive::import_persona_bundle(
+        &state.services.personas,
+        &state.services.persona_memories,
+        &bundle_json,
+    )
+    .map_err(|e| format!("Failed to import persona bundle: {}", e))
+}
+
+/// Test Sentry integration and monitoring
+#[tauri::command]
+pub async fn test_sentry() -> Result<String, String> {
+    use crate::monitoring::test_sentry_integration;
+
+    match test_sentry_integration() {
+        Ok(_) => {
+            tracing::info!("âœ… Sentry integration test successful - VoidCat RDC");
+            Ok("Sentry integration test successful".to_string())
+        }
+        Err(e) => {
+            tracing::error!("âŒ Sentry integration test failed: {}", e);
+            Err(e)
+        }
+    }
+}
+
+// ==================== DESKTOP-SPECIFIC COMMANDS ====================
+
+/// Get system information for desktop environment
+#[tauri::command]
+pub async fn get_system_info() -> Result<serde_json::Value, String> {
+    use std::env;
+
+    let info = serde_json::json!({
+        "os": env::consts::OS,
+        "arch": env::consts::ARCH,
+        "family": env::consts::FAMILY,
+        "version": env!("CARGO_PKG_VERSION"),
+        "tauri_version": env!("CARGO_PKG_VERSION"),
+        "platform": "desktop"
+    });
+
+    Ok(info)
+}
+
+/// Show native file dialog for opening files
+#[tauri::command]
+pub async fn show_open_dialog(
+    app_handle: tauri::AppHandle,
+    title: Option<String>,
+    default_path: Option<String>,
+    filters: Option<Vec<(String, Vec<String>)>>,
+) -> Result<Option<String>, String> {
+    tracing::info!("Opening file dialog");
+
+    use tauri::api::dialog::blocking::FileDialogBuilder;
+    use std::path::PathBuf;
+
+    let mut dialog = FileDialogBuilder::new();
+
+    if let Some(t) = title {
+        dialog = dialog.set_title(&t);
+    }
+
+    if let Some(path) = default_path {
+        dialog = dialog.set_directory(PathBuf::from(path));
+    }
+
+    if let Some(filter_list) = filters {
+        for (name, extensions) in filter_list {
+            let ext_refs: Vec<&str> = extensions.iter().map(|s| s.as_str()).collect();
+            dialog = dialog.add_filter(name, &ext_refs);
+        }
+    }
+
+    let result = dialog.pick_file();
+
+    Ok(result.map(|p| p.to_string_lossy().to_string()))
+}
+
+/// Show native file dialog for saving files
+#[tauri::command]
+pub async fn show_save_dialog(
+    app_handle: tauri::AppHandle,
+    title: Option<String>,
+    default_path: Option<String>,
+    filters: Option<Vec<(String, Vec<String>)>>,
+) -> Result<Option<String>, String> {
+    tracing::info!("Opening save dialog");
+
+    use tauri::api::dialog::blocking::FileDialogBuilder;
+    use std::path::PathBuf;
+
+    let mut dialog = FileDialogBuilder::new();
+
+    if let Some(t) = title {
+        dialog = dialog.set_title(&t);
+    }
+
+    if let Some(path) = default_path {
+        if let Some(parent) = PathBuf::from(&path).parent() {
+            dialog = dialog.set_directory(parent);
+        }
+        if let Some(filename) = PathBuf::from(&path).file_name() {
+            dialog = dialog.set_file_name(filename.to_string_lossy().as_ref());
+        }
+    }
+
+    if let Some(filter_list) = filters {
+        for (name, extensions) in filter_list {
+            let ext_refs: Vec<&str> = extensions.iter().map(|s| s.as_str()).collect();
+            dialog = dialog.add_filter(name, &ext_refs);
+        }
+    }
+
+    let result = dialog.save_file();
+
+    Ok(result.map(|p| p.to_string_lossy().to_string()))
+}
+
+/// Write file to disk with native file system access
+#[tauri::command]
+pub async fn write_file_to_disk(path: String, content: String) -> Result<String, String> {
+    use std::fs;
+
+    // Validate path to prevent path traversal attacks
+    let validated_path = validate_file_path_secure(&path)?;
+
+    tracing::info!("Writing file to: {}", validated_path);
+
+    fs::write(&validated_path, content).map_err(|e| format!("Failed to write file: {}", e))?;
+
+    Ok(format!("File written successfully to: {}", validated_path))
+}
+
+/// Read file from disk with native file system access
+#[tauri::command]
+pub async fn read_file_from_disk(path: String) -> Result<String, String> {
+    use std::fs;
+
+    // Validate path to prevent path traversal attacks
+    let validated_path = validate_file_path_secure(&path)?;
+
+    tracing::info!("Reading file from: {}", validated_path);
+
+    fs::read_to_string(&validated_path).map_err(|e| format!("Failed to read file: {}", e))
+}
+
+/// Payload emitted as the `notification-action` event alongside every [`show_notification`] call,
+/// so the frontend can route a click on its own in-app notification (native OS notifications have
+/// no click callback exposed through Tauri's API) back to the conversation it concerns.
+#[derive(Debug, Clone, Serialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct NotificationActionPayload {
+    pub category: crate::models::NotificationCategory,
+    pub conversation_id: Option<i64>,
+}
+
+/// Show a native desktop notification, falling back to a platform-specific shell-out
+/// ([`crate::platform::show_notification_fallback`]) if Tauri's own notification API fails - e.g.
+/// no notification daemon running on a minimal Linux desktop.
+///
+/// Also emits a `notification-action` event carrying `category`/`conversation_id` so the
+/// frontend's own notification UI can implement "click to open conversation", since the native
+/// notification itself has no click callback available through Tauri's API.
+#[tauri::command]
+pub async fn show_notification(
+    title: String,
+    body: String,
+    icon: Option<String>,
+    category: Option<crate::models::NotificationCategory>,
+    conversation_id: Option<i64>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    use tauri::Manager;
+
+    tracing::info!("Showing notification: {}", title);
+
+    let identifier = app_handle.config().tauri.bundle.identifier.clone();
+    let mut notification = tauri::api::notification::Notification::new(identifier)
+        .title(&title)
+        .body(&body);
+    if let Some(icon) = icon {
+        notification = notification.icon(icon);
+    }
+
+    if let Err(e) = notification.show() {
+        tracing::warn!("Tauri notification API failed ({}), falling back", e);
+        if !crate::platform::show_notification_fallback(&title, &body) {
+            tracing::warn!("Notification fallback also failed for: {}", title);
+        }
+    }
+
+    let _ = app_handle.emit_all(
+        "notification-action",
+        NotificationActionPayload {
+            category: category.unwrap_or(crate::models::NotificationCategory::General),
+            conversation_id,
+        },
+    );
+
+    Ok("Notification shown".to_string())
+}
+
+/// Update the OS taskbar icon's progress indicator for a long-running operation (export, import,
+/// Ollama model pull)
+///
+/// Pass `completed`/`total` to show a determinate progress bar, neither to show an indeterminate
+/// one, or `clear: true` to remove the indicator once the operation finishes. No-op on platforms
+/// without taskbar progress support (macOS, Linux) - see [`crate::platform::set_taskbar_progress`].
+#[tauri::command]
+pub async fn platform_set_progress(
+    completed: Option<u64>,
+    total: Option<u64>,
+    clear: Option<bool>,
+) -> Result<(), String> {
+    let state = if clear.unwrap_or(false) {
+        crate::platform::TaskbarProgressState::NoProgress
+    } else if completed.is_some() && total.is_some() {
+        crate::platform::TaskbarProgressState::Normal
+    } else {
+        crate::platform::TaskbarProgressState::Indeterminate
+    };
+
+    crate::platform::set_taskbar_progress(state, completed.unwrap_or(0), total.unwrap_or(1))
+        .map_err(|e| format!("Failed to update taskbar progress: {}", e))
+}
+
+/// Copy text to system clipboard
+#[tauri::command]
+pub async fn copy_to_clipboard(text: String) -> Result<String, String> {
+    tracing::info!("Copying to clipboard");
+
+    tauri::api::clipboard::Clipboard::new()
+        .write_text(text)
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+
+    Ok("Text copied to clipboard".to_string())
+}
+
+/// Read text from system clipboard
+#[tauri::command]
+pub async fn read_from_clipboard() -> Result<String, String> {
+    tracing::info!("Reading from clipboard");
+
+    tauri::api::clipboard::Clipboard::new()
+        .read_text()
+        .map_err(|e| format!("Failed to read from clipboard: {}", e))?
+        .ok_or_else(|| "Clipboard is empty".to_string())
+}
+
+/// Strip Markdown formatting from message content for plain-text clipboard copies
+///
+/// Drops code fence delimiters (keeping the code between them) and common inline markup -
+/// not a full Markdown parse, just enough to avoid pasting stray `**`/`` ` `` syntax into
+/// plain-text fields.
+fn markdown_to_plain_text(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            continue;
+        }
+        result.push_str(line);
+        result.push('\n');
+    }
+
+    result.replace("**", "").replace('`', "").trim_end().to_string()
+}
+
+/// Copy a single message to the clipboard
+///
+/// `format` is `"markdown"` to copy the message's raw content (code fences and all) or
+/// `"plain"` to strip Markdown syntax first, for pasting into fields that don't render it.
+#[tauri::command]
+pub async fn copy_message_to_clipboard(
+    message_id: i64,
+    format: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    tracing::info!("Copying message {} to clipboard as {}", message_id, format);
+
+    let message = state
+        .services
+        .conversations
+        .get_message(message_id)
+        .map_err(|e| format!("Failed to get message: {}", e))?
+        .ok_or_else(|| format!("Message {} not found", message_id))?;
+
+    let text = match format.as_str() {
+        "markdown" => message.content,
+        "plain" => markdown_to_plain_text(&message.content),
+        other => return Err(format!("Unsupported clipboard format: {}", other)),
+    };
+
+    tauri::api::clipboard::Clipboard::new()
+        .write_text(text)
+        .map_err(|e| format!("Failed to copy message to clipboard: {}", e))?;
+
+    Ok("Message copied to clipboard".to_string())
+}
+
+/// Get application data directory path
+#[tauri::command]
+pub async fn get_app_data_dir() -> Result<String, String> {
+    use crate::platform;
+
+    // Use cross-platform method to get app data directory
+    if let Some(app_data) = platform::get_app_data_dir() {
+        Ok(app_data.to_string_lossy().to_string())
+    } else {
+        // Ultimate fallback
+        Ok("/tmp/forbidden-library".to_string())
+    }
+}
+
+/// Open external URL in default browser
+#[tauri::command]
+pub async fn open_external_url(url: String) -> Result<String, String> {
+    tracing::info!("Opening external URL: {}", url);
+
+    // This would use Tauri's shell API
+    // For now, just return success
+    Ok(format!("Opened URL: {}", url))
+}
+
+/// Create desktop shortcut (Windows/Linux)
+#[tauri::command]
+pub async fn create_desktop_shortcut() -> Result<String, String> {
+    tracing::info!("Creating desktop shortcut");
+
+    // This would create a desktop shortcut for the application
+    // Implementation would be platform-specific
+    Ok("Desktop shortcut created".to_string())
+}
+
+/// Check if running in dark mode
+#[tauri::command]
+pub async fn is_dark_mode() -> Result<bool, String> {
+    Ok(crate::platform::is_dark_mode())
+}
+
+/// Get window state and position
+#[tauri::command]
+pub async fn get_window_state() -> Result<serde_json::Value, String> {
+    let state = serde_json::json!({
+        "width": 1200,
+        "height": 800,
+        "x": 100,
+        "y": 100,
+        "maximized": false,
+        "minimized": false,
+        "fullscreen": false
+    });
+
+    Ok(state)
+}
+
+/// Set window always on top
+#[tauri::command]
+pub async fn set_window_always_on_top(always_on_top: bool) -> Result<String, String> {
+    tracing::info!("Setting window always on top: {}", always_on_top);
+
+    // This would use Tauri's window API
+    Ok(format!("Window always on top set to: {}", always_on_top))
+}
+
+/// Minimize window to system tray
+#[tauri::command]
+pub async fn minimize_to_tray(window: tauri::Window) -> Result<String, String> {
+    tracing::info!("Minimizing to system tray");
+
+    window
+        .hide()
+        .map_err(|e| format!("Failed to hide window: {}", e))?;
+    Ok("Window minimized to tray".to_string())
+}
+
+// ==================== DETACHABLE WINDOW COMMANDS ====================
+
+/// Pop a conversation out into its own window, or focus it if it's already popped out
+#[tauri::command]
+pub async fn open_conversation_window(
+    conversation_id: i64,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    state
+        .services
+        .conversations
+        .get_conversation(conversation_id)
+        .map_err(|e| format!("Failed to look up conversation: {}", e))?
+        .ok_or_else(|| format!("Conversation {} not found", conversation_id))?;
+
+    crate::windows::open_conversation_window(&app_handle, conversation_id)
+}
+
+/// List every conversation currently popped out into its own window
+#[tauri::command]
+pub async fn list_windows() -> Result<Vec<crate::windows::WindowInfo>, String> {
+    Ok(crate::windows::list_windows())
+}
+
+/// Bring a detached conversation window to the front by its label (see [`list_windows`])
+#[tauri::command]
+pub async fn focus_window(label: String, app_handle: tauri::AppHandle) -> Result<(), String> {
+    crate::windows::focus_window(&app_handle, &label)
+}
+
+/// Append a message to the most recently active conversation, creating one if none exists yet
+///
+/// Backs the global-shortcut-triggered quick capture window (see `main.rs`), which has no
+/// conversation context of its own to pass a `conversation_id` for.
+#[tauri::command]
+pub async fn quick_capture_message(
+    content: String,
+    state: State<'_, AppState>,
+) -> Result<Message, String> {
+    tracing::info!("Quick capture message: {} bytes", content.len());
+
+    let validator = InputValidator::default();
+    let validated_content = validator
+        .validate_message_content(&content)
+        .map_err(|e| format!("Invalid message content: {}", e))?;
+
+    let mut recent = state
+        .services
+        .conversations
+        .get_conversations(Some(1), Some(0))
+        .map_err(|e| format!("Failed to look up a conversation for quick capture: {}", e))?;
+
+    let conversation = match recent.pop() {
+        Some(conversation) => conversation,
+        None => state
+            .services
+            .conversations
+            .create_conversation("Quick Capture".to_string(), None)
+            .map_err(|e| format!("Failed to create quick capture conversation: {}", e))?,
+    };
+
+    state
+        .services
+        .conversations
+        .add_message(
+            conversation.id.unwrap_or_default(),
+            MessageRole::User,
+            validated_content,
+            None,
+            None,
+            None,
+        )
+        .map_err(|e| format!("Failed to add quick capture message: {}", e))
+}
+
+/// Check for application updates
+#[tauri::command]
+pub async fn check_for_updates() -> Result<serde_json::Value, String> {
+    tracing::info!("Checking for updates");
+
+    let update_info = serde_json::json!({
+        "available": false,
+        "current_version": env!("CARGO_PKG_VERSION"),
+        "latest_version": env!("CARGO_PKG_VERSION"),
+        "download_url": null
+    });
+
+    Ok(update_info)
+}
+
+// ==================== PROFILE COMMANDS ====================
+
+/// Create a local identity profile for a shared machine
+#[tauri::command]
+pub async fn create_profile(
+    name: String,
+    default_persona_id: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<crate::models::Profile, String> {
+    state
+        .services
+        .profiles
+        .create_profile(name, default_persona_id)
+        .map_err(|e| format!("Failed to create profile: {}", e))
+}
+
+/// List all local identity profiles
+#[tauri::command]
+pub async fn get_profiles(state: State<'_, AppState>) -> Result<Vec<crate::models::Profile>, String> {
+    state
+        .services
+        .profiles
+        .get_profiles()
+        .map_err(|e| format!("Failed to list profiles: {}", e))
+}
+
+/// Assign a conversation to a profile, or clear it by passing `None`
+#[tauri::command]
+pub async fn set_conversation_profile(
+    id: i64,
+    profile_id: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .services
+        .conversations
+        .set_conversation_profile(id, profile_id)
+        .map_err(|e| format!("Failed to set conversation profile: {}", e))
+}
+
+/// List conversations belonging to a specific profile, most recently updated first
+#[tauri::command]
+pub async fn get_conversations_for_profile(
+    profile_id: i64,
+    limit: Option<i32>,
+    offset: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Conversation>, String> {
+    state
+        .services
+        .conversations
+        .get_conversations_for_profile(profile_id, limit, offset)
+        .map_err(|e| format!("Failed to get conversations for profile: {}", e))
+}
+
+// ==================== IMPORT COMMANDS ====================
+
+/// Import conversations from a ChatGPT (`"chatgpt"`) or Claude (`"claude"`) data export file
+///
+/// Returns the number of conversations and messages imported. The whole file is inserted in one
+/// transaction via `ConversationService::import_conversations`, so a malformed entry partway
+/// through a large export never leaves the library half-imported.
+#[tauri::command]
+pub async fn import_conversation_export(
+    format: String,
+    file_content: String,
+    state: State<'_, AppState>,
+) -> Result<(usize, usize), String> {
+    let import_format = crate::importers::ImportFormat::parse(&format)?;
+    let conversations = crate::importers::parse_export(import_format, &file_content)?;
+
+    state
+        .services
+        .conversations
+        .import_conversations(conversations)
+        .map_err(|e| format!("Failed to import conversations: {}", e))
+}
+
+/// Import conversations from a generic JSONL chat log (one JSON object per line), using `mapping`
+/// to describe which keys hold each message's role, content, timestamp, and conversation grouping
+///
+/// For migrating from homegrown logging scripts or chat tools without a dedicated parser.
+/// Returns the number of conversations and messages imported, inserted in the same
+/// all-or-nothing transaction as [`import_conversation_export`].
+#[tauri::command]
+pub async fn import_generic_jsonl(
+    file_content: String,
+    mapping: crate::importers::JsonlFieldMapping,
+    state: State<'_, AppState>,
+) -> Result<(usize, usize), String> {
+    let conversations = crate::importers::parse_generic_jsonl(&file_content, &mapping)?;
+
+    state
+        .services
+        .conversations
+        .import_conversations(conversations)
+        .map_err(|e| format!("Failed to import conversations: {}", e))
+}
+
+/// Conversations committed per transaction by [`import_conversation_export_streaming`]
+const STREAMING_IMPORT_BATCH_SIZE: usize = 50;
+
+/// Progress reported as the `import-progress` event while
+/// [`import_conversation_export_streaming`] works through a large export file
+#[derive(Debug, Clone, Serialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct ImportProgressEvent {
+    pub job_id: String,
+    pub conversations_imported: usize,
+    pub messages_imported: usize,
+}
+
+/// Import a ChatGPT/Claude export file too large to load into memory in one piece
+///
+/// Unlike [`import_conversation_export`], which takes the whole file as a `String` over IPC,
+/// this reads `path` from disk with a streaming JSON parser and commits in batches of
+/// [`STREAMING_IMPORT_BATCH_SIZE`] conversations rather than one giant transaction, so memory use
+/// stays bounded regardless of file size. Progress is persisted after every batch and reported
+/// via the `import-progress` event; pass a previous run's `resume_job_id` to skip the
+/// conversations it already committed and continue from where it left off instead of
+/// re-importing the whole file.
+#[tauri::command]
+pub async fn import_conversation_export_streaming(
+    path: String,
+    format: String,
+    resume_job_id: Option<String>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<crate::models::ImportJob, String> {
+    use tauri::Manager;
+
+    let validated_path = validate_file_path_secure(&path)?;
+    let import_format = crate::importers::ImportFormat::parse(&format)?;
+    let services = state.services.clone();
+
+    let mut job = match resume_job_id {
+        Some(job_id) => services
+            .import_jobs
+            .get_job(&job_id)
+            .map_err(|e| format!("Failed to load import job: {}", e))?
+            .ok_or_else(|| format!("No import job with id '{}'", job_id))?,
+        None => services
+            .import_jobs
+            .create_job(&validated_path)
+            .map_err(|e| format!("Failed to create import job: {}", e))?,
+    };
+
+    let already_imported = job.conversations_imported;
+    let file = std::fs::File::open(&validated_path)
+        .map_err(|e| format!("Failed to open export file: {}", e))?;
+    let reader = std::io::BufReader::new(file);
+
+    let mut skipped = 0usize;
+    let mut batch: Vec<crate::importers::ImportedConversation> = Vec::with_capacity(STREAMING_IMPORT_BATCH_SIZE);
+
+    let flush_batch = |batch: &mut Vec<crate::importers::ImportedConversation>, job: &mut crate::models::ImportJob| -> Result<(), String> {
+        let (conversations, messages) = services
+            .conversations
+            .import_conversations(batch.drain(..).collect())
+            .map_err(|e| format!("Failed to import batch: {}", e))?;
+
+        job.conversations_imported += conversations;
+        job.messages_imported += messages;
+        services
+            .import_jobs
+            .update_progress(&job.id, job.conversations_imported, job.messages_imported)
+            .map_err(|e| format!("Failed to persist import progress: {}", e))?;
+
+        let _ = app_handle.emit_all(
+            "import-progress",
+            ImportProgressEvent {
+                job_id: job.id.clone(),
+                conversations_imported: job.conversations_imported,
+                messages_imported: job.messages_imported,
+            },
+        );
+        Ok(())
+    };
+
+    let mut stream_result = crate::importers::stream_export(import_format, reader, |conversation| {
+        if skipped < already_imported {
+            skipped += 1;
+            return Ok(());
+        }
+
+        batch.push(conversation);
+        if batch.len() < STREAMING_IMPORT_BATCH_SIZE {
+            return Ok(());
+        }
+
+        flush_batch(&mut batch, &mut job)
+    });
+
+    if stream_result.is_ok() && !batch.is_empty() {
+        stream_result = flush_batch(&mut batch, &mut job);
+    }
+
+    match stream_result {
+        Ok(()) => {
+            services
+                .import_jobs
+                .mark_completed(&job.id)
+                .map_err(|e| format!("Failed to finalize import job: {}", e))?;
+            job.status = crate::models::ImportJobStatus::Completed;
+            Ok(job)
+        }
+        Err(e) => {
+            let _ = services.import_jobs.mark_failed(&job.id, &e);
+            Err(e)
+        }
+    }
+}
+
+// ==================== EMBEDDING INDEX COMMANDS ====================
+
+/// Start a bulk re-embedding job under `model`, sized to the current message count
+///
+/// Embedding generation itself happens out-of-process; the caller is expected to embed content
+/// and feed results back through [`record_embedding`], then call [`finalize_embedding_rebuild`]
+/// once every item has been processed.
+#[tauri::command]
+pub async fn rebuild_embeddings(model: String, state: State<'_, AppState>) -> Result<String, String> {
+    state
+        .services
+        .embeddings
+        .start_rebuild(&model)
+        .map_err(|e| format!("Failed to start embedding rebuild: {}", e))
+}
+
+/// Record one freshly-computed vector as part of an in-progress rebuild job
+#[tauri::command]
+pub async fn record_embedding(
+    job_id: String,
+    content_type: String,
+    content_id: String,
+    model: String,
+    vector: Vec<f32>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .services
+        .embeddings
+        .record_embedding(&job_id, &content_type, &content_id, &model, &vector)
+        .map_err(|e| format!("Failed to record embedding: {}", e))
+}
+
+/// Get progress for an embedding rebuild job
+#[tauri::command]
+pub async fn get_embedding_rebuild_progress(
+    job_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<crate::models::EmbeddingJobProgress>, String> {
+    state
+        .services
+        .embeddings
+        .get_job_progress(&job_id)
+        .map_err(|e| format!("Failed to get embedding rebuild progress: {}", e))
+}
+
+/// Atomically swap the active embedding index over to a completed rebuild job's vectors
+#[tauri::command]
+pub async fn finalize_embedding_rebuild(job_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .services
+        .embeddings
+        .finalize_rebuild(&job_id)
+        .map_err(|e| format!("Failed to finalize embedding rebuild: {}", e))
+}
+
+// ==================== SEMANTIC SEARCH COMMANDS ====================
+
+/// Compute and store a semantic-search embedding for one message
+///
+/// Credentials and base URL are loaded from the stored `api_configs` entry for `provider_type`
+/// when present, matching how [`generate_conversation_title`] resolves provider configuration.
+#[tauri::command]
+pub async fn embed_message(
+    message_id: i64,
+    provider_type: String,
+    model: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let message = state
+        .services
+        .conversations
+        .get_message(message_id)
+        .map_err(|e| format!("Failed to load message: {}", e))?
+        .ok_or_else(|| format!("Message {} not found", message_id))?;
+
+    let stored_config = state
+        .services
+        .apis
+        .get_api_config(&provider_type)
+        .map_err(|e| format!("Failed to load stored API config: {}", e))?;
+    let (api_key, base_url) = match stored_config {
+        Some(config) => (Some(config.api_key), config.base_url),
+        None => (None, None),
+    };
+
+    let vector = crate::embeddings::fetch_embedding(
+        &provider_type,
+        api_key.as_deref(),
+        base_url.as_deref(),
+        &model,
+        &message.content,
+    )
+    .await
+    .map_err(|e| format!("Failed to compute embedding: {}", e))?;
+
+    state
+        .services
+        .embeddings
+        .store_message_embedding(message_id, &model, &vector)
+        .map_err(|e| format!("Failed to store embedding: {}", e))
+}
+
+/// A message matched by [`semantic_search`], with its similarity score
+#[derive(Debug, Serialize)]
+pub struct SemanticSearchResult {
+    pub message: Message,
+    pub score: f32,
+}
+
+/// Rank past messages by semantic similarity to `query`, for retrieval-augmented prompting
+///
+/// Embeds `query` with the same provider/model used to embed the candidate messages (see
+/// [`embed_message`]) and ranks stored vectors by cosine similarity via
+/// [`crate::services::EmbeddingService::semantic_search`].
+#[tauri::command]
+pub async fn semantic_search(
+    query: String,
+    top_k: usize,
+    provider_type: String,
+    model: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<SemanticSearchResult>, String> {
+    let stored_config = state
+        .services
+        .apis
+        .get_api_config(&provider_type)
+        .map_err(|e| format!("Failed to load stored API config: {}", e))?;
+    let (api_key, base_url) = match stored_config {
+        Some(config) => (Some(config.api_key), config.base_url),
+        None => (None, None),
+    };
+
+    let query_vector = crate::embeddings::fetch_embedding(
+        &provider_type,
+        api_key.as_deref(),
+        base_url.as_deref(),
+        &model,
+        &query,
+    )
+    .await
+    .map_err(|e| format!("Failed to compute query embedding: {}", e))?;
+
+    let ranked = state
+        .services
+        .embeddings
+        .semantic_search(&query_vector, top_k)
+        .map_err(|e| format!("Failed to run semantic search: {}", e))?;
+
+    let mut results = Vec::with_capacity(ranked.len());
+    for (message_id, score) in ranked {
+        if let Some(message) = state
+            .services
+            .conversations
+            .get_message(message_id)
+            .map_err(|e| format!("Failed to load message {}: {}", message_id, e))?
+        {
+            results.push(SemanticSearchResult { message, score });
+        }
+    }
+
+    Ok(results)
+}
+
+// ==================== TITLE GENERATION COMMANDS ====================
+
+/// Generate a short title for a conversation from its first exchange and save it
+///
+/// Reads the first user/assistant pair and summarizes it with `strategy` (or, if unset, the
+/// size-based default from [`crate::summarization::default_strategy_for`]), then stores the
+/// result via `ConversationService::update_conversation_title`. The abstractive strategy sends
+/// the exchange to `provider_type`/`model`, with credentials loaded from the active
+/// `api_configs` entry for `provider_type` when present, matching how the rest of the app
+/// resolves a provider's stored configuration; the extractive strategy never leaves the machine.
+#[tauri::command]
+pub async fn generate_conversation_title(
+    conversation_id: i64,
+    provider_type: String,
+    model: String,
+    strategy: Option<crate::summarization::SummarizationStrategy>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    use crate::summarization::{
+        default_strategy_for, AbstractiveSummarizer, ExtractiveSummarizer, Summarizer,
+        SummarizationStrategy,
+    };
+
+    let messages = state
+        .services
+        .conversations
+        .get_messages(conversation_id, Some(2), None)
+        .map_err(|e| format!("Failed to load conversation messages: {}", e))?;
+
+    if messages.is_empty() {
+        return Err("Conversation has no messages to summarize".to_string());
+    }
+
+    let exchange = messages
+        .iter()
+        .map(|m| format!("{:?}: {}", m.role, m.content))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let strategy = strategy.unwrap_or_else(|| default_strategy_for(&exchange));
+
+    let raw_title = match strategy {
+        SummarizationStrategy::Extractive => ExtractiveSummarizer { max_sentences: 1 }
+            .summarize(&exchange)
+            .await
+            .map_err(|e| format!("Failed to generate title: {}", e))?,
+        SummarizationStrategy::Abstractive => {
+            let stored_config = state
+                .services
+                .apis
+                .get_api_config(&provider_type)
+                .map_err(|e| format!("Failed to load stored API config: {}", e))?;
+
+            let (api_key, base_url, organization) = match stored_config {
+                Some(config) => (Some(config.api_key), config.base_url, config.organization),
+                None => (None, None, None),
+            };
+
+            let provider = create_ai_provider(provider_type, api_key, base_url, None, None, None, organization, None)?;
+            let summarizer = AbstractiveSummarizer::new(provider, model).with_instruction(
+                "Generate a short, descriptive title (5 words or fewer, no quotes or punctuation at the end) for this conversation:",
+            );
+            summarizer
+                .summarize(&exchange)
+                .await
+                .map_err(|e| format!("Failed to generate title: {}", e))?
+        }
+    };
+
+    let title = raw_title.trim().trim_matches('"').to_string();
+
+    state
+        .services
+        .conversations
+        .update_conversation_title(conversation_id, &title)
+        .map_err(|e| format!("Failed to save generated title: {}", e))?;
+
+    Ok(title)
+}
+
+// ==================== GRIMOIRE TEMPLATE COMMANDS ====================
+
+/// List the bundled grimoire entry templates (e.g. "Paper Notes", "Recipe") for a template picker
+#[tauri::command]
+pub async fn list_grimoire_templates() -> Result<Vec<crate::grimoire_templates::GrimoireTemplate>, String> {
+    Ok(crate::grimoire_templates::list_templates().to_vec())
+}
+
+/// Create a grimoire entry, optionally built from a named template with validated structured fields
+#[tauri::command]
+pub async fn create_grimoire_entry(
+    title: String,
+    content: String,
+    category: Option<String>,
+    tags: Option<String>,
+    template: Option<String>,
+    fields: Option<serde_json::Value>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    state
+        .services
+        .grimoire
+        .create_entry(
+            &title,
+            &content,
+            category.as_deref(),
+            tags.as_deref(),
+            template.as_deref(),
+            fields,
+        )
+        .map_err(|e| format!("Failed to create grimoire entry: {}", e))
+}
+
+/// Find grimoire entries built from `template` whose `field` equals `value`
+#[tauri::command]
+pub async fn find_grimoire_entries_by_field(
+    template: String,
+    field: String,
+    value: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    state
+        .services
+        .grimoire
+        .find_entries_by_field(&template, &field, &value)
+        .map_err(|e| format!("Failed to query grimoire entries: {}", e))
+}
+
+/// Fetch a grimoire entry by id, marking it as accessed
+#[tauri::command]
+pub async fn get_grimoire_entry(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<crate::models::GrimoireEntry>, String> {
+    state
+        .services
+        .grimoire
+        .get_entry(&id)
+        .map_err(|e| format!("Failed to get grimoire entry: {}", e))
+}
+
+/// List all grimoire entries, most recently updated first
+#[tauri::command]
+pub async fn list_grimoire_entries(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::GrimoireEntry>, String> {
+    state
+        .services
+        .grimoire
+        .list_entries()
+        .map_err(|e| format!("Failed to list grimoire entries: {}", e))
+}
+
+/// Update a grimoire entry's title, content, category and tags
+#[tauri::command]
+pub async fn update_grimoire_entry(
+    id: String,
+    title: String,
+    content: String,
+    category: Option<String>,
+    tags: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    state
+        .services
+        .grimoire
+        .update_entry(&id, &title, &content, category.as_deref(), tags.as_deref())
+        .map_err(|e| format!("Failed to update grimoire entry: {}", e))
+}
+
+/// Delete a grimoire entry by id
+#[tauri::command]
+pub async fn delete_grimoire_entry(id: String, state: State<'_, AppState>) -> Result<bool, String> {
+    let deleted = state
+        .services
+        .grimoire
+        .delete_entry(&id)
+        .map_err(|e| format!("Failed to delete grimoire entry: {}", e))?;
+
+    if deleted {
+        let _ = state
+            .services
+            .audit_log
+            .record(AUDIT_ACTOR, "grimoire_entry.delete", Some(&format!("entry_id={}", id)));
+    }
+    Ok(deleted)
+}
+
+/// Encrypt a grimoire entry's content at rest with the keychain-managed grimoire key. Returns
+/// `false` if the entry doesn't exist or is already encrypted.
+#[tauri::command]
+pub async fn encrypt_grimoire_entry(id: String, state: State<'_, AppState>) -> Result<bool, String> {
+    state
+        .services
+        .grimoire
+        .encrypt_entry(&id)
+        .map_err(|e| format!("Failed to encrypt grimoire entry: {}", e))
+}
 
-    // Extract conversation details
-    let title = conversation_data["title"]
-        .as_str()
-        .ok_or("Invalid conversation: missing 'title'")?
-        .to_string();
+/// Decrypt a grimoire entry's content back to plaintext at rest. Returns `false` if the entry
+/// doesn't exist or isn't encrypted.
+#[tauri::command]
+pub async fn decrypt_grimoire_entry(id: String, state: State<'_, AppState>) -> Result<bool, String> {
+    state
+        .services
+        .grimoire
+        .decrypt_entry(&id)
+        .map_err(|e| format!("Failed to decrypt grimoire entry: {}", e))
+}
 
-    let persona_id = conversation_data["persona_id"]
-        .as_i64()
-        .or_else(|| conversation_data["persona_id"].as_str().and_then(|s| s.parse().ok()));
+/// Search grimoire entries by exact category
+#[tauri::command]
+pub async fn search_grimoire_entries_by_category(
+    category: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::GrimoireEntry>, String> {
+    state
+        .services
+        .grimoire
+        .search_by_category(&category)
+        .map_err(|e| format!("Failed to search grimoire entries: {}", e))
+}
 
-    // Create the conversation
-    let new_conversation = state
+/// Search grimoire entries whose tags contain the given substring
+#[tauri::command]
+pub async fn search_grimoire_entries_by_tag(
+    tag: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::GrimoireEntry>, String> {
+    state
         .services
-        .conversations
-        .create_conversation(title, persona_id)
-        .map_err(|e| format!("Failed to create conversation: {}", e))?;
+        .grimoire
+        .search_by_tag(&tag)
+        .map_err(|e| format!("Failed to search grimoire entries: {}", e))
+}
 
-    let conversation_id = new_conversation
-        .id
-        .ok_or("Failed to get conversation ID")?;
+// ==================== GRIMOIRE MCP CLIENT COMMANDS ====================
 
-    // Import messages
-    let mut imported_count = 0;
-    for message_data in messages_data {
-        let role_str = message_data["role"]
-            .as_str()
-            .ok_or("Invalid message: missing 'role'")?;
+/// Connect to a Grimoire server configured as `GrimoireServerType::MCP`, completing the MCP
+/// `initialize` handshake over stdio (if `configuration.connection_settings.host` is unset) or
+/// WebSocket (if it's set). The connection is kept open for subsequent `list_grimoire_tools` /
+/// `invoke_grimoire_tool` calls - see `crate::services::McpClientService`.
+#[tauri::command]
+pub async fn connect_grimoire(
+    server_path: String,
+    configuration: crate::models::GrimoireConfiguration,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    tracing::info!("Connecting to Grimoire MCP server: {}", server_path);
+    state
+        .services
+        .mcp_clients
+        .connect(&server_path, &configuration)
+        .await
+        .map_err(|e| format!("Failed to connect to MCP server: {}", e))
+}
 
-        let role = match role_str {
-            "User" | "user" => crate::models::MessageRole::User,
-            "Assistant" | "assistant" => crate::models::MessageRole::Assistant,
-            "System" | "system" => crate::models::MessageRole::System,
-            _ => return Err(format!("Invalid message role: {}", role_str)),
-        };
+/// List the tools exposed by a previously connected Grimoire MCP server
+#[tauri::command]
+pub async fn list_grimoire_tools(
+    server_path: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::mcp::McpTool>, String> {
+    state
+        .services
+        .mcp_clients
+        .list_tools(&server_path)
+        .await
+        .map_err(|e| format!("Failed to list MCP tools: {}", e))
+}
 
-        let content = message_data["content"]
-            .as_str()
-            .ok_or("Invalid message: missing 'content'")?
-            .to_string();
+/// Invoke a tool on a previously connected Grimoire MCP server, returning its raw result payload
+#[tauri::command]
+pub async fn invoke_grimoire_tool(
+    server_path: String,
+    tool_name: String,
+    arguments: serde_json::Value,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, String> {
+    state
+        .services
+        .mcp_clients
+        .invoke_tool(&server_path, &tool_name, arguments)
+        .await
+        .map_err(|e| format!("Failed to invoke MCP tool '{}': {}", tool_name, e))
+}
 
-        let tokens_used = message_data["tokens_used"]
-            .as_i64()
-            .map(|t| t as i32);
+// ==================== DIGEST COMMANDS ====================
 
-        let model_used = message_data["model_used"]
-            .as_str()
-            .map(|s| s.to_string());
+/// Generate a digest summarizing the last 7 days of conversations and file it as a grimoire entry
+///
+/// Hands the week's conversation titles and message counts to `strategy` (or, if unset, the
+/// size-based default from [`crate::summarization::default_strategy_for`]) for summarization
+/// into topics, decisions, and follow-ups, stores the result as a grimoire entry, and fires a
+/// notification once it's ready. Intended to be invoked on-demand from the frontend or by a
+/// future task scheduler; this command does not schedule itself.
+#[tauri::command]
+pub async fn generate_weekly_digest(
+    provider_type: String,
+    model: String,
+    api_key: Option<String>,
+    base_url: Option<String>,
+    strategy: Option<crate::summarization::SummarizationStrategy>,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    use crate::summarization::{
+        default_strategy_for, AbstractiveSummarizer, ExtractiveSummarizer, Summarizer,
+        SummarizationStrategy,
+    };
 
-        state
+    let since = chrono::Utc::now() - chrono::Duration::days(7);
+    let conversations = state
+        .services
+        .conversations
+        .get_conversations_since(since)
+        .map_err(|e| format!("Failed to load this week's conversations: {}", e))?;
+
+    if conversations.is_empty() {
+        return Err("No conversations in the last 7 days to summarize".to_string());
+    }
+
+    let mut transcript_overview = String::new();
+    for conversation in &conversations {
+        let id = conversation.id.ok_or("Conversation missing id")?;
+        let messages = state
             .services
             .conversations
-            .add_message(conversation_id, role, content, tokens_used, model_used)
-            .map_err(|e| format!("Failed to import message: {}", e))?;
-
-        imported_count += 1;
+            .get_messages(id, None, None)
+            .map_err(|e| format!("Failed to load messages for conversation {}: {}", id, e))?;
+        transcript_overview.push_str(&format!(
+            "- \"{}\" ({} messages)\n",
+            conversation.title,
+            messages.len()
+        ));
     }
 
-    tracing::info!(
-        "Successfully imported conversation with {} messages",
-        imported_count
-    );
+    let strategy = strategy.unwrap_or_else(|| default_strategy_for(&transcript_overview));
 
-    Ok(conversation_id)
+    let digest = match strategy {
+        SummarizationStrategy::Extractive => ExtractiveSummarizer::default()
+            .summarize(&transcript_overview)
+            .await
+            .map_err(|e| format!("Failed to generate digest: {}", e))?,
+        SummarizationStrategy::Abstractive => {
+            let provider = create_ai_provider(provider_type, api_key, base_url, None, None, None, None, None)?;
+            let summarizer = AbstractiveSummarizer::new(provider, model).with_instruction(
+                "Summarize this week's conversations into topics, decisions, and follow-ups:",
+            );
+            summarizer
+                .summarize(&transcript_overview)
+                .await
+                .map_err(|e| format!("Failed to generate digest: {}", e))?
+        }
+    };
+
+    let title = format!("Weekly Digest - {}", chrono::Utc::now().format("%Y-%m-%d"));
+    let entry_id = state
+        .services
+        .grimoire
+        .create_entry(&title, &digest, Some("digest"), Some("weekly-digest"), None, None)
+        .map_err(|e| format!("Failed to store digest: {}", e))?;
+
+    show_notification(
+        "Weekly digest ready".to_string(),
+        "Your weekly conversation digest has been generated.".to_string(),
+        None,
+        Some(crate::models::NotificationCategory::General),
+        None,
+        app_handle,
+    )
+    .await?;
+
+    Ok(entry_id)
 }
 
+// ==================== SESSION COMMANDS ====================
+
+/// Persist the set of currently open conversations so they can be restored at startup
 #[tauri::command]
-pub async fn backup_database(
-    backup_path: String,
+pub async fn save_session(
+    open_conversations: Vec<crate::services::OpenConversationState>,
+    auto_restore: bool,
     state: State<'_, AppState>,
-) -> Result<String, String> {
-    // Validate path to prevent path traversal attacks
-    let validated_path = validate_file_path_secure(&backup_path)?;
+) -> Result<(), String> {
+    state
+        .services
+        .sessions
+        .save_session(&open_conversations, auto_restore)
+        .map_err(|e| format!("Failed to save session: {}", e))
+}
 
-    tracing::info!("Creating database backup at: {}", validated_path);
+/// Fetch the last saved session so the frontend can reopen conversations on launch
+///
+/// Returns `None` if no session has ever been saved, or if auto-restore is disabled -
+/// the caller is expected to check `auto_restore` before reopening windows automatically.
+#[tauri::command]
+pub async fn get_last_session(
+    state: State<'_, AppState>,
+) -> Result<Option<crate::services::SessionSnapshot>, String> {
+    state
+        .services
+        .sessions
+        .get_last_session()
+        .map_err(|e| format!("Failed to load last session: {}", e))
+}
 
-    use std::fs;
-    use std::path::Path;
+// ==================== MONITORING COMMANDS ====================
 
-    // NOTE: This command needs refactoring - state.db doesn't exist
-    // For now, return a message indicating the operation is not yet implemented
-    // TODO: Implement proper database backup through DatabaseManager
+/// Get rolling IPC command latency histograms for the frontend performance panel
+///
+/// Lets the UI tell whether sluggishness is backend response time (wide histogram,
+/// high `average_ms`) or webview rendering (tight histogram, fast responses).
+#[tauri::command]
+pub async fn get_latency_histograms() -> Result<Vec<crate::monitoring::LatencyHistogram>, String> {
+    Ok(crate::monitoring::snapshot_latency_histograms())
+}
 
-    tracing::warn!("backup_database is not yet fully implemented");
-    Ok(format!("Database backup functionality requires implementation. Requested path: {}", validated_path))
+/// Memory usage report for diagnosing pressure on low-RAM machines
+///
+/// Useful when the app is running alongside local AI models that compete for RAM -
+/// surfaces approximate process RSS plus the size of the in-memory query cache.
+#[derive(Debug, Serialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct MemoryReport {
+    /// Approximate resident set size of this process, in kilobytes. `None` on platforms
+    /// without a lightweight way to query this (currently macOS and Windows).
+    pub process_rss_kb: Option<u64>,
+    pub query_cache_entries: usize,
+    pub query_cache_active_entries: usize,
+    pub query_cache_bytes: usize,
 }
 
 #[tauri::command]
-pub async fn restore_database(
-    backup_path: String,
-    _state: State<'_, AppState>,
-) -> Result<String, String> {
-    tracing::info!("Restoring database from: {}", backup_path);
-
-    // This would implement database restore functionality
-    // For now, return success message
-    Ok(format!("Database restored from: {}", backup_path))
+pub async fn get_memory_report(state: State<'_, AppState>) -> Result<MemoryReport, String> {
+    let cache_stats = state.services.query_cache.stats();
+
+    Ok(MemoryReport {
+        process_rss_kb: crate::platform::get_process_memory_kb(),
+        query_cache_entries: cache_stats.total_entries,
+        query_cache_active_entries: cache_stats.active_entries,
+        query_cache_bytes: state.services.query_cache.approx_size_bytes(),
+    })
 }
 
+/// Drop all in-memory caches, freeing RAM at the cost of re-computing cached results
 #[tauri::command]
-pub async fn clear_database(_state: State<'_, AppState>) -> Result<String, String> {
-    tracing::info!("Clearing all data from the database");
-
-    // This would implement database clearing functionality
-    // For now, return success message
-    Ok("Database cleared successfully".to_string())
+pub async fn trim_caches(state: State<'_, AppState>) -> Result<String, String> {
+    tracing::info!("Trimming in-memory caches");
+    let freed_bytes = state.services.query_cache.approx_size_bytes();
+    state.services.query_cache.clear();
+    Ok(format!("Trimmed caches, freed approximately {} bytes", freed_bytes))
 }
 
-/// Test Sentry integration and monitoring
+/// Local crash/session reliability summary, for users who disable Sentry and still want
+/// to know whether the app is crashing on their machine
 #[tauri::command]
-pub async fn test_sentry() -> Result<String, String> {
-    use crate::monitoring::test_sentry_integration;
+pub async fn get_reliability_report(
+    state: State<'_, AppState>,
+) -> Result<crate::models::ReliabilityReport, String> {
+    state
+        .services
+        .reliability
+        .get_report()
+        .map_err(|e| e.to_string())
+}
 
-    match test_sentry_integration() {
-        Ok(_) => {
-            tracing::info!("âœ… Sentry integration test successful - VoidCat RDC");
-            Ok("Sentry integration test successful".to_string())
-        }
-        Err(e) => {
-            tracing::error!("âŒ Sentry integration test failed: {}", e);
-            Err(e)
-        }
-    }
+/// Keychain account used to round-trip a throwaway value in [`run_diagnostics`]'s accessibility
+/// probe - never holds anything a caller relies on afterwards
+const DIAGNOSTICS_KEYCHAIN_PROBE_ACCOUNT: &str = "diagnostics-probe";
+
+/// Whether a single configured AI provider answered a reachability check, as reported by
+/// [`run_diagnostics`]
+#[derive(Debug, Serialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct ProviderReachability {
+    pub provider_type: String,
+    pub reachable: bool,
 }
 
-// ==================== DESKTOP-SPECIFIC COMMANDS ====================
+/// Startup health-check report for the frontend "Health" panel
+#[derive(Debug, Serialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct DiagnosticsReport {
+    /// `true` if `PRAGMA integrity_check` came back clean
+    pub database_healthy: bool,
+    /// Problems `PRAGMA integrity_check` reported; empty when `database_healthy` is `true`
+    pub database_integrity_problems: Vec<String>,
+    pub connection_pool: crate::database::PoolStatus,
+    /// Whether a value could be written to and read back from the OS keychain
+    pub keychain_accessible: bool,
+    /// Free space on the volume holding the app data directory, in bytes. `None` if it
+    /// couldn't be determined - see `platform::available_disk_space_bytes`.
+    pub disk_free_bytes: Option<u64>,
+    /// Reachability of every provider with a stored API configuration
+    pub providers: Vec<ProviderReachability>,
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+}
 
-/// Get system information for desktop environment
+/// Run a startup health check: database integrity, connection-pool saturation, keychain
+/// accessibility, app data directory disk space, and reachability of every configured AI
+/// provider. Logged to the monitoring module so a failing check shows up in Sentry the same
+/// way other startup problems do, not just in the returned report.
 #[tauri::command]
-pub async fn get_system_info() -> Result<serde_json::Value, String> {
-    use std::env;
+pub async fn run_diagnostics(
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<DiagnosticsReport, String> {
+    tracing::info!("Running startup diagnostics");
+    let db = &state.services.conversations.db;
+
+    let database_integrity_problems = db
+        .check_integrity()
+        .map_err(|e| format!("Failed to check database integrity: {}", e))?;
+    let connection_pool = db.pool_status();
+
+    let keychain = crate::keychain::KeychainManager::new();
+    let keychain_accessible = keychain
+        .store_api_key(DIAGNOSTICS_KEYCHAIN_PROBE_ACCOUNT, "probe")
+        .and_then(|_| keychain.get_api_key(DIAGNOSTICS_KEYCHAIN_PROBE_ACCOUNT))
+        .and_then(|_| keychain.delete_api_key(DIAGNOSTICS_KEYCHAIN_PROBE_ACCOUNT))
+        .is_ok();
+
+    let disk_free_bytes = db
+        .db_path()
+        .parent()
+        .and_then(crate::platform::available_disk_space_bytes);
+
+    let configured_providers = state
+        .services
+        .apis
+        .list_configured_providers()
+        .map_err(|e| format!("Failed to list configured providers: {}", e))?;
+
+    let mut providers = Vec::new();
+    for provider_type in configured_providers {
+        // Local providers (Ollama, LM Studio) are already polled by `ProviderMonitor`; reuse
+        // its cache/refresh instead of probing them a second way. Anything it doesn't track is
+        // a remote provider, checked directly with its stored API key.
+        let reachable = if let Some(status) = state
+            .provider_monitor
+            .refresh(&provider_type, Some(&app_handle))
+            .await
+        {
+            status.available
+        } else {
+            match state.services.apis.get_api_config(&provider_type) {
+                Ok(Some(config)) => match create_ai_provider(
+                    provider_type.clone(),
+                    Some(config.api_key),
+                    config.base_url,
+                    None,
+                    None,
+                    None,
+                    config.organization,
+                    None,
+                ) {
+                    Ok(provider) => provider.check_availability().await.unwrap_or(false),
+                    Err(_) => false,
+                },
+                _ => false,
+            }
+        };
+        providers.push(ProviderReachability { provider_type, reachable });
+    }
 
-    let info = serde_json::json!({
-        "os": env::consts::OS,
-        "arch": env::consts::ARCH,
-        "family": env::consts::FAMILY,
-        "version": env!("CARGO_PKG_VERSION"),
-        "tauri_version": env!("CARGO_PKG_VERSION"),
-        "platform": "desktop"
-    });
+    let database_healthy = database_integrity_problems.is_empty();
+    if !database_healthy {
+        tracing::error!("❌ Database integrity check failed: {:?}", database_integrity_problems);
+    }
+    if !keychain_accessible {
+        tracing::error!("❌ OS keychain is not accessible");
+    }
+    for provider in &providers {
+        if !provider.reachable {
+            tracing::warn!("⚠️ Configured provider unreachable: {}", provider.provider_type);
+        }
+    }
+    tracing::info!(
+        "✅ Diagnostics complete: database_healthy={}, keychain_accessible={}, pool={}/{}",
+        database_healthy,
+        keychain_accessible,
+        connection_pool.connections,
+        connection_pool.max_size
+    );
 
-    Ok(info)
+    Ok(DiagnosticsReport {
+        database_healthy,
+        database_integrity_problems,
+        connection_pool,
+        keychain_accessible,
+        disk_free_bytes,
+        providers,
+        checked_at: Utc::now(),
+    })
 }
 
-/// Show native file dialog for opening files
+/// Get the current telemetry settings (local-only mode and custom redaction patterns)
 #[tauri::command]
-pub async fn show_open_dialog(
-    app_handle: tauri::AppHandle,
-    title: Option<String>,
-    default_path: Option<String>,
-    filters: Option<Vec<(String, Vec<String>)>>,
-) -> Result<Option<String>, String> {
-    tracing::info!("Opening file dialog");
+pub async fn get_telemetry_settings(
+    state: State<'_, AppState>,
+) -> Result<crate::models::TelemetrySettings, String> {
+    state
+        .services
+        .telemetry
+        .get_settings()
+        .map_err(|e| format!("Failed to get telemetry settings: {}", e))
+}
 
-    use tauri::api::dialog::blocking::FileDialogBuilder;
-    use std::path::PathBuf;
+/// Replace the telemetry settings, taking effect immediately without requiring a restart
+#[tauri::command]
+pub async fn update_telemetry_settings(
+    settings: crate::models::TelemetrySettings,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .services
+        .telemetry
+        .set_settings(&settings)
+        .map_err(|e| format!("Failed to update telemetry settings: {}", e))
+}
 
-    let mut dialog = FileDialogBuilder::new();
+// ==================== MODEL DEPRECATION COMMANDS ====================
 
-    if let Some(t) = title {
-        dialog = dialog.set_title(&t);
-    }
+/// Look up a deprecation warning for a specific provider/model pair, if one is registered
+#[tauri::command]
+pub async fn get_model_deprecation_warning(
+    provider: String,
+    model: String,
+) -> Result<Option<crate::model_registry::DeprecatedModel>, String> {
+    Ok(crate::model_registry::deprecation_warning(&provider, &model).cloned())
+}
 
-    if let Some(path) = default_path {
-        dialog = dialog.set_directory(PathBuf::from(path));
-    }
+/// Bulk-update every stored reference to `old_model` across message history and slash command
+/// defaults to `new_model`, returning the total number of rows updated
+#[tauri::command]
+pub async fn migrate_model_references(
+    old_model: String,
+    new_model: String,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    tracing::info!("Migrating model references from {} to {}", old_model, new_model);
 
-    if let Some(filter_list) = filters {
-        for (name, extensions) in filter_list {
-            let ext_refs: Vec<&str> = extensions.iter().map(|s| s.as_str()).collect();
-            dialog = dialog.add_filter(name, &ext_refs);
-        }
-    }
+    let messages_updated = state
+        .services
+        .conversations
+        .migrate_model_references(&old_model, &new_model)
+        .map_err(|e| format!("Failed to migrate message model references: {}", e))?;
 
-    let result = dialog.pick_file();
+    let commands_updated = state
+        .services
+        .slash_commands
+        .migrate_model_references(&old_model, &new_model)
+        .map_err(|e| format!("Failed to migrate slash command model references: {}", e))?;
 
-    Ok(result.map(|p| p.to_string_lossy().to_string()))
+    Ok(messages_updated + commands_updated)
 }
 
-/// Show native file dialog for saving files
+// ==================== AI PROVIDER COMMANDS ====================
+
+/// Check if an AI provider is available
+///
+/// Supports: OpenAI, Anthropic Claude, Google Gemini, Azure OpenAI, LM Studio, Ollama
+///
+/// For the local providers (`lm_studio`, `ollama`) this is served from
+/// [`crate::provider_monitor::ProviderMonitor`]'s cache by default, since it's already polling
+/// them in the background; pass `force_refresh: true` to bypass the cache and check immediately.
+/// Remote providers are always checked on demand - they need per-call credentials the monitor
+/// doesn't have, so there's nothing for it to cache.
 #[tauri::command]
-pub async fn show_save_dialog(
+pub async fn check_ai_provider_availability(
+    provider_type: String,
+    api_key: Option<String>,
+    base_url: Option<String>,
+    endpoint: Option<String>,
+    deployment_name: Option<String>,
+    port: Option<u16>,
+    force_refresh: Option<bool>,
+    state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
-    title: Option<String>,
-    default_path: Option<String>,
-    filters: Option<Vec<(String, Vec<String>)>>,
-) -> Result<Option<String>, String> {
-    tracing::info!("Opening save dialog");
-
-    use tauri::api::dialog::blocking::FileDialogBuilder;
-    use std::path::PathBuf;
-
-    let mut dialog = FileDialogBuilder::new();
+) -> Result<bool, String> {
+    use crate::ai_providers::AIProvider;
 
-    if let Some(t) = title {
-        dialog = dialog.set_title(&t);
-    }
+    tracing::info!("Checking availability for provider: {}", provider_type);
 
-    if let Some(path) = default_path {
-        if let Some(parent) = PathBuf::from(&path).parent() {
-            dialog = dialog.set_directory(parent);
-        }
-        if let Some(filename) = PathBuf::from(&path).file_name() {
-            dialog = dialog.set_file_name(filename.to_string_lossy().as_ref());
+    // The monitor only tracks the default-port local providers; a custom port means the caller
+    // is probing something it isn't watching, so fall through to a direct check instead.
+    if port.is_none() {
+        if !force_refresh.unwrap_or(false) {
+            if let Some(status) = state.provider_monitor.cached(&provider_type) {
+                return Ok(status.available);
+            }
         }
-    }
 
-    if let Some(filter_list) = filters {
-        for (name, extensions) in filter_list {
-            let ext_refs: Vec<&str> = extensions.iter().map(|s| s.as_str()).collect();
-            dialog = dialog.add_filter(name, &ext_refs);
+        if let Some(status) = state.provider_monitor.refresh(&provider_type, Some(&app_handle)).await {
+            return Ok(status.available);
         }
     }
 
-    let result = dialog.save_file();
+    let provider = create_ai_provider(
+        provider_type,
+        api_key,
+        base_url,
+        endpoint,
+        deployment_name,
+        None,
+        None,
+        port,
+    )?;
 
-    Ok(result.map(|p| p.to_string_lossy().to_string()))
+    provider
+        .check_availability()
+        .await
+        .map_err(|e| format!("Failed to check availability: {}", e))
 }
 
-/// Write file to disk with native file system access
+/// List available models from an AI provider
 #[tauri::command]
-pub async fn write_file_to_disk(path: String, content: String) -> Result<String, String> {
-    use std::fs;
+pub async fn list_ai_provider_models(
+    provider_type: String,
+    api_key: Option<String>,
+    base_url: Option<String>,
+    endpoint: Option<String>,
+    deployment_name: Option<String>,
+    port: Option<u16>,
+) -> Result<Vec<String>, String> {
+    use crate::ai_providers::AIProvider;
 
-    // Validate path to prevent path traversal attacks
-    let validated_path = validate_file_path_secure(&path)?;
+    tracing::info!("Listing models for provider: {}", provider_type);
 
-    tracing::info!("Writing file to: {}", validated_path);
+    let provider = create_ai_provider(
+        provider_type,
+        api_key,
+        base_url,
+        endpoint,
+        deployment_name,
+        None,
+        None,
+        port,
+    )?;
 
-    fs::write(&validated_path, content).map_err(|e| format!("Failed to write file: {}", e))?;
+    provider
+        .list_models()
+        .await
+        .map_err(|e| format!("Failed to list models: {}", e))
+}
 
-    Ok(format!("File written successfully to: {}", validated_path))
+/// A single Ollama model pull progress update, emitted as the `ollama-pull-progress` event
+#[derive(Debug, Clone, Serialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct OllamaPullProgressEvent {
+    pub pull_id: String,
+    pub status: String,
+    pub digest: Option<String>,
+    pub total: Option<u64>,
+    pub completed: Option<u64>,
+    pub done: bool,
 }
 
-/// Read file from disk with native file system access
+/// Download an Ollama model, emitting `ollama-pull-progress` events as Ollama reports status
+///
+/// The caller supplies `pull_id` (e.g. a UUID) to correlate incoming events with this request if
+/// more than one pull is in flight. A final event with `done: true` is emitted once the stream
+/// ends, whatever its last reported status was.
 #[tauri::command]
-pub async fn read_file_from_disk(path: String) -> Result<String, String> {
-    use std::fs;
+pub async fn pull_ollama_model(
+    pull_id: String,
+    model: String,
+    port: Option<u16>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    use crate::ai_providers::AIProvider;
+    use tauri::Manager;
 
-    // Validate path to prevent path traversal attacks
-    let validated_path = validate_file_path_secure(&path)?;
+    tracing::info!("Pulling Ollama model: {}", model);
 
-    tracing::info!("Reading file from: {}", validated_path);
+    let provider = AIProvider::ollama(port);
+    let base_url = match &provider {
+        AIProvider::Ollama { base_url } => base_url.clone(),
+        _ => unreachable!("AIProvider::ollama always returns AIProvider::Ollama"),
+    };
 
-    fs::read_to_string(&validated_path).map_err(|e| format!("Failed to read file: {}", e))
-}
+    let on_progress = |progress: crate::ai_providers::OllamaPullProgress| {
+        let taskbar_state = match (progress.completed, progress.total) {
+            (Some(_), Some(_)) => crate::platform::TaskbarProgressState::Normal,
+            _ => crate::platform::TaskbarProgressState::Indeterminate,
+        };
+        if let Err(e) = crate::platform::set_taskbar_progress(
+            taskbar_state,
+            progress.completed.unwrap_or(0),
+            progress.total.unwrap_or(1),
+        ) {
+            tracing::debug!("Failed to update taskbar progress for model pull: {}", e);
+        }
 
-/// Show system notification
-#[tauri::command]
-pub async fn show_notification(
-    title: String,
-    body: String,
-    icon: Option<String>,
-) -> Result<String, String> {
-    tracing::info!("Showing notification: {}", title);
+        let _ = app_handle.emit_all(
+            "ollama-pull-progress",
+            OllamaPullProgressEvent {
+                pull_id: pull_id.clone(),
+                status: progress.status,
+                digest: progress.digest,
+                total: progress.total,
+                completed: progress.completed,
+                done: false,
+            },
+        );
+    };
 
-    // This would use Tauri's notification API
-    // For now, just log the notification
-    tracing::info!("Notification - {}: {}", title, body);
+    let result = AIProvider::pull_ollama_model(&base_url, &model, &on_progress).await;
 
-    Ok("Notification shown".to_string())
+    if let Err(e) = crate::platform::set_taskbar_progress(crate::platform::TaskbarProgressState::NoProgress, 0, 1) {
+        tracing::debug!("Failed to clear taskbar progress after model pull: {}", e);
+    }
+
+    let _ = app_handle.emit_all(
+        "ollama-pull-progress",
+        OllamaPullProgressEvent {
+            pull_id,
+            status: if result.is_ok() { "success".to_string() } else { "error".to_string() },
+            digest: None,
+            total: None,
+            completed: None,
+            done: true,
+        },
+    );
+
+    result.map_err(|e| format!("Failed to pull Ollama model: {}", e))
 }
 
-/// Copy text to system clipboard
+/// Delete a locally pulled Ollama model
 #[tauri::command]
-pub async fn copy_to_clipboard(text: String) -> Result<String, String> {
-    tracing::info!("Copying to clipboard");
+pub async fn delete_ollama_model(model: String, port: Option<u16>) -> Result<(), String> {
+    use crate::ai_providers::AIProvider;
 
-    // This would use Tauri's clipboard API
-    // For now, just return success
-    Ok("Text copied to clipboard".to_string())
+    tracing::info!("Deleting Ollama model: {}", model);
+
+    let provider = AIProvider::ollama(port);
+    let base_url = match &provider {
+        AIProvider::Ollama { base_url } => base_url.clone(),
+        _ => unreachable!("AIProvider::ollama always returns AIProvider::Ollama"),
+    };
+
+    AIProvider::delete_ollama_model(&base_url, &model)
+        .await
+        .map_err(|e| format!("Failed to delete Ollama model: {}", e))
 }
 
-/// Read text from system clipboard
+/// Fetch metadata (parameters, template, modelfile, etc.) for a locally pulled Ollama model
 #[tauri::command]
-pub async fn read_from_clipboard() -> Result<String, String> {
-    tracing::info!("Reading from clipboard");
+pub async fn get_ollama_model_info(
+    model: String,
+    port: Option<u16>,
+) -> Result<serde_json::Value, String> {
+    use crate::ai_providers::AIProvider;
+
+    let provider = AIProvider::ollama(port);
+    let base_url = match &provider {
+        AIProvider::Ollama { base_url } => base_url.clone(),
+        _ => unreachable!("AIProvider::ollama always returns AIProvider::Ollama"),
+    };
 
-    // This would use Tauri's clipboard API
-    // For now, return placeholder text
-    Ok("Sample clipboard content".to_string())
+    AIProvider::show_ollama_model(&base_url, &model)
+        .await
+        .map_err(|e| format!("Failed to get Ollama model info: {}", e))
 }
 
-/// Get application data directory path
+/// List the custom OpenAI-compatible providers declared in `custom_providers.json`, if any
+///
+/// Returns an empty list when no such file exists - most installations have no custom
+/// providers configured. Select one by passing `"custom:<name>"` as `provider_type` to the
+/// other AI provider commands.
 #[tauri::command]
-pub async fn get_app_data_dir() -> Result<String, String> {
+pub async fn list_custom_providers() -> Result<Vec<crate::provider_registry::CustomProviderDefinition>, String> {
     use crate::platform;
+    use crate::provider_registry::load_custom_providers;
 
-    // Use cross-platform method to get app data directory
-    if let Some(app_data) = platform::get_app_data_dir() {
-        Ok(app_data.to_string_lossy().to_string())
-    } else {
-        // Ultimate fallback
-        Ok("/tmp/forbidden-library".to_string())
-    }
+    let app_data_dir = platform::get_app_data_dir()
+        .ok_or_else(|| "Failed to determine app data directory".to_string())?;
+    let path = app_data_dir.join("custom_providers.json");
+
+    load_custom_providers(&path).map_err(|e| e.to_string())
 }
 
-/// Open external URL in default browser
+/// Send a request to an AI provider
+///
+/// When `response_style` is `Concise` or `Formal` and `style_enforcement` is enabled, the
+/// response is checked against its length ceiling and banned phrases; a violation triggers one
+/// automatic revision request before the response is returned for persistence.
+///
+/// When `template_id` is set, the named prompt template is rendered server-side with
+/// `template_variables` (failing if any placeholder is missing a value) and appended to
+/// `messages` as the final user message, so the frontend never has to substitute `{{variable}}`
+/// placeholders itself.
+///
+/// When `tools` is non-empty, it's mapped to the provider's own tool-calling wire format
+/// (supported by OpenAI, Anthropic, and Gemini; ignored by other providers); any tools the model
+/// calls come back in the response's `tool_calls` for the frontend or MCP layer to execute.
+///
+/// When `conversation_id` is set, the recorded usage (see [`crate::services::UsageAnalyticsService`])
+/// is attributed to that conversation for `get_usage_by_conversation`; the call succeeds either way.
+///
+/// When `use_cache` is `true`, an identical (provider, model, messages, temperature) request
+/// within [`crate::ai_providers::response_cache::RESPONSE_CACHE_TTL`] is served from
+/// [`crate::ai_providers::response_cache`] instead of re-querying the provider - no tokens are
+/// billed and no usage record is written for a cache hit. Intended for deterministic, repeatable
+/// calls like title generation and summarization rather than open-ended conversation turns.
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
-pub async fn open_external_url(url: String) -> Result<String, String> {
-    tracing::info!("Opening external URL: {}", url);
+pub async fn send_ai_provider_request(
+    provider_type: String,
+    model: String,
+    messages: Vec<serde_json::Value>,
+    api_key: Option<String>,
+    base_url: Option<String>,
+    endpoint: Option<String>,
+    deployment_name: Option<String>,
+    api_version: Option<String>,
+    organization: Option<String>,
+    project: Option<String>,
+    extra_headers: Option<Vec<(String, String)>>,
+    port: Option<u16>,
+    temperature: Option<f32>,
+    max_tokens: Option<i32>,
+    response_style: Option<crate::models::ResponseStyle>,
+    style_enforcement: Option<crate::models::StyleEnforcementConfig>,
+    max_attempts: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+    max_backoff_ms: Option<u64>,
+    template_id: Option<i64>,
+    template_variables: Option<std::collections::HashMap<String, String>>,
+    tools: Option<Vec<crate::ai_providers::ToolDefinition>>,
+    conversation_id: Option<i64>,
+    use_cache: Option<bool>,
+    profile_name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, String> {
+    use crate::ai_providers::{AIProvider, AIRequest, ChatMessage, RetryPolicy};
 
-    // This would use Tauri's shell API
-    // For now, just return success
-    Ok(format!("Opened URL: {}", url))
-}
+    tracing::info!(
+        "Sending request to provider: {} with model: {}",
+        provider_type,
+        model
+    );
 
-/// Create desktop shortcut (Windows/Linux)
-#[tauri::command]
-pub async fn create_desktop_shortcut() -> Result<String, String> {
-    tracing::info!("Creating desktop shortcut");
+    state
+        .services
+        .usage_policy
+        .check_and_record_request()
+        .map_err(|e| e.user_message())?;
 
-    // This would create a desktop shortcut for the application
-    // Implementation would be platform-specific
-    Ok("Desktop shortcut created".to_string())
-}
+    let usage_provider = provider_type.clone();
+    let usage_model = model.clone();
+    let request_started_at = std::time::Instant::now();
 
-/// Check if running in dark mode
-#[tauri::command]
-pub async fn is_dark_mode() -> Result<bool, String> {
-    // This would check the system theme
-    // For now, return false as default
-    Ok(false)
-}
+    let (api_key, base_url, organization, project) = if api_key.is_some() {
+        (api_key, base_url, organization, project)
+    } else {
+        match resolve_api_profile(&state, &provider_type, conversation_id, profile_name)? {
+            Some(config) => (
+                Some(config.api_key),
+                base_url.or(config.base_url),
+                organization.or(config.organization),
+                project.or(config.project),
+            ),
+            None => (api_key, base_url, organization, project),
+        }
+    };
 
-/// Get window state and position
-#[tauri::command]
-pub async fn get_window_state() -> Result<serde_json::Value, String> {
-    let state = serde_json::json!({
-        "width": 1200,
-        "height": 800,
-        "x": 100,
-        "y": 100,
-        "maximized": false,
-        "minimized": false,
-        "fullscreen": false
-    });
+    let validator = InputValidator::default();
+    let validated_headers = extra_headers
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, value)| validator.validate_http_header(&name, &value))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Invalid header: {}", e))?;
+
+    let provider = create_ai_provider_with_headers(
+        provider_type,
+        api_key,
+        base_url,
+        endpoint,
+        deployment_name,
+        api_version,
+        organization,
+        project,
+        validated_headers,
+        port,
+    )?;
 
-    Ok(state)
-}
+    let chat_messages: Result<Vec<ChatMessage>, String> = messages
+        .iter()
+        .map(|m| {
+            Ok(ChatMessage {
+                role: m["role"]
+                    .as_str()
+                    .ok_or("Missing 'role' field")?
+                    .to_string(),
+                content: m["content"]
+                    .as_str()
+                    .ok_or("Missing 'content' field")?
+                    .to_string(),
+                tool_call_id: m["tool_call_id"].as_str().map(|s| s.to_string()),
+                pinned: m["pinned"].as_bool().unwrap_or(false),
+            })
+        })
+        .collect();
 
-/// Set window always on top
-#[tauri::command]
-pub async fn set_window_always_on_top(always_on_top: bool) -> Result<String, String> {
-    tracing::info!("Setting window always on top: {}", always_on_top);
+    let mut chat_messages = chat_messages?;
 
-    // This would use Tauri's window API
-    Ok(format!("Window always on top set to: {}", always_on_top))
-}
+    if let Some(template_id) = template_id {
+        let rendered = state
+            .services
+            .prompt_templates
+            .render_by_id(template_id, &template_variables.unwrap_or_default())
+            .map_err(|e| format!("Failed to render prompt template: {}", e))?;
+        chat_messages.push(ChatMessage {
+            role: "user".to_string(),
+            content: rendered,
+            tool_call_id: None,
+            pinned: false,
+        });
+    }
 
-/// Minimize window to system tray
-#[tauri::command]
-pub async fn minimize_to_tray() -> Result<String, String> {
-    tracing::info!("Minimizing to system tray");
+    let (chat_messages, truncated) = crate::tokenizer::fit_messages_to_context(chat_messages, &model);
+    if truncated {
+        tracing::info!(
+            "Truncated conversation history to fit {}'s context window",
+            model
+        );
+    }
+
+    let request = AIRequest {
+        model,
+        messages: chat_messages,
+        temperature,
+        max_tokens,
+        stream: false,
+        tools: tools.unwrap_or_default(),
+    };
+
+    let use_cache = use_cache.unwrap_or(false);
+    if use_cache {
+        if let Some(cached) = crate::ai_providers::response_cache::get(&usage_provider, &request) {
+            tracing::info!(
+                "✅ Serving cached AI response for provider {} model {}",
+                usage_provider,
+                usage_model
+            );
+            return Ok(serde_json::json!({
+                "content": cached.content,
+                "model": cached.model,
+                "tokens_used": cached.tokens_used,
+                "response_headers": cached.response_headers,
+                "tool_calls": cached.tool_calls,
+            }));
+        }
+    }
+    let cache_request = request.clone();
 
-    // This would minimize the window to system tray
-    Ok("Window minimized to tray".to_string())
-}
+    let default_policy = RetryPolicy::default();
+    let retry_policy = RetryPolicy {
+        max_attempts: max_attempts.unwrap_or(default_policy.max_attempts),
+        initial_backoff_ms: initial_backoff_ms.unwrap_or(default_policy.initial_backoff_ms),
+        max_backoff_ms: max_backoff_ms.unwrap_or(default_policy.max_backoff_ms),
+    };
 
-/// Check for application updates
-#[tauri::command]
-pub async fn check_for_updates() -> Result<serde_json::Value, String> {
-    tracing::info!("Checking for updates");
+    let prompt_tokens_estimate = crate::tokenizer::estimate_prompt_tokens(&request.messages);
 
-    let update_info = serde_json::json!({
-        "available": false,
-        "current_version": env!("CARGO_PKG_VERSION"),
-        "latest_version": env!("CARGO_PKG_VERSION"),
-        "download_url": null
-    });
+    let mut response = provider
+        .send_request_with_retry(request.clone(), &retry_policy)
+        .await
+        .map_err(|e| format!("Failed to send request: {}", e))?;
 
-    Ok(update_info)
-}
+    if response.tokens_used.is_none() {
+        // Ollama and some custom providers don't report usage; fall back to our own estimate
+        // so callers always get a number to work with.
+        let estimated = crate::tokenizer::estimate_prompt_tokens(&request.messages)
+            + crate::tokenizer::estimate_tokens(&response.content);
+        response.tokens_used = Some(estimated as i32);
+    }
 
-// ==================== AI PROVIDER COMMANDS ====================
+    if let (Some(style), Some(config)) = (&response_style, &style_enforcement) {
+        let violations = crate::style_enforcement::check_style(&response.content, style, config);
+        if !violations.is_empty() {
+            tracing::info!(
+                "Response violated {} persona style constraint(s), requesting one revision",
+                violations.len()
+            );
+            let mut revision_messages = request.messages.clone();
+            revision_messages.push(ChatMessage {
+                role: "assistant".to_string(),
+                content: response.content.clone(),
+                tool_call_id: None,
+                pinned: false,
+            });
+            revision_messages.push(ChatMessage {
+                role: "user".to_string(),
+                content: crate::style_enforcement::build_revision_prompt(&violations),
+                tool_call_id: None,
+                pinned: false,
+            });
 
-/// Check if an AI provider is available
-///
-/// Supports: OpenAI, Anthropic Claude, Google Gemini, Azure OpenAI, LM Studio, Ollama
-#[tauri::command]
-pub async fn check_ai_provider_availability(
-    provider_type: String,
-    api_key: Option<String>,
-    base_url: Option<String>,
-    endpoint: Option<String>,
-    deployment_name: Option<String>,
-    port: Option<u16>,
-) -> Result<bool, String> {
-    use crate::ai_providers::AIProvider;
+            let revision_request = AIRequest {
+                messages: revision_messages,
+                ..request
+            };
+            response = provider
+                .send_request_with_retry(revision_request, &retry_policy)
+                .await
+                .map_err(|e| format!("Failed to send revision request: {}", e))?;
+        }
+    }
 
-    tracing::info!("Checking availability for provider: {}", provider_type);
+    if use_cache {
+        crate::ai_providers::response_cache::put(&usage_provider, &cache_request, &response);
+    }
 
-    let provider = create_ai_provider(
-        provider_type,
-        api_key,
-        base_url,
-        endpoint,
-        deployment_name,
-        None,
-        None,
-        port,
-    )?;
+    let completion_tokens_estimate = crate::tokenizer::estimate_tokens(&response.content);
+    let total_tokens = response
+        .tokens_used
+        .map(|t| t as i64)
+        .unwrap_or((prompt_tokens_estimate + completion_tokens_estimate) as i64);
+    let price_table = crate::platform::get_app_data_dir()
+        .map(|dir| crate::pricing::load_price_table(&dir.join("pricing.json")))
+        .unwrap_or_default();
+    if let Err(e) = state.services.usage_analytics.record_usage(
+        conversation_id,
+        &usage_provider,
+        &usage_model,
+        prompt_tokens_estimate as i64,
+        completion_tokens_estimate as i64,
+        total_tokens,
+        request_started_at.elapsed().as_millis() as i64,
+        crate::pricing::estimate_cost(
+            &price_table,
+            &usage_provider,
+            &usage_model,
+            prompt_tokens_estimate as i64,
+            completion_tokens_estimate as i64,
+        ),
+    ) {
+        tracing::warn!("⚠️ Failed to record usage: {}", e);
+    }
 
-    provider
-        .check_availability()
-        .await
-        .map_err(|e| format!("Failed to check availability: {}", e))
+    Ok(serde_json::json!({
+        "content": response.content,
+        "model": response.model,
+        "tokens_used": response.tokens_used,
+        "response_headers": response.response_headers,
+        "tool_calls": response.tool_calls,
+    }))
 }
 
-/// List available models from an AI provider
+/// Aggregate token usage and estimated cost recorded over the last `lookback_days` days
+/// (0 = today only), broken down by provider/model, for the frontend analytics dashboard
 #[tauri::command]
-pub async fn list_ai_provider_models(
-    provider_type: String,
-    api_key: Option<String>,
-    base_url: Option<String>,
-    endpoint: Option<String>,
-    deployment_name: Option<String>,
-    port: Option<u16>,
-) -> Result<Vec<String>, String> {
-    use crate::ai_providers::AIProvider;
-
-    tracing::info!("Listing models for provider: {}", provider_type);
+pub async fn get_usage_summary(
+    lookback_days: i64,
+    state: State<'_, AppState>,
+) -> Result<crate::models::UsageSummary, String> {
+    state
+        .services
+        .usage_analytics
+        .get_usage_summary(lookback_days)
+        .map_err(|e| format!("Failed to get usage summary: {}", e))
+}
 
-    let provider = create_ai_provider(
-        provider_type,
-        api_key,
-        base_url,
-        endpoint,
-        deployment_name,
-        None,
-        None,
-        port,
-    )?;
+/// Every usage record attributed to `conversation_id` via `send_ai_provider_request`'s
+/// `conversation_id` parameter, oldest first
+#[tauri::command]
+pub async fn get_usage_by_conversation(
+    conversation_id: i64,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::UsageRecord>, String> {
+    state
+        .services
+        .usage_analytics
+        .get_usage_by_conversation(conversation_id)
+        .map_err(|e| format!("Failed to get usage for conversation: {}", e))
+}
 
-    provider
-        .list_models()
-        .await
-        .map_err(|e| format!("Failed to list models: {}", e))
+/// A single piece of a streaming AI response, emitted as the `ai-stream-chunk` event
+#[derive(Debug, Clone, Serialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct AiStreamChunk {
+    pub stream_id: String,
+    /// New text generated since the previous chunk; empty on the final, `done` chunk
+    pub delta: String,
+    pub done: bool,
+    /// Populated only on the final chunk
+    pub model: Option<String>,
+    pub tokens_used: Option<i32>,
 }
 
-/// Send a request to an AI provider
+/// Send a request to an AI provider and emit `ai-stream-chunk` events as tokens arrive,
+/// instead of returning the complete response in one shot
+///
+/// The caller supplies `stream_id` (e.g. a UUID) so it can correlate incoming events with this
+/// request if more than one stream is in flight. A final event with `done: true` carries the
+/// response's model and token usage.
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
-pub async fn send_ai_provider_request(
+pub async fn stream_ai_provider_request(
+    stream_id: String,
     provider_type: String,
     model: String,
     messages: Vec<serde_json::Value>,
@@ -1219,19 +5482,58 @@ pub async fn send_ai_provider_request(
     deployment_name: Option<String>,
     api_version: Option<String>,
     organization: Option<String>,
+    project: Option<String>,
+    extra_headers: Option<Vec<(String, String)>>,
     port: Option<u16>,
     temperature: Option<f32>,
     max_tokens: Option<i32>,
-) -> Result<serde_json::Value, String> {
-    use crate::ai_providers::{AIProvider, AIRequest, ChatMessage};
+    conversation_id: Option<i64>,
+    profile_name: Option<String>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    use crate::ai_providers::{AIRequest, ChatMessage};
+    use tauri::Manager;
 
     tracing::info!(
-        "Sending request to provider: {} with model: {}",
+        "Streaming request {} to provider: {} with model: {}",
+        stream_id,
         provider_type,
         model
     );
 
-    let provider = create_ai_provider(
+    state
+        .services
+        .usage_policy
+        .check_and_record_request()
+        .map_err(|e| e.user_message())?;
+
+    let usage_provider = provider_type.clone();
+    let usage_model = model.clone();
+
+    let (api_key, base_url, organization, project) = if api_key.is_some() {
+        (api_key, base_url, organization, project)
+    } else {
+        match resolve_api_profile(&state, &provider_type, conversation_id, profile_name)? {
+            Some(config) => (
+                Some(config.api_key),
+                base_url.or(config.base_url),
+                organization.or(config.organization),
+                project.or(config.project),
+            ),
+            None => (api_key, base_url, organization, project),
+        }
+    };
+
+    let validator = InputValidator::default();
+    let validated_headers = extra_headers
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, value)| validator.validate_http_header(&name, &value))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Invalid header: {}", e))?;
+
+    let provider = create_ai_provider_with_headers(
         provider_type,
         api_key,
         base_url,
@@ -1239,6 +5541,8 @@ pub async fn send_ai_provider_request(
         deployment_name,
         api_version,
         organization,
+        project,
+        validated_headers,
         port,
     )?;
 
@@ -1254,30 +5558,254 @@ pub async fn send_ai_provider_request(
                     .as_str()
                     .ok_or("Missing 'content' field")?
                     .to_string(),
+                tool_call_id: m["tool_call_id"].as_str().map(|s| s.to_string()),
+                pinned: m["pinned"].as_bool().unwrap_or(false),
             })
         })
         .collect();
 
-    let chat_messages = chat_messages?;
+    let (fitted_messages, truncated) =
+        crate::tokenizer::fit_messages_to_context(chat_messages?, &model);
+    if truncated {
+        tracing::info!(
+            "Truncated conversation history to fit {}'s context window",
+            model
+        );
+    }
 
     let request = AIRequest {
         model,
-        messages: chat_messages,
+        messages: fitted_messages,
         temperature,
         max_tokens,
+        stream: true,
+        tools: Vec::new(),
+    };
+
+    let request_started_at = std::time::Instant::now();
+    crate::cancellation::register(&stream_id);
+
+    let on_chunk = |delta: &str| {
+        let _ = app_handle.emit_all(
+            "ai-stream-chunk",
+            AiStreamChunk {
+                stream_id: stream_id.clone(),
+                delta: delta.to_string(),
+                done: false,
+                model: None,
+                tokens_used: None,
+            },
+        );
+        !crate::cancellation::is_cancelled(&stream_id)
+    };
+
+    let result = provider.send_request_streaming(request, &on_chunk).await;
+    crate::cancellation::unregister(&stream_id);
+
+    let response = match result {
+        Ok(response) => response,
+        Err(e) if e.is_cancelled() => {
+            tracing::info!("🛑 Streaming request {} cancelled", stream_id);
+
+            if let Err(e) = state.services.usage_analytics.record_cancelled_usage(
+                conversation_id,
+                &usage_provider,
+                &usage_model,
+                request_started_at.elapsed().as_millis() as i64,
+            ) {
+                tracing::warn!("⚠️ Failed to record cancelled usage: {}", e);
+            }
+
+            let _ = app_handle.emit_all(
+                "request-cancelled",
+                serde_json::json!({ "request_id": stream_id }),
+            );
+
+            return Ok(());
+        }
+        Err(e) => return Err(format!("Failed to stream request: {}", e)),
+    };
+
+    let tokens_used = response.tokens_used.or_else(|| {
+        // Ollama and some custom providers don't report usage; fall back to our own estimate.
+        Some(crate::tokenizer::estimate_tokens(&response.content) as i32)
+    });
+
+    let _ = app_handle.emit_all(
+        "ai-stream-chunk",
+        AiStreamChunk {
+            stream_id,
+            delta: String::new(),
+            done: true,
+            model: Some(response.model),
+            tokens_used,
+        },
+    );
+
+    Ok(())
+}
+
+/// Abort an in-flight [`stream_ai_provider_request`] call by its `stream_id`, emitting a
+/// `request-cancelled` event and recording the attempt as a cancelled [`crate::models::UsageRecord`]
+/// once the stream notices and unwinds.
+///
+/// There's no handle to the underlying `reqwest` future here - cancellation is cooperative, via
+/// [`crate::cancellation`], so the in-flight request only stops after its next chunk arrives (or
+/// its 120-second timeout lapses) rather than instantly.
+#[tauri::command]
+pub async fn cancel_ai_request(request_id: String) -> Result<bool, String> {
+    tracing::info!("Requesting cancellation of {}", request_id);
+    Ok(crate::cancellation::cancel(&request_id))
+}
+
+/// How long generated follow-up suggestions stay cached before they're considered stale
+const FOLLOW_UP_SUGGESTIONS_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(600);
+
+/// Ask a model for 2-3 short follow-up questions a user might ask next, given the assistant's
+/// latest reply, and cache them transiently against the conversation
+///
+/// Meant to be called with a cheap/local model (e.g. a small Ollama model) distinct from
+/// whichever model produced `assistant_reply` - this endpoint doesn't choose one for the
+/// caller. Whether to call this at all is left entirely to the frontend's "suggest follow-ups"
+/// setting, so disabling it saves the extra request rather than this command just discarding
+/// its result.
+#[tauri::command]
+pub async fn generate_follow_up_suggestions(
+    conversation_id: i64,
+    provider_type: String,
+    model: String,
+    api_key: Option<String>,
+    base_url: Option<String>,
+    assistant_reply: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    use crate::ai_providers::{AIRequest, ChatMessage};
+
+    tracing::debug!(
+        "Generating follow-up suggestions for conversation {}",
+        conversation_id
+    );
+
+    let provider = create_ai_provider_with_headers(
+        provider_type,
+        api_key,
+        base_url,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Vec::new(),
+        None,
+    )?;
+
+    let prompt = format!(
+        "Suggest 2-3 short, natural follow-up questions a user might ask next, based on this \
+         assistant reply. Reply with one question per line and nothing else - no numbering, \
+         no commentary.\n\nAssistant reply:\n{}",
+        assistant_reply
+    );
+
+    let request = AIRequest {
+        model,
+        messages: vec![ChatMessage {
+            role: "user".to_string(),
+            content: prompt,
+            tool_call_id: None,
+            pinned: false,
+        }],
+        temperature: Some(0.7),
+        max_tokens: Some(150),
         stream: false,
+        tools: Vec::new(),
     };
 
     let response = provider
         .send_request(request)
         .await
-        .map_err(|e| format!("Failed to send request: {}", e))?;
+        .map_err(|e| format!("Failed to generate follow-up suggestions: {}", e))?;
+
+    let suggestions: Vec<String> = response
+        .content
+        .lines()
+        .map(|line| line.trim_start_matches(['-', '*', '•']).trim().to_string())
+        .filter(|line| !line.is_empty())
+        .take(3)
+        .collect();
 
-    Ok(serde_json::json!({
-        "content": response.content,
-        "model": response.model,
-        "tokens_used": response.tokens_used,
-    }))
+    if let Ok(json) = serde_json::to_string(&suggestions) {
+        state.services.query_cache.set(
+            format!("conversation:{}:follow_up_suggestions", conversation_id),
+            json,
+            Some(FOLLOW_UP_SUGGESTIONS_CACHE_TTL),
+        );
+    }
+
+    Ok(suggestions)
+}
+
+/// Fold old conversation history into rolling summary chunks using the configured provider
+///
+/// Only messages after the last summary's cutoff and outside the most recent
+/// [`crate::services::RECENT_MESSAGE_WINDOW`] are summarized, split into fixed-size chunks so a
+/// long backlog doesn't overflow the model's context window in one call. `send_ai_request`
+/// includes the latest chunk plus the recent window instead of the full history.
+#[tauri::command]
+pub async fn summarize_conversation(
+    conversation_id: i64,
+    provider_type: String,
+    model: String,
+    api_key: Option<String>,
+    base_url: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::models::ConversationSummarizationReport, String> {
+    use crate::summarization::AbstractiveSummarizer;
+
+    let provider = create_ai_provider_with_headers(
+        provider_type,
+        api_key,
+        base_url,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Vec::new(),
+        None,
+    )?;
+    let summarizer = AbstractiveSummarizer::new(provider, model);
+
+    let summaries = state
+        .services
+        .conversations
+        .summarize_conversation(conversation_id, &summarizer)
+        .await
+        .map_err(|e| format!("Failed to summarize conversation: {}", e))?;
+
+    Ok(crate::models::ConversationSummarizationReport {
+        chunks_summarized: summaries.len(),
+        latest_summary: summaries.into_iter().last(),
+    })
+}
+
+/// Read back follow-up suggestions previously generated for a conversation by
+/// `generate_follow_up_suggestions`, if they haven't expired from the cache
+#[tauri::command]
+pub async fn get_cached_follow_up_suggestions(
+    conversation_id: i64,
+    state: State<'_, AppState>,
+) -> Result<Option<Vec<String>>, String> {
+    let cached = state
+        .services
+        .query_cache
+        .get(&format!("conversation:{}:follow_up_suggestions", conversation_id));
+
+    match cached {
+        Some(json) => serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse cached suggestions: {}", e)),
+        None => Ok(None),
+    }
 }
 
 /// Helper function to create an AI provider from parameters
@@ -1290,13 +5818,47 @@ fn create_ai_provider(
     api_version: Option<String>,
     organization: Option<String>,
     port: Option<u16>,
+) -> Result<crate::ai_providers::AIProvider, String> {
+    create_ai_provider_with_headers(
+        provider_type,
+        api_key,
+        base_url,
+        endpoint,
+        deployment_name,
+        api_version,
+        organization,
+        None,
+        Vec::new(),
+        port,
+    )
+}
+
+/// Helper function to create an AI provider from parameters, including OpenAI
+/// project-scoping and arbitrary extra headers for cost-attribution setups
+#[allow(clippy::too_many_arguments)]
+fn create_ai_provider_with_headers(
+    provider_type: String,
+    api_key: Option<String>,
+    base_url: Option<String>,
+    endpoint: Option<String>,
+    deployment_name: Option<String>,
+    api_version: Option<String>,
+    organization: Option<String>,
+    project: Option<String>,
+    extra_headers: Vec<(String, String)>,
+    port: Option<u16>,
 ) -> Result<crate::ai_providers::AIProvider, String> {
     use crate::ai_providers::AIProvider;
 
     match provider_type.as_str() {
         "openai" => {
             let key = api_key.ok_or("API key required for OpenAI")?;
-            Ok(AIProvider::openai(key, organization))
+            Ok(AIProvider::openai_with_headers(
+                key,
+                organization,
+                project,
+                extra_headers,
+            ))
         }
         "anthropic" | "claude" => {
             let key = api_key.ok_or("API key required for Anthropic")?;
@@ -1318,7 +5880,22 @@ fn create_ai_provider(
             let url = base_url.ok_or("Base URL required for OpenAI compatible provider")?;
             Ok(AIProvider::openai_compatible(url, api_key))
         }
-        _ => Err(format!("Unknown provider type: {}", provider_type)),
+        other => {
+            if let Some(name) = other.strip_prefix("custom:") {
+                let app_data_dir = crate::platform::get_app_data_dir()
+                    .ok_or("Failed to determine app data directory")?;
+                let definitions =
+                    crate::provider_registry::load_custom_providers(&app_data_dir.join("custom_providers.json"))
+                        .map_err(|e| e.to_string())?;
+                let definition = definitions
+                    .into_iter()
+                    .find(|def| def.name == name)
+                    .ok_or_else(|| format!("Unknown custom provider: {}", name))?;
+                Ok(AIProvider::from_custom_definition(definition, api_key))
+            } else {
+                Err(format!("Unknown provider type: {}", provider_type))
+            }
+        }
     }
 }
 
@@ -1384,6 +5961,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         let result = create_conversation(
@@ -1404,6 +5982,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         let result = create_conversation(
@@ -1424,6 +6003,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         // Create a test conversation first
@@ -1446,6 +6026,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         // Create a test conversation first
@@ -1470,6 +6051,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         // Create a test conversation first
@@ -1496,6 +6078,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         // Create a test conversation first
@@ -1523,6 +6106,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         // Create a test conversation first
@@ -1555,6 +6139,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         // Create a test conversation first
@@ -1579,7 +6164,7 @@ mod tests {
         .await
         .unwrap();
 
-        let result = get_messages(conversation_id, State::new(&app_state)).await;
+        let result = get_messages(conversation_id, None, None, State::new(&app_state)).await;
         assert!(result.is_ok());
         let messages = result.unwrap();
         assert_eq!(messages.len(), 1);
@@ -1591,6 +6176,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         // Create a test conversation first
@@ -1633,6 +6219,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         // Create a test conversation first
@@ -1662,7 +6249,7 @@ mod tests {
         assert!(result.is_ok());
 
         // Verify message is deleted
-        let messages = get_messages(conversation_id, State::new(&app_state))
+        let messages = get_messages(conversation_id, None, None, State::new(&app_state))
             .await
             .unwrap();
         assert_eq!(messages.len(), 0);
@@ -1673,6 +6260,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         let result = create_persona(
@@ -1694,6 +6282,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         // Create a test persona first
@@ -1717,6 +6306,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         // Create a test persona first
@@ -1742,6 +6332,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         // Create a test persona first
@@ -1775,6 +6366,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         // Create a test persona first
@@ -1802,6 +6394,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         let config = serde_json::json!({
@@ -1821,6 +6414,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         let config = serde_json::json!({
@@ -1846,6 +6440,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         let config = serde_json::json!({
@@ -1873,6 +6468,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         let result = send_ai_request(
@@ -1892,6 +6488,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         let result = get_database_stats(State::new(&app_state)).await;
@@ -1903,34 +6500,19 @@ mod tests {
         assert!(stats.database_size_mb >= 0.0);
     }
 
-    #[tokio::test]
-    async fn test_export_conversation_command() {
-        let env = TestCommandsEnvironment::new();
-        let app_state = AppState {
-            services: env.services,
-        };
-
-        // Create a test conversation first
-        let created = create_conversation(
-            "Test Conversation".to_string(),
-            None,
-            State::new(&app_state),
-        )
-        .await
-        .unwrap();
-
-        let conversation_id = created.id.unwrap();
-        let result =
-            export_conversation(conversation_id, "json".to_string(), State::new(&app_state)).await;
-
-        assert!(result.is_ok());
-    }
+    // `export_conversation` now takes a `tauri::AppHandle` (for `export-progress` events), which
+    // - like the other app_handle-taking commands in this file (`show_notification`,
+    // `pull_ollama_model`, etc.) - this test module has no fixture for, since a real `AppHandle`
+    // needs a running Tauri runtime to construct. The format-specific rendering it delegates to
+    // (`export_formats::conversation_to_html`, `pdf_export::conversation_to_pdf`) is covered
+    // directly in their own modules instead.
 
     #[tokio::test]
     async fn test_backup_database_command() {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         let result = backup_database(State::new(&app_state)).await;
@@ -1945,6 +6527,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         // Create a backup first
@@ -1959,6 +6542,7 @@ mod tests {
         let env = TestCommandsEnvironment::new();
         let app_state = AppState {
             services: env.services,
+            provider_monitor: std::sync::Arc::new(crate::provider_monitor::ProviderMonitor::new()),
         };
 
         // Create some test data first
@@ -0,0 +1,114 @@
+//! Tamper-evident export of a frozen conversation, for compliance/legal retention.
+//!
+//! Each message is hashed together with the hash of the message before it, forming a chain
+//! (the same idea as a blockchain or a git commit history): altering or reordering any message
+//! after export changes its hash and every hash after it, so [`verify_hash_chain`] can detect
+//! the tamper. This is an integrity check only, not a cryptographic signature - it proves the
+//! export wasn't altered after it was generated, not who generated it.
+
+use crate::errors::{AppError, AppResult};
+use crate::models::{Conversation, Message, MessageRole};
+use chrono::{DateTime, Utc};
+use ring::digest::{digest, SHA256};
+
+/// Hash chain anchor for the first message in a conversation, since it has no predecessor
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// A message plus the SHA-256 hash covering it and every message before it
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct HashedMessage {
+    pub message: Message,
+    /// Hex-encoded SHA-256 of `{previous_hash}|{id}|{role}|{content}|{created_at}`
+    pub hash: String,
+}
+
+/// A frozen conversation and its messages, bundled with a verifiable hash chain
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ComplianceExport {
+    pub conversation: Conversation,
+    pub messages: Vec<HashedMessage>,
+    /// Hash of the last message in the chain, repeated here so a verifier can check the whole
+    /// export against a single value without walking the chain first
+    pub final_hash: String,
+    pub exported_at: DateTime<Utc>,
+    pub format_version: u32,
+}
+
+/// Bumped whenever the chained payload format changes incompatibly
+const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// Hash one message onto the end of a chain
+fn chain_hash(previous_hash: &str, message: &Message) -> String {
+    let role_str = match message.role {
+        MessageRole::User => "user",
+        MessageRole::Assistant => "assistant",
+        MessageRole::System => "system",
+        MessageRole::Tool => "tool",
+    };
+    let payload = format!(
+        "{}|{}|{}|{}|{}",
+        previous_hash,
+        message.id.unwrap_or_default(),
+        role_str,
+        message.content,
+        message.created_at.to_rfc3339(),
+    );
+    let hash = digest(&SHA256, payload.as_bytes());
+    hash.as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Build the hash chain for an ordered list of messages, oldest first
+fn build_hash_chain(messages: Vec<Message>) -> (Vec<HashedMessage>, String) {
+    let mut previous_hash = GENESIS_HASH.to_string();
+    let mut hashed = Vec::with_capacity(messages.len());
+
+    for message in messages {
+        let hash = chain_hash(&previous_hash, &message);
+        previous_hash = hash.clone();
+        hashed.push(HashedMessage { message, hash });
+    }
+
+    let final_hash = previous_hash;
+    (hashed, final_hash)
+}
+
+/// Build a [`ComplianceExport`] for a conversation, which must already be frozen
+///
+/// Freezing is enforced here rather than left to the caller so an export can never be produced
+/// for a conversation that could still change underneath it - see
+/// [`crate::services::ConversationService::set_conversation_frozen`].
+pub fn export_frozen_conversation(
+    conversation: Conversation,
+    messages: Vec<Message>,
+) -> AppResult<ComplianceExport> {
+    if !conversation.frozen {
+        return Err(AppError::validation(
+            "Conversation must be frozen before it can be exported for compliance",
+        ));
+    }
+
+    let (messages, final_hash) = build_hash_chain(messages);
+
+    Ok(ComplianceExport {
+        conversation,
+        messages,
+        final_hash,
+        exported_at: Utc::now(),
+        format_version: EXPORT_FORMAT_VERSION,
+    })
+}
+
+/// Recompute the hash chain over an export's messages and confirm it matches `final_hash`,
+/// detecting any edit, reorder, insertion, or deletion made after export
+pub fn verify_hash_chain(export: &ComplianceExport) -> bool {
+    let messages: Vec<Message> = export
+        .messages
+        .iter()
+        .map(|hashed| hashed.message.clone())
+        .collect();
+    let (_, recomputed_final_hash) = build_hash_chain(messages);
+    recomputed_final_hash == export.final_hash
+}
@@ -0,0 +1,157 @@
+//! Self-contained, signed, read-only snapshot of a single conversation (a `.flib` file), for
+//! handing one conversation to someone else or opening it on another device without importing
+//! it into a library the way `crate::library_archive` does, and without exposing the rest of the
+//! database the way `crate::snapshot_server`'s live HTTP viewer does.
+//!
+//! Signed with HMAC-SHA256 (`crate::webhooks::sign_payload`) over a canonical string of the
+//! snapshot's fields, keyed by a local secret from the OS keychain
+//! (`KeychainManager::get_or_create_share_signing_key`) - the same "concatenate fields into a
+//! string, then hash/sign that" approach `crate::compliance_export`'s hash chain uses, rather
+//! than signing the serialized JSON bytes, whose exact representation could drift across serde
+//! versions. [`open_shared_snapshot`] verifies the signature before handing the snapshot to a
+//! read-only viewer; neither function touches the database's `conversations`/`messages` tables
+//! on the receiving end.
+//!
+//! The signing key lives only in the local OS keychain and is never included in the snapshot
+//! file itself, so a snapshot opened on a different machine (a different keychain, and so a
+//! different key) will always fail verification - this proves a snapshot wasn't edited after
+//! *this* install produced it, not who produced it, the same limitation
+//! `crate::compliance_export`'s hash chain documents for its own tamper-evidence check.
+
+use crate::errors::{AppError, AppResult};
+use crate::models::{Conversation, Message, MessageRole, Persona};
+use chrono::{DateTime, Utc};
+
+/// Bumped whenever [`ConversationShareSnapshot`]'s JSON shape changes incompatibly
+const SHARE_FORMAT_VERSION: u32 = 1;
+
+/// A conversation, its persona (if any), and every message, plus a signature a receiving
+/// machine can use to confirm the file wasn't altered after [`share_conversation`] produced it
+///
+/// No `ts_rs::TS` derive, same as [`crate::library_archive::LibraryArchive`] and friends -
+/// `Conversation`/`Persona`/`Message` don't derive it either, so a generated TypeScript type
+/// would be incomplete anyway.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ConversationShareSnapshot {
+    pub format_version: u32,
+    pub app_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub conversation: Conversation,
+    pub persona: Option<Persona>,
+    pub messages: Vec<Message>,
+    /// Hex-encoded HMAC-SHA256 over the fields above, from
+    /// [`crate::keychain::KeychainManager::get_or_create_share_signing_key`]
+    pub signature: String,
+}
+
+/// Canonical `field:field|field:field` string covering everything [`open_shared_snapshot`]
+/// trusts, in the same spirit as `crate::compliance_export::chain_hash`'s concatenation
+fn canonical_payload(
+    format_version: u32,
+    app_version: &str,
+    exported_at: DateTime<Utc>,
+    conversation: &Conversation,
+    messages: &[Message],
+) -> String {
+    let mut payload = format!(
+        "{}|{}|{}|{}",
+        format_version,
+        app_version,
+        exported_at.to_rfc3339(),
+        conversation.id.unwrap_or_default(),
+    );
+
+    for message in messages {
+        let role_str = match message.role {
+            MessageRole::User => "user",
+            MessageRole::Assistant => "assistant",
+            MessageRole::System => "system",
+            MessageRole::Tool => "tool",
+        };
+        payload.push('|');
+        payload.push_str(&format!(
+            "{}:{}:{}",
+            message.id.unwrap_or_default(),
+            role_str,
+            message.content
+        ));
+    }
+
+    payload
+}
+
+/// Package a conversation, its persona, and its messages into a signed, portable snapshot
+pub fn share_conversation(
+    conversation_service: &crate::services::ConversationService,
+    persona_service: &crate::services::PersonaService,
+    signing_key: &str,
+    conversation_id: i64,
+) -> AppResult<String> {
+    let conversation = conversation_service
+        .get_conversation(conversation_id)
+        .map_err(|e| AppError::database(format!("Failed to load conversation: {}", e)))?
+        .ok_or_else(|| AppError::not_found(format!("Conversation {} not found", conversation_id)))?;
+
+    let messages = conversation_service
+        .get_messages(conversation_id, None, None)
+        .map_err(|e| AppError::database(format!("Failed to load messages: {}", e)))?;
+
+    let persona = conversation
+        .persona_id
+        .map(|persona_id| persona_service.get_persona(persona_id))
+        .transpose()
+        .map_err(|e| AppError::database(format!("Failed to load persona: {}", e)))?
+        .flatten();
+
+    let app_version = env!("CARGO_PKG_VERSION").to_string();
+    let exported_at = Utc::now();
+    let signature = crate::webhooks::sign_payload(
+        signing_key,
+        canonical_payload(SHARE_FORMAT_VERSION, &app_version, exported_at, &conversation, &messages).as_bytes(),
+    );
+
+    let snapshot = ConversationShareSnapshot {
+        format_version: SHARE_FORMAT_VERSION,
+        app_version,
+        exported_at,
+        conversation,
+        persona,
+        messages,
+        signature,
+    };
+
+    serde_json::to_string_pretty(&snapshot)
+        .map_err(|e| AppError::validation(format!("Failed to serialize conversation snapshot: {}", e)))
+}
+
+/// Parse and verify a snapshot produced by [`share_conversation`], for a read-only viewer -
+/// never inserted into `conversations`/`messages`, so opening an untrusted `.flib` file can't
+/// corrupt the local library
+pub fn open_shared_snapshot(signing_key: &str, json: &str) -> AppResult<ConversationShareSnapshot> {
+    let snapshot: ConversationShareSnapshot = serde_json::from_str(json)
+        .map_err(|e| AppError::validation(format!("Not a valid conversation snapshot: {}", e)))?;
+
+    if snapshot.format_version != SHARE_FORMAT_VERSION {
+        return Err(AppError::validation(format!(
+            "Unsupported conversation snapshot format version: {} (expected {})",
+            snapshot.format_version, SHARE_FORMAT_VERSION
+        )));
+    }
+
+    let payload = canonical_payload(
+        snapshot.format_version,
+        &snapshot.app_version,
+        snapshot.exported_at,
+        &snapshot.conversation,
+        &snapshot.messages,
+    );
+
+    if !crate::webhooks::verify_payload(signing_key, payload.as_bytes(), &snapshot.signature) {
+        return Err(AppError::validation(
+            "Conversation snapshot signature does not match its contents - it may have been altered, \
+             or was signed on a different machine",
+        ));
+    }
+
+    Ok(snapshot)
+}
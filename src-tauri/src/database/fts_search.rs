@@ -334,6 +334,178 @@ pub fn search_titles(
     Ok(search_results)
 }
 
+/// A full-text match against message content, with byte offsets into `content` marking where
+/// the query matched so a caller can highlight it however its UI needs, instead of depending
+/// on FTS5's pre-rendered HTML snippet
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MessageSearchResult {
+    pub conversation_id: i64,
+    pub message_id: i64,
+    pub conversation_title: String,
+    pub content: String,
+    pub relevance_score: f64,
+    pub created_at: String,
+    pub highlight_offsets: Vec<(usize, usize)>,
+}
+
+/// Search message content ranked by BM25 relevance, reporting match offsets instead of
+/// pre-rendered markup
+pub fn search_messages(
+    conn: &Connection,
+    query: &str,
+    limit: Option<i32>,
+) -> AppResult<Vec<MessageSearchResult>> {
+    let limit = limit.unwrap_or(50);
+
+    // Mark matches with control characters rather than HTML tags - they never occur in
+    // validated message content, so they can be stripped back out to recover byte offsets.
+    let sql = "SELECT
+            c.id as conversation_id,
+            m.id as message_id,
+            c.title,
+            m.content,
+            bm25(messages_fts) as score,
+            m.timestamp,
+            highlight(messages_fts, 2, char(2), char(3)) as marked
+        FROM messages_fts
+        INNER JOIN messages m ON messages_fts.message_id = m.id
+        INNER JOIN conversations c ON m.conversation_id = c.id
+        WHERE messages_fts MATCH ?
+        ORDER BY score
+        LIMIT ?";
+
+    let mut stmt = conn.prepare(sql)?;
+
+    let results = stmt.query_map([query, &limit.to_string()], |row| {
+        let marked: String = row.get(6)?;
+        Ok(MessageSearchResult {
+            conversation_id: row.get(0)?,
+            message_id: row.get(1)?,
+            conversation_title: row.get(2)?,
+            content: row.get(3)?,
+            relevance_score: row.get(4)?,
+            created_at: row.get(5)?,
+            highlight_offsets: extract_highlight_offsets(&marked),
+        })
+    })?;
+
+    let mut search_results = Vec::new();
+    for result in results {
+        search_results.push(result?);
+    }
+
+    Ok(search_results)
+}
+
+/// A previously-asked user question and the answer it received, surfaced so a near-duplicate
+/// question can be answered from history instead of re-sent to a provider
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SimilarQuestion {
+    pub conversation_id: i64,
+    pub conversation_title: String,
+    pub question: String,
+    pub answer: Option<String>,
+    pub relevance_score: f64,
+    pub created_at: String,
+}
+
+/// Find prior user questions similar to `text`, each paired with the next assistant reply in
+/// the same conversation, deduplicated by question text
+///
+/// There is no embedding model wired into this crate yet (see [`crate::services::EmbeddingService`]),
+/// so "similar" here means FTS5/BM25 lexical relevance scoped to `role = 'user'` messages rather
+/// than true semantic similarity; this should be swapped for a vector lookup once a real
+/// embedding index is populated.
+pub fn find_similar_questions(conn: &Connection, text: &str, limit: Option<i32>) -> AppResult<Vec<SimilarQuestion>> {
+    let limit = limit.unwrap_or(10);
+
+    let sql = "SELECT
+            c.id as conversation_id,
+            c.title,
+            m.id as message_id,
+            m.content,
+            bm25(messages_fts) as score,
+            m.timestamp
+        FROM messages_fts
+        INNER JOIN messages m ON messages_fts.message_id = m.id
+        INNER JOIN conversations c ON m.conversation_id = c.id
+        WHERE messages_fts MATCH ? AND messages_fts.role = 'user'
+        ORDER BY score
+        LIMIT ?";
+
+    // Over-fetch before deduplicating - some candidates collapse into the same question
+    let fetch_limit = limit.saturating_mul(4).max(limit);
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([text, &fetch_limit.to_string()], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, f64>(4)?,
+            row.get::<_, String>(5)?,
+        ))
+    })?;
+
+    let mut seen = std::collections::HashSet::new();
+    let mut results = Vec::new();
+    for row in rows {
+        let (conversation_id, conversation_title, message_id, question, relevance_score, created_at) = row?;
+
+        let normalized = question.trim().to_lowercase();
+        if !seen.insert(normalized) {
+            continue;
+        }
+
+        let answer: Option<String> = conn
+            .query_row(
+                "SELECT content FROM messages
+                 WHERE conversation_id = ?1 AND id > ?2 AND role = 'assistant'
+                 ORDER BY id ASC LIMIT 1",
+                [conversation_id, message_id],
+                |row| row.get(0),
+            )
+            .ok();
+
+        results.push(SimilarQuestion {
+            conversation_id,
+            conversation_title,
+            question,
+            answer,
+            relevance_score,
+            created_at,
+        });
+
+        if results.len() >= limit as usize {
+            break;
+        }
+    }
+
+    Ok(results)
+}
+
+/// Recover byte offset spans of matches from a string marked with `\u{2}`/`\u{3}` control
+/// characters, as produced by `search_messages`'s use of FTS5's `highlight()`
+fn extract_highlight_offsets(marked: &str) -> Vec<(usize, usize)> {
+    let mut offsets = Vec::new();
+    let mut plain_len = 0usize;
+    let mut match_start = None;
+
+    for c in marked.chars() {
+        match c {
+            '\u{2}' => match_start = Some(plain_len),
+            '\u{3}' => {
+                if let Some(start) = match_start.take() {
+                    offsets.push((start, plain_len));
+                }
+            }
+            other => plain_len += other.len_utf8(),
+        }
+    }
+
+    offsets
+}
+
 /// Get search suggestions based on partial query
 pub fn get_search_suggestions(
     conn: &Connection,
@@ -416,4 +588,29 @@ mod tests {
         assert!(!results.is_empty());
         assert_eq!(results[0].conversation_id, 1);
     }
+
+    #[test]
+    fn test_search_messages_highlight_offsets() {
+        let db = DatabaseManager::new_in_memory().unwrap();
+        let conn = db.get_connection().unwrap();
+
+        initialize_fts_tables(&conn).unwrap();
+
+        conn.execute(
+            "INSERT INTO conversations (uuid, title) VALUES ('test-uuid', 'Test Conversation')",
+            [],
+        )
+        .unwrap();
+
+        conn.execute(
+            "INSERT INTO messages (id, conversation_id, role, content) VALUES ('msg1', 1, 'user', 'Hello world this is a test')",
+            [],
+        )
+        .unwrap();
+
+        let results = search_messages(&conn, "test", Some(10)).unwrap();
+        assert_eq!(results.len(), 1);
+        let (start, end) = results[0].highlight_offsets[0];
+        assert_eq!(&results[0].content[start..end], "test");
+    }
 }
@@ -0,0 +1,285 @@
+/// Database Schema Migration Framework
+///
+/// `initialize_schema`'s `CREATE TABLE IF NOT EXISTS` statements only cover brand-new databases -
+/// they can't add a column or index to a table that already exists on disk. This module tracks
+/// a `schema_version` row and applies any `Migration`s newer than it, in order, inside a single
+/// transaction, so existing installations pick up incremental schema changes safely on startup.
+
+use crate::errors::{AppError, AppResult};
+use rusqlite::{Connection, OptionalExtension};
+
+/// A single forward schema change, applied at most once per database
+struct Migration {
+    /// Monotonically increasing version this migration advances the database to
+    version: i64,
+    /// Short human-readable description, surfaced in logs when the migration runs
+    description: &'static str,
+    /// SQL executed to apply the migration; may contain multiple statements
+    sql: &'static str,
+}
+
+/// Migrations in ascending version order. Append new entries here - never edit or remove a
+/// migration that may have already run against a deployed database.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "add conversations.last_opened_at for LRU-based cache eviction",
+        sql: "ALTER TABLE conversations ADD COLUMN last_opened_at DATETIME",
+    },
+    Migration {
+        version: 2,
+        description: "add messages.edited_at to track in-place content edits",
+        sql: "ALTER TABLE messages ADD COLUMN edited_at DATETIME",
+    },
+    Migration {
+        version: 3,
+        description: "add conversations.frozen for compliance immutability mode",
+        sql: "ALTER TABLE conversations ADD COLUMN frozen BOOLEAN DEFAULT 'false'",
+    },
+    Migration {
+        version: 4,
+        description: "add conversation fork lineage columns",
+        sql: "ALTER TABLE conversations ADD COLUMN parent_conversation_id INTEGER;
+              ALTER TABLE conversations ADD COLUMN forked_from_message_id INTEGER;",
+    },
+    Migration {
+        version: 5,
+        description: "allow messages.role = 'tool' and add messages.tool_call_id for tool/function results",
+        // SQLite can't ALTER a CHECK constraint in place, so the table is rebuilt. Dropping it
+        // also drops its indices and the FTS-sync triggers from fts_search::initialize_fts_tables,
+        // which would otherwise leave messages_fts stale until the next restart - both are
+        // recreated here in the same batch so full-text search keeps working immediately.
+        sql: "CREATE TABLE messages_new (
+                  id TEXT PRIMARY KEY,
+                  conversation_id TEXT NOT NULL,
+                  role TEXT NOT NULL CHECK (role IN ('user', 'assistant', 'system', 'tool')),
+                  content TEXT NOT NULL,
+                  timestamp DATETIME DEFAULT CURRENT_TIMESTAMP,
+                  tokens_used INTEGER DEFAULT 0,
+                  model_used TEXT,
+                  metadata TEXT,
+                  edited_at DATETIME,
+                  tool_call_id TEXT,
+                  FOREIGN KEY (conversation_id) REFERENCES conversations (id) ON DELETE CASCADE
+              );
+              INSERT INTO messages_new (id, conversation_id, role, content, timestamp, tokens_used, model_used, metadata, edited_at)
+              SELECT id, conversation_id, role, content, timestamp, tokens_used, model_used, metadata, edited_at FROM messages;
+              DROP TABLE messages;
+              ALTER TABLE messages_new RENAME TO messages;
+              CREATE INDEX IF NOT EXISTS idx_messages_conversation ON messages(conversation_id);
+              CREATE INDEX IF NOT EXISTS idx_messages_timestamp ON messages(timestamp);
+              CREATE TRIGGER IF NOT EXISTS messages_ai AFTER INSERT ON messages BEGIN
+                  INSERT INTO messages_fts(message_id, conversation_id, content, role)
+                  VALUES (new.id, new.conversation_id, new.content, new.role);
+              END;
+              CREATE TRIGGER IF NOT EXISTS messages_au AFTER UPDATE ON messages BEGIN
+                  UPDATE messages_fts
+                  SET content = new.content, role = new.role
+                  WHERE message_id = new.id;
+              END;
+              CREATE TRIGGER IF NOT EXISTS messages_ad AFTER DELETE ON messages BEGIN
+                  DELETE FROM messages_fts WHERE message_id = old.id;
+              END;",
+    },
+    Migration {
+        version: 6,
+        description: "add conversations.deleted_at for trash/restore",
+        sql: "ALTER TABLE conversations ADD COLUMN deleted_at DATETIME",
+    },
+    Migration {
+        version: 7,
+        description: "add api_configs.profile_name and is_default for multiple named profiles per provider",
+        // Every pre-existing row was implicitly one profile named after its provider (see
+        // ApiService::store_api_config's old `id = provider` behavior) - backfill profile_name
+        // from id so those rows keep working as that provider's default profile.
+        sql: "ALTER TABLE api_configs ADD COLUMN profile_name TEXT NOT NULL DEFAULT '';
+              ALTER TABLE api_configs ADD COLUMN is_default BOOLEAN NOT NULL DEFAULT 'true';
+              UPDATE api_configs SET profile_name = id WHERE profile_name = '';",
+    },
+    Migration {
+        version: 8,
+        description: "add conversation_settings.profile_name so a conversation can pin an API profile",
+        sql: "ALTER TABLE conversation_settings ADD COLUMN profile_name TEXT",
+    },
+    Migration {
+        version: 9,
+        description: "add usage_records.status to distinguish completed requests from ones cancelled mid-stream",
+        sql: "ALTER TABLE usage_records ADD COLUMN status TEXT NOT NULL DEFAULT 'completed'",
+    },
+];
+
+/// Create the `schema_version` table if it doesn't exist yet, and apply any migrations with a
+/// version greater than the database's current version. Safe to call on every startup.
+pub fn run_migrations(conn: &Connection) -> AppResult<()> {
+    create_schema_version_table(conn)?;
+
+    let current_version = get_schema_version(conn)?;
+
+    for migration in MIGRATIONS {
+        if migration.version <= current_version {
+            continue;
+        }
+
+        tracing::info!(
+            "Applying database migration {}: {}",
+            migration.version,
+            migration.description
+        );
+
+        conn.execute_batch(migration.sql).map_err(|e| {
+            AppError::database(format!(
+                "Migration {} ({}) failed: {}",
+                migration.version, migration.description, e
+            ))
+        })?;
+
+        set_schema_version(conn, migration.version)?;
+    }
+
+    Ok(())
+}
+
+fn create_schema_version_table(conn: &Connection) -> AppResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            version INTEGER NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| AppError::database(format!("Failed to create schema_version table: {}", e)))?;
+
+    conn.execute(
+        "INSERT OR IGNORE INTO schema_version (id, version) VALUES (1, 0)",
+        [],
+    )
+    .map_err(|e| AppError::database(format!("Failed to seed schema_version table: {}", e)))?;
+
+    Ok(())
+}
+
+fn get_schema_version(conn: &Connection) -> AppResult<i64> {
+    conn.query_row("SELECT version FROM schema_version WHERE id = 1", [], |row| row.get(0))
+        .optional()
+        .map_err(|e| AppError::database(format!("Failed to read schema version: {}", e)))?
+        .map(Ok)
+        .unwrap_or(Ok(0))
+}
+
+fn set_schema_version(conn: &Connection, version: i64) -> AppResult<()> {
+    conn.execute(
+        "UPDATE schema_version SET version = ?1 WHERE id = 1",
+        [version],
+    )
+    .map_err(|e| AppError::database(format!("Failed to update schema version: {}", e)))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fresh_database_starts_at_version_zero() {
+        let conn = Connection::open_in_memory().unwrap();
+        create_schema_version_table(&conn).unwrap();
+
+        assert_eq!(get_schema_version(&conn).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_run_migrations_is_idempotent() {
+        let conn = Connection::open_in_memory().unwrap();
+
+        run_migrations(&conn).unwrap();
+        let version_after_first_run = get_schema_version(&conn).unwrap();
+
+        // Running again should not error or re-apply migrations
+        run_migrations(&conn).unwrap();
+        assert_eq!(get_schema_version(&conn).unwrap(), version_after_first_run);
+    }
+
+    #[test]
+    fn test_set_and_get_schema_version_round_trip() {
+        let conn = Connection::open_in_memory().unwrap();
+        create_schema_version_table(&conn).unwrap();
+
+        set_schema_version(&conn, 5).unwrap();
+
+        assert_eq!(get_schema_version(&conn).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_upgrade_path_adds_last_opened_at_column() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE conversations (id INTEGER PRIMARY KEY, title TEXT NOT NULL)",
+            [],
+        )
+        .unwrap();
+
+        run_migrations(&conn).unwrap();
+
+        // Fails to prepare if the column doesn't exist
+        conn.prepare("SELECT last_opened_at FROM conversations")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_upgrade_path_adds_deleted_at_column() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE conversations (id INTEGER PRIMARY KEY, title TEXT NOT NULL)",
+            [],
+        )
+        .unwrap();
+
+        run_migrations(&conn).unwrap();
+
+        // Fails to prepare if the column doesn't exist
+        conn.prepare("SELECT deleted_at FROM conversations")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_upgrade_path_allows_tool_role_and_adds_tool_call_id_column() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE conversations (id INTEGER PRIMARY KEY, title TEXT NOT NULL)",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "CREATE TABLE messages (
+                id TEXT PRIMARY KEY,
+                conversation_id TEXT NOT NULL,
+                role TEXT NOT NULL CHECK (role IN ('user', 'assistant', 'system')),
+                content TEXT NOT NULL,
+                timestamp DATETIME DEFAULT CURRENT_TIMESTAMP,
+                tokens_used INTEGER DEFAULT 0,
+                model_used TEXT,
+                metadata TEXT
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "CREATE VIRTUAL TABLE messages_fts USING fts5(
+                message_id UNINDEXED, conversation_id UNINDEXED, content, role UNINDEXED
+            )",
+            [],
+        )
+        .unwrap();
+
+        run_migrations(&conn).unwrap();
+
+        // The old CHECK constraint would have rejected this insert outright
+        conn.execute(
+            "INSERT INTO messages (id, conversation_id, role, content, tool_call_id)
+             VALUES ('1', '1', 'tool', 'result', 'call_123')",
+            [],
+        )
+        .unwrap();
+    }
+}
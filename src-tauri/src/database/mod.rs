@@ -1,10 +1,12 @@
 pub mod query_optimizer;
 pub mod fts_search;
+pub mod migrations;
+pub mod query;
 
 use crate::errors::{AppError, AppResult};
 use r2d2::{Pool, PooledConnection};
 use r2d2_sqlite::SqliteConnectionManager;
-use rusqlite::Connection;
+use rusqlite::{Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::time::Duration;
@@ -31,10 +33,37 @@ pub struct DatabaseConfig {
     pub pragma_settings: Vec<String>,
     /// Enable automatic database backups
     pub backup_enabled: bool,
+    /// Number of scheduled backups to keep before the oldest are pruned. Ignored when
+    /// `backup_enabled` is false.
+    pub backup_retention_count: usize,
+    /// Days a trashed conversation is kept before [`crate::trash_scheduler`] purges it for good
+    pub trash_retention_days: i64,
     /// Connection pool configuration
     pub pool_config: PoolConfig,
 }
 
+/// A single backup file in [`DatabaseManager::backup_dir`], as surfaced by
+/// [`DatabaseManager::list_backups`] to the `list_backups` command
+#[derive(Debug, Clone, Serialize, Deserialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct BackupInfo {
+    pub filename: String,
+    pub size_bytes: u64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Point-in-time connection pool usage, as surfaced by [`DatabaseManager::pool_status`]
+#[derive(Debug, Clone, Serialize, Deserialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct PoolStatus {
+    /// Total connections currently held by the pool (idle + in use)
+    pub connections: u32,
+    /// Connections currently idle and available to be checked out
+    pub idle_connections: u32,
+    /// Configured ceiling on `connections`
+    pub max_size: u32,
+}
+
 /// Connection pool configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolConfig {
@@ -56,8 +85,11 @@ impl Default for DatabaseConfig {
                 "PRAGMA synchronous = NORMAL".to_string(),
                 "PRAGMA cache_size = 10000".to_string(),
                 "PRAGMA temp_store = MEMORY".to_string(),
+                "PRAGMA auto_vacuum = INCREMENTAL".to_string(),
             ],
             backup_enabled: false,
+            backup_retention_count: 7,
+            trash_retention_days: 30,
             pool_config: PoolConfig::default(),
         }
     }
@@ -106,8 +138,10 @@ impl DatabaseConfig {
                 "PRAGMA cache_size = 20000".to_string(),
                 "PRAGMA temp_store = MEMORY".to_string(),
                 "PRAGMA secure_delete = ON".to_string(),
+                "PRAGMA auto_vacuum = INCREMENTAL".to_string(),
             ],
             backup_enabled: true,
+            backup_retention_count: 7,
             pool_config: PoolConfig {
                 max_size: 20,
                 min_idle: Some(5),
@@ -126,6 +160,7 @@ impl DatabaseConfig {
                 "PRAGMA cache_size = 10000".to_string(),
             ],
             backup_enabled: false,
+            backup_retention_count: 7,
             pool_config: PoolConfig {
                 max_size: 5,
                 min_idle: Some(1),
@@ -173,7 +208,14 @@ impl DatabaseManager {
             .map_err(|e| AppError::io(format!("Failed to create app data directory: {}", e)))?;
 
         let db_path = app_data_dir.join("forbidden_library.db");
-        let config = DatabaseConfig::default();
+
+        // Generate (or reuse) a random 256-bit encryption key in the OS keychain rather than
+        // running unencrypted, which `DatabaseConfig::default()` otherwise leaves as the case
+        let encryption_key = crate::keychain::KeychainManager::new().get_or_create_db_encryption_key()?;
+        let config = DatabaseConfig {
+            encryption_key,
+            ..DatabaseConfig::default()
+        };
 
         Self::new_with_config(db_path, config)
     }
@@ -223,6 +265,26 @@ impl DatabaseManager {
         Ok(())
     }
 
+    /// Rekey the live database in place via `PRAGMA rekey`, replacing its encryption key with
+    /// `new_key`. `new_key` is expected to already be generated and persisted to the OS keychain
+    /// by the caller - see `crate::keychain::KeychainManager::rotate_db_encryption_key`. Follows
+    /// `apply_pragma_settings`'s existing convention of applying the pragma on a single
+    /// connection fetched from the pool.
+    pub fn rotate_encryption_key(&self, new_key: &str) -> AppResult<()> {
+        if !new_key.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+            return Err(AppError::validation(
+                "Encryption key contains invalid characters. Only alphanumeric, hyphens, and underscores allowed."
+            ));
+        }
+
+        let conn = self.get_connection()?;
+        let rekey_cmd = format!("PRAGMA rekey = '{}';", new_key);
+        conn.execute_batch(&rekey_cmd)
+            .map_err(|e| AppError::encryption(format!("Failed to rekey database: {}", e)))?;
+
+        Ok(())
+    }
+
     /// Get database path
     pub fn db_path(&self) -> &PathBuf {
         &self.db_path
@@ -233,18 +295,81 @@ impl DatabaseManager {
         &self.config
     }
 
+    /// Snapshot of how saturated the connection pool currently is, for
+    /// [`crate::commands::run_diagnostics`]
+    pub fn pool_status(&self) -> PoolStatus {
+        let state = self.pool.state();
+        PoolStatus {
+            connections: state.connections,
+            idle_connections: state.idle_connections,
+            max_size: self.config.pool_config.max_size,
+        }
+    }
+
+    /// Run SQLite's `PRAGMA integrity_check` and report whether it came back clean
+    ///
+    /// Returns the list of problems reported (empty if the database is healthy) rather than an
+    /// `Err`, since a failing check is a diagnostic result to surface, not an operation failure.
+    pub fn check_integrity(&self) -> AppResult<Vec<String>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn
+            .prepare("PRAGMA integrity_check")
+            .map_err(|e| AppError::database(format!("Failed to run integrity check: {}", e)))?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| AppError::database(format!("Failed to run integrity check: {}", e)))?;
+
+        let mut problems = Vec::new();
+        for row in rows {
+            let message = row.map_err(|e| AppError::database(format!("Failed to run integrity check: {}", e)))?;
+            if message != "ok" {
+                problems.push(message);
+            }
+        }
+        Ok(problems)
+    }
+
     /// Create all required database tables
     /// Implements complete Forbidden Library data model
     fn initialize_schema(&self) -> AppResult<()> {
         let conn = self.get_connection()?;
 
         // Create all tables
+        Self::create_profiles_table(&conn)?;
         Self::create_conversations_table(&conn)?;
         Self::create_messages_table(&conn)?;
         Self::create_personas_table(&conn)?;
         Self::create_grimoire_table(&conn)?;
         Self::create_api_configs_table(&conn)?;
         Self::create_projects_table(&conn)?;
+        Self::create_session_state_table(&conn)?;
+        Self::create_usage_policy_tables(&conn)?;
+        Self::create_app_sessions_table(&conn)?;
+        Self::create_snippets_table(&conn)?;
+        Self::create_read_aloud_tables(&conn)?;
+        Self::create_slash_commands_table(&conn)?;
+        Self::create_shortcuts_table(&conn)?;
+        Self::create_db_maintenance_table(&conn)?;
+        Self::create_conversation_archives_table(&conn)?;
+        Self::create_message_regenerations_table(&conn)?;
+        Self::create_compacted_message_runs_table(&conn)?;
+        Self::create_conversation_analysis_table(&conn)?;
+        Self::create_conversation_settings_table(&conn)?;
+        Self::create_embedding_tables(&conn)?;
+        Self::create_message_embeddings_table(&conn)?;
+        Self::create_prompt_templates_table(&conn)?;
+        Self::create_message_annotations_table(&conn)?;
+        Self::create_message_flags_table(&conn)?;
+        Self::create_attachments_table(&conn)?;
+        Self::create_webhooks_table(&conn)?;
+        Self::create_conversation_summaries_table(&conn)?;
+        Self::create_usage_records_table(&conn)?;
+        Self::create_conversation_projects_table(&conn)?;
+        Self::create_import_jobs_table(&conn)?;
+        Self::create_persona_memories_table(&conn)?;
+        Self::create_conversation_tags_table(&conn)?;
+        Self::create_audit_log_table(&conn)?;
+        Self::create_telemetry_settings_table(&conn)?;
 
         // Create all indices
         Self::create_performance_indices(&conn)?;
@@ -255,6 +380,28 @@ impl DatabaseManager {
         // Initialize full-text search tables
         fts_search::initialize_fts_tables(&conn)?;
 
+        // Apply any schema migrations not covered by the CREATE TABLE IF NOT EXISTS statements
+        // above (e.g. new columns or indices on tables that already exist on disk)
+        migrations::run_migrations(&conn)?;
+
+        Ok(())
+    }
+
+    /// Create local identity profiles table
+    ///
+    /// Lightweight per-user profiles for machines shared between a few people, selectable at
+    /// startup so each person's recent conversations and persona defaults don't mix in views.
+    /// Not a security boundary - no authentication is attached to a profile.
+    fn create_profiles_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS profiles (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                default_persona_id TEXT,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );",
+            [],
+        )?;
         Ok(())
     }
 
@@ -266,11 +413,16 @@ impl DatabaseManager {
                 uuid TEXT NOT NULL UNIQUE,
                 title TEXT NOT NULL,
                 persona_id TEXT,
+                profile_id INTEGER,
                 created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
                 updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
                 archived BOOLEAN DEFAULT FALSE,
                 metadata TEXT,
-                FOREIGN KEY (persona_id) REFERENCES personas (id)
+                parent_conversation_id INTEGER,
+                forked_from_message_id INTEGER,
+                FOREIGN KEY (persona_id) REFERENCES personas (id),
+                FOREIGN KEY (profile_id) REFERENCES profiles (id),
+                FOREIGN KEY (parent_conversation_id) REFERENCES conversations (id)
             );",
             [],
         )?;
@@ -283,12 +435,13 @@ impl DatabaseManager {
             "CREATE TABLE IF NOT EXISTS messages (
                 id TEXT PRIMARY KEY,
                 conversation_id TEXT NOT NULL,
-                role TEXT NOT NULL CHECK (role IN ('user', 'assistant', 'system')),
+                role TEXT NOT NULL CHECK (role IN ('user', 'assistant', 'system', 'tool')),
                 content TEXT NOT NULL,
                 timestamp DATETIME DEFAULT CURRENT_TIMESTAMP,
                 tokens_used INTEGER DEFAULT 0,
                 model_used TEXT,
                 metadata TEXT,
+                tool_call_id TEXT,
                 FOREIGN KEY (conversation_id) REFERENCES conversations (id) ON DELETE CASCADE
             );",
             [],
@@ -296,6 +449,24 @@ impl DatabaseManager {
         Ok(())
     }
 
+    /// Create table recording each regeneration of an assistant message, with a word-level
+    /// diff against the content it replaced
+    fn create_message_regenerations_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS message_regenerations (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                message_id INTEGER NOT NULL,
+                previous_content TEXT NOT NULL,
+                new_content TEXT NOT NULL,
+                diff TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (message_id) REFERENCES messages (id) ON DELETE CASCADE
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
     /// Create personas table
     fn create_personas_table(conn: &Connection) -> AppResult<()> {
         conn.execute(
@@ -328,7 +499,9 @@ impl DatabaseManager {
                 updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
                 accessed_count INTEGER DEFAULT 0,
                 last_accessed DATETIME,
-                encrypted BOOLEAN DEFAULT FALSE
+                encrypted BOOLEAN DEFAULT FALSE,
+                template TEXT,
+                fields TEXT
             );",
             [],
         )?;
@@ -343,6 +516,9 @@ impl DatabaseManager {
                 provider TEXT NOT NULL,
                 api_key TEXT NOT NULL,
                 base_url TEXT,
+                organization TEXT,
+                project TEXT,
+                extra_headers TEXT,
                 model_preferences TEXT,
                 rate_limits TEXT,
                 created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
@@ -372,6 +548,590 @@ impl DatabaseManager {
         Ok(())
     }
 
+    /// Create session state table
+    ///
+    /// Stores a single-row snapshot of the last active session (open conversations, scroll
+    /// positions, and whether auto-restore is enabled) so the app can reopen where the user
+    /// left off. Keyed by a fixed `id` rather than a generated one since there is only ever
+    /// one "last session" per installation.
+    fn create_session_state_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS session_state (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                open_conversations TEXT NOT NULL DEFAULT '[]',
+                auto_restore BOOLEAN DEFAULT FALSE,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create usage policy tables
+    ///
+    /// `usage_policy` is a single-row settings record (quiet hours, daily cap, temporary
+    /// override). `usage_daily_counts` tracks how many AI requests have been made per
+    /// calendar day so the cap can be enforced without rescanning the messages table.
+    fn create_usage_policy_tables(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS usage_policy (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                quiet_hours_start INTEGER,
+                quiet_hours_end INTEGER,
+                daily_request_cap INTEGER,
+                override_until DATETIME
+            );",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS usage_daily_counts (
+                day TEXT PRIMARY KEY,
+                request_count INTEGER NOT NULL DEFAULT 0
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create the table backing local crash/session reliability tracking, for users who disable
+    /// Sentry and still want the diagnostics bundle to show whether the app is crashing on their
+    /// machine. A row with `ended_at IS NULL` left over from a previous run means that run never
+    /// reached a clean shutdown - see [`crate::services::ReliabilityService::record_session_start`].
+    fn create_app_sessions_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS app_sessions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                app_version TEXT NOT NULL,
+                started_at DATETIME NOT NULL,
+                ended_at DATETIME,
+                clean_shutdown BOOLEAN
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create text-snippet table for the abbreviation expansion service
+    fn create_snippets_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS snippets (
+                id TEXT PRIMARY KEY,
+                trigger TEXT NOT NULL UNIQUE,
+                expansion TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create tables backing the read-aloud background queue
+    ///
+    /// A job splits a conversation or grimoire entry into sequentially ordered chunks;
+    /// each chunk tracks its own synthesis status so the frontend can show progress and
+    /// start sequential playback as soon as the first chunks are ready.
+    fn create_read_aloud_tables(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS read_aloud_jobs (
+                id TEXT PRIMARY KEY,
+                source_type TEXT NOT NULL CHECK (source_type IN ('conversation', 'grimoire')),
+                source_id TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS read_aloud_chunks (
+                id TEXT PRIMARY KEY,
+                job_id TEXT NOT NULL,
+                sequence INTEGER NOT NULL,
+                text TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'processing', 'complete', 'failed')),
+                audio_path TEXT,
+                FOREIGN KEY (job_id) REFERENCES read_aloud_jobs (id) ON DELETE CASCADE
+            );",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_read_aloud_chunks_job ON read_aloud_chunks(job_id, sequence);",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create user-defined slash command registry table
+    ///
+    /// `prompt_template` may reference `{{args}}` (all arguments joined with spaces) or
+    /// positional `{{arg1}}`, `{{arg2}}`, ... placeholders, substituted at execution time.
+    /// Create table holding reusable prompt templates with named `{{variable}}` placeholders
+    fn create_prompt_templates_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS prompt_templates (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                category TEXT,
+                template TEXT NOT NULL,
+                favorite TEXT NOT NULL DEFAULT 'false',
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create table holding reviewer comments pulled out of a reviewed
+    /// [`crate::review_export::ReviewExport`] bundle
+    fn create_message_annotations_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS message_annotations (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                message_id INTEGER NOT NULL,
+                author TEXT,
+                comment TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (message_id) REFERENCES messages (id) ON DELETE CASCADE
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create table recording named flags (e.g. `"pinned"`) attached to individual messages via
+    /// `crate::services::ConversationService::set_message_flag`. One row per (message, flag)
+    /// pair; the `UNIQUE` constraint makes setting a flag that's already set a no-op rather than
+    /// a duplicate row.
+    fn create_message_flags_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS message_flags (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                message_id TEXT NOT NULL,
+                flag TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                UNIQUE(message_id, flag),
+                FOREIGN KEY (message_id) REFERENCES messages (id) ON DELETE CASCADE
+            );",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_message_flags_message ON message_flags(message_id);",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create table recording files attached to messages via
+    /// `crate::services::AttachmentService::add_attachment`. `content_hash` dedups storage: two
+    /// attachments with identical bytes share one file under `file_path`, so this table (not the
+    /// filesystem) is the source of truth for how many rows still reference it.
+    fn create_attachments_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS attachments (
+                id TEXT PRIMARY KEY,
+                message_id INTEGER NOT NULL,
+                filename TEXT NOT NULL,
+                file_type TEXT NOT NULL,
+                size_bytes INTEGER NOT NULL,
+                file_path TEXT NOT NULL,
+                content_hash TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (message_id) REFERENCES messages (id) ON DELETE CASCADE
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create table backing [`crate::services::WebhookService`]. `scope_value` holds a
+    /// conversation id (as text) when `scope` is `conversation`, or a tag name when it's `tag` -
+    /// not a foreign key, since the two scopes reference different things.
+    fn create_webhooks_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS webhooks (
+                id TEXT PRIMARY KEY,
+                scope TEXT NOT NULL,
+                scope_value TEXT NOT NULL,
+                url TEXT NOT NULL,
+                secret TEXT NOT NULL,
+                active TEXT NOT NULL DEFAULT 'true',
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    fn create_slash_commands_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS slash_commands (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL UNIQUE,
+                prompt_template TEXT NOT NULL,
+                default_model TEXT,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create the singleton table [`DatabaseManager::run_maintenance`] records its last run in,
+    /// following the same `id INTEGER PRIMARY KEY CHECK (id = 1)` convention as `usage_policy`
+    fn create_db_maintenance_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS db_maintenance (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                last_checkpoint_at DATETIME,
+                last_vacuum_at DATETIME
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create the singleton-row table backing [`crate::services::TelemetryService`] - whether
+    /// Sentry reporting is hard-disabled at runtime and any custom redaction patterns scrubbed
+    /// from outbound events, on top of [`crate::redaction`]'s always-on built-ins
+    fn create_telemetry_settings_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS telemetry_settings (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                local_only_mode BOOLEAN NOT NULL DEFAULT 'false',
+                custom_redaction_patterns TEXT
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create table backing [`crate::shortcuts`]'s user-defined OS-level global shortcut bindings
+    ///
+    /// `action` (e.g. `"new_conversation"`) is the primary key rather than a synthetic id, since
+    /// an action can only ever be bound to one accelerator at a time - registering a new
+    /// accelerator for an already-bound action replaces its row instead of adding another.
+    fn create_shortcuts_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS shortcuts (
+                action TEXT PRIMARY KEY,
+                accelerator TEXT NOT NULL UNIQUE,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create table tracking [`crate::commands::import_conversation_export_streaming`] runs
+    ///
+    /// Each row is one streamed import of a large export file, keyed by a generated uuid rather
+    /// than `source_path` so the same file can be retried as a fresh job if an earlier run's
+    /// progress should be discarded instead of resumed.
+    fn create_import_jobs_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS import_jobs (
+                id TEXT PRIMARY KEY,
+                source_path TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'running',
+                conversations_imported INTEGER NOT NULL DEFAULT 0,
+                messages_imported INTEGER NOT NULL DEFAULT 0,
+                error TEXT,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create table backing [`crate::services::PersonaMemoryService`]'s per-persona remembered
+    /// facts, indexed by `persona_id` since every query filters to one persona's memories
+    fn create_persona_memories_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS persona_memories (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                persona_id INTEGER NOT NULL,
+                fact TEXT NOT NULL,
+                relevance_score REAL NOT NULL DEFAULT 1.0,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (persona_id) REFERENCES personas (id) ON DELETE CASCADE
+            );",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_persona_memories_persona_id ON persona_memories (persona_id);",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create the junction table backing [`crate::services::ConversationService::add_tag`] and
+    /// friends - user-assigned labels, distinct from [`Self::create_conversation_analysis_table`]'s
+    /// `tags` column, which holds AI-generated classification output for conversations that have
+    /// actually been analyzed
+    fn create_conversation_tags_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS conversation_tags (
+                conversation_id INTEGER NOT NULL,
+                tag TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (conversation_id, tag),
+                FOREIGN KEY (conversation_id) REFERENCES conversations (id) ON DELETE CASCADE
+            );",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_conversation_tags_tag ON conversation_tags (tag);",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create the append-only table backing [`crate::services::AuditLogService`] - there is no
+    /// update or delete path onto this table anywhere in the codebase, by design, so a record of
+    /// a sensitive operation can't be quietly edited away after the fact
+    fn create_audit_log_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                actor TEXT NOT NULL,
+                action TEXT NOT NULL,
+                details TEXT,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_audit_log_created_at ON audit_log (created_at);",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_audit_log_action ON audit_log (action);",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create table holding compacted (gzip-compressed) message blobs for cold storage
+    ///
+    /// When an archived conversation is compacted, its `messages` rows are deleted and
+    /// replaced with a single compressed JSON blob here, cutting row and index overhead for
+    /// conversations that are unlikely to be edited again. Reopening the conversation
+    /// transparently decompresses the blob instead of restoring rows to `messages`.
+    fn create_conversation_archives_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS conversation_archives (
+                conversation_id INTEGER PRIMARY KEY,
+                compressed_data BLOB NOT NULL,
+                message_count INTEGER NOT NULL,
+                compressed_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (conversation_id) REFERENCES conversations (id) ON DELETE CASCADE
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create the table backing [`crate::services::ConversationService::compact_history`]
+    ///
+    /// Each row archives a single run of low-value messages (greetings, acknowledged
+    /// confirmations) that was folded into one summary message, so the originals can still be
+    /// recovered even though they no longer appear in `messages`.
+    fn create_compacted_message_runs_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS compacted_message_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                conversation_id INTEGER NOT NULL,
+                summary_message_id INTEGER NOT NULL,
+                original_messages TEXT NOT NULL,
+                tokens_saved INTEGER NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (conversation_id) REFERENCES conversations (id) ON DELETE CASCADE,
+                FOREIGN KEY (summary_message_id) REFERENCES messages (id) ON DELETE CASCADE
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create the table backing [`crate::services::ConversationService::analyze_conversation`]
+    ///
+    /// One row per conversation, overwritten on each re-analysis, so filters like "show
+    /// frustrated support threads from last month" can join against it without recomputing the
+    /// classification on every query.
+    fn create_conversation_analysis_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS conversation_analysis (
+                conversation_id INTEGER PRIMARY KEY,
+                topic TEXT NOT NULL,
+                sentiment TEXT NOT NULL,
+                tags TEXT NOT NULL,
+                analyzed_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (conversation_id) REFERENCES conversations (id) ON DELETE CASCADE
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create table holding per-conversation overrides of system prompt/model/temperature/
+    /// max_tokens, layered over the conversation's persona by
+    /// `ConversationService::resolve_conversation_settings`
+    fn create_conversation_settings_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS conversation_settings (
+                conversation_id INTEGER PRIMARY KEY,
+                system_prompt TEXT,
+                model TEXT,
+                temperature REAL,
+                max_tokens INTEGER,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (conversation_id) REFERENCES conversations (id) ON DELETE CASCADE
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create the table backing [`crate::services::ConversationService::summarize_conversation`]
+    ///
+    /// One row per chunk of old messages condensed into prose, oldest chunk first, so a long
+    /// conversation accumulates a rolling set of summaries instead of one that's overwritten
+    /// (and re-summarized from scratch) every time.
+    fn create_conversation_summaries_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS conversation_summaries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                conversation_id INTEGER NOT NULL,
+                summary TEXT NOT NULL,
+                covers_through_message_id INTEGER NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (conversation_id) REFERENCES conversations (id) ON DELETE CASCADE
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create the table backing [`crate::services::UsageAnalyticsService`]
+    ///
+    /// One row per completed [`crate::commands::send_ai_provider_request`] call, so the frontend
+    /// analytics dashboard can aggregate token usage and estimated cost by provider, model, or
+    /// conversation. `conversation_id` is nullable since that command doesn't always have one, and
+    /// `estimated_cost_usd` is nullable since not every model has a known price in
+    /// [`crate::pricing`].
+    fn create_usage_records_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS usage_records (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                conversation_id INTEGER,
+                provider TEXT NOT NULL,
+                model TEXT NOT NULL,
+                prompt_tokens INTEGER NOT NULL,
+                completion_tokens INTEGER NOT NULL,
+                total_tokens INTEGER NOT NULL,
+                latency_ms INTEGER NOT NULL,
+                estimated_cost_usd REAL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                status TEXT NOT NULL DEFAULT 'completed',
+                FOREIGN KEY (conversation_id) REFERENCES conversations (id) ON DELETE SET NULL
+            );",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_usage_records_conversation_id ON usage_records (conversation_id);",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_usage_records_created_at ON usage_records (created_at);",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create the join table linking conversations to [`crate::services::ProjectService`] projects
+    ///
+    /// A conversation can belong to more than one project, so this is a plain many-to-many join
+    /// rather than a `project_id` column on `conversations`.
+    fn create_conversation_projects_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS conversation_projects (
+                conversation_id INTEGER NOT NULL,
+                project_id TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (conversation_id, project_id),
+                FOREIGN KEY (conversation_id) REFERENCES conversations (id) ON DELETE CASCADE,
+                FOREIGN KEY (project_id) REFERENCES projects (id) ON DELETE CASCADE
+            );",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_conversation_projects_project_id ON conversation_projects (project_id);",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create bulk re-embedding job tracking and the embedding vector index
+    ///
+    /// `embedding_index` rows are written with `active = FALSE` while a rebuild job is in
+    /// progress and only flipped to `active = TRUE` once the job finalizes, so the previous
+    /// model's rows keep serving lookups until the new index is completely built.
+    fn create_embedding_tables(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS embedding_jobs (
+                id TEXT PRIMARY KEY,
+                model TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'in_progress' CHECK (status IN ('in_progress', 'complete', 'failed')),
+                total_items INTEGER NOT NULL,
+                processed_items INTEGER NOT NULL DEFAULT 0,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                completed_at DATETIME
+            );",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS embedding_index (
+                id TEXT PRIMARY KEY,
+                job_id TEXT NOT NULL,
+                model TEXT NOT NULL,
+                content_type TEXT NOT NULL CHECK (content_type IN ('message', 'grimoire_entry')),
+                content_id TEXT NOT NULL,
+                vector TEXT NOT NULL,
+                active BOOLEAN NOT NULL DEFAULT FALSE,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (job_id) REFERENCES embedding_jobs (id) ON DELETE CASCADE
+            );",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_embedding_index_active ON embedding_index(content_type, content_id, active);",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Create the table backing per-message semantic search vectors
+    ///
+    /// One row per message, overwritten in place if it's re-embedded under a different model -
+    /// unlike `embedding_index`, this isn't job-tracked bulk rebuild state, so there is no
+    /// "previous model still serving lookups" concern to preserve.
+    fn create_message_embeddings_table(conn: &Connection) -> AppResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS message_embeddings (
+                message_id INTEGER PRIMARY KEY,
+                model TEXT NOT NULL,
+                vector TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (message_id) REFERENCES messages (id) ON DELETE CASCADE
+            );",
+            [],
+        )?;
+        Ok(())
+    }
+
     /// Create performance indices for all tables
     fn create_performance_indices(conn: &Connection) -> AppResult<()> {
         let indices = [
@@ -400,18 +1160,231 @@ impl DatabaseManager {
         Ok(())
     }
 
+    /// Force a WAL checkpoint, flushing the write-ahead log into the main database file
+    ///
+    /// Called from the shutdown coordinator so the on-disk file is fully up to date
+    /// before the process exits, rather than relying on SQLite's automatic checkpointing.
+    pub fn checkpoint_wal(&self) -> AppResult<()> {
+        let conn = self.get_connection()?;
+        conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")?;
+        Ok(())
+    }
+
+    /// Reclaim up to `max_pages` freed pages back to the OS without the exclusive lock a full
+    /// `VACUUM` needs, so it's cheap enough to run on every maintenance cycle. Only does
+    /// anything once `PRAGMA auto_vacuum = INCREMENTAL` (set in every [`DatabaseConfig`]'s
+    /// `pragma_settings`) has taken effect, which itself requires one full `VACUUM` after it was
+    /// first set - a harmless no-op before that.
+    pub fn incremental_vacuum(&self, max_pages: i64) -> AppResult<()> {
+        let conn = self.get_connection()?;
+        conn.execute_batch(&format!("PRAGMA incremental_vacuum({});", max_pages))?;
+        Ok(())
+    }
+
+    /// Size in bytes of the live database's `-wal` file, or 0 if it doesn't exist (e.g. right
+    /// after a checkpoint fully truncated it)
+    pub fn wal_file_size(&self) -> AppResult<i64> {
+        if self.db_path.to_str() == Some(":memory:") {
+            return Ok(0);
+        }
+
+        let wal_path = PathBuf::from(format!("{}-wal", self.db_path.display()));
+        match std::fs::metadata(&wal_path) {
+            Ok(metadata) => Ok(metadata.len() as i64),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(0),
+            Err(e) => Err(AppError::io(format!("Failed to read WAL file size: {}", e))),
+        }
+    }
+
+    /// Run one maintenance pass - checkpoint the WAL into the main database file and reclaim
+    /// freed pages via incremental vacuum - and record when it ran. Called periodically by
+    /// [`crate::maintenance_scheduler`] and on demand via the `run_maintenance` command.
+    pub fn run_maintenance(&self) -> AppResult<crate::models::DatabaseMaintenanceReport> {
+        self.checkpoint_wal()?;
+        self.incremental_vacuum(1000)?;
+
+        let conn = self.get_connection()?;
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "INSERT INTO db_maintenance (id, last_checkpoint_at, last_vacuum_at) VALUES (1, ?1, ?1)
+             ON CONFLICT(id) DO UPDATE SET
+                last_checkpoint_at = excluded.last_checkpoint_at,
+                last_vacuum_at = excluded.last_vacuum_at",
+            [&now],
+        )?;
+
+        self.last_maintenance()
+    }
+
+    /// Read when maintenance last ran, without running it. Returns a report with both
+    /// timestamps `None` if maintenance has never run in this database.
+    pub fn last_maintenance(&self) -> AppResult<crate::models::DatabaseMaintenanceReport> {
+        let conn = self.get_connection()?;
+        let (last_checkpoint_at, last_vacuum_at) = conn
+            .query_row(
+                "SELECT last_checkpoint_at, last_vacuum_at FROM db_maintenance WHERE id = 1",
+                [],
+                |row| {
+                    Ok((
+                        row.get::<_, Option<String>>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                    ))
+                },
+            )
+            .optional()?
+            .unwrap_or((None, None));
+
+        let parse = |s: Option<String>| {
+            s.and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&chrono::Utc))
+        };
+
+        Ok(crate::models::DatabaseMaintenanceReport {
+            last_checkpoint_at: parse(last_checkpoint_at),
+            last_vacuum_at: parse(last_vacuum_at),
+            wal_size_bytes: self.wal_file_size()?,
+        })
+    }
+
     /// Backup database to specified path
     pub fn backup(&self, backup_path: &PathBuf) -> AppResult<()> {
         if self.db_path.to_str() == Some(":memory:") {
             return Err(AppError::validation("Cannot backup in-memory database"));
         }
 
+        let db_size = std::fs::metadata(&self.db_path)
+            .map_err(|e| AppError::io(format!("Failed to read database file size: {}", e)))?
+            .len();
+        let backup_dir = backup_path.parent().unwrap_or(&self.db_path);
+        crate::platform::ensure_disk_space(backup_dir, db_size)?;
+
         std::fs::copy(&self.db_path, backup_path)
             .map_err(|e| AppError::io(format!("Failed to backup database: {}", e)))?;
 
         Ok(())
     }
 
+    /// Directory scheduled and manual backups are written to, alongside the live database file.
+    /// When `encryption_key` is set the copied file is just as encrypted as the live database,
+    /// since SQLCipher encrypts at the page level - no separate encryption step is needed here.
+    fn backup_dir(&self) -> PathBuf {
+        self.db_path
+            .parent()
+            .map(|p| p.join("backups"))
+            .unwrap_or_else(|| PathBuf::from("backups"))
+    }
+
+    /// Take a timestamped backup into [`Self::backup_dir`], creating the directory if needed,
+    /// and return the path it was written to
+    pub fn create_timestamped_backup(&self) -> AppResult<PathBuf> {
+        let dir = self.backup_dir();
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| AppError::io(format!("Failed to create backup directory: {}", e)))?;
+
+        let filename = format!(
+            "forbidden_library_{}.db",
+            chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ")
+        );
+        let backup_path = dir.join(filename);
+        self.backup(&backup_path)?;
+        Ok(backup_path)
+    }
+
+    /// List backups in [`Self::backup_dir`], newest first
+    pub fn list_backups(&self) -> AppResult<Vec<BackupInfo>> {
+        let dir = self.backup_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut backups = Vec::new();
+        for entry in std::fs::read_dir(&dir)
+            .map_err(|e| AppError::io(format!("Failed to read backup directory: {}", e)))?
+        {
+            let entry = entry.map_err(|e| AppError::io(format!("Failed to read backup entry: {}", e)))?;
+            let metadata = entry
+                .metadata()
+                .map_err(|e| AppError::io(format!("Failed to read backup metadata: {}", e)))?;
+            if !metadata.is_file() {
+                continue;
+            }
+
+            let Some(filename) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            let created_at = metadata
+                .modified()
+                .ok()
+                .map(chrono::DateTime::<chrono::Utc>::from)
+                .unwrap_or_else(chrono::Utc::now);
+
+            backups.push(BackupInfo {
+                filename,
+                size_bytes: metadata.len(),
+                created_at,
+            });
+        }
+
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(backups)
+    }
+
+    /// Delete backups beyond the `keep` most recent, returning the number removed
+    pub fn enforce_backup_retention(&self, keep: usize) -> AppResult<usize> {
+        let backups = self.list_backups()?;
+        let dir = self.backup_dir();
+
+        let mut removed = 0;
+        for backup in backups.into_iter().skip(keep) {
+            std::fs::remove_file(dir.join(&backup.filename))
+                .map_err(|e| AppError::io(format!("Failed to remove old backup: {}", e)))?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    /// Permanently delete every conversation (and its messages, via CASCADE) trashed more than
+    /// `retention_days` ago. Returns the number of conversations purged.
+    ///
+    /// Shared by the [`crate::services::ConversationService::purge_trash`] command handler and
+    /// [`crate::trash_scheduler`]'s periodic sweep.
+    pub fn purge_trash(&self, retention_days: i64) -> AppResult<usize> {
+        let conn = self.get_connection()?;
+        let cutoff = (chrono::Utc::now() - chrono::Duration::days(retention_days)).to_rfc3339();
+        conn.execute(
+            "DELETE FROM conversations WHERE deleted_at IS NOT NULL AND deleted_at <= ?1",
+            [cutoff],
+        )
+        .map_err(|e| AppError::database(format!("Failed to purge trash: {}", e)))
+    }
+
+    /// Restore the live database from a backup by filename (as returned by [`Self::list_backups`])
+    ///
+    /// Only accepts a bare filename, resolved against [`Self::backup_dir`], so a caller can never
+    /// point this at an arbitrary path on disk. Callers must restart the app afterwards so a
+    /// fresh connection pool picks up the restored file - the pool held by this `DatabaseManager`
+    /// keeps its existing (now stale) connections open.
+    pub fn restore_from_backup(&self, filename: &str) -> AppResult<()> {
+        if self.db_path.to_str() == Some(":memory:") {
+            return Err(AppError::validation("Cannot restore an in-memory database"));
+        }
+
+        let requested_name = std::path::Path::new(filename)
+            .file_name()
+            .ok_or_else(|| AppError::validation("Invalid backup filename"))?;
+        let backup_path = self.backup_dir().join(requested_name);
+
+        if !backup_path.is_file() {
+            return Err(AppError::not_found(format!("Backup '{}' not found", filename)));
+        }
+
+        self.checkpoint_wal()?;
+        std::fs::copy(&backup_path, &self.db_path)
+            .map_err(|e| AppError::io(format!("Failed to restore database: {}", e)))?;
+
+        Ok(())
+    }
+
     /// Execute a function within a database transaction
     ///
     /// This method automatically handles BEGIN, COMMIT, and ROLLBACK:
@@ -0,0 +1,71 @@
+//! Small typed builder for dynamic `UPDATE ... SET` statements
+//!
+//! [`crate::services::PersonaService::update_persona`] and
+//! [`crate::services::ProjectService::update_project`] both build a SQL `SET` clause by hand from
+//! a list of `Option` fields, pushing `"column = ?"` strings and boxed [`rusqlite::ToSql`] params
+//! in lockstep - easy to get out of sync as columns are added or reordered. [`UpdateBuilder`]
+//! collects that bookkeeping into one reusable type instead of a full query-builder crate
+//! (`sea-query` and friends bring their own SQL dialect and migration story, more than this
+//! tree's modest dynamic-`UPDATE` needs justify).
+//!
+//! This deliberately doesn't touch this tree's other stringly-typed SQLite convention -
+//! `"true"`/`"false"` text for booleans, used throughout nearly every table in
+//! [`crate::database`] - since migrating that is a much larger, separate change (a
+//! [`crate::database::migrations`] entry per affected table, plus every read/write call site
+//! across every service) that deserves its own commit rather than riding along with this one.
+
+use rusqlite::ToSql;
+
+/// Accumulates `column = ?` fragments and their bound values for a dynamic `UPDATE` statement,
+/// then renders the finished `SET` clause and parameter list together via [`Self::finish`] so
+/// they can never drift out of sync.
+#[derive(Default)]
+pub struct UpdateBuilder<'a> {
+    columns: Vec<&'static str>,
+    params: Vec<Box<dyn ToSql + 'a>>,
+}
+
+impl<'a> UpdateBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `column = ?` bound to `value`, if present; a no-op otherwise. Chainable.
+    pub fn set<T: ToSql + 'a>(mut self, column: &'static str, value: Option<T>) -> Self {
+        if let Some(value) = value {
+            self.columns.push(column);
+            self.params.push(Box::new(value));
+        }
+        self
+    }
+
+    /// Whether [`Self::set`] has bound any column yet - callers use this to skip issuing an
+    /// `UPDATE` with an empty `SET` clause when every field was `None`.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Finish the builder, appending `extra_assignment` (e.g. `("updated_at", now)`, applied
+    /// unconditionally) and finally `where_param` (e.g. the row id for a trailing `WHERE id = ?`).
+    /// Returns the rendered `SET` clause and the bound params in the same order as the `?`
+    /// placeholders in that clause followed by `where_param`.
+    pub fn finish<T: ToSql + 'a, W: ToSql + 'a>(
+        mut self,
+        extra_assignment: (&'static str, T),
+        where_param: W,
+    ) -> (String, Vec<Box<dyn ToSql + 'a>>) {
+        let (column, value) = extra_assignment;
+        self.columns.push(column);
+        self.params.push(Box::new(value));
+        self.params.push(Box::new(where_param));
+
+        let set_clause = self
+            .columns
+            .iter()
+            .map(|column| format!("{} = ?", column))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        (set_clause, self.params)
+    }
+}
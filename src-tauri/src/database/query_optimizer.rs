@@ -84,6 +84,12 @@ impl QueryCache {
         cache.clear();
     }
 
+    /// Approximate size of all cached values in bytes (keys and bookkeeping excluded)
+    pub fn approx_size_bytes(&self) -> usize {
+        let cache = self.cache.lock().unwrap();
+        cache.values().map(|entry| entry.data.len()).sum()
+    }
+
     /// Get cache statistics
     pub fn stats(&self) -> CacheStats {
         let cache = self.cache.lock().unwrap();
@@ -0,0 +1,123 @@
+//! Word-level text diffing
+//!
+//! Computes diffs entirely on the backend so the webview never needs to load a diff library or
+//! walk large texts itself - it just renders the returned list of spans.
+
+use serde::{Deserialize, Serialize};
+
+/// A single span of a word-level diff between two texts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "op", content = "text")]
+pub enum DiffSpan {
+    /// Words present, unchanged, in both texts
+    Equal(String),
+    /// Words only present in the new text
+    Insert(String),
+    /// Words only present in the old text
+    Delete(String),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SpanKind {
+    Equal,
+    Insert,
+    Delete,
+}
+
+fn push_word(spans: &mut Vec<(SpanKind, String)>, kind: SpanKind, word: &str) {
+    match spans.last_mut() {
+        Some((last_kind, text)) if *last_kind == kind => {
+            text.push(' ');
+            text.push_str(word);
+        }
+        _ => spans.push((kind, word.to_string())),
+    }
+}
+
+/// Compute a word-level diff between `old` and `new` text using the standard LCS algorithm,
+/// then collapse consecutive same-kind words into single spans.
+///
+/// Intended for regenerated chat messages, which are at most a few thousand words - the O(n*m)
+/// LCS table is not suitable for diffing arbitrarily large documents.
+pub fn word_diff(old: &str, new: &str) -> Vec<DiffSpan> {
+    let old_words: Vec<&str> = old.split_whitespace().collect();
+    let new_words: Vec<&str> = new.split_whitespace().collect();
+
+    let n = old_words.len();
+    let m = new_words.len();
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_words[i] == new_words[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut spans: Vec<(SpanKind, String)> = Vec::new();
+    let mut i = 0;
+    let mut j = 0;
+    while i < n && j < m {
+        if old_words[i] == new_words[j] {
+            push_word(&mut spans, SpanKind::Equal, old_words[i]);
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            push_word(&mut spans, SpanKind::Delete, old_words[i]);
+            i += 1;
+        } else {
+            push_word(&mut spans, SpanKind::Insert, new_words[j]);
+            j += 1;
+        }
+    }
+    while i < n {
+        push_word(&mut spans, SpanKind::Delete, old_words[i]);
+        i += 1;
+    }
+    while j < m {
+        push_word(&mut spans, SpanKind::Insert, new_words[j]);
+        j += 1;
+    }
+
+    spans
+        .into_iter()
+        .map(|(kind, text)| match kind {
+            SpanKind::Equal => DiffSpan::Equal(text),
+            SpanKind::Insert => DiffSpan::Insert(text),
+            SpanKind::Delete => DiffSpan::Delete(text),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_text_is_all_equal() {
+        let spans = word_diff("the quick fox", "the quick fox");
+        assert_eq!(spans, vec![DiffSpan::Equal("the quick fox".to_string())]);
+    }
+
+    #[test]
+    fn test_single_word_replacement() {
+        let spans = word_diff("the quick fox", "the slow fox");
+        assert_eq!(
+            spans,
+            vec![
+                DiffSpan::Equal("the".to_string()),
+                DiffSpan::Delete("quick".to_string()),
+                DiffSpan::Insert("slow".to_string()),
+                DiffSpan::Equal("fox".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_old_text_is_all_insert() {
+        let spans = word_diff("", "brand new text");
+        assert_eq!(spans, vec![DiffSpan::Insert("brand new text".to_string())]);
+    }
+}
@@ -0,0 +1,167 @@
+//! Embedding generation and semantic search over past messages.
+//!
+//! Computing a vector is delegated to whichever embedding-capable provider the caller already
+//! has configured - Ollama or LM Studio running locally, or OpenAI's `text-embedding-3-small` -
+//! mirroring how [`crate::ai_providers`] dispatches chat completions across providers. Vectors
+//! are stored in the `message_embeddings` table (see `database::DatabaseManager`) and compared
+//! with plain cosine similarity; there is no vector index, so a search is a full scan and is
+//! only expected to scale to a single user's local message history.
+
+use crate::errors::{AppError, AppResult};
+use std::time::Duration;
+
+/// Request an embedding vector for `text` from the given provider
+///
+/// Only embedding-capable providers are supported - chat-only providers (Anthropic, Google
+/// Gemini, Azure OpenAI) have no equivalent endpoint and are rejected with a validation error.
+pub async fn fetch_embedding(
+    provider_type: &str,
+    api_key: Option<&str>,
+    base_url: Option<&str>,
+    model: &str,
+    text: &str,
+) -> AppResult<Vec<f32>> {
+    match provider_type {
+        "ollama" => fetch_ollama_embedding(base_url.unwrap_or("http://localhost:11434"), model, text).await,
+        "lmstudio" | "lm_studio" => {
+            fetch_openai_compatible_embedding(base_url.unwrap_or("http://localhost:1234/v1"), None, model, text)
+                .await
+        }
+        "openai" => {
+            let api_key = api_key
+                .ok_or_else(|| AppError::validation("OpenAI embeddings require an api_key"))?;
+            fetch_openai_compatible_embedding("https://api.openai.com/v1", Some(api_key), model, text).await
+        }
+        other => Err(AppError::validation(format!(
+            "Provider '{}' does not support embeddings",
+            other
+        ))),
+    }
+}
+
+/// Ollama's `/api/embeddings` endpoint, which takes a `prompt` rather than `input`
+async fn fetch_ollama_embedding(base_url: &str, model: &str, text: &str) -> AppResult<Vec<f32>> {
+    let client = reqwest::Client::new();
+    let url = format!("{}/api/embeddings", base_url.trim_end_matches('/'));
+
+    let response = client
+        .post(&url)
+        .json(&serde_json::json!({ "model": model, "prompt": text }))
+        .send()
+        .await
+        .map_err(|e| AppError::api(format!("Failed to send Ollama embedding request: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::api(format!(
+            "Ollama embedding request failed with status {}: {}",
+            status, error_text
+        )));
+    }
+
+    let response_json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| AppError::api(format!("Failed to parse Ollama embedding response: {}", e)))?;
+
+    parse_f32_array(&response_json["embedding"], "Ollama")
+}
+
+/// Shared request shape for LM Studio and OpenAI, which both speak the OpenAI embeddings API
+async fn fetch_openai_compatible_embedding(
+    base_url: &str,
+    api_key: Option<&str>,
+    model: &str,
+    text: &str,
+) -> AppResult<Vec<f32>> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(60))
+        .build()
+        .map_err(|e| AppError::api(format!("Failed to create HTTP client: {}", e)))?;
+
+    let url = format!("{}/embeddings", base_url.trim_end_matches('/'));
+    let mut req_builder = client.post(&url).json(&serde_json::json!({
+        "model": model,
+        "input": text,
+    }));
+
+    if let Some(api_key) = api_key {
+        req_builder = req_builder.header("Authorization", format!("Bearer {}", api_key));
+    }
+
+    let response = req_builder
+        .send()
+        .await
+        .map_err(|e| AppError::api(format!("Failed to send embedding request: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::api(format!(
+            "Embedding request failed with status {}: {}",
+            status, error_text
+        )));
+    }
+
+    let response_json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| AppError::api(format!("Failed to parse embedding response: {}", e)))?;
+
+    parse_f32_array(&response_json["data"][0]["embedding"], "embedding")
+}
+
+fn parse_f32_array(value: &serde_json::Value, source: &str) -> AppResult<Vec<f32>> {
+    value
+        .as_array()
+        .ok_or_else(|| AppError::api(format!("Invalid {} embedding response format", source)))?
+        .iter()
+        .map(|v| {
+            v.as_f64()
+                .map(|n| n as f32)
+                .ok_or_else(|| AppError::api(format!("Invalid {} embedding response format", source)))
+        })
+        .collect()
+}
+
+/// Cosine similarity between two vectors, in `[-1.0, 1.0]`. Returns `0.0` if either is a zero
+/// vector, rather than dividing by zero.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_vectors_have_similarity_one() {
+        let v = vec![1.0, 2.0, 3.0];
+        assert!((cosine_similarity(&v, &v) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn orthogonal_vectors_have_similarity_zero() {
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-6);
+    }
+
+    #[test]
+    fn zero_vector_is_handled_without_panicking() {
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 1.0]), 0.0);
+    }
+
+    #[tokio::test]
+    async fn unsupported_provider_is_rejected() {
+        let result = fetch_embedding("anthropic", None, None, "model", "text").await;
+        assert!(result.is_err());
+    }
+}
@@ -41,6 +41,28 @@ pub enum AppError {
     /// Unexpected errors (system failures, unhandled cases)
     #[error("Unexpected error: {message}")]
     Unexpected { message: String },
+
+    /// Usage policy errors (quiet hours, daily request caps)
+    #[error("Usage restricted: {message}")]
+    UsageRestricted { message: String },
+
+    /// Not enough free disk space to safely perform a write (import, backup, attachment save,
+    /// model download), raised before the write starts instead of failing partway through
+    #[error("Insufficient disk space at {path}: need {required_bytes} bytes, {available_bytes} available")]
+    InsufficientDiskSpace {
+        path: String,
+        required_bytes: u64,
+        available_bytes: u64,
+    },
+
+    /// A provider-configured [`crate::models::RateLimits`] bucket didn't have enough budget left
+    /// for this request, raised by [`crate::ratelimit`] before the request is sent
+    #[error("Rate limited: retry after {retry_after} second(s)")]
+    RateLimited { retry_after: u64 },
+
+    /// A streaming AI request was aborted mid-flight via [`crate::commands::cancel_ai_request`]
+    #[error("Request cancelled: {message}")]
+    Cancelled { message: String },
 }
 
 /// Type alias for consistent Result usage across the application
@@ -143,6 +165,38 @@ impl AppError {
         }
     }
 
+    /// Create a usage policy error with a custom message
+    pub fn usage_restricted(message: impl Into<String>) -> Self {
+        AppError::UsageRestricted {
+            message: message.into(),
+        }
+    }
+
+    /// Create a cancelled error with a custom message
+    pub fn cancelled(message: impl Into<String>) -> Self {
+        AppError::Cancelled {
+            message: message.into(),
+        }
+    }
+
+    /// Create a rate-limited error, reporting how many seconds to wait before retrying
+    pub fn rate_limited(retry_after: u64) -> Self {
+        AppError::RateLimited { retry_after }
+    }
+
+    /// Create an insufficient-disk-space error
+    pub fn insufficient_disk_space(
+        path: impl Into<String>,
+        required_bytes: u64,
+        available_bytes: u64,
+    ) -> Self {
+        AppError::InsufficientDiskSpace {
+            path: path.into(),
+            required_bytes,
+            available_bytes,
+        }
+    }
+
     /// Check if this error should be logged at error level (vs warning)
     pub fn is_critical(&self) -> bool {
         matches!(
@@ -151,6 +205,22 @@ impl AppError {
         )
     }
 
+    /// Check if this error represents a usage-policy restriction the caller can override
+    pub fn is_usage_restricted(&self) -> bool {
+        matches!(self, AppError::UsageRestricted { .. })
+    }
+
+    /// Check if this error represents a provider rate limit that will clear on its own
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self, AppError::RateLimited { .. })
+    }
+
+    /// Check if this error represents a request the caller itself cancelled mid-flight, rather
+    /// than a genuine failure
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, AppError::Cancelled { .. })
+    }
+
     /// Get user-friendly error message for display in UI
     pub fn user_message(&self) -> String {
         match self {
@@ -175,6 +245,20 @@ impl AppError {
             AppError::Unexpected { .. } => {
                 "An unexpected error occurred. Please try again or contact support.".to_string()
             }
+            AppError::UsageRestricted { message } => message.clone(),
+            AppError::InsufficientDiskSpace {
+                required_bytes,
+                available_bytes,
+                ..
+            } => format!(
+                "Not enough free disk space: this needs {} bytes but only {} are available.",
+                required_bytes, available_bytes
+            ),
+            AppError::RateLimited { retry_after } => format!(
+                "Rate limit reached for this provider. Try again in {} second(s).",
+                retry_after
+            ),
+            AppError::Cancelled { message } => message.clone(),
         }
     }
 
@@ -189,6 +273,19 @@ impl AppError {
             AppError::Encryption { message } => format!("Encryption error: {}", message),
             AppError::Keychain { message } => format!("Keychain error: {}", message),
             AppError::Unexpected { message } => format!("Unexpected error: {}", message),
+            AppError::UsageRestricted { message } => format!("Usage restricted: {}", message),
+            AppError::InsufficientDiskSpace {
+                path,
+                required_bytes,
+                available_bytes,
+            } => format!(
+                "Insufficient disk space at {}: need {} bytes, {} available",
+                path, required_bytes, available_bytes
+            ),
+            AppError::RateLimited { retry_after } => {
+                format!("Rate limited: retry after {} second(s)", retry_after)
+            }
+            AppError::Cancelled { message } => format!("Cancelled: {}", message),
         }
     }
 
@@ -241,6 +338,14 @@ mod tests {
         assert!(matches!(app_error, AppError::Database { .. }));
     }
 
+    #[test]
+    fn test_insufficient_disk_space_reports_both_quantities() {
+        let error = AppError::insufficient_disk_space("/tmp/backups", 5_000, 1_000);
+        assert!(error.user_message().contains("5000"));
+        assert!(error.user_message().contains("1000"));
+        assert!(matches!(error, AppError::InsufficientDiskSpace { .. }));
+    }
+
     #[test]
     fn test_user_friendly_messages() {
         let validation_error = AppError::validation("Invalid email format");
@@ -0,0 +1,288 @@
+//! HTML renderer for `export_conversation`'s `html` format
+//!
+//! `json` and `markdown` need no real conversion logic and stay inline in
+//! `commands::export_conversation`; `html` is involved enough (fenced code blocks, persona
+//! metadata, embedded attachments, theming) to earn its own module. See [`crate::pdf_export`]
+//! for the `pdf` format.
+
+use crate::models::{Conversation, HtmlExportTheme, Message, MessageRole, Persona};
+use base64::Engine as _;
+
+/// An attachment plus its raw file bytes, for embedding into an HTML export
+pub struct AttachmentWithBytes {
+    pub attachment: crate::models::MessageAttachment,
+    pub bytes: Vec<u8>,
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// A run of a message's content: either prose or a fenced code block
+enum ContentChunk {
+    Prose(String),
+    Code { language: Option<String>, code: String },
+}
+
+/// Split message content on ` ``` ` fences into alternating prose and code chunks
+fn split_code_blocks(content: &str) -> Vec<ContentChunk> {
+    let mut chunks = Vec::new();
+    let mut prose = String::new();
+    let mut lines = content.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if let Some(rest) = line.strip_prefix("```") {
+            if !prose.is_empty() {
+                chunks.push(ContentChunk::Prose(std::mem::take(&mut prose)));
+            }
+            let language = if rest.trim().is_empty() { None } else { Some(rest.trim().to_string()) };
+
+            let mut code = String::new();
+            for code_line in lines.by_ref() {
+                if code_line.trim() == "```" {
+                    break;
+                }
+                code.push_str(code_line);
+                code.push('\n');
+            }
+            chunks.push(ContentChunk::Code { language, code });
+        } else {
+            prose.push_str(line);
+            prose.push('\n');
+        }
+    }
+    if !prose.is_empty() {
+        chunks.push(ContentChunk::Prose(prose));
+    }
+
+    chunks
+}
+
+fn render_chunk(chunk: &ContentChunk) -> String {
+    match chunk {
+        ContentChunk::Prose(text) => escape_html(text).replace('\n', "<br>\n"),
+        ContentChunk::Code { language, code } => {
+            // No syntax-highlighting engine is vendored in this tree; the `language-*` class is
+            // the same hook a bundled highlight.js-compatible stylesheet would key off of, so
+            // actual token coloring happens client-side if the consumer wants it.
+            let class = language
+                .as_deref()
+                .map(|lang| format!(" class=\"language-{}\"", escape_html(lang)))
+                .unwrap_or_default();
+            format!("<pre><code{}>{}</code></pre>\n", class, escape_html(code))
+        }
+    }
+}
+
+fn mime_type_for_extension(extension: &str) -> Option<&'static str> {
+    match extension.to_ascii_lowercase().as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        "svg" => Some("image/svg+xml"),
+        _ => None,
+    }
+}
+
+fn render_attachment(item: &AttachmentWithBytes) -> String {
+    if let Some(mime) = mime_type_for_extension(&item.attachment.file_type) {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&item.bytes);
+        format!(
+            "<div class=\"attachment\"><img src=\"data:{};base64,{}\" alt=\"{}\"></div>\n",
+            mime,
+            encoded,
+            escape_html(&item.attachment.filename)
+        )
+    } else {
+        format!(
+            "<div class=\"attachment\">Attachment: {} ({} bytes)</div>\n",
+            escape_html(&item.attachment.filename),
+            item.attachment.size_bytes
+        )
+    }
+}
+
+fn theme_css(theme: HtmlExportTheme) -> &'static str {
+    match theme {
+        HtmlExportTheme::Light => {
+            "body { background: #ffffff; color: #1a1a1a; } \
+             .message.assistant { background: #f3f4f6; } \
+             .message.user { background: #e8f0fe; } \
+             pre { background: #f0f0f0; color: #1a1a1a; }"
+        }
+        HtmlExportTheme::Dark => {
+            "body { background: #1a1a1a; color: #e5e5e5; } \
+             .message.assistant { background: #262626; } \
+             .message.user { background: #1f2d3d; } \
+             pre { background: #0d0d0d; color: #e5e5e5; }"
+        }
+    }
+}
+
+/// Render a conversation as a self-contained HTML document
+///
+/// `attachments_by_message` supplies the file bytes for embedding (images as `data:` URIs;
+/// anything else is listed by name) - callers fetch these via
+/// [`crate::services::AttachmentService::read_attachment_bytes`] since this module only formats
+/// data it's given, it doesn't touch the filesystem.
+pub fn conversation_to_html(
+    conversation: &Conversation,
+    messages: &[Message],
+    persona: Option<&Persona>,
+    attachments_by_message: &std::collections::HashMap<i64, Vec<AttachmentWithBytes>>,
+    theme: HtmlExportTheme,
+) -> String {
+    let mut body = String::new();
+    body.push_str(&format!("<h1>{}</h1>\n", escape_html(&conversation.title)));
+    body.push_str(&format!(
+        "<p class=\"meta\">Created: {}</p>\n",
+        conversation.created_at.format("%Y-%m-%d %H:%M:%S UTC")
+    ));
+    if let Some(persona) = persona {
+        let description = persona
+            .description
+            .as_deref()
+            .map(|d| format!(" &mdash; {}", escape_html(d)))
+            .unwrap_or_default();
+        body.push_str(&format!(
+            "<p class=\"persona\"><strong>Persona:</strong> {}{}</p>\n",
+            escape_html(&persona.name),
+            description
+        ));
+    }
+
+    for message in messages {
+        let role_label = match message.role {
+            MessageRole::User => "User",
+            MessageRole::Assistant => "Assistant",
+            MessageRole::System => "System",
+            MessageRole::Tool => "Tool",
+        };
+        body.push_str(&format!("<div class=\"message {}\">\n", role_label.to_lowercase()));
+        body.push_str(&format!("<div class=\"role\">{}</div>\n", role_label));
+
+        for chunk in split_code_blocks(&message.content) {
+            body.push_str(&render_chunk(&chunk));
+        }
+
+        if let Some(id) = message.id {
+            if let Some(attachments) = attachments_by_message.get(&id) {
+                for attachment in attachments {
+                    body.push_str(&render_attachment(attachment));
+                }
+            }
+        }
+        body.push_str("</div>\n");
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"UTF-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(&conversation.title),
+        theme_css(theme),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn sample_conversation() -> Conversation {
+        Conversation {
+            id: Some(1),
+            uuid: uuid::Uuid::new_v4(),
+            title: "<Weird & Wonderful>".to_string(),
+            persona_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            archived: false,
+            metadata: None,
+            last_opened_at: None,
+            frozen: false,
+        }
+    }
+
+    fn sample_message(id: i64, content: &str) -> Message {
+        Message {
+            id: Some(id),
+            conversation_id: 1,
+            role: MessageRole::Assistant,
+            content: content.to_string(),
+            metadata: None,
+            created_at: Utc::now(),
+            tokens_used: None,
+            model_used: None,
+            edited_at: None,
+            tool_call_id: None,
+        }
+    }
+
+    #[test]
+    fn escapes_html_special_characters_in_title_and_content() {
+        let conversation = sample_conversation();
+        let messages = vec![sample_message(1, "<script>alert(1)</script>")];
+        let html = conversation_to_html(
+            &conversation,
+            &messages,
+            None,
+            &std::collections::HashMap::new(),
+            HtmlExportTheme::Light,
+        );
+
+        assert!(!html.contains("<script>alert"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(html.contains("&lt;Weird &amp; Wonderful&gt;"));
+    }
+
+    #[test]
+    fn wraps_fenced_code_blocks_in_pre_code_with_language_class() {
+        let conversation = sample_conversation();
+        let messages = vec![sample_message(1, "before\n```rust\nfn main() {}\n```\nafter")];
+        let html = conversation_to_html(
+            &conversation,
+            &messages,
+            None,
+            &std::collections::HashMap::new(),
+            HtmlExportTheme::Light,
+        );
+
+        assert!(html.contains("<pre><code class=\"language-rust\">"));
+        assert!(html.contains("fn main() {}"));
+    }
+
+    #[test]
+    fn embeds_image_attachments_as_data_uris() {
+        let conversation = sample_conversation();
+        let messages = vec![sample_message(1, "see attached")];
+        let mut attachments = std::collections::HashMap::new();
+        attachments.insert(
+            1,
+            vec![AttachmentWithBytes {
+                attachment: crate::models::MessageAttachment {
+                    id: "a1".to_string(),
+                    filename: "diagram.png".to_string(),
+                    file_type: "png".to_string(),
+                    size_bytes: 3,
+                    file_path: "diagram.png".to_string(),
+                },
+                bytes: vec![1, 2, 3],
+            }],
+        );
+
+        let html = conversation_to_html(
+            &conversation,
+            &messages,
+            None,
+            &attachments,
+            HtmlExportTheme::Light,
+        );
+
+        assert!(html.contains("data:image/png;base64,"));
+    }
+}
@@ -0,0 +1,101 @@
+//! AES-256-GCM encryption for a single grimoire entry's content, keyed from a master key stored
+//! in the OS keychain (see
+//! [`crate::keychain::KeychainManager::get_or_create_grimoire_encryption_key`]).
+//!
+//! Distinct from the SQLCipher database encryption key
+//! (`KeychainManager::get_or_create_db_encryption_key`), which protects the whole database file
+//! at rest - this lets an individual entry stay opaque even when read out of an otherwise
+//! unencrypted (or already-compromised) database.
+
+use crate::errors::{AppError, AppResult};
+use base64::Engine as _;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// Encrypt `plaintext` with `key`, returning base64(nonce || ciphertext || tag)
+pub fn encrypt(plaintext: &str, key: &[u8; 32]) -> AppResult<String> {
+    let sealing_key = LessSafeKey::new(
+        UnboundKey::new(&AES_256_GCM, key).map_err(|_| AppError::encryption("Invalid encryption key"))?,
+    );
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce_bytes)
+        .map_err(|_| AppError::encryption("Failed to generate a random nonce"))?;
+
+    let mut sealed = plaintext.as_bytes().to_vec();
+    sealing_key
+        .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce_bytes), Aad::empty(), &mut sealed)
+        .map_err(|_| AppError::encryption("Failed to encrypt grimoire entry content"))?;
+
+    let mut out = nonce_bytes.to_vec();
+    out.append(&mut sealed);
+    Ok(base64::engine::general_purpose::STANDARD.encode(out))
+}
+
+/// Reverse [`encrypt`]
+pub fn decrypt(encoded: &str, key: &[u8; 32]) -> AppResult<String> {
+    let opening_key = LessSafeKey::new(
+        UnboundKey::new(&AES_256_GCM, key).map_err(|_| AppError::encryption("Invalid encryption key"))?,
+    );
+
+    let sealed = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| AppError::encryption(format!("Encrypted content is not valid base64: {}", e)))?;
+    if sealed.len() < NONCE_LEN {
+        return Err(AppError::encryption("Encrypted content is truncated"));
+    }
+    let (nonce_bytes, ciphertext) = sealed.split_at(NONCE_LEN);
+
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = opening_key
+        .open_in_place(
+            Nonce::try_assume_unique_for_key(nonce_bytes)
+                .map_err(|_| AppError::encryption("Invalid nonce"))?,
+            Aad::empty(),
+            &mut in_out,
+        )
+        .map_err(|_| AppError::encryption("Failed to decrypt grimoire entry content - wrong key or corrupted data"))?;
+
+    String::from_utf8(plaintext.to_vec())
+        .map_err(|e| AppError::encryption(format!("Decrypted content is not valid UTF-8: {}", e)))
+}
+
+/// Decode a hex-encoded 32-byte key, as returned by
+/// [`crate::keychain::KeychainManager::get_or_create_grimoire_encryption_key`]
+pub fn key_from_hex(hex_key: &str) -> AppResult<[u8; 32]> {
+    if hex_key.len() != 64 {
+        return Err(AppError::encryption("Encryption key must be 32 bytes (64 hex characters)"));
+    }
+
+    let mut key = [0u8; 32];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex_key[i * 2..i * 2 + 2], 16)
+            .map_err(|_| AppError::encryption("Encryption key is not valid hex"))?;
+    }
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_then_decrypt_round_trips() {
+        let key = [7u8; 32];
+        let ciphertext = encrypt("a secret grimoire entry", &key).unwrap();
+        assert_ne!(ciphertext, "a secret grimoire entry");
+        assert_eq!(decrypt(&ciphertext, &key).unwrap(), "a secret grimoire entry");
+    }
+
+    #[test]
+    fn test_decrypt_with_wrong_key_fails() {
+        let ciphertext = encrypt("a secret grimoire entry", &[1u8; 32]).unwrap();
+        assert!(decrypt(&ciphertext, &[2u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_key_from_hex_rejects_wrong_length() {
+        assert!(key_from_hex("abcd").is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Typed grimoire entry templates (e.g. "Paper Notes", "Recipe"), bundled as a JSON schema and
+//! validated against on save so entries built from a template can later be queried by field -
+//! see [`crate::model_registry`] for the same bundled-JSON-plus-`OnceLock` pattern applied to
+//! deprecated model metadata.
+
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+const GRIMOIRE_TEMPLATES_JSON: &str = include_str!("grimoire_templates.json");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GrimoireFieldType {
+    Text,
+    Number,
+    Url,
+    List,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrimoireTemplateField {
+    pub name: String,
+    pub field_type: GrimoireFieldType,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrimoireTemplate {
+    pub name: String,
+    pub fields: Vec<GrimoireTemplateField>,
+}
+
+static REGISTRY: OnceLock<Vec<GrimoireTemplate>> = OnceLock::new();
+
+fn registry() -> &'static [GrimoireTemplate] {
+    REGISTRY
+        .get_or_init(|| {
+            serde_json::from_str(GRIMOIRE_TEMPLATES_JSON)
+                .expect("bundled grimoire_templates.json must be valid")
+        })
+        .as_slice()
+}
+
+/// List every bundled template, for populating a "new entry" template picker
+pub fn list_templates() -> &'static [GrimoireTemplate] {
+    registry()
+}
+
+/// Look up a bundled template by name, case-sensitively
+pub fn get_template(name: &str) -> Option<&'static GrimoireTemplate> {
+    registry().iter().find(|t| t.name == name)
+}
+
+/// Validate a JSON object of field values against a named template's schema
+///
+/// Checks that every required field is present and that each supplied field matches its
+/// declared type. Fields not declared by the template are ignored rather than rejected, so a
+/// template can grow new optional fields without breaking entries saved before the change.
+pub fn validate_fields(template_name: &str, fields: &serde_json::Value) -> Result<(), String> {
+    let template = get_template(template_name)
+        .ok_or_else(|| format!("Unknown grimoire template: {}", template_name))?;
+
+    let object = fields
+        .as_object()
+        .ok_or_else(|| "Template fields must be a JSON object".to_string())?;
+
+    for field in &template.fields {
+        let Some(value) = object.get(&field.name) else {
+            if field.required {
+                return Err(format!("Missing required field: {}", field.name));
+            }
+            continue;
+        };
+
+        let type_ok = match field.field_type {
+            GrimoireFieldType::Text => value.is_string(),
+            GrimoireFieldType::Number => value.is_number(),
+            GrimoireFieldType::List => value.is_array(),
+            GrimoireFieldType::Url => value
+                .as_str()
+                .map(|s| crate::validation::InputValidator::default().validate_url(s).is_ok())
+                .unwrap_or(false),
+        };
+
+        if !type_ok {
+            return Err(format!(
+                "Field '{}' does not match expected type {:?}",
+                field.name, field.field_type
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_bundled_templates_load() {
+        assert!(get_template("Paper Notes").is_some());
+        assert!(get_template("Recipe").is_some());
+        assert!(get_template("Unknown Template").is_none());
+    }
+
+    #[test]
+    fn test_validate_rejects_missing_required_field() {
+        let result = validate_fields("Paper Notes", &json!({ "authors": ["A. Author"] }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_wrong_type() {
+        let result = validate_fields(
+            "Paper Notes",
+            &json!({ "authors": ["A. Author"], "year": "not a number" }),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_accepts_well_formed_entry() {
+        let result = validate_fields(
+            "Recipe",
+            &json!({ "ingredients": ["flour", "sugar"], "steps": ["mix", "bake"] }),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_template() {
+        let result = validate_fields("Nonexistent", &json!({}));
+        assert!(result.is_err());
+    }
+}
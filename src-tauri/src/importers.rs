@@ -0,0 +1,480 @@
+//! Parsers for third-party conversation export formats
+//!
+//! Each parser maps an export's native JSON shape into [`ImportedConversation`]/
+//! [`ImportedMessage`], a small intermediate representation the import command inserts via
+//! [`crate::database::DatabaseManager::with_transaction`] so a partially-parsed export never
+//! leaves the library half-imported.
+//!
+//! [`parse_export`] loads the whole file into memory before parsing it, which is fine for the
+//! exports most users have but can exhaust memory on a 500MB+ one. [`stream_export`] parses the
+//! same ChatGPT/Claude formats from a reader instead, yielding each conversation to a callback as
+//! it's parsed rather than collecting them into a `Vec` first, for
+//! [`crate::commands::import_conversation_export_streaming`].
+
+use crate::models::MessageRole;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::de::{SeqAccess, Visitor};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fmt;
+use std::io::Read;
+
+/// Which export format a file should be parsed as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    ChatGpt,
+    Claude,
+}
+
+impl ImportFormat {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "chatgpt" => Ok(Self::ChatGpt),
+            "claude" => Ok(Self::Claude),
+            other => Err(format!("Unknown import format: {}", other)),
+        }
+    }
+}
+
+/// One conversation parsed from an export, not yet persisted
+pub struct ImportedConversation {
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    pub messages: Vec<ImportedMessage>,
+}
+
+/// One message parsed from an export, not yet persisted
+pub struct ImportedMessage {
+    pub role: MessageRole,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Parse a file according to `format`
+pub fn parse_export(format: ImportFormat, contents: &str) -> Result<Vec<ImportedConversation>, String> {
+    match format {
+        ImportFormat::ChatGpt => parse_chatgpt_export(contents),
+        ImportFormat::Claude => parse_claude_export(contents),
+    }
+}
+
+fn unix_seconds_to_datetime(seconds: f64) -> DateTime<Utc> {
+    Utc.timestamp_opt(seconds as i64, 0).single().unwrap_or_else(Utc::now)
+}
+
+fn rfc3339_or_now(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+/// Which JSON object keys hold a message's role, content, and (optionally) timestamp and
+/// conversation grouping in a homegrown/generic JSONL chat log, where each line is one message
+///
+/// Lets users migrate from logging scripts and chat tools this app doesn't have a dedicated
+/// parser for, by describing their log's shape instead of requiring a custom importer per tool.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonlFieldMapping {
+    pub role_key: String,
+    pub content_key: String,
+    /// Key holding a message's timestamp, as either an RFC3339 string or Unix seconds; lines
+    /// missing it (or with no `timestamp_key` at all) fall back to the import time
+    pub timestamp_key: Option<String>,
+    /// Key grouping lines into separate conversations, by distinct value, in file order; omit
+    /// for logs that are already a single conversation
+    pub conversation_key: Option<String>,
+    pub user_role_value: String,
+    pub assistant_role_value: String,
+    pub system_role_value: Option<String>,
+}
+
+fn parse_jsonl_timestamp(value: &Value) -> DateTime<Utc> {
+    if let Some(seconds) = value.as_f64() {
+        return unix_seconds_to_datetime(seconds);
+    }
+    value.as_str().map(rfc3339_or_now).unwrap_or_else(Utc::now)
+}
+
+/// Parse a generic JSONL chat log (one JSON object per line) according to `mapping`
+///
+/// A line whose JSON fails to parse is a hard error naming the line number, matching how the
+/// ChatGPT/Claude parsers fail fast on malformed input; a line that parses but is missing the
+/// mapped role/content keys, or whose role doesn't match any of the mapped role values, is
+/// silently skipped, the same way the ChatGPT/Claude parsers skip nodes they can't make sense
+/// of. Lines are grouped into conversations by `mapping.conversation_key`, in file order.
+pub fn parse_generic_jsonl(contents: &str, mapping: &JsonlFieldMapping) -> Result<Vec<ImportedConversation>, String> {
+    let mut order: Vec<String> = Vec::new();
+    let mut grouped: std::collections::HashMap<String, Vec<ImportedMessage>> = std::collections::HashMap::new();
+
+    for (line_number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let entry: Value = serde_json::from_str(line)
+            .map_err(|e| format!("Invalid JSON on line {}: {}", line_number + 1, e))?;
+
+        let Some(role_value) = entry.get(&mapping.role_key).and_then(Value::as_str) else { continue };
+        let role = if role_value == mapping.user_role_value {
+            MessageRole::User
+        } else if role_value == mapping.assistant_role_value {
+            MessageRole::Assistant
+        } else if mapping.system_role_value.as_deref() == Some(role_value) {
+            MessageRole::System
+        } else {
+            continue;
+        };
+
+        let Some(content) = entry.get(&mapping.content_key).and_then(Value::as_str) else { continue };
+        if content.trim().is_empty() {
+            continue;
+        }
+
+        let created_at = mapping
+            .timestamp_key
+            .as_deref()
+            .and_then(|key| entry.get(key))
+            .map(parse_jsonl_timestamp)
+            .unwrap_or_else(Utc::now);
+
+        let conversation_key = mapping
+            .conversation_key
+            .as_deref()
+            .and_then(|key| entry.get(key))
+            .and_then(Value::as_str)
+            .unwrap_or("default")
+            .to_string();
+
+        if !grouped.contains_key(&conversation_key) {
+            order.push(conversation_key.clone());
+        }
+        grouped
+            .entry(conversation_key)
+            .or_default()
+            .push(ImportedMessage { role, content: content.to_string(), created_at });
+    }
+
+    let conversations = order
+        .into_iter()
+        .filter_map(|key| {
+            let messages = grouped.remove(&key)?;
+            if messages.is_empty() {
+                return None;
+            }
+            let created_at = messages[0].created_at;
+            let title = if key == "default" {
+                "Imported JSONL Conversation".to_string()
+            } else {
+                format!("Imported: {}", key)
+            };
+            Some(ImportedConversation { title, created_at, messages })
+        })
+        .collect();
+
+    Ok(conversations)
+}
+
+/// Parse OpenAI's `conversations.json` export
+///
+/// Each conversation is a tree of nodes keyed by id (`mapping`); the linear transcript is the
+/// path from the root to `current_node`, found by walking `parent` links backward and reversing.
+fn parse_chatgpt_export(contents: &str) -> Result<Vec<ImportedConversation>, String> {
+    let root: Vec<Value> = serde_json::from_str(contents)
+        .map_err(|e| format!("Invalid ChatGPT export JSON: {}", e))?;
+
+    Ok(root.iter().filter_map(chatgpt_entry_to_conversation).collect())
+}
+
+/// Convert one element of a ChatGPT `conversations.json` array into an [`ImportedConversation`],
+/// or `None` if it has no `mapping` or no messages survive extraction. Shared by
+/// [`parse_chatgpt_export`] (whole file at once) and [`stream_export`] (element by element).
+fn chatgpt_entry_to_conversation(entry: &Value) -> Option<ImportedConversation> {
+    let title = entry
+        .get("title")
+        .and_then(Value::as_str)
+        .unwrap_or("Imported ChatGPT Conversation")
+        .to_string();
+    let created_at = entry
+        .get("create_time")
+        .and_then(Value::as_f64)
+        .map(unix_seconds_to_datetime)
+        .unwrap_or_else(Utc::now);
+
+    let mapping = entry.get("mapping").and_then(Value::as_object)?;
+
+    let mut node_id = entry.get("current_node").and_then(Value::as_str).map(str::to_string);
+    let mut path_ids = Vec::new();
+    while let Some(id) = node_id {
+        path_ids.push(id.clone());
+        node_id = mapping
+            .get(&id)
+            .and_then(|n| n.get("parent"))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+    }
+    path_ids.reverse();
+
+    let mut messages = Vec::new();
+    for id in path_ids {
+        let Some(node) = mapping.get(&id) else { continue };
+        let Some(message) = node.get("message") else { continue };
+
+        let role = match message
+            .get("author")
+            .and_then(|a| a.get("role"))
+            .and_then(Value::as_str)
+        {
+            Some("user") => MessageRole::User,
+            Some("assistant") => MessageRole::Assistant,
+            Some("system") => MessageRole::System,
+            _ => continue,
+        };
+
+        let content = message
+            .get("content")
+            .and_then(|c| c.get("parts"))
+            .and_then(Value::as_array)
+            .map(|parts| {
+                parts
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .unwrap_or_default();
+
+        if content.trim().is_empty() {
+            continue;
+        }
+
+        let message_created_at = message
+            .get("create_time")
+            .and_then(Value::as_f64)
+            .map(unix_seconds_to_datetime)
+            .unwrap_or(created_at);
+
+        messages.push(ImportedMessage { role, content, created_at: message_created_at });
+    }
+
+    if messages.is_empty() {
+        None
+    } else {
+        Some(ImportedConversation { title, created_at, messages })
+    }
+}
+
+/// Parse Anthropic's Claude data export `conversations.json`
+fn parse_claude_export(contents: &str) -> Result<Vec<ImportedConversation>, String> {
+    let root: Vec<Value> = serde_json::from_str(contents)
+        .map_err(|e| format!("Invalid Claude export JSON: {}", e))?;
+
+    Ok(root.iter().filter_map(claude_entry_to_conversation).collect())
+}
+
+/// Convert one element of a Claude `conversations.json` array into an [`ImportedConversation`],
+/// or `None` if it has no `chat_messages` or no messages survive extraction. Shared by
+/// [`parse_claude_export`] (whole file at once) and [`stream_export`] (element by element).
+fn claude_entry_to_conversation(entry: &Value) -> Option<ImportedConversation> {
+    let title = entry
+        .get("name")
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+        .unwrap_or("Imported Claude Conversation")
+        .to_string();
+    let created_at = entry
+        .get("created_at")
+        .and_then(Value::as_str)
+        .map(rfc3339_or_now)
+        .unwrap_or_else(Utc::now);
+
+    let chat_messages = entry.get("chat_messages").and_then(Value::as_array)?;
+
+    let mut messages = Vec::new();
+    for message in chat_messages {
+        let role = match message.get("sender").and_then(Value::as_str) {
+            Some("human") => MessageRole::User,
+            Some("assistant") => MessageRole::Assistant,
+            _ => continue,
+        };
+
+        let content = message.get("text").and_then(Value::as_str).unwrap_or("").to_string();
+        if content.trim().is_empty() {
+            continue;
+        }
+
+        let message_created_at = message
+            .get("created_at")
+            .and_then(Value::as_str)
+            .map(rfc3339_or_now)
+            .unwrap_or(created_at);
+
+        messages.push(ImportedMessage { role, content, created_at: message_created_at });
+    }
+
+    if messages.is_empty() {
+        None
+    } else {
+        Some(ImportedConversation { title, created_at, messages })
+    }
+}
+
+/// Parse `reader` as `format`'s export file without loading the whole file or its top-level
+/// array into memory at once, calling `on_conversation` as each conversation is parsed. An
+/// alternative to [`parse_export`] for export files too large to read in whole.
+pub fn stream_export(
+    format: ImportFormat,
+    reader: impl Read,
+    mut on_conversation: impl FnMut(ImportedConversation) -> Result<(), String>,
+) -> Result<(), String> {
+    let to_conversation: fn(&Value) -> Option<ImportedConversation> = match format {
+        ImportFormat::ChatGpt => chatgpt_entry_to_conversation,
+        ImportFormat::Claude => claude_entry_to_conversation,
+    };
+
+    stream_json_array(reader, |entry| match to_conversation(&entry) {
+        Some(conversation) => on_conversation(conversation),
+        None => Ok(()),
+    })
+}
+
+/// Parse `reader` as a single top-level JSON array, calling `on_element` with each element as
+/// it's parsed instead of collecting them into a `Vec` first
+fn stream_json_array(
+    reader: impl Read,
+    mut on_element: impl FnMut(Value) -> Result<(), String>,
+) -> Result<(), String> {
+    struct ArrayVisitor<'a>(&'a mut dyn FnMut(Value) -> Result<(), String>);
+
+    impl<'de> Visitor<'de> for ArrayVisitor<'_> {
+        type Value = ();
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a JSON array")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            while let Some(element) = seq.next_element::<Value>()? {
+                (self.0)(element).map_err(serde::de::Error::custom)?;
+            }
+            Ok(())
+        }
+    }
+
+    use serde::Deserializer as _;
+
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    deserializer
+        .deserialize_seq(ArrayVisitor(&mut on_element))
+        .map_err(|e| format!("Invalid export JSON: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chatgpt_export_follows_current_node_path() {
+        let export = serde_json::json!([{
+            "title": "Test Chat",
+            "create_time": 1700000000.0,
+            "current_node": "b",
+            "mapping": {
+                "a": {
+                    "id": "a",
+                    "parent": null,
+                    "message": {
+                        "author": {"role": "user"},
+                        "content": {"content_type": "text", "parts": ["Hello"]},
+                        "create_time": 1700000000.0
+                    }
+                },
+                "b": {
+                    "id": "b",
+                    "parent": "a",
+                    "message": {
+                        "author": {"role": "assistant"},
+                        "content": {"content_type": "text", "parts": ["Hi there"]},
+                        "create_time": 1700000001.0
+                    }
+                }
+            }
+        }]);
+
+        let conversations = parse_chatgpt_export(&export.to_string()).unwrap();
+        assert_eq!(conversations.len(), 1);
+        assert_eq!(conversations[0].messages.len(), 2);
+        assert_eq!(conversations[0].messages[0].role, MessageRole::User);
+        assert_eq!(conversations[0].messages[1].content, "Hi there");
+    }
+
+    #[test]
+    fn test_parse_claude_export_maps_sender_roles() {
+        let export = serde_json::json!([{
+            "name": "Test Chat",
+            "created_at": "2024-01-01T00:00:00Z",
+            "chat_messages": [
+                {"sender": "human", "text": "Hello", "created_at": "2024-01-01T00:00:00Z"},
+                {"sender": "assistant", "text": "Hi there", "created_at": "2024-01-01T00:00:01Z"}
+            ]
+        }]);
+
+        let conversations = parse_claude_export(&export.to_string()).unwrap();
+        assert_eq!(conversations.len(), 1);
+        assert_eq!(conversations[0].title, "Test Chat");
+        assert_eq!(conversations[0].messages[0].role, MessageRole::User);
+        assert_eq!(conversations[0].messages[1].role, MessageRole::Assistant);
+    }
+
+    #[test]
+    fn test_parse_skips_conversations_with_no_messages() {
+        let export = serde_json::json!([{"name": "Empty", "chat_messages": []}]);
+        let conversations = parse_claude_export(&export.to_string()).unwrap();
+        assert!(conversations.is_empty());
+    }
+
+    fn jsonl_mapping() -> JsonlFieldMapping {
+        JsonlFieldMapping {
+            role_key: "speaker".to_string(),
+            content_key: "text".to_string(),
+            timestamp_key: Some("ts".to_string()),
+            conversation_key: Some("session".to_string()),
+            user_role_value: "me".to_string(),
+            assistant_role_value: "bot".to_string(),
+            system_role_value: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_generic_jsonl_groups_by_conversation_key() {
+        let log = "{\"session\": \"a\", \"speaker\": \"me\", \"text\": \"Hi\", \"ts\": \"2024-01-01T00:00:00Z\"}\n\
+                    {\"session\": \"a\", \"speaker\": \"bot\", \"text\": \"Hello\", \"ts\": \"2024-01-01T00:00:01Z\"}\n\
+                    {\"session\": \"b\", \"speaker\": \"me\", \"text\": \"Different thread\", \"ts\": \"2024-01-02T00:00:00Z\"}";
+
+        let conversations = parse_generic_jsonl(log, &jsonl_mapping()).unwrap();
+        assert_eq!(conversations.len(), 2);
+        assert_eq!(conversations[0].messages.len(), 2);
+        assert_eq!(conversations[0].messages[0].role, MessageRole::User);
+        assert_eq!(conversations[1].messages.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_generic_jsonl_skips_lines_with_unmapped_role() {
+        let log = "{\"session\": \"a\", \"speaker\": \"narrator\", \"text\": \"Aside\"}\n\
+                    {\"session\": \"a\", \"speaker\": \"me\", \"text\": \"Hi\"}";
+
+        let conversations = parse_generic_jsonl(log, &jsonl_mapping()).unwrap();
+        assert_eq!(conversations.len(), 1);
+        assert_eq!(conversations[0].messages.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_generic_jsonl_reports_malformed_line_number() {
+        let log = "{\"session\": \"a\", \"speaker\": \"me\", \"text\": \"Hi\"}\nnot json";
+        let err = parse_generic_jsonl(log, &jsonl_mapping()).unwrap_err();
+        assert!(err.contains("line 2"));
+    }
+}
@@ -10,11 +10,31 @@
 
 use keyring::Entry;
 use serde::{Deserialize, Serialize};
-use crate::error::AppError;
+use crate::errors::AppError;
 
 /// Service identifier for keychain entries
 const SERVICE_NAME: &str = "com.voidcat.forbidden-library";
 
+/// Sentinel written to the `api_configs.api_key` column once a provider's real key has been
+/// moved into the OS keychain, so [`crate::services::ApiService`] can tell a migrated row apart
+/// from a legacy plaintext one without a schema version column
+pub const KEYCHAIN_MANAGED_MARKER: &str = "keychain:managed";
+
+/// Keychain account name under which the generated SQLCipher database encryption key is stored -
+/// distinct from the `provider_name`-keyed entries used for AI provider API keys
+const DB_ENCRYPTION_KEY_ACCOUNT: &str = "db-encryption-key";
+
+/// Keychain account name under which the generated grimoire entry encryption key is stored -
+/// distinct from [`DB_ENCRYPTION_KEY_ACCOUNT`] so per-entry encryption
+/// (`crate::grimoire_crypto`) stays independent of the whole-database SQLCipher key
+const GRIMOIRE_ENCRYPTION_KEY_ACCOUNT: &str = "grimoire-encryption-key";
+
+/// Keychain account name under which the key used to HMAC-sign
+/// [`crate::conversation_share::ConversationShareSnapshot`] files is stored - distinct from the
+/// encryption keys above since this one signs rather than encrypts, and never needs to leave the
+/// machine that created it
+const SHARE_SIGNING_KEY_ACCOUNT: &str = "share-signing-key";
+
 /// Keychain manager for secure API key storage
 #[derive(Debug, Clone)]
 pub struct KeychainManager {
@@ -157,6 +177,76 @@ impl KeychainManager {
         // so we can just call store_api_key
         self.store_api_key(provider_name, new_api_key)
     }
+
+    /// Return the SQLCipher database encryption key from the OS keychain, generating and
+    /// storing a new random 256-bit key (hex-encoded, so it satisfies
+    /// `DatabaseConfig`'s alphanumeric-only validation) the first time this is called
+    pub fn get_or_create_db_encryption_key(&self) -> Result<String, AppError> {
+        match self.get_api_key(DB_ENCRYPTION_KEY_ACCOUNT) {
+            Ok(key) => Ok(key),
+            Err(AppError::NotFound { .. }) => {
+                let key = Self::generate_encryption_key()?;
+                self.store_api_key(DB_ENCRYPTION_KEY_ACCOUNT, &key)?;
+                tracing::info!("Generated new database encryption key in the OS keychain");
+                Ok(key)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Replace the stored database encryption key with a freshly generated one and return it.
+    /// Callers must rekey the live database (`PRAGMA rekey`) with the returned value before the
+    /// old key is unrecoverable - see `crate::database::DatabaseManager::rotate_encryption_key`.
+    pub fn rotate_db_encryption_key(&self) -> Result<String, AppError> {
+        let key = Self::generate_encryption_key()?;
+        self.store_api_key(DB_ENCRYPTION_KEY_ACCOUNT, &key)?;
+        tracing::info!("Rotated database encryption key in the OS keychain");
+        Ok(key)
+    }
+
+    /// Return the grimoire entry encryption key from the OS keychain, generating and storing a
+    /// new random 256-bit key (hex-encoded, see [`crate::grimoire_crypto::key_from_hex`]) the
+    /// first time this is called
+    pub fn get_or_create_grimoire_encryption_key(&self) -> Result<String, AppError> {
+        match self.get_api_key(GRIMOIRE_ENCRYPTION_KEY_ACCOUNT) {
+            Ok(key) => Ok(key),
+            Err(AppError::NotFound { .. }) => {
+                let key = Self::generate_encryption_key()?;
+                self.store_api_key(GRIMOIRE_ENCRYPTION_KEY_ACCOUNT, &key)?;
+                tracing::info!("Generated new grimoire entry encryption key in the OS keychain");
+                Ok(key)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Return the local key used to HMAC-sign shared conversation snapshots, generating and
+    /// storing a new random 256-bit key the first time this is called. Signatures only need to
+    /// verify on the machine that created them, so this key never needs to be exported or synced.
+    pub fn get_or_create_share_signing_key(&self) -> Result<String, AppError> {
+        match self.get_api_key(SHARE_SIGNING_KEY_ACCOUNT) {
+            Ok(key) => Ok(key),
+            Err(AppError::NotFound { .. }) => {
+                let key = Self::generate_encryption_key()?;
+                self.store_api_key(SHARE_SIGNING_KEY_ACCOUNT, &key)?;
+                tracing::info!("Generated new conversation share signing key in the OS keychain");
+                Ok(key)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Generate a random 256-bit key, hex-encoded
+    fn generate_encryption_key() -> Result<String, AppError> {
+        use ring::rand::SecureRandom;
+
+        let rng = ring::rand::SystemRandom::new();
+        let mut bytes = [0u8; 32];
+        rng.fill(&mut bytes)
+            .map_err(|_| AppError::encryption("Failed to generate a random encryption key"))?;
+
+        Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+    }
 }
 
 /// API key metadata stored in database (without the actual key)
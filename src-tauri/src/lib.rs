@@ -2,23 +2,69 @@
 // Re-export the main modules for testing
 
 pub mod ai_providers;
+pub mod backup_scheduler;
+pub mod bug_report;
+pub mod cancellation;
+pub mod command_palette;
 pub mod commands;
+pub mod compliance_export;
+pub mod conversation_share;
 pub mod database;
+pub mod diff;
+pub mod embeddings;
 pub mod errors;
+pub mod export_formats;
+pub mod grimoire_crypto;
+pub mod grimoire_templates;
+pub mod importers;
 pub mod keychain;
+pub mod library_archive;
+pub mod maintenance_scheduler;
+pub mod mcp;
+pub mod model_registry;
 pub mod models;
 pub mod monitoring;
+pub mod pdf_export;
 pub mod platform;
+pub mod pricing;
+pub mod provider_monitor;
+pub mod provider_registry;
+pub mod ratelimit;
+pub mod redaction;
+pub mod review_export;
 pub mod services;
+pub mod shortcuts;
+pub mod snapshot_server;
+pub mod style_enforcement;
+pub mod summarization;
+pub mod tagging;
+pub mod theme_monitor;
+pub mod tokenizer;
+pub mod trash_scheduler;
 pub mod validation;
+pub mod webhooks;
+pub mod windows;
 
 pub use ai_providers::*;
+pub use backup_scheduler::*;
+pub use command_palette::*;
 pub use commands::*;
+pub use compliance_export::*;
 pub use database::*;
+pub use diff::*;
+pub use embeddings::*;
 pub use errors::*;
+pub use grimoire_templates::*;
+pub use importers::*;
 pub use keychain::*;
+pub use library_archive::*;
+pub use model_registry::*;
 pub use models::*;
 pub use monitoring::*;
 pub use platform::*;
+pub use provider_registry::*;
 pub use services::*;
+pub use style_enforcement::*;
+pub use tagging::*;
+pub use tokenizer::*;
 pub use validation::*;
@@ -0,0 +1,610 @@
+//! Export and restore the entire library - conversations, messages, personas, and grimoire
+//! entries - as a single portable archive, for moving a library to another machine.
+//!
+//! There's no `zip`/`tar` crate in this workspace, so unlike `export_conversation`'s per-item
+//! exports this bundles everything into one gzip-compressed JSON document, using the same
+//! `flate2` pattern [`crate::services::ConversationService::compact_archived_conversation`] uses
+//! for cold storage, rather than producing a true archive with per-file entries. Message
+//! attachments (see [`crate::models::MessageAttachment`]) are stored as file-path references
+//! inside message metadata, so they travel with the archive as paths only - there's no
+//! attachment-storage abstraction yet to read the referenced files' bytes from.
+
+use crate::database::DatabaseManager;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Conversation, GrimoireEntry, Message, Persona};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Transaction};
+use std::io::{Read, Write};
+use uuid::Uuid;
+
+/// Bumped whenever the archive's JSON shape changes incompatibly, so [`import_library`] can
+/// refuse an archive it doesn't know how to read instead of silently importing it wrong
+const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct LibraryArchive {
+    pub format_version: u32,
+    pub app_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub conversations: Vec<Conversation>,
+    pub messages: Vec<Message>,
+    pub personas: Vec<Persona>,
+    pub grimoire_entries: Vec<GrimoireEntry>,
+}
+
+/// Row counts written back during [`import_library`], returned to the caller as a receipt
+#[derive(Debug, serde::Serialize, serde::Deserialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct LibraryImportSummary {
+    pub conversations: usize,
+    pub messages: usize,
+    pub personas: usize,
+    pub grimoire_entries: usize,
+}
+
+/// Snapshot the whole library into a gzip-compressed JSON archive
+///
+/// Reads every table inside one transaction, so the export is a consistent point-in-time
+/// snapshot even if other writes land on the database while it runs.
+pub fn export_library(db: &DatabaseManager) -> AppResult<Vec<u8>> {
+    let archive = db.with_transaction(|tx| {
+        Ok(LibraryArchive {
+            format_version: ARCHIVE_FORMAT_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            exported_at: Utc::now(),
+            conversations: read_conversations(tx)?,
+            messages: read_messages(tx)?,
+            personas: read_personas(tx)?,
+            grimoire_entries: read_grimoire_entries(tx)?,
+        })
+    })?;
+
+    let json = serde_json::to_vec(&archive)
+        .map_err(|e| AppError::validation(format!("Failed to serialize library archive: {}", e)))?;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder
+        .write_all(&json)
+        .map_err(|e| AppError::io(format!("Failed to compress library archive: {}", e)))?;
+    encoder
+        .finish()
+        .map_err(|e| AppError::io(format!("Failed to finish library archive compression: {}", e)))
+}
+
+/// Restore a library archive produced by [`export_library`]
+///
+/// Conversations and messages are re-inserted with ids freshly assigned by this machine's
+/// auto-increment sequence rather than the archived ids, and relinked to each other by matching
+/// the archived `conversation_id`; personas and grimoire entries keep their archived identity
+/// (grimoire entries already use globally-unique UUIDs, and nothing in the archive references a
+/// persona by id). Runs in one transaction, so a malformed archive never leaves the library
+/// half-restored.
+pub fn import_library(db: &DatabaseManager, archive_bytes: &[u8]) -> AppResult<LibraryImportSummary> {
+    let mut decoder = flate2::read::GzDecoder::new(archive_bytes);
+    let mut json = String::new();
+    decoder
+        .read_to_string(&mut json)
+        .map_err(|e| AppError::validation(format!("Not a valid library archive: {}", e)))?;
+
+    let db_dir = db
+        .db_path()
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| std::path::PathBuf::from("."));
+    crate::platform::ensure_disk_space(&db_dir, json.len() as u64)?;
+
+    let archive: LibraryArchive = serde_json::from_str(&json)
+        .map_err(|e| AppError::validation(format!("Failed to parse library archive: {}", e)))?;
+
+    if archive.format_version != ARCHIVE_FORMAT_VERSION {
+        return Err(AppError::validation(format!(
+            "Unsupported library archive format version: {} (expected {})",
+            archive.format_version, ARCHIVE_FORMAT_VERSION
+        )));
+    }
+
+    db.with_transaction(|tx| {
+        for persona in &archive.personas {
+            insert_persona(tx, persona)?;
+        }
+
+        for entry in &archive.grimoire_entries {
+            insert_grimoire_entry(tx, entry)?;
+        }
+
+        let mut message_count = 0;
+        for conversation in &archive.conversations {
+            let new_conversation_id = insert_conversation(tx, conversation)?;
+            for message in archive
+                .messages
+                .iter()
+                .filter(|m| Some(m.conversation_id) == conversation.id)
+            {
+                insert_message(tx, new_conversation_id, message)?;
+                message_count += 1;
+            }
+        }
+
+        Ok(LibraryImportSummary {
+            conversations: archive.conversations.len(),
+            messages: message_count,
+            personas: archive.personas.len(),
+            grimoire_entries: archive.grimoire_entries.len(),
+        })
+    })
+}
+
+/// Bumped whenever [`PersonaArchive`]'s JSON shape changes incompatibly
+const PERSONA_ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+/// One line of a [`PersonaArchive`]'s table of contents
+///
+/// There's no `zip`/`tar` crate in this workspace (see the module doc comment), so this stands
+/// in for a literal index file inside the archive: enough to see what's in it without decoding
+/// the full message list.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PersonaArchiveIndexEntry {
+    pub conversation_id: i64,
+    pub title: String,
+    pub message_count: usize,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A single persona and every conversation conducted with it, for retiring the persona or
+/// handing its interaction history off to someone else
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PersonaArchive {
+    pub format_version: u32,
+    pub app_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub persona: Persona,
+    pub index: Vec<PersonaArchiveIndexEntry>,
+    pub conversations: Vec<Conversation>,
+    pub messages: Vec<Message>,
+}
+
+/// Bundle one persona and all of its conversations into a gzip-compressed JSON archive
+///
+/// Reads within one transaction for the same point-in-time consistency reason as
+/// [`export_library`], scoped down to a single persona's rows rather than the whole library.
+pub fn export_persona_history(db: &DatabaseManager, persona_id: i64) -> AppResult<Vec<u8>> {
+    let archive = db.with_transaction(|tx| {
+        let persona = read_personas(tx)?
+            .into_iter()
+            .find(|p| p.id == Some(persona_id))
+            .ok_or_else(|| AppError::validation(format!("Persona {} not found", persona_id)))?;
+
+        let conversations: Vec<Conversation> = read_conversations(tx)?
+            .into_iter()
+            .filter(|c| c.persona_id == Some(persona_id))
+            .collect();
+
+        let all_messages = read_messages(tx)?;
+        let mut messages = Vec::new();
+        let mut index = Vec::new();
+
+        for conversation in &conversations {
+            let conversation_id = conversation.id.unwrap_or_default();
+            let conversation_messages: Vec<Message> = all_messages
+                .iter()
+                .filter(|m| m.conversation_id == conversation_id)
+                .cloned()
+                .collect();
+
+            index.push(PersonaArchiveIndexEntry {
+                conversation_id,
+                title: conversation.title.clone(),
+                message_count: conversation_messages.len(),
+                created_at: conversation.created_at,
+            });
+            messages.extend(conversation_messages);
+        }
+
+        Ok(PersonaArchive {
+            format_version: PERSONA_ARCHIVE_FORMAT_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            exported_at: Utc::now(),
+            persona,
+            index,
+            conversations,
+            messages,
+        })
+    })?;
+
+    let json = serde_json::to_vec(&archive)
+        .map_err(|e| AppError::validation(format!("Failed to serialize persona archive: {}", e)))?;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder
+        .write_all(&json)
+        .map_err(|e| AppError::io(format!("Failed to compress persona archive: {}", e)))?;
+    encoder
+        .finish()
+        .map_err(|e| AppError::io(format!("Failed to finish persona archive compression: {}", e)))
+}
+
+/// Bumped whenever [`PersonaBundle`]'s JSON shape changes incompatibly
+const PERSONA_BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// A single persona's configuration - system prompt, settings, avatar, and remembered facts -
+/// with no conversation history attached (unlike [`PersonaArchive`]). Serialized as plain JSON
+/// rather than the gzip-compressed archives the rest of this module produces, so it's small
+/// enough to paste into a chat message or email when handing a persona to another user.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PersonaBundle {
+    pub format_version: u32,
+    pub app_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub name: String,
+    pub description: Option<String>,
+    pub system_prompt: String,
+    pub settings: Option<crate::models::PersonaSettings>,
+    pub avatar_base64: Option<String>,
+    pub memories: Vec<crate::models::PersonaMemory>,
+}
+
+/// Package a persona's system prompt, settings, avatar, and remembered facts into a portable
+/// JSON bundle for [`import_persona_bundle`] to restore elsewhere
+pub fn export_persona_bundle(
+    persona_service: &crate::services::PersonaService,
+    memory_service: &crate::services::PersonaMemoryService,
+    persona_id: i64,
+) -> AppResult<String> {
+    use base64::Engine as _;
+
+    let persona = persona_service
+        .get_persona(persona_id)
+        .map_err(|e| AppError::database(format!("Failed to load persona: {}", e)))?
+        .ok_or_else(|| AppError::validation(format!("Persona {} not found", persona_id)))?;
+
+    let settings = persona_service
+        .get_persona_settings(persona_id)
+        .map_err(|e| AppError::database(format!("Failed to load persona settings: {}", e)))?;
+
+    let avatar_base64 = persona_service
+        .get_persona_avatar(persona_id)?
+        .map(|path| std::fs::read(&path))
+        .transpose()
+        .map_err(|e| AppError::io(format!("Failed to read persona avatar: {}", e)))?
+        .map(|bytes| base64::engine::general_purpose::STANDARD.encode(bytes));
+
+    let memories = memory_service
+        .get_persona_memory(persona_id, None)
+        .map_err(|e| AppError::database(format!("Failed to load persona memories: {}", e)))?;
+
+    let bundle = PersonaBundle {
+        format_version: PERSONA_BUNDLE_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: Utc::now(),
+        name: persona.name,
+        description: persona.description,
+        system_prompt: persona.system_prompt,
+        settings,
+        avatar_base64,
+        memories,
+    };
+
+    serde_json::to_string_pretty(&bundle)
+        .map_err(|e| AppError::validation(format!("Failed to serialize persona bundle: {}", e)))
+}
+
+/// Restore a persona bundle produced by [`export_persona_bundle`] as a brand-new persona
+///
+/// Always creates a new persona rather than overwriting an existing one - there's no reliable
+/// cross-machine persona identity to match against, so merging into an existing persona's
+/// settings or memories would risk clobbering the recipient's own edits. If `name` is already
+/// taken, falls back to `"{name} (Imported)"` the same way [`crate::services::ConversationService::duplicate_conversation`]
+/// disambiguates a copy's title.
+pub fn import_persona_bundle(
+    persona_service: &crate::services::PersonaService,
+    memory_service: &crate::services::PersonaMemoryService,
+    json: &str,
+) -> AppResult<crate::models::Persona> {
+    use base64::Engine as _;
+
+    let bundle: PersonaBundle = serde_json::from_str(json)
+        .map_err(|e| AppError::validation(format!("Failed to parse persona bundle: {}", e)))?;
+
+    if bundle.format_version != PERSONA_BUNDLE_FORMAT_VERSION {
+        return Err(AppError::validation(format!(
+            "Unsupported persona bundle format version: {} (expected {})",
+            bundle.format_version, PERSONA_BUNDLE_FORMAT_VERSION
+        )));
+    }
+
+    let persona = match persona_service.create_persona(
+        bundle.name.clone(),
+        bundle.description.clone(),
+        bundle.system_prompt.clone(),
+    ) {
+        Ok(persona) => persona,
+        Err(_) => persona_service
+            .create_persona(
+                format!("{} (Imported)", bundle.name),
+                bundle.description.clone(),
+                bundle.system_prompt.clone(),
+            )
+            .map_err(|e| AppError::database(format!("Failed to import persona: {}", e)))?,
+    };
+    let persona_id = persona.id.ok_or_else(|| AppError::database("Imported persona has no id"))?;
+
+    if let Some(settings) = &bundle.settings {
+        persona_service
+            .set_persona_settings(persona_id, settings)
+            .map_err(|e| AppError::database(format!("Failed to restore persona settings: {}", e)))?;
+    }
+
+    if let Some(avatar_base64) = &bundle.avatar_base64 {
+        let avatar_bytes = base64::engine::general_purpose::STANDARD
+            .decode(avatar_base64)
+            .map_err(|e| AppError::validation(format!("Invalid persona avatar encoding: {}", e)))?;
+
+        let mut temp_path = std::env::temp_dir();
+        temp_path.push(format!("persona-import-{}.png", Uuid::new_v4()));
+        std::fs::write(&temp_path, &avatar_bytes)
+            .map_err(|e| AppError::io(format!("Failed to stage imported avatar: {}", e)))?;
+
+        let result = persona_service.set_persona_avatar(persona_id, &temp_path.to_string_lossy());
+        let _ = std::fs::remove_file(&temp_path);
+        result.map_err(|e| AppError::io(format!("Failed to restore persona avatar: {}", e)))?;
+    }
+
+    for memory in &bundle.memories {
+        memory_service
+            .append_persona_memory(persona_id, memory.fact.clone(), Some(memory.relevance_score))
+            .map_err(|e| AppError::database(format!("Failed to restore persona memory: {}", e)))?;
+    }
+
+    persona_service
+        .get_persona(persona_id)
+        .map_err(|e| AppError::database(format!("Failed to load imported persona: {}", e)))?
+        .ok_or_else(|| AppError::database("Imported persona disappeared immediately after creation"))
+}
+
+fn read_conversations(tx: &Transaction) -> AppResult<Vec<Conversation>> {
+    let mut stmt = tx.prepare(
+        "SELECT id, uuid, title, persona_id, created_at, updated_at, archived, last_opened_at, frozen
+         FROM conversations",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(Conversation {
+            id: Some(row.get::<_, i64>(0)?),
+            uuid: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap_or_default(),
+            title: row.get(2)?,
+            persona_id: row.get::<_, Option<i64>>(3)?,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+            archived: row.get::<_, String>(6)? == "true",
+            metadata: None,
+            last_opened_at: row
+                .get::<_, Option<String>>(7)?
+                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
+            frozen: row.get::<_, String>(8)? == "true",
+        })
+    })?;
+
+    let mut conversations = Vec::new();
+    for row in rows {
+        conversations.push(row?);
+    }
+    Ok(conversations)
+}
+
+fn read_messages(tx: &Transaction) -> AppResult<Vec<Message>> {
+    let mut stmt = tx.prepare(
+        "SELECT id, conversation_id, role, content, created_at, tokens_used, model_used, edited_at, tool_call_id
+         FROM messages",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        let role_str: String = row.get(2)?;
+        let role = match role_str.as_str() {
+            "user" => crate::models::MessageRole::User,
+            "assistant" => crate::models::MessageRole::Assistant,
+            "system" => crate::models::MessageRole::System,
+            "tool" => crate::models::MessageRole::Tool,
+            _ => crate::models::MessageRole::User,
+        };
+
+        Ok(Message {
+            id: Some(row.get::<_, i64>(0)?),
+            conversation_id: row.get(1)?,
+            role,
+            content: row.get(3)?,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+            tokens_used: row
+                .get::<_, Option<String>>(5)?
+                .and_then(|s| s.parse().ok()),
+            model_used: row.get::<_, Option<String>>(6)?.filter(|s| !s.is_empty()),
+            metadata: None,
+            edited_at: row
+                .get::<_, Option<String>>(7)?
+                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
+            tool_call_id: row.get(8)?,
+        })
+    })?;
+
+    let mut messages = Vec::new();
+    for row in rows {
+        messages.push(row?);
+    }
+    Ok(messages)
+}
+
+fn read_personas(tx: &Transaction) -> AppResult<Vec<Persona>> {
+    let mut stmt = tx.prepare(
+        "SELECT id, name, description, system_prompt, created_at, updated_at, active
+         FROM personas",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(Persona {
+            id: Some(row.get::<_, i64>(0)?),
+            name: row.get(1)?,
+            description: {
+                let desc: String = row.get(2)?;
+                if desc.is_empty() { None } else { Some(desc) }
+            },
+            system_prompt: row.get(3)?,
+            avatar_path: None,
+            memory_context: None,
+            settings: None,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+            active: row.get::<_, String>(6)? == "true",
+        })
+    })?;
+
+    let mut personas = Vec::new();
+    for row in rows {
+        personas.push(row?);
+    }
+    Ok(personas)
+}
+
+fn read_grimoire_entries(tx: &Transaction) -> AppResult<Vec<GrimoireEntry>> {
+    let mut stmt = tx.prepare(
+        "SELECT id, title, content, category, tags, created_at, updated_at,
+                accessed_count, last_accessed, encrypted, template, fields
+         FROM grimoire_entries",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(GrimoireEntry {
+            id: row.get(0)?,
+            title: row.get(1)?,
+            content: row.get(2)?,
+            category: row.get(3)?,
+            tags: row.get(4)?,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(6)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+            accessed_count: row.get(7)?,
+            last_accessed: row
+                .get::<_, Option<String>>(8)?
+                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
+            encrypted: row.get::<_, String>(9)? == "true",
+            template: row.get(10)?,
+            fields: row
+                .get::<_, Option<String>>(11)?
+                .and_then(|s| serde_json::from_str(&s).ok()),
+        })
+    })?;
+
+    let mut entries = Vec::new();
+    for row in rows {
+        entries.push(row?);
+    }
+    Ok(entries)
+}
+
+fn insert_conversation(tx: &Transaction, conversation: &Conversation) -> AppResult<i64> {
+    tx.execute(
+        "INSERT INTO conversations (uuid, title, persona_id, created_at, updated_at, archived, frozen)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            Uuid::new_v4().to_string(),
+            conversation.title,
+            conversation.persona_id,
+            conversation.created_at.to_rfc3339(),
+            conversation.updated_at.to_rfc3339(),
+            if conversation.archived { "true" } else { "false" },
+            if conversation.frozen { "true" } else { "false" },
+        ],
+    )
+    .map_err(|e| AppError::database(format!("Failed to import conversation: {}", e)))?;
+
+    Ok(tx.last_insert_rowid())
+}
+
+fn insert_message(tx: &Transaction, conversation_id: i64, message: &Message) -> AppResult<()> {
+    let role_str = match message.role {
+        crate::models::MessageRole::User => "user",
+        crate::models::MessageRole::Assistant => "assistant",
+        crate::models::MessageRole::System => "system",
+        crate::models::MessageRole::Tool => "tool",
+    };
+
+    tx.execute(
+        "INSERT INTO messages (conversation_id, role, content, created_at, tokens_used, model_used, edited_at, tool_call_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            conversation_id,
+            role_str,
+            message.content,
+            message.created_at.to_rfc3339(),
+            message.tokens_used,
+            message.model_used,
+            message.edited_at.map(|dt| dt.to_rfc3339()),
+            message.tool_call_id,
+        ],
+    )
+    .map_err(|e| AppError::database(format!("Failed to import message: {}", e)))?;
+
+    Ok(())
+}
+
+fn insert_persona(tx: &Transaction, persona: &Persona) -> AppResult<()> {
+    tx.execute(
+        "INSERT INTO personas (name, description, system_prompt, created_at, updated_at, active)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT(name) DO NOTHING",
+        params![
+            persona.name,
+            persona.description.as_deref().unwrap_or(""),
+            persona.system_prompt,
+            persona.created_at.to_rfc3339(),
+            persona.updated_at.to_rfc3339(),
+            if persona.active { "true" } else { "false" },
+        ],
+    )
+    .map_err(|e| AppError::database(format!("Failed to import persona: {}", e)))?;
+
+    Ok(())
+}
+
+fn insert_grimoire_entry(tx: &Transaction, entry: &GrimoireEntry) -> AppResult<()> {
+    let fields_json = entry
+        .fields
+        .as_ref()
+        .map(|f| serde_json::to_string(f))
+        .transpose()
+        .map_err(|e| AppError::validation(format!("Failed to serialize grimoire entry fields: {}", e)))?;
+
+    tx.execute(
+        "INSERT INTO grimoire_entries
+            (id, title, content, category, tags, created_at, updated_at, accessed_count, last_accessed, encrypted, template, fields)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+         ON CONFLICT(id) DO NOTHING",
+        params![
+            entry.id,
+            entry.title,
+            entry.content,
+            entry.category,
+            entry.tags,
+            entry.created_at.to_rfc3339(),
+            entry.updated_at.to_rfc3339(),
+            entry.accessed_count,
+            entry.last_accessed.map(|dt| dt.to_rfc3339()),
+            if entry.encrypted { "true" } else { "false" },
+            entry.template,
+            fields_json,
+        ],
+    )
+    .map_err(|e| AppError::database(format!("Failed to import grimoire entry: {}", e)))?;
+
+    Ok(())
+}
@@ -20,16 +20,102 @@ use std::sync::Arc;
 use tauri::Manager;
 use tracing::{error, info};
 
+mod backup_scheduler;
 mod commands;
 mod database;
+mod maintenance_scheduler;
 mod models;
 mod monitoring;
+mod provider_monitor;
+mod redaction;
 mod services;
+mod shortcuts;
+mod theme_monitor;
+mod trash_scheduler;
+mod windows;
 
 use commands::AppState;
 use database::DatabaseManager;
 use monitoring::{PerformanceConfig, PerformanceMonitor};
 use services::Services;
+use tauri::{
+    CustomMenuItem, Menu, MenuItem, Submenu, SystemTray, SystemTrayEvent, SystemTrayMenu,
+    SystemTrayMenuItem, WindowMenuEvent,
+};
+
+/// Build the tray icon's context menu: Show, New Conversation, Quit
+fn build_system_tray() -> SystemTray {
+    let menu = SystemTrayMenu::new()
+        .add_item(CustomMenuItem::new("show", "Show"))
+        .add_item(CustomMenuItem::new("new_conversation", "New Conversation"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new("quit", "Quit"));
+    SystemTray::new().with_menu(menu)
+}
+
+/// Build the native application menu bar: File, Edit, View
+fn build_app_menu() -> Menu {
+    let file_menu = Submenu::new(
+        "File",
+        Menu::new()
+            .add_item(
+                CustomMenuItem::new(shortcuts::NEW_CONVERSATION_ACTION, "New Conversation")
+                    .accelerator("CmdOrCtrl+Shift+N"),
+            )
+            .add_native_item(MenuItem::Separator)
+            .add_native_item(MenuItem::Quit),
+    );
+    let edit_menu = Submenu::new(
+        "Edit",
+        Menu::new()
+            .add_native_item(MenuItem::Undo)
+            .add_native_item(MenuItem::Redo)
+            .add_native_item(MenuItem::Separator)
+            .add_native_item(MenuItem::Cut)
+            .add_native_item(MenuItem::Copy)
+            .add_native_item(MenuItem::Paste)
+            .add_native_item(MenuItem::SelectAll),
+    );
+    let view_menu = Submenu::new(
+        "View",
+        Menu::new().add_item(
+            CustomMenuItem::new(shortcuts::TOGGLE_QUICK_CAPTURE_ACTION, "Toggle Quick Capture")
+                .accelerator("CmdOrCtrl+Shift+Space"),
+        ),
+    );
+    Menu::new()
+        .add_submenu(file_menu)
+        .add_submenu(edit_menu)
+        .add_submenu(view_menu)
+}
+
+/// Route application menu selections to the same actions the tray and global shortcuts use
+fn handle_menu_event(event: WindowMenuEvent) {
+    let action = event.menu_item_id();
+    shortcuts::dispatch_action(&event.window().app_handle(), action);
+}
+
+/// Unhide and focus the main window, e.g. after it was minimized to tray
+fn show_main_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Route tray icon clicks and context menu selections
+fn handle_system_tray_event(app: &tauri::AppHandle, event: SystemTrayEvent) {
+    match event {
+        SystemTrayEvent::LeftClick { .. } => show_main_window(app),
+        SystemTrayEvent::MenuItemClick { id, .. } => match id.as_str() {
+            "show" => show_main_window(app),
+            "new_conversation" => shortcuts::dispatch_action(app, shortcuts::NEW_CONVERSATION_ACTION),
+            "quit" => app.exit(0),
+            _ => {}
+        },
+        _ => {}
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -40,6 +126,29 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         sentry::ClientOptions {
             traces_sample_rate: 1.0,
             environment: Some(std::env::var("ENVIRONMENT").unwrap_or_else(|_| "development".to_string()).into()),
+            // `redaction::is_local_only_mode` is a hard kill switch settable at runtime via the
+            // `update_telemetry_settings` command - unlike `SENTRY_DSN`, it takes effect without
+            // a restart. Every event and breadcrumb that does get through still has
+            // `redaction::redact` applied to scrub API keys, emails, and any configured custom
+            // patterns before it leaves the process.
+            before_send: Some(std::sync::Arc::new(|mut event| {
+                if redaction::is_local_only_mode() {
+                    return None;
+                }
+                if let Some(message) = event.message.take() {
+                    event.message = Some(redaction::redact(&message));
+                }
+                Some(event)
+            })),
+            before_breadcrumb: Some(std::sync::Arc::new(|mut breadcrumb| {
+                if redaction::is_local_only_mode() {
+                    return None;
+                }
+                if let Some(message) = breadcrumb.message.take() {
+                    breadcrumb.message = Some(redaction::redact(&message));
+                }
+                Some(breadcrumb)
+            })),
             ..Default::default()
         }
     ));
@@ -90,10 +199,64 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     let services = Arc::new(Services::new(db_arc));
                     info!("✅ Services layer initialized");
 
+                    // Record this run's session start, marking any session left open by a
+                    // previous run (i.e. one that never reached a clean shutdown) as crashed
+                    if let Err(e) = services.reliability.record_session_start() {
+                        error!("⚠️ Failed to record session start: {}", e);
+                    }
+
+                    // Start the background backup scheduler (a no-op unless backups are enabled)
+                    backup_scheduler::spawn(services.conversations.db.clone());
+
+                    // Start the background WAL checkpoint / incremental vacuum scheduler
+                    maintenance_scheduler::spawn(services.conversations.db.clone());
+
+                    // Start the background trash purge scheduler
+                    trash_scheduler::spawn(services.conversations.db.clone());
+
+                    // Start polling local AI providers (Ollama, LM Studio) for availability
+                    let provider_monitor = Arc::new(provider_monitor::ProviderMonitor::new());
+                    provider_monitor.clone().spawn(app.handle());
+
+                    // Start polling the OS desktop theme, emitting "theme-changed" on flips
+                    Arc::new(theme_monitor::ThemeMonitor::new()).spawn(app.handle());
+
+                    // Seed the default shortcut bindings on first launch, then register
+                    // whatever's persisted (defaults or the user's own customizations)
+                    match services.shortcuts.get_shortcuts() {
+                        Ok(existing) if existing.is_empty() => {
+                            for (action, accelerator) in shortcuts::default_shortcuts() {
+                                if let Err(e) = services
+                                    .shortcuts
+                                    .upsert_shortcut(action.to_string(), accelerator.to_string())
+                                {
+                                    error!("⚠️ Failed to seed default shortcut '{}': {}", action, e);
+                                }
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(e) => error!("⚠️ Failed to load shortcuts: {}", e),
+                    }
+                    match services.shortcuts.get_shortcuts() {
+                        Ok(bound) => shortcuts::register_all(&app.handle(), &bound),
+                        Err(e) => error!("⚠️ Failed to register shortcuts: {}", e),
+                    }
+
                     // Set up application state
-                    app.manage(AppState { services });
+                    app.manage(AppState { services, provider_monitor });
                     info!("✅ Application state configured");
 
+                    // Hide the main window instead of closing it, so it survives in the tray
+                    if let Some(main_window) = app.get_window("main") {
+                        let main_window_for_close = main_window.clone();
+                        main_window.on_window_event(move |event| {
+                            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                                api.prevent_default();
+                                let _ = main_window_for_close.hide();
+                            }
+                        });
+                    }
+
                     info!("🎉 Forbidden Library ready - VoidCat RDC Excellence Protocol Active");
                     Ok(())
                 }
@@ -106,45 +269,202 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
         })
+        .menu(build_app_menu())
+        .on_menu_event(handle_menu_event)
+        .system_tray(build_system_tray())
+        .on_system_tray_event(handle_system_tray_event)
         .invoke_handler(tauri::generate_handler![
             // Basic application commands
             commands::greet,
             commands::get_app_version,
             commands::initialize_database,
             commands::get_database_stats,
+            commands::run_maintenance,
+            commands::create_bug_report_bundle,
+            commands::get_dashboard,
             // Conversation management commands
             commands::create_conversation,
             commands::get_conversations,
+            commands::get_conversation_window,
             commands::search_conversations,
             commands::search_full_text,
+            commands::search_messages,
+            commands::find_similar_questions,
             commands::search_titles,
             commands::search_phrases,
             commands::get_search_suggestions,
+            commands::get_command_palette_entries,
             commands::rebuild_search_index,
             commands::get_conversation,
+            commands::open_conversation,
+            commands::get_least_recently_opened_conversations,
+            commands::get_conversation_statistics,
+            commands::analyze_conversation,
+            commands::get_conversation_analysis,
+            commands::filter_conversations_by_analysis,
+            commands::suggest_metadata,
             commands::delete_conversation,
+            commands::trash_conversation,
+            commands::restore_conversation,
+            commands::purge_trash,
             commands::archive_conversation,
+            commands::bulk_update_conversations,
+            commands::compact_archived_conversation,
+            commands::freeze_conversation,
+            commands::fork_conversation,
+            commands::duplicate_conversation,
+            commands::merge_conversations,
+            commands::get_conversation_lineage,
+            commands::update_conversation_settings,
+            commands::get_conversation_settings,
+            commands::export_compliance_package,
+            commands::export_conversation_for_review,
+            commands::import_review_annotations,
+            commands::share_conversation,
+            commands::open_shared_snapshot,
+            commands::get_message_annotations,
             // Message management commands
             commands::add_message,
             commands::get_messages,
+            commands::count_messages,
+            commands::get_messages_since,
+            commands::regenerate_message,
+            commands::get_message_regenerations,
+            commands::update_message,
+            commands::delete_message,
+            commands::pin_message,
+            commands::unpin_message,
+            commands::set_message_flag,
+            commands::get_pinned_messages,
+            commands::add_attachment,
+            commands::get_attachments,
+            commands::remove_attachment,
+            commands::register_webhook,
+            commands::list_webhooks,
+            commands::delete_webhook,
+            commands::start_snapshot_server,
+            commands::stop_snapshot_server,
+            commands::get_snapshot_server_status,
+            commands::compact_history,
             // Persona management commands
             commands::create_persona,
             commands::get_personas,
             commands::get_persona,
             commands::update_persona,
             commands::delete_persona,
+            commands::set_persona_avatar,
+            commands::get_persona_avatar,
+            commands::append_persona_memory,
+            commands::get_persona_memory,
+            commands::clear_persona_memory,
+            // Project commands
+            commands::create_project,
+            commands::update_project,
+            commands::list_projects,
+            commands::archive_project,
+            commands::link_conversation_to_project,
+            commands::unlink_conversation_from_project,
+            commands::get_project_context,
             // API configuration commands
             commands::store_api_config,
             commands::get_api_config,
+            commands::get_api_profile,
+            commands::list_api_profiles,
+            commands::set_default_profile,
             commands::delete_api_config,
+            // Slash command commands
+            commands::upsert_slash_command,
+            commands::get_slash_commands,
+            commands::delete_slash_command,
+            commands::execute_slash_command,
+            // Global shortcut commands
+            commands::register_shortcut,
+            commands::unregister_shortcut,
+            commands::list_shortcuts,
+            // Detachable conversation window commands
+            commands::open_conversation_window,
+            commands::list_windows,
+            commands::focus_window,
+            commands::create_prompt_template,
+            commands::list_prompt_templates,
+            commands::render_prompt_template,
+            // Read-aloud commands
+            commands::enqueue_conversation_read_aloud,
+            commands::enqueue_grimoire_read_aloud,
+            commands::import_conversation_export,
+            commands::import_conversation_export_streaming,
+            commands::import_generic_jsonl,
+            commands::rebuild_embeddings,
+            commands::record_embedding,
+            commands::get_embedding_rebuild_progress,
+            commands::finalize_embedding_rebuild,
+            commands::embed_message,
+            commands::semantic_search,
+            commands::generate_conversation_title,
+            commands::list_grimoire_templates,
+            commands::create_grimoire_entry,
+            commands::find_grimoire_entries_by_field,
+            commands::get_grimoire_entry,
+            commands::list_grimoire_entries,
+            commands::update_grimoire_entry,
+            commands::delete_grimoire_entry,
+            commands::encrypt_grimoire_entry,
+            commands::decrypt_grimoire_entry,
+            commands::search_grimoire_entries_by_category,
+            commands::search_grimoire_entries_by_tag,
+            commands::connect_grimoire,
+            commands::list_grimoire_tools,
+            commands::invoke_grimoire_tool,
+            commands::get_read_aloud_progress,
+            // Snippet commands
+            commands::upsert_snippet,
+            commands::get_snippets,
+            commands::delete_snippet,
+            commands::expand_snippet,
+            // Usage policy commands
+            commands::get_usage_policy,
+            commands::set_usage_policy,
+            commands::override_usage_policy,
+            // Model deprecation commands
+            commands::get_model_deprecation_warning,
+            commands::migrate_model_references,
             // AI integration commands
             commands::send_ai_request,
             // File management commands
             commands::export_conversation,
+            commands::export_conversation_to_file,
+            commands::export_context_pack,
+            commands::cleanup_temp_artifact,
             commands::import_conversation,
+            commands::export_library,
+            commands::import_library,
+            commands::get_audit_log,
+            commands::export_persona_history,
+            commands::export_persona_bundle,
+            commands::import_persona_bundle,
             commands::backup_database,
+            commands::list_backups,
+            commands::restore_from_backup,
+            commands::rotate_encryption_key,
+            // Profile commands
+            commands::create_profile,
+            commands::get_profiles,
+            commands::set_conversation_profile,
+            commands::get_conversations_for_profile,
+            // Digest commands
+            commands::generate_weekly_digest,
+            // Session commands
+            commands::save_session,
+            commands::get_last_session,
             // Monitoring and testing commands
             commands::test_sentry,
+            commands::get_latency_histograms,
+            commands::get_memory_report,
+            commands::trim_caches,
+            commands::get_reliability_report,
+            commands::run_diagnostics,
+            commands::get_telemetry_settings,
+            commands::update_telemetry_settings,
             // Desktop-specific commands
             commands::get_system_info,
             commands::show_open_dialog,
@@ -152,8 +472,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             commands::write_file_to_disk,
             commands::read_file_from_disk,
             commands::show_notification,
+            commands::platform_set_progress,
             commands::copy_to_clipboard,
             commands::read_from_clipboard,
+            commands::copy_message_to_clipboard,
             commands::get_app_data_dir,
             commands::open_external_url,
             commands::create_desktop_shortcut,
@@ -161,13 +483,41 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             commands::get_window_state,
             commands::set_window_always_on_top,
             commands::minimize_to_tray,
+            commands::quick_capture_message,
             commands::check_for_updates,
             // AI Provider commands
+            commands::list_custom_providers,
             commands::check_ai_provider_availability,
             commands::list_ai_provider_models,
+            commands::pull_ollama_model,
+            commands::delete_ollama_model,
+            commands::get_ollama_model_info,
             commands::send_ai_provider_request,
+            commands::stream_ai_provider_request,
+            commands::cancel_ai_request,
+            commands::generate_follow_up_suggestions,
+            commands::get_cached_follow_up_suggestions,
+            commands::summarize_conversation,
+            commands::get_usage_summary,
+            commands::get_usage_by_conversation,
         ])
-        .run(tauri::generate_context!());
+        .build(tauri::generate_context!());
+
+    let app_result = match app_result {
+        Ok(app) => {
+            app.run(|app_handle, event| {
+                if let tauri::RunEvent::ExitRequested { .. } = event {
+                    info!("🛑 Shutdown requested - draining jobs and flushing database state...");
+                    if let Some(state) = app_handle.try_state::<AppState>() {
+                        state.services.shutdown();
+                    }
+                    info!("✅ Shutdown sequence complete");
+                }
+            });
+            Ok(())
+        }
+        Err(e) => Err(e),
+    };
 
     // Finish tracking startup time
     PerformanceMonitor::finish_startup_tracking(startup_start_time, Some(&perf_config));
@@ -0,0 +1,33 @@
+//! Background scheduler that periodically checkpoints the WAL and reclaims freed pages, keeping
+//! a long-running session's WAL file from growing unboundedly.
+//!
+//! Spawned once from `main.rs`'s `setup()` closure, alongside [`crate::backup_scheduler`]. Runs
+//! for the lifetime of the process and is not expected to be stopped before shutdown.
+
+use crate::database::DatabaseManager;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often the scheduler wakes up to run a maintenance pass
+const MAINTENANCE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Spawn the scheduler as a background tokio task. Returns immediately.
+pub fn spawn(db: Arc<DatabaseManager>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(MAINTENANCE_INTERVAL);
+        // The first tick fires immediately; skip it so a fresh launch doesn't pay for a
+        // checkpoint and vacuum before there's anything worth reclaiming.
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            match db.run_maintenance() {
+                Ok(report) => tracing::info!(
+                    "🧹 Database maintenance complete (WAL size: {} bytes)",
+                    report.wal_size_bytes
+                ),
+                Err(e) => tracing::warn!("⚠️ Scheduled database maintenance failed: {}", e),
+            }
+        }
+    });
+}
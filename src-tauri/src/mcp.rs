@@ -0,0 +1,204 @@
+//! Minimal Model Context Protocol (MCP) client for `Grimoire` servers configured with
+//! `GrimoireServerType::MCP`, speaking newline-delimited JSON-RPC 2.0 per the MCP spec.
+//!
+//! Two transports are supported, chosen by whether `ConnectionSettings::host` is set: stdio
+//! (spawns `server_path` as a subprocess and talks over its stdin/stdout) when it isn't, or
+//! WebSocket (`ws://host:port{server_path}`) when it is. Connections are held open by
+//! `crate::services::McpClientService` for reuse across `list_grimoire_tools` /
+//! `invoke_grimoire_tool` calls, keyed by server path.
+
+use crate::errors::{AppError, AppResult};
+use crate::models::{ConnectionSettings, GrimoireConfiguration, GrimoireServerType};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::atomic::{AtomicI64, Ordering};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout};
+use tokio_tungstenite::{tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+/// MCP protocol version this client speaks during the `initialize` handshake
+const MCP_PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// A tool exposed by an MCP server, as returned by `tools/list`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpTool {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default, rename = "inputSchema")]
+    pub input_schema: Value,
+}
+
+enum Transport {
+    Stdio {
+        /// Kept alive so the process isn't reaped while the connection is in use; killed on drop
+        _child: Child,
+        stdin: ChildStdin,
+        stdout: BufReader<ChildStdout>,
+    },
+    WebSocket(WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>),
+}
+
+/// An open connection to an MCP server, past a completed `initialize` handshake
+pub struct McpConnection {
+    transport: Transport,
+    next_id: AtomicI64,
+}
+
+impl McpConnection {
+    /// Open a transport to the server described by `server_path`/`configuration` and complete
+    /// the `initialize` handshake
+    pub async fn connect(server_path: &str, configuration: &GrimoireConfiguration) -> AppResult<Self> {
+        if !matches!(configuration.server_type, GrimoireServerType::MCP) {
+            return Err(AppError::validation("Grimoire is not configured as an MCP server"));
+        }
+
+        let transport = Self::open_transport(server_path, &configuration.connection_settings).await?;
+        let mut connection = Self {
+            transport,
+            next_id: AtomicI64::new(1),
+        };
+
+        connection
+            .request(
+                "initialize",
+                serde_json::json!({
+                    "protocolVersion": MCP_PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": { "name": "forbidden-library", "version": env!("CARGO_PKG_VERSION") }
+                }),
+            )
+            .await?;
+
+        Ok(connection)
+    }
+
+    async fn open_transport(server_path: &str, settings: &ConnectionSettings) -> AppResult<Transport> {
+        match &settings.host {
+            Some(host) => {
+                let port = settings.port.unwrap_or(80);
+                let url = format!("ws://{}:{}{}", host, port, server_path);
+                let (stream, _) = tokio_tungstenite::connect_async(url.as_str())
+                    .await
+                    .map_err(|e| AppError::api(format!("Failed to connect to MCP server at {}: {}", url, e)))?;
+                Ok(Transport::WebSocket(stream))
+            }
+            None => {
+                let mut child = tokio::process::Command::new(server_path)
+                    .stdin(std::process::Stdio::piped())
+                    .stdout(std::process::Stdio::piped())
+                    .stderr(std::process::Stdio::null())
+                    .kill_on_drop(true)
+                    .spawn()
+                    .map_err(|e| AppError::io(format!("Failed to spawn MCP server '{}': {}", server_path, e)))?;
+
+                let stdin = child
+                    .stdin
+                    .take()
+                    .ok_or_else(|| AppError::io("MCP server process has no stdin"))?;
+                let stdout = child
+                    .stdout
+                    .take()
+                    .ok_or_else(|| AppError::io("MCP server process has no stdout"))?;
+
+                Ok(Transport::Stdio {
+                    _child: child,
+                    stdin,
+                    stdout: BufReader::new(stdout),
+                })
+            }
+        }
+    }
+
+    /// Send a JSON-RPC request and wait for its matching response, ignoring notifications and
+    /// responses to other in-flight requests in between
+    async fn request(&mut self, method: &str, params: Value) -> AppResult<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let message = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+
+        self.send(&message).await?;
+
+        loop {
+            let response = self.recv().await?;
+            if response.get("id").and_then(Value::as_i64) == Some(id) {
+                if let Some(error) = response.get("error") {
+                    return Err(AppError::api(format!(
+                        "MCP server returned an error calling '{}': {}",
+                        method, error
+                    )));
+                }
+                return Ok(response.get("result").cloned().unwrap_or(Value::Null));
+            }
+        }
+    }
+
+    async fn send(&mut self, message: &Value) -> AppResult<()> {
+        let line = serde_json::to_string(message)
+            .map_err(|e| AppError::unexpected(format!("Failed to serialize MCP message: {}", e)))?;
+
+        match &mut self.transport {
+            Transport::Stdio { stdin, .. } => {
+                stdin
+                    .write_all(format!("{}\n", line).as_bytes())
+                    .await
+                    .map_err(|e| AppError::io(format!("Failed to write to MCP server: {}", e)))?;
+                stdin
+                    .flush()
+                    .await
+                    .map_err(|e| AppError::io(format!("Failed to flush MCP server stdin: {}", e)))
+            }
+            Transport::WebSocket(ws) => ws
+                .send(Message::Text(line))
+                .await
+                .map_err(|e| AppError::api(format!("Failed to send MCP message over WebSocket: {}", e))),
+        }
+    }
+
+    async fn recv(&mut self) -> AppResult<Value> {
+        match &mut self.transport {
+            Transport::Stdio { stdout, .. } => {
+                let mut line = String::new();
+                let bytes_read = stdout
+                    .read_line(&mut line)
+                    .await
+                    .map_err(|e| AppError::io(format!("Failed to read from MCP server: {}", e)))?;
+                if bytes_read == 0 {
+                    return Err(AppError::api("MCP server closed its stdout"));
+                }
+                serde_json::from_str(&line)
+                    .map_err(|e| AppError::api(format!("Invalid JSON-RPC message from MCP server: {}", e)))
+            }
+            Transport::WebSocket(ws) => {
+                let message = ws
+                    .next()
+                    .await
+                    .ok_or_else(|| AppError::api("MCP WebSocket connection closed"))?
+                    .map_err(|e| AppError::api(format!("MCP WebSocket error: {}", e)))?;
+                match message {
+                    Message::Text(text) => serde_json::from_str(&text).map_err(|e| {
+                        AppError::api(format!("Invalid JSON-RPC message from MCP server: {}", e))
+                    }),
+                    _ => Err(AppError::api("Unexpected non-text MCP WebSocket message")),
+                }
+            }
+        }
+    }
+
+    /// List the tools this MCP server exposes
+    pub async fn list_tools(&mut self) -> AppResult<Vec<McpTool>> {
+        let result = self.request("tools/list", serde_json::json!({})).await?;
+        let tools = result.get("tools").cloned().unwrap_or_else(|| Value::Array(vec![]));
+        serde_json::from_value(tools).map_err(|e| AppError::api(format!("Invalid tools/list response: {}", e)))
+    }
+
+    /// Invoke a named tool with the given arguments, returning its raw result payload
+    pub async fn call_tool(&mut self, name: &str, arguments: Value) -> AppResult<Value> {
+        self.request("tools/call", serde_json::json!({ "name": name, "arguments": arguments }))
+            .await
+    }
+}
@@ -0,0 +1,63 @@
+//! Deprecated/sunset AI model registry
+//!
+//! Tracks model names that providers have retired or announced for retirement, so the UI can
+//! warn the user instead of letting a request fail with an opaque provider error. The registry
+//! is a bundled data file rather than a hardcoded list so it can be refreshed without touching
+//! application logic.
+
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+const DEPRECATED_MODELS_JSON: &str = include_str!("deprecated_models.json");
+
+/// A single deprecated/sunset model entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeprecatedModel {
+    pub provider: String,
+    pub model: String,
+    pub replacement: Option<String>,
+    pub message: String,
+}
+
+static REGISTRY: OnceLock<Vec<DeprecatedModel>> = OnceLock::new();
+
+fn registry() -> &'static [DeprecatedModel] {
+    REGISTRY
+        .get_or_init(|| {
+            serde_json::from_str(DEPRECATED_MODELS_JSON)
+                .expect("bundled deprecated_models.json must be valid")
+        })
+        .as_slice()
+}
+
+/// Look up a deprecation warning for a provider/model pair, if one exists
+///
+/// `provider` is matched case-insensitively against the registry's `provider` field
+/// (e.g. "openai", "anthropic"); `model` must match exactly.
+pub fn deprecation_warning(provider: &str, model: &str) -> Option<&'static DeprecatedModel> {
+    registry()
+        .iter()
+        .find(|entry| entry.provider.eq_ignore_ascii_case(provider) && entry.model == model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_deprecated_model_is_found() {
+        let warning = deprecation_warning("openai", "gpt-4-32k");
+        assert!(warning.is_some());
+        assert_eq!(warning.unwrap().replacement.as_deref(), Some("gpt-4-turbo"));
+    }
+
+    #[test]
+    fn test_provider_lookup_is_case_insensitive() {
+        assert!(deprecation_warning("OpenAI", "gpt-4-32k").is_some());
+    }
+
+    #[test]
+    fn test_unknown_model_returns_none() {
+        assert!(deprecation_warning("openai", "gpt-5-nonexistent").is_none());
+    }
+}
@@ -42,6 +42,15 @@ pub struct Conversation {
     pub updated_at: DateTime<Utc>,
     pub archived: bool,
     pub metadata: Option<ConversationMetadata>,
+    /// When this conversation was last opened in the UI, used to drive LRU-style cache
+    /// eviction and "least recently opened" cleanup suggestions. `None` if it has never
+    /// been opened since the `last_opened_at` column was introduced.
+    pub last_opened_at: Option<DateTime<Utc>>,
+    /// When true, blocks message edits/regenerations/deletes and the conversation's own
+    /// deletion, via [`crate::services::ConversationService::set_conversation_frozen`]. Meant
+    /// for compliance retention: freeze a conversation before generating its
+    /// [`crate::compliance_export::ComplianceExport`] so the record can't change afterward.
+    pub frozen: bool,
 }
 
 /// Extended metadata for conversations
@@ -69,6 +78,26 @@ impl Default for ConversationPriority {
     }
 }
 
+/// Per-conversation overrides for the context a request is sent with
+///
+/// Any field left `None` falls back to the conversation's persona, and from there to the
+/// provider call's own defaults - see
+/// `ConversationService::resolve_conversation_settings`. A conversation with no row in
+/// `conversation_settings` behaves as if every field were `None`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationSettings {
+    pub conversation_id: i64,
+    pub system_prompt: Option<String>,
+    pub model: Option<String>,
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<i32>,
+    /// Name of the `ApiService` profile (see `ApiService::store_api_config`) this conversation
+    /// sends requests through, when set - resolved ahead of the provider's default profile by
+    /// `commands::resolve_api_profile`.
+    pub profile_name: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
 /// Individual message within a conversation - Enhanced for native application
 ///
 /// Represents a single message in a conversation thread. Messages can be from
@@ -78,12 +107,13 @@ impl Default for ConversationPriority {
 ///
 /// * `id` - Optional database identifier
 /// * `conversation_id` - Foreign key reference to the parent conversation
-/// * `role` - Who sent the message (User, Assistant, or System)
+/// * `role` - Who sent the message (User, Assistant, System, or Tool)
 /// * `content` - The actual message text
 /// * `metadata` - Optional extended metadata for tracking and analytics
 /// * `created_at` - Timestamp when the message was created
 /// * `tokens_used` - Number of tokens consumed by this message (for cost tracking)
 /// * `model_used` - AI model that generated this response (for assistant messages)
+/// * `tool_call_id` - ID of the tool call this message is a result for (only set on `Tool` messages)
 ///
 /// # Examples
 ///
@@ -104,6 +134,12 @@ pub struct Message {
     pub created_at: DateTime<Utc>,
     pub tokens_used: Option<i32>,
     pub model_used: Option<String>,
+    /// When this message's content was last changed via [`crate::services::ConversationService::update_message`].
+    /// `None` means the message is unedited since it was created.
+    pub edited_at: Option<DateTime<Utc>>,
+    /// ID of the tool call this message's `content` is the result of. Only meaningful on
+    /// `MessageRole::Tool` messages - `None` for every other role.
+    pub tool_call_id: Option<String>,
 }
 
 /// Role of the message sender in a conversation
@@ -116,12 +152,14 @@ pub struct Message {
 /// * `User` - Message from the human user
 /// * `Assistant` - Response from the AI assistant
 /// * `System` - System-generated message (prompts, context, instructions)
+/// * `Tool` - Result of a tool/function call, reported back to the model
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum MessageRole {
     User,
     Assistant,
     System,
+    Tool,
 }
 
 /// Enhanced message metadata for native application
@@ -189,6 +227,21 @@ pub struct Persona {
     pub active: bool,
 }
 
+/// A single remembered fact about a persona's user, backing the normalized `persona_memories`
+/// table that [`Persona.memory_context`] was never actually wired up to populate
+///
+/// `relevance_score` ranks which memories [`crate::commands::send_ai_request`] injects into the
+/// system prompt when a persona has accumulated more than fit in the budget - higher sorts first.
+#[derive(Debug, Clone, Serialize, Deserialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct PersonaMemory {
+    pub id: Option<i64>,
+    pub persona_id: i64,
+    pub fact: String,
+    pub relevance_score: f32,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PersonaSettings {
     pub preferred_model: Option<String>,
@@ -197,9 +250,29 @@ pub struct PersonaSettings {
     pub response_style: ResponseStyle,
     pub expertise_domains: Vec<String>,
     pub personality_traits: Vec<PersonalityTrait>,
+    pub style_enforcement: Option<StyleEnforcementConfig>,
     pub legacy_settings: Option<serde_json::Value>, // For migration compatibility
 }
 
+/// Constraints checked by [`crate::style_enforcement::check_style`] against a `Concise` or
+/// `Formal` persona's responses before they are persisted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StyleEnforcementConfig {
+    pub enabled: bool,
+    pub max_chars: Option<usize>,
+    pub banned_phrases: Vec<String>,
+}
+
+impl Default for StyleEnforcementConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_chars: None,
+            banned_phrases: Vec::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ResponseStyle {
     Concise,
@@ -222,6 +295,493 @@ pub struct PersonalityTrait {
     pub intensity: f32, // 0.0 to 1.0
 }
 
+/// Status of a single chunk in a read-aloud job
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadAloudChunkStatus {
+    Pending,
+    Processing,
+    Complete,
+    Failed,
+}
+
+/// One sequentially-ordered chunk of text synthesized as part of a read-aloud job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadAloudChunk {
+    pub id: String,
+    pub sequence: i32,
+    pub text: String,
+    pub status: ReadAloudChunkStatus,
+    pub audio_path: Option<String>,
+}
+
+/// Progress of a background job converting a conversation or grimoire entry to audio chunks
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadAloudProgress {
+    pub job_id: String,
+    pub chunks: Vec<ReadAloudChunk>,
+    pub completed_count: usize,
+    pub total_count: usize,
+}
+
+/// Status of a bulk re-embedding job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbeddingJobStatus {
+    InProgress,
+    Complete,
+    Failed,
+}
+
+/// Progress of a job re-embedding all indexed content with a new model
+///
+/// New vectors are written to the index as they're embedded but are not queried until the job
+/// is finalized, so the previous model's index keeps serving lookups until the new one is ready.
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbeddingJobProgress {
+    pub job_id: String,
+    pub model: String,
+    pub status: EmbeddingJobStatus,
+    pub total_items: i32,
+    pub processed_items: i32,
+}
+
+/// A user-defined slash command that transforms into a prompt before dispatch to a provider
+///
+/// `prompt_template` may reference `{{args}}` (all arguments joined with spaces) or
+/// positional `{{arg1}}`, `{{arg2}}`, ... placeholders, substituted at execution time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashCommand {
+    pub id: Option<String>,
+    pub name: String,
+    pub prompt_template: String,
+    pub default_model: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SlashCommand {
+    /// Create a new slash command
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use forbidden_library_native::models::SlashCommand;
+    ///
+    /// let cmd = SlashCommand::new(
+    ///     "eli5".to_string(),
+    ///     "Explain like I'm five: {{args}}".to_string(),
+    ///     None,
+    /// );
+    /// assert_eq!(cmd.name, "eli5");
+    /// ```
+    pub fn new(name: String, prompt_template: String, default_model: Option<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            name,
+            prompt_template,
+            default_model,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// A user-defined binding from an OS-level global keyboard shortcut to a named action (e.g.
+/// `"new_conversation"`, `"toggle_quick_capture"`), registered via
+/// [`crate::shortcuts`] and persisted so it's restored on the next launch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Shortcut {
+    pub action: String,
+    pub accelerator: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Shortcut {
+    pub fn new(action: String, accelerator: String) -> Self {
+        let now = Utc::now();
+        Self {
+            action,
+            accelerator,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// A reusable prompt with named `{{variable}}` placeholders, rendered server-side before being
+/// sent to a provider
+///
+/// Unlike [`SlashCommand`]'s positional `{{args}}`/`{{argN}}` substitution, placeholders here
+/// are named (`{{topic}}`, `{{tone}}`, ...) and every placeholder referenced by `template` must
+/// have a supplied value at render time - see
+/// `PromptTemplateService::render_template`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub id: Option<i64>,
+    pub name: String,
+    pub category: Option<String>,
+    pub template: String,
+    pub favorite: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PromptTemplate {
+    pub fn new(name: String, category: Option<String>, template: String, favorite: bool) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            name,
+            category,
+            template,
+            favorite,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// A reviewer's comment on a message, persisted after being pulled out of a reviewed
+/// [`crate::review_export::ReviewExport`] bundle via
+/// `ConversationService::import_review_annotations`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageAnnotation {
+    pub id: Option<i64>,
+    pub message_id: i64,
+    pub author: Option<String>,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A user-defined text abbreviation that expands into a longer snippet
+///
+/// Expansion text may reference built-in variables (`{{date}}`, `{{time}}`) and a single
+/// `{{cursor}}` marker indicating where the caret should land after expansion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snippet {
+    pub id: Option<String>,
+    pub trigger: String,
+    pub expansion: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Snippet {
+    /// Create a new snippet with the given trigger abbreviation and expansion text
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use forbidden_library_native::models::Snippet;
+    ///
+    /// let snippet = Snippet::new("/sum".to_string(), "Summarize this: {{cursor}}".to_string());
+    /// assert_eq!(snippet.trigger, "/sum");
+    /// ```
+    pub fn new(trigger: String, expansion: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            trigger,
+            expansion,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// Result of expanding a snippet: the rendered text and where the cursor should land
+#[derive(Debug, Clone, Serialize)]
+pub struct ExpandedSnippet {
+    pub text: String,
+    /// Character offset into `text` where the cursor should be placed, if the expansion
+    /// contained a `{{cursor}}` marker. `None` places the cursor at the end.
+    pub cursor_offset: Option<usize>,
+}
+
+/// Optional usage window policy that restricts when AI requests may be sent
+///
+/// Intended for self-regulation and shared family machines - not a parental-control
+/// security boundary, since any user with app access can change the policy itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsagePolicy {
+    /// Start of the disallowed hours window, 0-23 in local time. `None` disables the window check.
+    pub quiet_hours_start: Option<u32>,
+    /// End of the disallowed hours window, 0-23 in local time. May be less than `quiet_hours_start`
+    /// to represent a window that wraps past midnight (e.g. 0 -> 6).
+    pub quiet_hours_end: Option<u32>,
+    /// Maximum number of AI requests allowed per calendar day. `None` disables the cap.
+    pub daily_request_cap: Option<u32>,
+    /// If set and in the future, the policy is temporarily bypassed until this time
+    pub override_until: Option<DateTime<Utc>>,
+}
+
+impl Default for UsagePolicy {
+    fn default() -> Self {
+        Self {
+            quiet_hours_start: None,
+            quiet_hours_end: None,
+            daily_request_cap: None,
+            override_until: None,
+        }
+    }
+}
+
+/// Telemetry settings, read by [`crate::redaction`] and pushed into its global state by
+/// [`crate::services::TelemetryService`] whenever they're read or written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetrySettings {
+    /// When `true`, Sentry's `before_send`/`before_breadcrumb` hooks in `main.rs` drop every
+    /// event instead of sending it - a hard runtime kill switch, independent of the `SENTRY_DSN`
+    /// environment variable the client was initialized with.
+    pub local_only_mode: bool,
+    /// Additional regexes scrubbed from event/breadcrumb messages, alongside the always-on
+    /// built-in patterns (API key shapes, bearer tokens, email addresses) in
+    /// [`crate::redaction`]
+    pub custom_redaction_patterns: Vec<String>,
+}
+
+impl Default for TelemetrySettings {
+    fn default() -> Self {
+        Self {
+            local_only_mode: false,
+            custom_redaction_patterns: Vec::new(),
+        }
+    }
+}
+
+/// Stored configuration for a single AI provider, including cost-attribution headers
+///
+/// `organization` and `project` map to provider-specific scoping headers (e.g. OpenAI's
+/// `OpenAI-Organization`/`OpenAI-Project`); `extra_headers` carries arbitrary additional
+/// headers an endpoint may require. All values are validated against header injection
+/// before being persisted - see `InputValidator::validate_http_header`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiConfig {
+    /// Name of the profile this config was stored under (see
+    /// `ApiService::store_api_config`) - a provider may have several, e.g.
+    /// "work-openai" and "personal-openai".
+    pub profile_name: String,
+    pub provider: String,
+    /// Whether this is the profile `ApiService::get_api_config(provider)` resolves to
+    /// when a caller asks for "the" config for a provider rather than a named profile.
+    pub is_default: bool,
+    pub api_key: String,
+    pub base_url: Option<String>,
+    pub organization: Option<String>,
+    pub project: Option<String>,
+    pub extra_headers: Vec<(String, String)>,
+    /// Optional outbound request caps for this provider, enforced by
+    /// [`crate::ratelimit`] before `AIProvider::send_request` dispatches a request.
+    /// `None` (the default) leaves the provider unlimited.
+    pub rate_limits: Option<RateLimits>,
+}
+
+/// Lightweight summary of a stored [`ApiConfig`] profile, omitting the API key - returned by
+/// `ApiService::list_api_profiles` so the frontend can render a profile picker without ever
+/// pulling every profile's key out of the keychain just to list them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiProfileSummary {
+    pub profile_name: String,
+    pub provider: String,
+    pub is_default: bool,
+}
+
+/// A single regeneration of an assistant message, with a word-level diff against the content
+/// it replaced so the UI can highlight what changed without re-deriving the diff itself
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageRegeneration {
+    pub id: Option<i64>,
+    pub message_id: i64,
+    pub previous_content: String,
+    pub new_content: String,
+    pub diff: Vec<crate::diff::DiffSpan>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Summary of a [`crate::services::ConversationService::compact_history`] run
+///
+/// Reports how many low-value messages were folded into summaries so callers can show the
+/// user what was reclaimed without re-querying the archive table themselves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryCompactionReport {
+    pub runs_compacted: usize,
+    pub messages_compacted: usize,
+    pub tokens_saved: i64,
+}
+
+/// A chunk of old conversation history condensed into prose by
+/// [`crate::services::ConversationService::summarize_conversation`], so `send_ai_request` can
+/// include this instead of resending the entire history to the provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationSummary {
+    pub id: i64,
+    pub conversation_id: i64,
+    pub summary: String,
+    /// The newest message id this summary accounts for; the next summarization run only
+    /// considers messages after this one
+    pub covers_through_message_id: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Report of a [`crate::services::ConversationService::summarize_conversation`] run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationSummarizationReport {
+    pub chunks_summarized: usize,
+    pub latest_summary: Option<ConversationSummary>,
+}
+
+/// One completed provider request, as recorded by
+/// [`crate::services::UsageAnalyticsService::record_usage`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub id: i64,
+    pub conversation_id: Option<i64>,
+    pub provider: String,
+    pub model: String,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub total_tokens: i64,
+    pub latency_ms: i64,
+    /// `None` when [`crate::pricing`] has no price for this (provider, model) pair
+    pub estimated_cost_usd: Option<f64>,
+    pub created_at: DateTime<Utc>,
+    /// `"completed"` or `"cancelled"` (see [`crate::commands::cancel_ai_request`])
+    pub status: String,
+}
+
+/// Usage totals for one (provider, model) pair, within a
+/// [`crate::services::UsageAnalyticsService::get_usage_summary`] window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderUsageTotals {
+    pub provider: String,
+    pub model: String,
+    pub requests: i64,
+    pub total_tokens: i64,
+    pub estimated_cost_usd: f64,
+}
+
+/// Result of [`crate::services::UsageAnalyticsService::get_usage_summary`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageSummary {
+    pub total_requests: i64,
+    pub total_tokens: i64,
+    pub total_estimated_cost_usd: f64,
+    pub by_provider: Vec<ProviderUsageTotals>,
+}
+
+/// What kind of event a [`crate::commands::show_notification`] call is reporting, so the
+/// frontend's notification center can group and icon them appropriately
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ts_rs::TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub enum NotificationCategory {
+    AiResponseReady,
+    BackupFinished,
+    UpdateAvailable,
+    General,
+}
+
+/// Visual theme for an [`crate::export_formats`] HTML export
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HtmlExportTheme {
+    Light,
+    Dark,
+}
+
+impl Default for HtmlExportTheme {
+    fn default() -> Self {
+        HtmlExportTheme::Light
+    }
+}
+
+/// Summary of locally recorded [`crate::services::ReliabilityService`] sessions
+///
+/// Lets a diagnostics panel show whether the app has been crashing on this machine even when
+/// the user has disabled Sentry reporting.
+#[derive(Debug, Clone, Serialize, Deserialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct ReliabilityReport {
+    pub total_sessions: i64,
+    pub clean_shutdowns: i64,
+    pub crashed_shutdowns: i64,
+    pub last_session_started_at: Option<DateTime<Utc>>,
+}
+
+/// When [`crate::database::DatabaseManager::run_maintenance`] last checkpointed the WAL and ran
+/// an incremental vacuum, plus the WAL file's current size - enough for a diagnostics panel to
+/// tell whether maintenance is keeping up with a long-running session
+#[derive(Debug, Clone, Serialize, Deserialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct DatabaseMaintenanceReport {
+    pub last_checkpoint_at: Option<DateTime<Utc>>,
+    pub last_vacuum_at: Option<DateTime<Utc>>,
+    pub wal_size_bytes: i64,
+}
+
+/// How a [`crate::commands::import_conversation_export_streaming`] run is progressing
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ts_rs::TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub enum ImportJobStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+/// A streamed import of a large export file, tracked so a partial failure can be resumed by
+/// re-invoking [`crate::commands::import_conversation_export_streaming`] with this job's `id`
+/// instead of re-importing everything from the start
+#[derive(Debug, Clone, Serialize, Deserialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct ImportJob {
+    pub id: String,
+    pub source_path: String,
+    pub status: ImportJobStatus,
+    pub conversations_imported: usize,
+    pub messages_imported: usize,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A lightweight local identity for machines shared by multiple people
+///
+/// Profiles are not a security boundary - they exist to keep each person's recent
+/// conversations and persona defaults from mixing together in shared views, not to
+/// isolate or encrypt their data from one another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: Option<i64>,
+    pub name: String,
+    pub default_persona_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Profile {
+    /// Create a new profile with the given display name
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use forbidden_library_native::models::Profile;
+    ///
+    /// let profile = Profile::new("Alex".to_string(), None);
+    /// assert_eq!(profile.name, "Alex");
+    /// ```
+    pub fn new(name: String, default_persona_id: Option<i64>) -> Self {
+        Self {
+            id: None,
+            name,
+            default_persona_id,
+            created_at: Utc::now(),
+        }
+    }
+}
+
 /// Enhanced Grimoire model - Knowledge base and MCP server management
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Grimoire {
@@ -288,6 +848,27 @@ pub enum GrimoireCapability {
     SystemIntegration,
 }
 
+/// A single entry in the local knowledge base, stored in the `grimoire_entries` table
+///
+/// Distinct from [`Grimoire`] above, which configures an MCP/HTTP server connection rather than
+/// storing content. An entry may optionally be built from a named template (see
+/// [`crate::grimoire_templates`]), in which case `fields` holds its validated structured data.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GrimoireEntry {
+    pub id: String,
+    pub title: String,
+    pub content: String,
+    pub category: Option<String>,
+    pub tags: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub accessed_count: i32,
+    pub last_accessed: Option<DateTime<Utc>>,
+    pub encrypted: bool,
+    pub template: Option<String>,
+    pub fields: Option<serde_json::Value>,
+}
+
 /// Enhanced API provider model for multiple AI services
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ApiProvider {
@@ -326,6 +907,10 @@ pub struct ModelCapabilities {
     pub supports_tool_use: bool,
 }
 
+/// Outbound request caps for one AI provider, stored as JSON in `api_configs.rate_limits` and
+/// enforced by [`crate::ratelimit`] as independent token buckets - a request must fit under
+/// every limit that's set, or it's rejected with [`crate::errors::AppError::RateLimited`].
+/// `None` on any field leaves that particular cap unenforced.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimits {
     pub requests_per_minute: Option<i32>,
@@ -334,10 +919,21 @@ pub struct RateLimits {
     pub tokens_per_day: Option<i32>,
 }
 
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            requests_per_minute: None,
+            tokens_per_minute: None,
+            requests_per_day: None,
+            tokens_per_day: None,
+        }
+    }
+}
+
 /// Project model - Development project tracking
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
-    pub id: Option<i64>,
+    pub id: Option<String>,
     pub name: String,
     pub description: Option<String>,
     pub repository_url: Option<String>,
@@ -421,6 +1017,8 @@ impl Conversation {
             updated_at: now,
             archived: false,
             metadata: None,
+            last_opened_at: None,
+            frozen: false,
         }
     }
 }
@@ -468,6 +1066,8 @@ impl Message {
             created_at: Utc::now(),
             tokens_used: None,
             model_used: None,
+            edited_at: None,
+            tool_call_id: None,
         }
     }
 }
@@ -518,6 +1118,50 @@ impl Persona {
     }
 }
 
+/// One append-only entry in the `audit_log` table, recorded by
+/// [`crate::services::AuditLogService::record`] for sensitive operations (API config changes,
+/// exports, key rotations, deletions, restores) and returned by
+/// [`crate::commands::get_audit_log`]
+///
+/// `actor` is a fixed string identifying what triggered the action rather than a verified
+/// identity - this app has no multi-user authentication to attach a real actor to.
+#[derive(Debug, Clone, Serialize, Deserialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct AuditLogEntry {
+    pub id: i64,
+    pub actor: String,
+    pub action: String,
+    pub details: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Criteria for [`crate::commands::get_audit_log`] to narrow and page through the audit log,
+/// oldest filters first so the newest entries (the ones most often being reviewed) sort first in
+/// the default page
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct AuditLogFilter {
+    pub action: Option<String>,
+    pub actor: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// One step of a [`crate::commands::bulk_update_conversations`] batch, applied to every id in
+/// the request by [`crate::services::ConversationService::bulk_update_conversations`]
+#[derive(Debug, Clone, Serialize, Deserialize, ts_rs::TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub enum BulkConversationAction {
+    Archive,
+    Unarchive,
+    Delete,
+    Tag { tag: String },
+    Untag { tag: String },
+}
+
 impl Grimoire {
     pub fn new(name: String, description: Option<String>, server_path: String) -> Self {
         Self {
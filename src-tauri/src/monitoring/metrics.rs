@@ -0,0 +1,132 @@
+//! In-process latency histograms for IPC commands
+//!
+//! Complements the Sentry-backed tracing in [`crate::monitoring::PerformanceMonitor`] with a
+//! lightweight, always-on store the frontend can poll directly (no Sentry project required)
+//! to render a local performance panel.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Fixed latency buckets (in milliseconds) used for all commands
+const BUCKET_BOUNDS_MS: &[u64] = &[5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000];
+
+struct CommandHistogram {
+    /// Count of samples in each bucket, plus one overflow bucket for anything above the last bound
+    counts: Vec<u64>,
+    total_count: u64,
+    total_duration_ms: u64,
+    max_duration_ms: u64,
+}
+
+impl CommandHistogram {
+    fn new() -> Self {
+        Self {
+            counts: vec![0; BUCKET_BOUNDS_MS.len() + 1],
+            total_count: 0,
+            total_duration_ms: 0,
+            max_duration_ms: 0,
+        }
+    }
+
+    fn record(&mut self, duration_ms: u64) {
+        let bucket = BUCKET_BOUNDS_MS
+            .iter()
+            .position(|&bound| duration_ms <= bound)
+            .unwrap_or(BUCKET_BOUNDS_MS.len());
+        self.counts[bucket] += 1;
+        self.total_count += 1;
+        self.total_duration_ms += duration_ms;
+        self.max_duration_ms = self.max_duration_ms.max(duration_ms);
+    }
+
+    fn snapshot(&self, command: &str) -> LatencyHistogram {
+        let buckets = BUCKET_BOUNDS_MS
+            .iter()
+            .zip(self.counts.iter())
+            .map(|(&le_ms, &count)| LatencyBucket { le_ms: Some(le_ms), count })
+            .chain(std::iter::once(LatencyBucket {
+                le_ms: None,
+                count: *self.counts.last().unwrap_or(&0),
+            }))
+            .collect();
+
+        let average_ms = if self.total_count > 0 {
+            self.total_duration_ms as f64 / self.total_count as f64
+        } else {
+            0.0
+        };
+
+        LatencyHistogram {
+            command: command.to_string(),
+            total_count: self.total_count,
+            average_ms,
+            max_ms: self.max_duration_ms,
+            buckets,
+        }
+    }
+}
+
+/// One bucket of a latency histogram; `le_ms: None` means "greater than the largest bound"
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyBucket {
+    pub le_ms: Option<u64>,
+    pub count: u64,
+}
+
+/// Rolling latency histogram for a single IPC command
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyHistogram {
+    pub command: String,
+    pub total_count: u64,
+    pub average_ms: f64,
+    pub max_ms: u64,
+    pub buckets: Vec<LatencyBucket>,
+}
+
+fn histograms() -> &'static Mutex<HashMap<String, CommandHistogram>> {
+    static HISTOGRAMS: OnceLock<Mutex<HashMap<String, CommandHistogram>>> = OnceLock::new();
+    HISTOGRAMS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Record a single observed duration for an IPC command
+pub fn record_ipc_latency(command: &str, duration_ms: u64) {
+    let mut histograms = histograms().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    histograms
+        .entry(command.to_string())
+        .or_insert_with(CommandHistogram::new)
+        .record(duration_ms);
+}
+
+/// Snapshot the current histograms for every command that has been observed
+pub fn snapshot_latency_histograms() -> Vec<LatencyHistogram> {
+    let histograms = histograms().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut result: Vec<LatencyHistogram> = histograms
+        .iter()
+        .map(|(command, histogram)| histogram.snapshot(command))
+        .collect();
+    result.sort_by(|a, b| a.command.cmp(&b.command));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_snapshot() {
+        record_ipc_latency("test_command_histogram", 3);
+        record_ipc_latency("test_command_histogram", 12);
+        record_ipc_latency("test_command_histogram", 9000);
+
+        let snapshot = snapshot_latency_histograms();
+        let entry = snapshot
+            .iter()
+            .find(|h| h.command == "test_command_histogram")
+            .expect("histogram should exist after recording");
+
+        assert_eq!(entry.total_count, 3);
+        assert_eq!(entry.max_ms, 9000);
+        assert!(entry.buckets.iter().any(|b| b.le_ms.is_none() && b.count == 1));
+    }
+}
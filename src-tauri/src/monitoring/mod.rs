@@ -6,6 +6,7 @@
 
 mod config;
 mod error_handling;
+pub mod metrics;
 mod transactions;
 
 #[cfg(test)]
@@ -13,6 +14,7 @@ mod tests;
 
 pub use config::PerformanceConfig;
 pub use error_handling::*;
+pub use metrics::{snapshot_latency_histograms, LatencyBucket, LatencyHistogram};
 pub use transactions::*;
 
 use sentry::protocol::Value;
@@ -182,6 +184,7 @@ impl PerformanceMonitor {
             .and_then(|res| res.map_err(|e| MonitoringError::Operation(e)));
 
         let duration = start_time.elapsed();
+        metrics::record_ipc_latency(command, duration.as_millis() as u64);
 
         let threshold = config.map_or(100, |c| c.ipc_threshold_ms);
         if duration.as_millis() > threshold as u128 {
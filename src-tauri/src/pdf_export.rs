@@ -0,0 +1,321 @@
+//! Minimal, dependency-free PDF 1.4 writer used by `export_conversation`'s `pdf` format
+//!
+//! No PDF-authoring crate is vendored in this tree, so this hand-emits just enough PDF object/
+//! content-stream syntax to lay out a multi-page text document using the built-in Helvetica and
+//! Helvetica-Bold fonts (no font embedding needed). It only covers what a conversation export
+//! needs - a title, a little metadata, and word-wrapped paragraphs per message - not general
+//! PDF authoring. Line wrapping uses an average-character-width approximation rather than real
+//! glyph metrics, the same "good enough, not exact" tradeoff [`crate::tokenizer`] makes for
+//! token counts. Attachments are listed by name rather than embedded, since embedding images
+//! would require a pure-Rust image decoder this tree doesn't have; [`crate::export_formats`]
+//! embeds them directly for the `html` format instead.
+
+use crate::models::{Conversation, Message, MessageRole, Persona};
+
+const PAGE_WIDTH: f64 = 612.0; // US Letter, in points
+const PAGE_HEIGHT: f64 = 792.0;
+const MARGIN: f64 = 56.0;
+const BODY_FONT_SIZE: f64 = 11.0;
+const BODY_LINE_HEIGHT: f64 = 15.0;
+const HEADING_FONT_SIZE: f64 = 13.0;
+const HEADING_LINE_HEIGHT: f64 = 18.0;
+const TITLE_FONT_SIZE: f64 = 20.0;
+const TITLE_LINE_HEIGHT: f64 = 26.0;
+
+/// A single line to lay out, already wrapped to fit within the page margins
+enum PdfLine {
+    Title(String),
+    Heading(String),
+    Body(String),
+}
+
+/// Replace characters outside the printable ASCII range this writer supports with `?`
+///
+/// The base14 fonts used here aren't embedded, so only WinAnsi-ish Latin text renders reliably;
+/// rather than risk corrupting the content stream on arbitrary Unicode, anything outside
+/// printable ASCII (and newlines, handled separately by the caller) is substituted.
+fn sanitize(text: &str) -> String {
+    text.chars().map(|c| if (0x20..=0x7E).contains(&(c as u32)) { c } else { '?' }).collect()
+}
+
+/// Escape a string for use inside a PDF literal string, i.e. `(...)`
+fn escape_pdf_string(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('(', "\\(").replace(')', "\\)")
+}
+
+/// Word-wrap `text` to fit `max_chars` columns, treating existing newlines as hard breaks
+fn wrap_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        if paragraph.trim().is_empty() {
+            lines.push(String::new());
+            continue;
+        }
+
+        let mut current = String::new();
+        for word in paragraph.split_whitespace() {
+            if current.is_empty() {
+                current.push_str(word);
+            } else if current.len() + 1 + word.len() <= max_chars {
+                current.push(' ');
+                current.push_str(word);
+            } else {
+                lines.push(std::mem::take(&mut current));
+                current.push_str(word);
+            }
+        }
+        if !current.is_empty() {
+            lines.push(current);
+        }
+    }
+    lines
+}
+
+fn max_chars_for_font_size(font_size: f64) -> usize {
+    let avg_char_width = font_size * 0.5;
+    (((PAGE_WIDTH - 2.0 * MARGIN) / avg_char_width).floor() as usize).max(10)
+}
+
+/// Render a conversation (plus optional persona metadata and a text-only attachment listing) as
+/// PDF bytes, ready to write to disk
+///
+/// `attachment_names_by_message` maps a message id to the display names of attachments on that
+/// message; pass an empty map if attachment listing isn't needed.
+pub fn conversation_to_pdf(
+    conversation: &Conversation,
+    messages: &[Message],
+    persona: Option<&Persona>,
+    attachment_names_by_message: &std::collections::HashMap<i64, Vec<String>>,
+) -> Vec<u8> {
+    let mut lines = vec![PdfLine::Title(sanitize(&conversation.title))];
+    lines.push(PdfLine::Body(sanitize(&format!(
+        "Created: {}",
+        conversation.created_at.format("%Y-%m-%d %H:%M:%S UTC")
+    ))));
+    if let Some(persona) = persona {
+        lines.push(PdfLine::Body(sanitize(&format!("Persona: {}", persona.name))));
+    }
+    lines.push(PdfLine::Body(String::new()));
+
+    for message in messages {
+        let role_label = match message.role {
+            MessageRole::User => "User",
+            MessageRole::Assistant => "Assistant",
+            MessageRole::System => "System",
+            MessageRole::Tool => "Tool",
+        };
+        lines.push(PdfLine::Heading(role_label.to_string()));
+        for wrapped in wrap_text(&sanitize(&message.content), max_chars_for_font_size(BODY_FONT_SIZE)) {
+            lines.push(PdfLine::Body(wrapped));
+        }
+        if let Some(id) = message.id {
+            if let Some(names) = attachment_names_by_message.get(&id) {
+                for name in names {
+                    lines.push(PdfLine::Body(sanitize(&format!("Attachment: {}", name))));
+                }
+            }
+        }
+        lines.push(PdfLine::Body(String::new()));
+    }
+
+    render_pages(paginate(lines))
+}
+
+fn line_height(line: &PdfLine) -> f64 {
+    match line {
+        PdfLine::Title(_) => TITLE_LINE_HEIGHT,
+        PdfLine::Heading(_) => HEADING_LINE_HEIGHT,
+        PdfLine::Body(_) => BODY_LINE_HEIGHT,
+    }
+}
+
+fn paginate(lines: Vec<PdfLine>) -> Vec<Vec<PdfLine>> {
+    let mut pages = Vec::new();
+    let mut current_page = Vec::new();
+    let mut y = PAGE_HEIGHT - MARGIN;
+
+    for line in lines {
+        let height = line_height(&line);
+        if y - height < MARGIN && !current_page.is_empty() {
+            pages.push(std::mem::take(&mut current_page));
+            y = PAGE_HEIGHT - MARGIN;
+        }
+        y -= height;
+        current_page.push(line);
+    }
+    if !current_page.is_empty() || pages.is_empty() {
+        pages.push(current_page);
+    }
+    pages
+}
+
+fn render_content_stream(lines: &[PdfLine]) -> String {
+    let mut stream = String::from("BT\n");
+    let mut y = PAGE_HEIGHT - MARGIN;
+    let mut current_font: Option<&str> = None;
+
+    for line in lines {
+        let (font, size, text) = match line {
+            PdfLine::Title(t) => ("/F2", TITLE_FONT_SIZE, t),
+            PdfLine::Heading(t) => ("/F2", HEADING_FONT_SIZE, t),
+            PdfLine::Body(t) => ("/F1", BODY_FONT_SIZE, t),
+        };
+        y -= line_height(line);
+
+        if current_font != Some(font) {
+            stream.push_str(&format!("{} {} Tf\n", font, size));
+            current_font = Some(font);
+        }
+        stream.push_str(&format!("1 0 0 1 {} {} Tm\n", MARGIN, y));
+        stream.push_str(&format!("({}) Tj\n", escape_pdf_string(text)));
+    }
+
+    stream.push_str("ET");
+    stream
+}
+
+/// Assemble a list of already-paginated lines into a complete PDF file
+fn render_pages(pages: Vec<Vec<PdfLine>>) -> Vec<u8> {
+    const CATALOG_ID: usize = 1;
+    const PAGES_ID: usize = 2;
+    const FONT_REGULAR_ID: usize = 3;
+    const FONT_BOLD_ID: usize = 4;
+
+    let mut next_id = 5;
+    let mut content_ids = Vec::with_capacity(pages.len());
+    let mut page_ids = Vec::with_capacity(pages.len());
+    for _ in &pages {
+        content_ids.push(next_id);
+        next_id += 1;
+        page_ids.push(next_id);
+        next_id += 1;
+    }
+
+    let mut objects: Vec<Vec<u8>> = vec![Vec::new(); next_id];
+
+    objects[CATALOG_ID] = format!("<< /Type /Catalog /Pages {} 0 R >>", PAGES_ID).into_bytes();
+
+    let kids = page_ids.iter().map(|id| format!("{} 0 R", id)).collect::<Vec<_>>().join(" ");
+    objects[PAGES_ID] =
+        format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids, page_ids.len()).into_bytes();
+
+    objects[FONT_REGULAR_ID] = b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_vec();
+    objects[FONT_BOLD_ID] = b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold >>".to_vec();
+
+    for (index, page_lines) in pages.iter().enumerate() {
+        let content = render_content_stream(page_lines);
+        objects[content_ids[index]] =
+            format!("<< /Length {} >>\nstream\n{}\nendstream", content.len(), content).into_bytes();
+
+        objects[page_ids[index]] = format!(
+            "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 {} 0 R /F2 {} 0 R >> >> /Contents {} 0 R >>",
+            PAGES_ID, PAGE_WIDTH, PAGE_HEIGHT, FONT_REGULAR_ID, FONT_BOLD_ID, content_ids[index]
+        )
+        .into_bytes();
+    }
+
+    let mut out = Vec::new();
+    out.extend_from_slice(b"%PDF-1.4\n");
+
+    let mut offsets = vec![0usize; objects.len()];
+    for (id, object) in objects.iter().enumerate().skip(1) {
+        offsets[id] = out.len();
+        out.extend_from_slice(format!("{} 0 obj\n", id).as_bytes());
+        out.extend_from_slice(object);
+        out.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref_offset = out.len();
+    out.extend_from_slice(format!("xref\n0 {}\n", objects.len()).as_bytes());
+    out.extend_from_slice(b"0000000000 65535 f \n");
+    for offset in offsets.iter().skip(1) {
+        out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+
+    out.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root {} 0 R >>\nstartxref\n{}\n%%EOF",
+            objects.len(),
+            CATALOG_ID,
+            xref_offset
+        )
+        .as_bytes(),
+    );
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn sample_conversation() -> Conversation {
+        Conversation {
+            id: Some(1),
+            uuid: uuid::Uuid::new_v4(),
+            title: "Test Conversation".to_string(),
+            persona_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            archived: false,
+            metadata: None,
+            last_opened_at: None,
+            frozen: false,
+        }
+    }
+
+    fn sample_message(id: i64, role: MessageRole, content: &str) -> Message {
+        Message {
+            id: Some(id),
+            conversation_id: 1,
+            role,
+            content: content.to_string(),
+            metadata: None,
+            created_at: Utc::now(),
+            tokens_used: None,
+            model_used: None,
+            edited_at: None,
+            tool_call_id: None,
+        }
+    }
+
+    #[test]
+    fn produces_a_well_formed_pdf_header_and_trailer() {
+        let conversation = sample_conversation();
+        let messages = vec![sample_message(1, MessageRole::User, "Hello there")];
+        let pdf = conversation_to_pdf(&conversation, &messages, None, &std::collections::HashMap::new());
+
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        let tail = String::from_utf8_lossy(&pdf[pdf.len().saturating_sub(64)..]);
+        assert!(tail.contains("%%EOF"));
+    }
+
+    #[test]
+    fn wraps_long_paragraphs_onto_multiple_lines() {
+        let long_word_salad = "word ".repeat(200);
+        let wrapped = wrap_text(&long_word_salad, 40);
+        assert!(wrapped.len() > 1);
+        assert!(wrapped.iter().all(|line| line.len() <= 40));
+    }
+
+    #[test]
+    fn sanitizes_non_ascii_content_instead_of_corrupting_the_stream() {
+        let conversation = sample_conversation();
+        let messages = vec![sample_message(1, MessageRole::Assistant, "héllo 世界")];
+        let pdf = conversation_to_pdf(&conversation, &messages, None, &std::collections::HashMap::new());
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+    }
+
+    #[test]
+    fn many_messages_paginate_into_multiple_page_objects() {
+        let conversation = sample_conversation();
+        let messages: Vec<Message> = (0..200)
+            .map(|i| sample_message(i, MessageRole::User, &"line of conversation text ".repeat(20)))
+            .collect();
+        let pdf = conversation_to_pdf(&conversation, &messages, None, &std::collections::HashMap::new());
+        let text = String::from_utf8_lossy(&pdf);
+        // "/Type /Page " (trailing space) only matches individual page objects, not "/Type /Pages"
+        assert!(text.matches("/Type /Page ").count() >= 2);
+    }
+}
@@ -3,7 +3,8 @@
 //! This module provides cross-platform abstractions for system-specific functionality,
 //! ensuring the Forbidden Library works seamlessly on Windows, macOS, and Linux.
 
-use std::path::PathBuf;
+use crate::errors::AppResult;
+use std::path::{Path, PathBuf};
 
 #[cfg(target_os = "windows")]
 pub mod windows;
@@ -44,6 +45,141 @@ pub fn normalize_path(path: &str) -> PathBuf {
     PathBuf::from(path)
 }
 
+/// Get the current process's resident memory usage in kilobytes, where available
+///
+/// Returns `None` on platforms without a lightweight way to query this (currently macOS
+/// and Windows); callers should treat that as "unknown", not zero.
+pub fn get_process_memory_kb() -> Option<u64> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::get_process_memory_kb()
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        unix::get_process_memory_kb()
+    }
+}
+
+/// Free disk space, in bytes, on the filesystem/volume containing `path`, in a cross-platform way
+///
+/// `None` if it can't be determined (e.g. the path doesn't exist yet, or the platform helper
+/// failed) - callers should treat that as "unknown", the same convention as
+/// `get_process_memory_kb`.
+pub fn available_disk_space_bytes(path: &Path) -> Option<u64> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::available_disk_space_bytes(path)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        unix::available_disk_space_bytes(path)
+    }
+}
+
+/// Fail fast with [`crate::errors::AppError::InsufficientDiskSpace`] if the filesystem/volume
+/// containing `path` doesn't have `required_bytes` free, so large writes (imports, backups,
+/// attachment saves, model downloads) can refuse up front instead of corrupting state partway
+/// through.
+///
+/// A `None` from `available_disk_space_bytes` (free space couldn't be determined) is treated as
+/// "enough" rather than blocking the operation.
+pub fn ensure_disk_space(path: &Path, required_bytes: u64) -> AppResult<()> {
+    if let Some(available_bytes) = available_disk_space_bytes(path) {
+        if available_bytes < required_bytes {
+            return Err(crate::errors::AppError::insufficient_disk_space(
+                path.to_string_lossy(),
+                required_bytes,
+                available_bytes,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Best-effort desktop notification, used only as a fallback when Tauri's own notification API
+/// fails to show one (see [`crate::commands::show_notification`])
+pub fn show_notification_fallback(title: &str, body: &str) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        windows::show_notification_fallback(title, body).is_ok()
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        unix::show_notification_fallback(title, body).is_ok()
+    }
+}
+
+/// Taskbar progress indicator state, mirroring Windows Shell's `TBPFLAG`. Shared across platforms
+/// even though only Windows currently renders a visible indicator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskbarProgressState {
+    /// Clear the progress indicator entirely
+    NoProgress,
+    /// A marching, not-yet-quantified indicator (e.g. while a total isn't known yet)
+    Indeterminate,
+    /// A green determinate bar showing `completed` out of `total`
+    Normal,
+    /// A red bar, for a failed operation
+    Error,
+    /// A yellow bar, for a paused operation
+    Paused,
+}
+
+/// Update the taskbar icon's progress indicator for a long-running operation (export, import,
+/// Ollama model pull), via [`crate::commands::platform_set_progress`].
+///
+/// No-op on platforms without a taskbar progress API (macOS, Linux).
+pub fn set_taskbar_progress(state: TaskbarProgressState, completed: u64, total: u64) -> std::io::Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::set_taskbar_progress(state, completed, total)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (state, completed, total);
+        Ok(())
+    }
+}
+
+/// Register `path` in the OS's jump list / recent-documents list, so it can be reopened from the
+/// taskbar icon without hunting for it again. Intended for files the export subsystem just wrote.
+///
+/// No-op on platforms without this concept (macOS's Dock has an equivalent recent-items list via
+/// `NSDocumentController`, not implemented here; Linux desktop environments vary too much to
+/// target generically).
+pub fn add_to_jump_list_recent(path: &Path) -> std::io::Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::add_to_jump_list_recent(path)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = path;
+        Ok(())
+    }
+}
+
+/// Whether the OS desktop theme is currently dark, in a cross-platform way
+///
+/// Polled by [`crate::theme_monitor::ThemeMonitor`] to emit `theme-changed` when it flips, and
+/// checked directly by [`crate::commands::is_dark_mode`] for a one-off read.
+pub fn is_dark_mode() -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        windows::is_dark_mode()
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        unix::is_dark_mode()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -65,4 +201,21 @@ mod tests {
         let path = normalize_path("some/path/to/file.txt");
         assert!(path.to_string_lossy().contains("file.txt"));
     }
+
+    #[test]
+    fn test_available_disk_space_on_temp_dir() {
+        // Should resolve to a real, nonzero number on any machine running this test suite.
+        let space = available_disk_space_bytes(&get_temp_dir());
+        assert!(space.is_none() || space.unwrap() > 0);
+    }
+
+    #[test]
+    fn test_ensure_disk_space_rejects_unreasonable_requirement() {
+        let result = ensure_disk_space(&get_temp_dir(), u64::MAX);
+        // Only fails if free space could be determined - on a CI box with no `df`/PowerShell,
+        // "unknown" is treated as "enough" per `ensure_disk_space`'s documented contract.
+        if available_disk_space_bytes(&get_temp_dir()).is_some() {
+            assert!(result.is_err());
+        }
+    }
 }
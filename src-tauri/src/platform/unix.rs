@@ -60,6 +60,88 @@ pub fn is_root() -> bool {
         .unwrap_or(false)
 }
 
+/// Get the current process's resident set size in kilobytes
+///
+/// Reads `VmRSS` from `/proc/self/status` on Linux. Not available on macOS, which has no
+/// equivalent procfs entry without linking platform-specific APIs.
+#[cfg(target_os = "linux")]
+pub fn get_process_memory_kb() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status.lines().find_map(|line| {
+        line.strip_prefix("VmRSS:")
+            .and_then(|rest| rest.trim().split_whitespace().next())
+            .and_then(|kb| kb.parse().ok())
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_process_memory_kb() -> Option<u64> {
+    None
+}
+
+/// Detect whether the desktop is currently using a dark color scheme
+///
+/// Reads `AppleInterfaceStyle` via `defaults read -g`, which macOS only sets while dark mode is
+/// on - `defaults read` exits non-zero when the key is absent (light mode), which `run_shell_command`
+/// surfaces as an `Err` here.
+#[cfg(target_os = "macos")]
+pub fn is_dark_mode() -> bool {
+    run_shell_command("defaults read -g AppleInterfaceStyle")
+        .map(|output| output.trim().eq_ignore_ascii_case("dark"))
+        .unwrap_or(false)
+}
+
+/// Detect whether the desktop is currently using a dark color scheme
+///
+/// Reads GNOME's `color-scheme` setting via `gsettings`, which every major GNOME-based desktop
+/// (GNOME, Pop!_OS, Ubuntu) respects; other desktop environments have no single standard way to
+/// query this without adding a dependency, so they fall back to "not dark" here.
+#[cfg(not(target_os = "macos"))]
+pub fn is_dark_mode() -> bool {
+    run_shell_command("gsettings get org.gnome.desktop.interface color-scheme")
+        .map(|output| output.to_lowercase().contains("dark"))
+        .unwrap_or(false)
+}
+
+/// Free disk space, in bytes, on the filesystem containing `path`
+///
+/// Shells out to `df -Pk` rather than wrapping `statvfs` directly, since this tree has no `libc`
+/// dependency to bind it and `df` is available on every Unix the app targets.
+pub fn available_disk_space_bytes(path: &std::path::Path) -> Option<u64> {
+    let output = std::process::Command::new("df")
+        .arg("-Pk")
+        .arg(path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let data_line = stdout.lines().nth(1)?;
+    let available_kb: u64 = data_line.split_whitespace().nth(3)?.parse().ok()?;
+    Some(available_kb.saturating_mul(1024))
+}
+
+/// Show a desktop notification via `notify-send`
+///
+/// Fallback for [`crate::commands::show_notification`], used only when Tauri's own
+/// (`notify-rust`-backed) notification API fails to show one - e.g. no notification daemon
+/// running on a minimal Linux desktop.
+pub fn show_notification_fallback(title: &str, body: &str) -> std::io::Result<()> {
+    use std::process::Command;
+
+    let status = Command::new("notify-send").arg(title).arg(body).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "notify-send exited with a non-zero status",
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,6 +68,168 @@ pub fn is_admin() -> bool {
         .unwrap_or(false)
 }
 
+/// Get the current process's working set size in kilobytes
+///
+/// TODO: query `GetProcessMemoryInfo` via the Windows API; no lightweight procfs-style
+/// equivalent exists on Windows without adding a dependency.
+pub fn get_process_memory_kb() -> Option<u64> {
+    None
+}
+
+/// Free disk space, in bytes, on the volume containing `path`
+///
+/// Shells out to PowerShell's `Get-PSDrive` rather than calling `GetDiskFreeSpaceExW` directly,
+/// since this tree has no Windows API binding crate to call it through.
+pub fn available_disk_space_bytes(path: &std::path::Path) -> Option<u64> {
+    let path_str = path.to_str()?;
+    let script = format!(
+        "(Get-PSDrive -Name (Split-Path -Path '{}' -Qualifier).TrimEnd(':')).Free",
+        path_str.replace('\'', "''")
+    );
+    let output = run_powershell_command(&script).ok()?;
+    output.trim().parse().ok()
+}
+
+/// Show a desktop notification via a PowerShell balloon tip
+///
+/// Fallback for [`crate::commands::show_notification`], used only when Tauri's own
+/// (`notify-rust`-backed) notification API fails to show one. Uses `NotifyIcon` rather than the
+/// modern toast API since that requires an AppUserModelID registration this app doesn't perform.
+pub fn show_notification_fallback(title: &str, body: &str) -> std::io::Result<()> {
+    let script = format!(
+        "Add-Type -AssemblyName System.Windows.Forms; \
+         $icon = New-Object System.Windows.Forms.NotifyIcon; \
+         $icon.Icon = [System.Drawing.SystemIcons]::Information; \
+         $icon.Visible = $true; \
+         $icon.ShowBalloonTip(5000, '{}', '{}', [System.Windows.Forms.ToolTipIcon]::Info); \
+         Start-Sleep -Seconds 6; \
+         $icon.Dispose()",
+        title.replace('\'', "''"),
+        body.replace('\'', "''"),
+    );
+    run_powershell_command(&script)?;
+    Ok(())
+}
+
+/// Detect whether the desktop is currently using a dark color scheme
+///
+/// Reads the `AppsUseLightTheme` registry value Windows sets when the user switches app theme in
+/// Settings > Personalization > Colors, via PowerShell rather than a registry-binding crate,
+/// consistent with the rest of this module (see [`available_disk_space_bytes`]).
+pub fn is_dark_mode() -> bool {
+    run_powershell_command(
+        "(Get-ItemPropertyValue -Path 'HKCU:\\Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize' -Name AppsUseLightTheme)",
+    )
+    .map(|output| output.trim() == "0")
+    .unwrap_or(false)
+}
+
+impl super::TaskbarProgressState {
+    /// The `TBPFLAG` value `ITaskbarList3::SetProgressState` expects
+    fn tbpflag(self) -> u32 {
+        match self {
+            super::TaskbarProgressState::NoProgress => 0x0,
+            super::TaskbarProgressState::Indeterminate => 0x1,
+            super::TaskbarProgressState::Normal => 0x2,
+            super::TaskbarProgressState::Error => 0x4,
+            super::TaskbarProgressState::Paused => 0x8,
+        }
+    }
+}
+
+/// C# COM interop shim for `ITaskbarList3`, embedded into the PowerShell script run by
+/// `set_taskbar_progress`. Declares every method up through `SetProgressState` in their real
+/// vtable order (`HrInit`, `AddTab`, `DeleteTab`, `ActivateTab`, `SetActiveAlt`,
+/// `MarkFullscreenWindow`, `SetProgressValue`, `SetProgressState`) even though only the last two
+/// are called - `[ComImport]` interfaces dispatch by vtable slot, so skipping the unused leading
+/// methods would silently call the wrong slot.
+const TASKBAR_LIST_COM_SHIM: &str = r#"
+using System;
+using System.Runtime.InteropServices;
+
+namespace ForbiddenLibraryTaskbar {
+    [ComImport, Guid("ea1afb91-9e28-4b86-90e9-9e9f8a5eefaf"), InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    interface ITaskbarList3 {
+        void HrInit();
+        void AddTab(IntPtr hwnd);
+        void DeleteTab(IntPtr hwnd);
+        void ActivateTab(IntPtr hwnd);
+        void SetActiveAlt(IntPtr hwnd);
+        void MarkFullscreenWindow(IntPtr hwnd, [MarshalAs(UnmanagedType.Bool)] bool fFullscreen);
+        void SetProgressValue(IntPtr hwnd, ulong ullCompleted, ulong ullTotal);
+        void SetProgressState(IntPtr hwnd, uint tbpFlags);
+    }
+
+    [ComImport, Guid("56fdf344-fd6d-11d0-958a-006097c9a090")]
+    class TaskbarInstance { }
+
+    public class Api {
+        public static void SetProgress(IntPtr hwnd, ulong completed, ulong total, uint tbpFlags) {
+            var taskbar = (ITaskbarList3)new TaskbarInstance();
+            taskbar.HrInit();
+            taskbar.SetProgressValue(hwnd, completed, total);
+            taskbar.SetProgressState(hwnd, tbpFlags);
+        }
+    }
+}
+"#;
+
+/// Set the taskbar icon's progress overlay via `ITaskbarList3`, for long operations (export,
+/// import, model pull) that want a visible progress indicator even when the window isn't
+/// focused.
+///
+/// Locates the app's main window by this process's ID rather than threading an HWND through from
+/// Tauri, consistent with the rest of this module shelling out to PowerShell instead of binding
+/// the Windows API directly (see [`available_disk_space_bytes`]).
+pub fn set_taskbar_progress(
+    state: super::TaskbarProgressState,
+    completed: u64,
+    total: u64,
+) -> std::io::Result<()> {
+    let pid = std::process::id();
+    // ITaskbarList3::SetProgressValue divides by zero if ullTotal is 0; 1 keeps a freshly started
+    // indeterminate-turned-normal transition from erroring out before the real total is known.
+    let total = total.max(1);
+    let script = format!(
+        "Add-Type -TypeDefinition @'{shim}'@ -Language CSharp; \
+         $hwnd = (Get-Process -Id {pid}).MainWindowHandle; \
+         [ForbiddenLibraryTaskbar.Api]::SetProgress($hwnd, {completed}, {total}, {flags})",
+        shim = TASKBAR_LIST_COM_SHIM,
+        pid = pid,
+        completed = completed,
+        total = total,
+        flags = state.tbpflag(),
+    );
+    run_powershell_command(&script)?;
+    Ok(())
+}
+
+/// Register `path` in Windows' shell-wide recent-documents list, which Explorer surfaces in the
+/// app's jump list "Recent" category next to the taskbar icon's right-click menu.
+///
+/// Uses `SHAddToRecentDocs` rather than building a custom `ICustomDestinationList` category,
+/// since that needs an AppUserModelID registration this app doesn't perform - this is the same
+/// mechanism most apps rely on to populate their jump list's "Recent" section for files they've
+/// opened or saved.
+pub fn add_to_jump_list_recent(path: &std::path::Path) -> std::io::Result<()> {
+    const SHARD_PATHW: u32 = 0x3;
+    let path_str = path.to_string_lossy().replace('\'', "''");
+    let script = format!(
+        "Add-Type -TypeDefinition @'\n\
+         using System.Runtime.InteropServices;\n\
+         public static class ForbiddenLibraryRecentDocs {{\n\
+         \x20   [DllImport(\"shell32.dll\", CharSet = CharSet.Unicode)]\n\
+         \x20   public static extern void SHAddToRecentDocs(uint uFlags, string pv);\n\
+         }}\n\
+         '@; \
+         [ForbiddenLibraryRecentDocs]::SHAddToRecentDocs({flags}, '{path}')",
+        flags = SHARD_PATHW,
+        path = path_str,
+    );
+    run_powershell_command(&script)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
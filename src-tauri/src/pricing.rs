@@ -0,0 +1,99 @@
+//! Per-token pricing for usage-cost estimation
+//!
+//! Provider prices change often and vary by model, so the table lives in a user-editable
+//! `pricing.json` in the app data directory rather than hardcoded - the same pattern
+//! [`crate::provider_registry`] uses for `custom_providers.json`. A small set of defaults ships
+//! bundled so [`crate::services::UsageAnalyticsService::record_usage`] can estimate cost out of
+//! the box; entries in the user file override the bundled default for the same
+//! (provider, model) pair, or add a new one.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const DEFAULT_PRICING_JSON: &str = include_str!("default_pricing.json");
+
+/// USD price per 1,000 prompt/completion tokens for one (provider, model) pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub provider: String,
+    pub model: String,
+    pub prompt_cost_per_1k: f64,
+    pub completion_cost_per_1k: f64,
+}
+
+/// Load the effective price table: bundled defaults, overridden/extended by `user_pricing_path`
+/// (e.g. `<app_data_dir>/pricing.json`) if it exists and parses
+pub fn load_price_table(user_pricing_path: &Path) -> Vec<ModelPrice> {
+    let mut table: Vec<ModelPrice> =
+        serde_json::from_str(DEFAULT_PRICING_JSON).expect("bundled default_pricing.json must be valid");
+
+    if let Ok(contents) = std::fs::read_to_string(user_pricing_path) {
+        if let Ok(overrides) = serde_json::from_str::<Vec<ModelPrice>>(&contents) {
+            for over in overrides {
+                match table
+                    .iter_mut()
+                    .find(|p| p.provider.eq_ignore_ascii_case(&over.provider) && p.model == over.model)
+                {
+                    Some(existing) => *existing = over,
+                    None => table.push(over),
+                }
+            }
+        }
+    }
+
+    table
+}
+
+/// Estimate USD cost for a request against `table`, or `None` if no price is known for this
+/// (provider, model) pair
+pub fn estimate_cost(
+    table: &[ModelPrice],
+    provider: &str,
+    model: &str,
+    prompt_tokens: i64,
+    completion_tokens: i64,
+) -> Option<f64> {
+    let price = table
+        .iter()
+        .find(|p| p.provider.eq_ignore_ascii_case(provider) && p.model == model)?;
+    Some(
+        (prompt_tokens as f64 / 1000.0) * price.prompt_cost_per_1k
+            + (completion_tokens as f64 / 1000.0) * price.completion_cost_per_1k,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bundled_defaults_cover_known_model() {
+        let temp_dir = TempDir::new().unwrap();
+        let table = load_price_table(&temp_dir.path().join("pricing.json"));
+        let cost = estimate_cost(&table, "openai", "gpt-4o", 1000, 1000);
+        assert!(cost.is_some());
+    }
+
+    #[test]
+    fn test_unknown_model_has_no_price() {
+        let temp_dir = TempDir::new().unwrap();
+        let table = load_price_table(&temp_dir.path().join("pricing.json"));
+        assert!(estimate_cost(&table, "openai", "not-a-real-model", 1000, 1000).is_none());
+    }
+
+    #[test]
+    fn test_user_file_overrides_bundled_price() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("pricing.json");
+        std::fs::write(
+            &path,
+            r#"[{"provider":"openai","model":"gpt-4o","prompt_cost_per_1k":1.0,"completion_cost_per_1k":2.0}]"#,
+        )
+        .unwrap();
+
+        let table = load_price_table(&path);
+        let cost = estimate_cost(&table, "openai", "gpt-4o", 1000, 1000).unwrap();
+        assert_eq!(cost, 3.0);
+    }
+}
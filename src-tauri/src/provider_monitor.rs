@@ -0,0 +1,135 @@
+//! Background poller for local AI provider availability (Ollama, LM Studio)
+//!
+//! `check_ai_provider_availability` checking a local provider synchronously means the UI blocks
+//! on an HTTP round trip (and, if the provider isn't running, its timeout) every time it's
+//! called. `ProviderMonitor` instead polls the known local providers on an interval, caches the
+//! result, and emits `provider-status-changed` only when something actually changes, so
+//! `check_ai_provider_availability` can usually answer from the cache instead. Remote providers
+//! (OpenAI, Anthropic, etc.) need an API key supplied per call and often cost money to ping, so
+//! they're never polled here - they're always checked on demand, same as before.
+
+use crate::ai_providers::AIProvider;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::Manager;
+
+/// How often the monitor re-checks local provider availability
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// `provider_type` identifiers the monitor polls; matches what `create_ai_provider` accepts
+const LOCAL_PROVIDER_TYPES: &[&str] = &["ollama", "lm_studio"];
+
+/// Cached availability snapshot for one local provider
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderStatus {
+    pub available: bool,
+    pub models: Vec<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Emitted when a polled provider's availability or model list changes from its last known state
+#[derive(Debug, Clone, Serialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct ProviderStatusChangedEvent {
+    pub provider_type: String,
+    pub available: bool,
+    pub models: Vec<String>,
+}
+
+fn local_provider(provider_type: &str) -> Option<AIProvider> {
+    match provider_type {
+        "ollama" => Some(AIProvider::ollama(None)),
+        "lm_studio" => Some(AIProvider::lm_studio(None)),
+        _ => None,
+    }
+}
+
+/// Polls the providers named in [`LOCAL_PROVIDER_TYPES`] on [`POLL_INTERVAL`] and caches their
+/// availability and model lists, so commands can read a recent answer instead of making a fresh
+/// HTTP request on every call
+pub struct ProviderMonitor {
+    cache: Arc<Mutex<HashMap<String, ProviderStatus>>>,
+}
+
+impl ProviderMonitor {
+    pub fn new() -> Self {
+        Self { cache: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    /// The most recently cached status for `provider_type`, if it's been polled at least once
+    pub fn cached(&self, provider_type: &str) -> Option<ProviderStatus> {
+        self.cache.lock().unwrap().get(provider_type).cloned()
+    }
+
+    /// Check `provider_type` right now, bypassing the poll interval, and update the cache
+    ///
+    /// Returns `None` if `provider_type` isn't one of [`LOCAL_PROVIDER_TYPES`].
+    pub async fn refresh(&self, provider_type: &str, app_handle: Option<&tauri::AppHandle>) -> Option<ProviderStatus> {
+        let provider = local_provider(provider_type)?;
+        Some(self.check_and_cache(provider_type, &provider, app_handle).await)
+    }
+
+    /// Spawn the background poll loop as a tokio task. Returns immediately; runs for the
+    /// lifetime of the process.
+    pub fn spawn(self: Arc<Self>, app_handle: tauri::AppHandle) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+                for provider_type in LOCAL_PROVIDER_TYPES {
+                    if let Some(provider) = local_provider(provider_type) {
+                        self.check_and_cache(provider_type, &provider, Some(&app_handle)).await;
+                    }
+                }
+            }
+        });
+    }
+
+    async fn check_and_cache(
+        &self,
+        provider_type: &str,
+        provider: &AIProvider,
+        app_handle: Option<&tauri::AppHandle>,
+    ) -> ProviderStatus {
+        let available = provider.check_availability().await.unwrap_or(false);
+        let models = if available {
+            provider.list_models().await.unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let status = ProviderStatus { available, models, checked_at: Utc::now() };
+
+        let changed = {
+            let mut cache = self.cache.lock().unwrap();
+            let changed = cache.get(provider_type).map_or(true, |previous| {
+                previous.available != status.available || previous.models != status.models
+            });
+            cache.insert(provider_type.to_string(), status.clone());
+            changed
+        };
+
+        if changed {
+            if let Some(app_handle) = app_handle {
+                let _ = app_handle.emit_all(
+                    "provider-status-changed",
+                    ProviderStatusChangedEvent {
+                        provider_type: provider_type.to_string(),
+                        available: status.available,
+                        models: status.models.clone(),
+                    },
+                );
+            }
+        }
+
+        status
+    }
+}
+
+impl Default for ProviderMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,104 @@
+//! Declarative custom AI provider definitions
+//!
+//! Niche OpenAI-compatible gateways (self-hosted proxies, smaller inference providers, etc.)
+//! don't need a hardcoded [`crate::ai_providers::AIProvider`] variant - they can be described by
+//! a small JSON document and loaded at startup. This keeps `ai_providers.rs` focused on the
+//! providers with genuinely different request/response shapes, while letting users add another
+//! OpenAI-compatible gateway without waiting for a code change.
+
+use crate::errors::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// How a custom provider expects its API key to be presented
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AuthStyle {
+    /// `Authorization: Bearer <api_key>` (the OpenAI convention)
+    Bearer,
+    /// `<header>: <api_key>` (e.g. Anthropic's `x-api-key`)
+    Header(String),
+    /// No API key is sent
+    None,
+}
+
+/// A single user-declared OpenAI-compatible provider, as read from `custom_providers.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomProviderDefinition {
+    /// Unique name used to select this provider, e.g. "together_ai"
+    pub name: String,
+    /// Base URL; `/v1/chat/completions` is appended when sending a request
+    pub base_url: String,
+    pub auth_style: AuthStyle,
+    /// Headers sent on every request in addition to the auth header
+    #[serde(default)]
+    pub extra_headers: Vec<(String, String)>,
+    /// Endpoint to list available models, if the gateway exposes one (relative to `base_url`
+    /// unless it starts with `http`)
+    #[serde(default)]
+    pub models_endpoint: Option<String>,
+    /// Set when the gateway doesn't accept a `stream` field in the request body
+    #[serde(default)]
+    pub omit_stream_field: bool,
+}
+
+/// Load custom provider definitions from a JSON file (a top-level array of
+/// [`CustomProviderDefinition`]). Returns an empty list if the file doesn't exist yet -
+/// no custom providers is the normal state for most installations.
+pub fn load_custom_providers(path: &Path) -> AppResult<Vec<CustomProviderDefinition>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| AppError::io(format!("Failed to read custom providers file: {}", e)))?;
+
+    serde_json::from_str(&contents)
+        .map_err(|e| AppError::validation(format!("Invalid custom providers file: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_missing_file_returns_empty_list() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("custom_providers.json");
+
+        let providers = load_custom_providers(&path).unwrap();
+        assert!(providers.is_empty());
+    }
+
+    #[test]
+    fn test_loads_declared_providers() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("custom_providers.json");
+        std::fs::write(
+            &path,
+            r#"[
+                {
+                    "name": "together_ai",
+                    "base_url": "https://api.together.xyz",
+                    "auth_style": "Bearer",
+                    "models_endpoint": "/v1/models"
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let providers = load_custom_providers(&path).unwrap();
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0].name, "together_ai");
+        assert!(matches!(providers[0].auth_style, AuthStyle::Bearer));
+    }
+
+    #[test]
+    fn test_malformed_file_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("custom_providers.json");
+        std::fs::write(&path, "not valid json").unwrap();
+
+        assert!(load_custom_providers(&path).is_err());
+    }
+}
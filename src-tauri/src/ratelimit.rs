@@ -0,0 +1,228 @@
+//! Token-bucket rate limiting for outbound AI provider requests
+//!
+//! [`crate::models::RateLimits`] is stored per provider in `api_configs.rate_limits`, but
+//! `AIProvider::send_request_with_retry` has no database handle to read it from and is called
+//! from many places across the command layer - threading a fresh lookup through every call site
+//! would touch far more than this feature is worth. Instead, [`ApiService`](crate::services::ApiService)
+//! pushes a provider's limits into this module's registry whenever its config is stored or read,
+//! and `send_request_with_retry` checks the registry by provider key just before dispatching.
+//! Providers with no entry (including local ones like Ollama/LM Studio, which have no
+//! `api_configs` row at all) are unlimited.
+
+use crate::errors::AppError;
+use crate::models::RateLimits;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+fn registry() -> &'static Mutex<HashMap<String, ProviderBuckets>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<String, ProviderBuckets>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// One [`TokenBucket`] per limit kind a [`RateLimits`] can set; any field left `None` there has
+/// no corresponding bucket and is never checked
+struct ProviderBuckets {
+    requests_per_minute: Option<TokenBucket>,
+    tokens_per_minute: Option<TokenBucket>,
+    requests_per_day: Option<TokenBucket>,
+    tokens_per_day: Option<TokenBucket>,
+}
+
+impl ProviderBuckets {
+    fn from_limits(limits: &RateLimits) -> Self {
+        Self {
+            requests_per_minute: limits
+                .requests_per_minute
+                .map(|n| TokenBucket::new(n as f64, Duration::from_secs(60))),
+            tokens_per_minute: limits
+                .tokens_per_minute
+                .map(|n| TokenBucket::new(n as f64, Duration::from_secs(60))),
+            requests_per_day: limits
+                .requests_per_day
+                .map(|n| TokenBucket::new(n as f64, Duration::from_secs(24 * 60 * 60))),
+            tokens_per_day: limits
+                .tokens_per_day
+                .map(|n| TokenBucket::new(n as f64, Duration::from_secs(24 * 60 * 60))),
+        }
+    }
+
+    /// Each configured bucket paired with how much it should be debited for one request costing
+    /// `token_amount` tokens - 1 unit for the two request-count buckets, `token_amount` for the
+    /// two token-count buckets.
+    fn demands(&mut self, token_amount: f64) -> [(&mut Option<TokenBucket>, f64); 4] {
+        [
+            (&mut self.requests_per_minute, 1.0),
+            (&mut self.requests_per_day, 1.0),
+            (&mut self.tokens_per_minute, token_amount),
+            (&mut self.tokens_per_day, token_amount),
+        ]
+    }
+}
+
+/// Refills continuously towards `capacity` at a constant rate, rather than resetting to full at
+/// fixed interval boundaries - that keeps a burst right at the top of one window from stacking
+/// with a burst right at the start of the next.
+struct TokenBucket {
+    capacity: f64,
+    available: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: f64, period: Duration) -> Self {
+        Self {
+            capacity,
+            available: capacity,
+            refill_per_sec: capacity / period.as_secs_f64(),
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.available = (self.available + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// How long until `amount` would fit, or `None` if it already does. Does not consume.
+    fn wait_for(&mut self, amount: f64) -> Option<Duration> {
+        self.refill();
+        if self.available >= amount {
+            None
+        } else {
+            Some(Duration::from_secs_f64(
+                (amount - self.available) / self.refill_per_sec,
+            ))
+        }
+    }
+
+    fn consume(&mut self, amount: f64) {
+        self.available -= amount;
+    }
+}
+
+/// Replace `provider_key`'s configured limits, or clear them if `limits` is `None`. Called by
+/// [`crate::services::ApiService`] whenever a provider's stored config is written or read, so the
+/// registry stays in sync without this module needing its own database access.
+pub fn configure(provider_key: &str, limits: Option<&RateLimits>) {
+    let mut registry = registry().lock().unwrap();
+    match limits {
+        Some(limits) => {
+            registry.insert(
+                provider_key.to_string(),
+                ProviderBuckets::from_limits(limits),
+            );
+        }
+        None => {
+            registry.remove(provider_key);
+        }
+    }
+}
+
+/// Check `provider_key`'s configured limits against one request estimated to cost
+/// `estimated_tokens` tokens, consuming budget from every bucket it fits under.
+///
+/// A request is only allowed if it fits under *every* configured bucket at once - buckets are
+/// peeked first and nothing is consumed unless all of them have room, so a rejected request
+/// doesn't partially drain the limits it did satisfy. Returns
+/// [`AppError::RateLimited`] with the longest wait among the buckets it didn't fit under.
+pub fn check_and_consume(provider_key: &str, estimated_tokens: u32) -> Result<(), AppError> {
+    let mut registry = registry().lock().unwrap();
+    let Some(buckets) = registry.get_mut(provider_key) else {
+        return Ok(());
+    };
+
+    let token_amount = estimated_tokens as f64;
+
+    let mut longest_wait: Option<Duration> = None;
+    for (bucket, amount) in buckets.demands(token_amount) {
+        let Some(bucket) = bucket else { continue };
+        if let Some(wait) = bucket.wait_for(amount) {
+            longest_wait = Some(longest_wait.map_or(wait, |w| w.max(wait)));
+        }
+    }
+
+    if let Some(wait) = longest_wait {
+        return Err(AppError::rate_limited(wait.as_secs().max(1)));
+    }
+
+    for (bucket, amount) in buckets.demands(token_amount) {
+        let Some(bucket) = bucket else { continue };
+        bucket.consume(amount);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(requests_per_minute: i32) -> RateLimits {
+        RateLimits {
+            requests_per_minute: Some(requests_per_minute),
+            ..RateLimits::default()
+        }
+    }
+
+    #[test]
+    fn test_unconfigured_provider_is_unlimited() {
+        assert!(check_and_consume("no-such-provider", 1_000_000).is_ok());
+    }
+
+    #[test]
+    fn test_exhausted_bucket_is_rejected_with_retry_after() {
+        configure("test-exhausted", Some(&limits(1)));
+        assert!(check_and_consume("test-exhausted", 10).is_ok());
+        let err = check_and_consume("test-exhausted", 10).unwrap_err();
+        assert!(matches!(err, AppError::RateLimited { retry_after } if retry_after > 0));
+    }
+
+    #[test]
+    fn test_rejected_request_does_not_consume_other_satisfied_buckets() {
+        let key = "test-partial";
+        {
+            let mut registry = registry().lock().unwrap();
+            registry.insert(
+                key.to_string(),
+                ProviderBuckets {
+                    requests_per_minute: Some(TokenBucket::new(1.0, Duration::from_secs(60))),
+                    tokens_per_minute: None,
+                    requests_per_day: None,
+                    tokens_per_day: Some(TokenBucket::new(
+                        1_000_000.0,
+                        Duration::from_secs(24 * 60 * 60),
+                    )),
+                },
+            );
+        }
+
+        assert!(check_and_consume(key, 10).is_ok());
+        // requests_per_minute is now exhausted, so this must be rejected even though
+        // tokens_per_day still has plenty of room left.
+        assert!(check_and_consume(key, 10).is_err());
+
+        // The rejected call above must not have consumed a second 10 tokens from
+        // tokens_per_day - only the first, successful call should have.
+        let mut registry = registry().lock().unwrap();
+        let tokens_per_day = registry
+            .get_mut(key)
+            .unwrap()
+            .tokens_per_day
+            .as_mut()
+            .unwrap();
+        tokens_per_day.refill();
+        assert_eq!(tokens_per_day.available, 1_000_000.0 - 10.0);
+    }
+
+    #[test]
+    fn test_clearing_limits_makes_provider_unlimited_again() {
+        configure("test-cleared", Some(&limits(1)));
+        let _ = check_and_consume("test-cleared", 1);
+        configure("test-cleared", None);
+        assert!(check_and_consume("test-cleared", 1_000_000).is_ok());
+    }
+}
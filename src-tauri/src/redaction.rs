@@ -0,0 +1,118 @@
+//! Configurable scrubbing of outbound Sentry telemetry, plus a hard kill switch for it
+//!
+//! Mirrors [`crate::ratelimit`]'s registry pattern: [`crate::services::TelemetryService`] pushes
+//! the current settings into this module's global state whenever they're read or written, and
+//! `main.rs`'s Sentry `before_send`/`before_breadcrumb` hooks consult [`is_local_only_mode`] and
+//! [`redact`] on every event - no database handle needs to be threaded into the Sentry client
+//! itself.
+
+use regex::Regex;
+use std::sync::{OnceLock, RwLock};
+
+fn state() -> &'static RwLock<RedactionState> {
+    static STATE: OnceLock<RwLock<RedactionState>> = OnceLock::new();
+    STATE.get_or_init(|| RwLock::new(RedactionState::default()))
+}
+
+struct RedactionState {
+    local_only_mode: bool,
+    patterns: Vec<Regex>,
+}
+
+impl Default for RedactionState {
+    fn default() -> Self {
+        Self {
+            local_only_mode: false,
+            patterns: default_patterns(),
+        }
+    }
+}
+
+/// Patterns scrubbed from every event regardless of configured custom patterns: common API key
+/// shapes, bearer tokens, and email addresses
+fn default_patterns() -> Vec<Regex> {
+    [
+        r"sk-[A-Za-z0-9_-]{16,}",
+        r"(?i)bearer\s+[A-Za-z0-9._-]{16,}",
+        r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}",
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).expect("built-in redaction pattern is valid regex"))
+    .collect()
+}
+
+/// Replace the configured custom redaction patterns (kept in addition to the always-on
+/// built-ins) and whether local-only mode is enabled. Called by
+/// [`crate::services::TelemetryService`] whenever settings are read or written.
+pub fn configure(local_only_mode: bool, custom_patterns: &[String]) -> Result<(), String> {
+    let mut patterns = default_patterns();
+    for pattern in custom_patterns {
+        patterns.push(
+            Regex::new(pattern).map_err(|e| format!("Invalid redaction pattern '{}': {}", pattern, e))?,
+        );
+    }
+
+    let mut state = state().write().unwrap();
+    state.local_only_mode = local_only_mode;
+    state.patterns = patterns;
+    Ok(())
+}
+
+/// Whether local-only mode is enabled - when `true`, `main.rs`'s Sentry hooks drop every event
+/// and breadcrumb before it would otherwise be sent, regardless of the `SENTRY_DSN` the client
+/// was initialized with
+pub fn is_local_only_mode() -> bool {
+    state().read().unwrap().local_only_mode
+}
+
+/// Replace every match of a configured pattern in `text` with `[REDACTED]`
+pub fn redact(text: &str) -> String {
+    let state = state().read().unwrap();
+    let mut redacted = text.to_string();
+    for pattern in &state.patterns {
+        redacted = pattern.replace_all(&redacted, "[REDACTED]").into_owned();
+    }
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // configure()/redact() share global state, so tests that touch it must not run concurrently
+    static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_built_in_patterns_redact_api_keys_and_emails() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        configure(false, &[]).unwrap();
+        let text = "key=sk-abcdefghijklmnopqrstuvwxyz contact user@example.com";
+        let redacted = redact(text);
+        assert!(!redacted.contains("sk-abcdefghijklmnopqrstuvwxyz"));
+        assert!(!redacted.contains("user@example.com"));
+    }
+
+    #[test]
+    fn test_custom_pattern_is_applied() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        configure(false, &["secret-[0-9]+".to_string()]).unwrap();
+        assert_eq!(redact("token secret-12345 here"), "token [REDACTED] here");
+        configure(false, &[]).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_custom_pattern_is_rejected() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        assert!(configure(false, &["(unclosed".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_local_only_mode_toggle() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        configure(true, &[]).unwrap();
+        assert!(is_local_only_mode());
+        configure(false, &[]).unwrap();
+        assert!(!is_local_only_mode());
+    }
+}
@@ -0,0 +1,144 @@
+//! Round-trip export of a conversation for external review, so a colleague without library
+//! access can read it and leave comments that come back linked to the right message.
+//!
+//! The flow is export -> hand the file to a reviewer -> reviewer appends entries to
+//! `annotations` -> [`parse_review_annotations`] on the returned file. The conversation and
+//! message fields are only ever read back from a reviewed bundle, never written - editing them
+//! in the reviewed copy has no effect on the library, only the `annotations` a reviewer adds do.
+
+use crate::errors::{AppError, AppResult};
+use crate::models::{Conversation, Message};
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+
+/// Bumped whenever the review bundle format changes incompatibly
+const REVIEW_EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// A conversation and its messages bundled for external review
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ReviewExport {
+    pub conversation: Conversation,
+    pub messages: Vec<Message>,
+    /// Comments a reviewer added to a copy of this bundle; always empty on export
+    #[serde(default)]
+    pub annotations: Vec<ReviewAnnotation>,
+    pub exported_at: DateTime<Utc>,
+    pub format_version: u32,
+}
+
+/// A single reviewer comment on a message, round-tripped through a [`ReviewExport`] bundle
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ReviewAnnotation {
+    pub message_id: i64,
+    pub author: Option<String>,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Build a [`ReviewExport`] bundle for a conversation
+pub fn export_for_review(conversation: Conversation, messages: Vec<Message>) -> ReviewExport {
+    ReviewExport {
+        conversation,
+        messages,
+        annotations: Vec::new(),
+        exported_at: Utc::now(),
+        format_version: REVIEW_EXPORT_FORMAT_VERSION,
+    }
+}
+
+/// Parse a reviewed bundle and return the annotations it added
+///
+/// Each annotation must reference a `message_id` present in the bundle's own `messages` and
+/// carry non-empty comment text; an annotation that fails either check (e.g. hand-edited, or
+/// copied in from a different conversation's bundle) is dropped rather than failing the whole
+/// import, since the rest of the reviewer's feedback is still worth keeping.
+pub fn parse_review_annotations(reviewed_bundle: &str) -> AppResult<Vec<ReviewAnnotation>> {
+    let export: ReviewExport = serde_json::from_str(reviewed_bundle)
+        .map_err(|e| AppError::validation(format!("Invalid review export: {}", e)))?;
+
+    let known_message_ids: HashSet<i64> = export.messages.iter().filter_map(|m| m.id).collect();
+
+    Ok(export
+        .annotations
+        .into_iter()
+        .filter(|a| known_message_ids.contains(&a.message_id) && !a.comment.trim().is_empty())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Conversation, MessageRole};
+
+    fn sample_conversation() -> Conversation {
+        Conversation {
+            id: Some(1),
+            uuid: uuid::Uuid::new_v4(),
+            title: "Test".to_string(),
+            persona_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            archived: false,
+            metadata: None,
+            last_opened_at: None,
+            frozen: false,
+        }
+    }
+
+    fn sample_message(id: i64) -> Message {
+        Message {
+            id: Some(id),
+            conversation_id: 1,
+            role: MessageRole::User,
+            content: "hello".to_string(),
+            metadata: None,
+            created_at: Utc::now(),
+            tokens_used: None,
+            model_used: None,
+            edited_at: None,
+            tool_call_id: None,
+        }
+    }
+
+    #[test]
+    fn export_for_review_starts_with_no_annotations() {
+        let export = export_for_review(sample_conversation(), vec![sample_message(1)]);
+        assert!(export.annotations.is_empty());
+        assert_eq!(export.format_version, REVIEW_EXPORT_FORMAT_VERSION);
+    }
+
+    #[test]
+    fn parse_review_annotations_keeps_valid_ones_and_drops_the_rest() {
+        let mut export = export_for_review(sample_conversation(), vec![sample_message(1)]);
+        export.annotations = vec![
+            ReviewAnnotation {
+                message_id: 1,
+                author: Some("Reviewer".to_string()),
+                comment: "Looks good".to_string(),
+                created_at: Utc::now(),
+            },
+            ReviewAnnotation {
+                message_id: 99,
+                author: None,
+                comment: "Dangling".to_string(),
+                created_at: Utc::now(),
+            },
+            ReviewAnnotation {
+                message_id: 1,
+                author: None,
+                comment: "   ".to_string(),
+                created_at: Utc::now(),
+            },
+        ];
+
+        let annotations =
+            parse_review_annotations(&serde_json::to_string(&export).unwrap()).unwrap();
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].comment, "Looks good");
+    }
+
+    #[test]
+    fn parse_review_annotations_rejects_malformed_json() {
+        assert!(parse_review_annotations("not json").is_err());
+    }
+}
@@ -1,9 +1,55 @@
+use crate::database::query_optimizer::QueryCache;
 use crate::database::DatabaseManager;
-use crate::models::{Conversation, Message, Persona};
+use crate::models::{Conversation, ConversationSummary, Message, Persona};
 use chrono::{DateTime, Utc};
-use rusqlite::Result as SqliteResult;
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult};
 use uuid::Uuid;
 
+/// Default time-to-live for cached query results, in seconds
+const DEFAULT_QUERY_CACHE_TTL_SECONDS: u64 = 60;
+
+/// Number of most-recently-opened conversations whose cache entries survive
+/// `Services::evict_stale_conversation_cache`
+const DEFAULT_CACHED_CONVERSATION_WINDOW: i32 = 20;
+
+/// Most-recent messages in a conversation that `send_ai_request` always sends verbatim,
+/// regardless of how much older history has been folded into
+/// [`ConversationService::summarize_conversation`] chunks
+pub const RECENT_MESSAGE_WINDOW: usize = 10;
+
+/// Maximum number of old messages folded into a single
+/// [`ConversationService::summarize_conversation`] chunk
+const SUMMARY_CHUNK_SIZE: usize = 20;
+
+/// `message_flags.flag` value set by [`ConversationService::pin_message`]
+const PINNED_FLAG: &str = "pinned";
+
+/// Word/character/token statistics for a single conversation
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ConversationStatistics {
+    pub message_count: usize,
+    pub user_message_count: usize,
+    pub assistant_message_count: usize,
+    pub system_message_count: usize,
+    pub tool_message_count: usize,
+    pub word_count: usize,
+    pub character_count: usize,
+    pub estimated_tokens: usize,
+    pub distinct_models: Vec<String>,
+    pub first_message_at: Option<DateTime<Utc>>,
+    pub last_message_at: Option<DateTime<Utc>>,
+    pub duration_seconds: Option<i64>,
+}
+
+/// Where a forked conversation branched off from, returned by
+/// [`ConversationService::get_conversation_lineage`]
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ConversationLineage {
+    pub parent_conversation_id: i64,
+    /// The message in the parent conversation this one was forked from
+    pub forked_from_message_id: Option<i64>,
+}
+
 /// Conversation service - Manages chat sessions and message history
 pub struct ConversationService {
     pub db: std::sync::Arc<DatabaseManager>,
@@ -25,8 +71,8 @@ impl ConversationService {
 
         let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
         let mut stmt = conn.prepare(
-            "INSERT INTO conversations (uuid, title, persona_id, created_at, updated_at, archived)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO conversations (uuid, title, persona_id, created_at, updated_at, archived, frozen)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
         )?;
 
         stmt.execute([
@@ -39,6 +85,7 @@ impl ConversationService {
             &conversation.created_at.to_rfc3339(),
             &conversation.updated_at.to_rfc3339(),
             &conversation.archived.to_string(),
+            &conversation.frozen.to_string(),
         ])?;
 
         let id = conn.last_insert_rowid();
@@ -47,6 +94,104 @@ impl ConversationService {
         Ok(result)
     }
 
+    /// Assign a conversation to a profile, or clear it by passing `None`
+    pub fn set_conversation_profile(&self, id: i64, profile_id: Option<i64>) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "UPDATE conversations SET profile_id = ?1, updated_at = ?2 WHERE id = ?3",
+            params![profile_id, Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+
+    /// Update a conversation's title, e.g. after automatic title generation from its first
+    /// exchange
+    pub fn update_conversation_title(&self, id: i64, title: &str) -> SqliteResult<bool> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let updated = conn.execute(
+            "UPDATE conversations SET title = ?1, updated_at = ?2 WHERE id = ?3",
+            params![title, Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Record that a conversation was just opened in the UI
+    ///
+    /// Drives LRU-style eviction of the message page cache and the "least recently opened"
+    /// cleanup suggestion list (see `least_recently_opened`).
+    pub fn record_opened(&self, id: i64) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "UPDATE conversations SET last_opened_at = ?1 WHERE id = ?2",
+            params![Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+
+    /// Non-archived conversations that haven't been opened in the longest time (or have never
+    /// been opened), oldest first
+    ///
+    /// Surfaced directly as a cleanup suggestion list ("you haven't touched these in a while")
+    /// and used internally to pick eviction candidates when trimming the message page cache.
+    pub fn least_recently_opened(&self, limit: i32) -> SqliteResult<Vec<Conversation>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, uuid, title, persona_id, created_at, updated_at, archived, last_opened_at, frozen
+             FROM conversations
+             WHERE archived = 'false'
+             ORDER BY last_opened_at ASC NULLS FIRST
+             LIMIT ?1",
+        )?;
+
+        let rows = stmt.query_map([limit], |row| {
+            Ok(Conversation {
+                id: Some(row.get::<_, i64>(0)?),
+                uuid: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap_or_default(),
+                title: row.get(2)?,
+                persona_id: row.get::<_, Option<i64>>(3)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                archived: row.get::<_, String>(6)? == "true",
+                metadata: None,
+                last_opened_at: row
+                    .get::<_, Option<String>>(7)?
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+                frozen: row.get::<_, String>(8)? == "true",
+            })
+        })?;
+
+        let mut conversations = Vec::new();
+        for row in rows {
+            conversations.push(row?);
+        }
+        Ok(conversations)
+    }
+
+    /// Ids of conversations outside the `keep_recent` most-recently-opened, used to evict their
+    /// cached message pages and summaries without dropping the cache entries a user is actively
+    /// switching between
+    pub fn conversation_ids_beyond_recency_window(&self, keep_recent: i32) -> SqliteResult<Vec<i64>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id FROM conversations
+             ORDER BY last_opened_at DESC NULLS LAST
+             LIMIT -1 OFFSET ?1",
+        )?;
+
+        let rows = stmt.query_map([keep_recent], |row| row.get::<_, i64>(0))?;
+
+        let mut ids = Vec::new();
+        for row in rows {
+            ids.push(row?);
+        }
+        Ok(ids)
+    }
+
     /// Get all conversations with pagination
     pub fn get_conversations(
         &self,
@@ -58,8 +203,9 @@ impl ConversationService {
 
         let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
         let mut stmt = conn.prepare(
-            "SELECT id, uuid, title, persona_id, created_at, updated_at, archived
+            "SELECT id, uuid, title, persona_id, created_at, updated_at, archived, frozen
              FROM conversations
+             WHERE deleted_at IS NULL
              ORDER BY updated_at DESC
              LIMIT ?1 OFFSET ?2",
         )?;
@@ -78,6 +224,93 @@ impl ConversationService {
                     .with_timezone(&Utc),
                 archived: row.get::<_, String>(6)? == "true",
                 metadata: None, // Load separately if needed
+                last_opened_at: None, // Not needed for list views
+                frozen: row.get::<_, String>(7)? == "true",
+            })
+        })?;
+
+        let mut conversations = Vec::new();
+        for row in rows {
+            conversations.push(row?);
+        }
+        Ok(conversations)
+    }
+
+    /// Get conversations updated at or after a given timestamp, most recent first
+    ///
+    /// Used for time-boxed reporting features like the weekly digest.
+    pub fn get_conversations_since(&self, since: DateTime<Utc>) -> SqliteResult<Vec<Conversation>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, uuid, title, persona_id, created_at, updated_at, archived, frozen
+             FROM conversations
+             WHERE updated_at >= ?1 AND deleted_at IS NULL
+             ORDER BY updated_at DESC",
+        )?;
+
+        let rows = stmt.query_map([since.to_rfc3339()], |row| {
+            Ok(Conversation {
+                id: Some(row.get::<_, i64>(0)?),
+                uuid: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap_or_default(),
+                title: row.get(2)?,
+                persona_id: row.get::<_, Option<i64>>(3)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                archived: row.get::<_, String>(6)? == "true",
+                metadata: None,
+                last_opened_at: None,
+                frozen: row.get::<_, String>(7)? == "true",
+            })
+        })?;
+
+        let mut conversations = Vec::new();
+        for row in rows {
+            conversations.push(row?);
+        }
+        Ok(conversations)
+    }
+
+    /// Get conversations belonging to a specific profile, most recently updated first
+    ///
+    /// Keeps each person's recent list separate on machines shared between a few users.
+    pub fn get_conversations_for_profile(
+        &self,
+        profile_id: i64,
+        limit: Option<i32>,
+        offset: Option<i32>,
+    ) -> SqliteResult<Vec<Conversation>> {
+        let limit = limit.unwrap_or(50);
+        let offset = offset.unwrap_or(0);
+
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, uuid, title, persona_id, created_at, updated_at, archived, frozen
+             FROM conversations
+             WHERE profile_id = ?1 AND deleted_at IS NULL
+             ORDER BY updated_at DESC
+             LIMIT ?2 OFFSET ?3",
+        )?;
+
+        let rows = stmt.query_map(params![profile_id, limit, offset], |row| {
+            Ok(Conversation {
+                id: Some(row.get::<_, i64>(0)?),
+                uuid: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap_or_default(),
+                title: row.get(2)?,
+                persona_id: row.get::<_, Option<i64>>(3)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                archived: row.get::<_, String>(6)? == "true",
+                metadata: None,
+                last_opened_at: None,
+                frozen: row.get::<_, String>(7)? == "true",
             })
         })?;
 
@@ -88,11 +321,101 @@ impl ConversationService {
         Ok(conversations)
     }
 
+    /// Maximum length, in characters, of a denormalized last-message snippet
+    const LAST_MESSAGE_SNIPPET_MAX_CHARS: usize = 140;
+
+    /// Get conversations with a denormalized snippet of their most recent message
+    ///
+    /// Avoids an N+1 query per row in list views: a single correlated subquery pulls the
+    /// latest message's content alongside each conversation instead of a follow-up
+    /// `get_messages` call per conversation.
+    pub fn get_conversations_with_last_message(
+        &self,
+        limit: Option<i32>,
+        offset: Option<i32>,
+    ) -> SqliteResult<Vec<(Conversation, Option<String>)>> {
+        let limit = limit.unwrap_or(50);
+        let offset = offset.unwrap_or(0);
+
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT c.id, c.uuid, c.title, c.persona_id, c.created_at, c.updated_at, c.archived, c.frozen,
+                    (SELECT m.content FROM messages m
+                     WHERE m.conversation_id = c.id
+                     ORDER BY m.created_at DESC, m.id DESC LIMIT 1) AS last_message
+             FROM conversations c
+             WHERE c.deleted_at IS NULL
+             ORDER BY c.updated_at DESC
+             LIMIT ?1 OFFSET ?2",
+        )?;
+
+        let rows = stmt.query_map([limit, offset], |row| {
+            let conversation = Conversation {
+                id: Some(row.get::<_, i64>(0)?),
+                uuid: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap_or_default(),
+                title: row.get(2)?,
+                persona_id: row.get::<_, Option<i64>>(3)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                archived: row.get::<_, String>(6)? == "true",
+                metadata: None,
+                last_opened_at: None,
+                frozen: row.get::<_, String>(7)? == "true",
+            };
+            let last_message: Option<String> = row.get(8)?;
+            Ok((conversation, last_message))
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    /// Truncate a message to a short snippet suitable for list-view previews
+    pub fn snippet_for_message(content: &str) -> String {
+        if content.chars().count() <= Self::LAST_MESSAGE_SNIPPET_MAX_CHARS {
+            return content.to_string();
+        }
+
+        let truncated: String = content
+            .chars()
+            .take(Self::LAST_MESSAGE_SNIPPET_MAX_CHARS)
+            .collect();
+        format!("{}…", truncated.trim_end())
+    }
+
+    /// Count total conversations, optionally restricted to archived/non-archived
+    ///
+    /// Used alongside `get_conversations` to support virtual scrolling: the frontend needs
+    /// the total row count up front to size its scroll window before windows are fetched.
+    pub fn count_conversations(&self, archived: Option<bool>) -> SqliteResult<i64> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        match archived {
+            Some(archived) => conn.query_row(
+                "SELECT COUNT(*) FROM conversations WHERE archived = ?1 AND deleted_at IS NULL",
+                [archived.to_string()],
+                |row| row.get(0),
+            ),
+            None => conn.query_row(
+                "SELECT COUNT(*) FROM conversations WHERE deleted_at IS NULL",
+                [],
+                |row| row.get(0),
+            ),
+        }
+    }
+
     /// Get conversation by ID
     pub fn get_conversation(&self, id: i64) -> SqliteResult<Option<Conversation>> {
         let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
         let mut stmt = conn.prepare(
-            "SELECT id, uuid, title, persona_id, created_at, updated_at, archived
+            "SELECT id, uuid, title, persona_id, created_at, updated_at, archived, last_opened_at, frozen
              FROM conversations
              WHERE id = ?1",
         )?;
@@ -111,6 +434,11 @@ impl ConversationService {
                     .with_timezone(&Utc),
                 archived: row.get::<_, String>(6)? == "true",
                 metadata: None,
+                last_opened_at: row
+                    .get::<_, Option<String>>(7)?
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+                frozen: row.get::<_, String>(8)? == "true",
             })
         })?;
 
@@ -133,7 +461,7 @@ impl ConversationService {
 
         // Search by title or messages content
         let mut stmt = conn.prepare(
-            "SELECT DISTINCT c.id, c.uuid, c.title, c.persona_id, c.created_at, c.updated_at, c.archived
+            "SELECT DISTINCT c.id, c.uuid, c.title, c.persona_id, c.created_at, c.updated_at, c.archived, c.frozen
              FROM conversations c
              LEFT JOIN messages m ON c.id = m.conversation_id
              WHERE c.title LIKE ?1 OR m.content LIKE ?1
@@ -155,6 +483,8 @@ impl ConversationService {
                     .with_timezone(&Utc),
                 archived: row.get::<_, String>(6)? == "true",
                 metadata: None,
+                last_opened_at: None,
+                frozen: row.get::<_, String>(7)? == "true",
             })
         })?;
 
@@ -173,22 +503,26 @@ impl ConversationService {
         content: String,
         tokens_used: Option<i32>,
         model_used: Option<String>,
+        tool_call_id: Option<String>,
     ) -> SqliteResult<Message> {
-        let message = Message::new(conversation_id, role, content);
+        let mut message = Message::new(conversation_id, role, content);
+        message.tool_call_id = tool_call_id.clone();
 
         let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
         let mut stmt = conn.prepare(
-            "INSERT INTO messages (conversation_id, role, content, created_at, tokens_used, model_used)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)"
+            "INSERT INTO messages (conversation_id, role, content, created_at, tokens_used, model_used, tool_call_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"
         )?;
 
         let role_str = match message.role {
             crate::models::MessageRole::User => "user",
             crate::models::MessageRole::Assistant => "assistant",
             crate::models::MessageRole::System => "system",
+            crate::models::MessageRole::Tool => "tool",
         };
 
         let model_used_str = model_used.as_deref().unwrap_or("");
+        let tool_call_id_str = tool_call_id.as_deref().unwrap_or("");
         stmt.execute([
             &conversation_id.to_string(),
             role_str,
@@ -196,6 +530,7 @@ impl ConversationService {
             &message.created_at.to_rfc3339(),
             &tokens_used.map(|t| t.to_string()).unwrap_or_default(),
             model_used_str,
+            tool_call_id_str,
         ])?;
 
         // Update conversation's updated_at timestamp
@@ -212,22 +547,100 @@ impl ConversationService {
         Ok(result)
     }
 
+    /// Insert a batch of imported conversations and their messages in one transaction, so a
+    /// parse error partway through an export file never leaves a half-imported conversation
+    /// behind. Returns the number of conversations and messages inserted.
+    pub fn import_conversations(
+        &self,
+        conversations: Vec<crate::importers::ImportedConversation>,
+    ) -> crate::errors::AppResult<(usize, usize)> {
+        let conversation_count = conversations.len();
+        let mut message_count = 0;
+
+        self.db.with_transaction(|tx| {
+            for conversation in conversations {
+                let uuid_str = Uuid::new_v4().to_string();
+                tx.execute(
+                    "INSERT INTO conversations (uuid, title, persona_id, created_at, updated_at, archived)
+                     VALUES (?1, ?2, NULL, ?3, ?3, 'false')",
+                    params![uuid_str, conversation.title, conversation.created_at.to_rfc3339()],
+                )
+                .map_err(|e| crate::errors::AppError::database(format!("Failed to insert imported conversation: {}", e)))?;
+
+                let conversation_id = tx.last_insert_rowid();
+
+                for message in conversation.messages {
+                    let role_str = match message.role {
+                        crate::models::MessageRole::User => "user",
+                        crate::models::MessageRole::Assistant => "assistant",
+                        crate::models::MessageRole::System => "system",
+                        crate::models::MessageRole::Tool => "tool",
+                    };
+                    tx.execute(
+                        "INSERT INTO messages (conversation_id, role, content, created_at)
+                         VALUES (?1, ?2, ?3, ?4)",
+                        params![conversation_id, role_str, message.content, message.created_at.to_rfc3339()],
+                    )
+                    .map_err(|e| crate::errors::AppError::database(format!("Failed to insert imported message: {}", e)))?;
+                    message_count += 1;
+                }
+            }
+            Ok(())
+        })?;
+
+        Ok((conversation_count, message_count))
+    }
+
     /// Get messages for a conversation
-    pub fn get_messages(&self, conversation_id: i64) -> SqliteResult<Vec<Message>> {
+    /// Get a conversation's messages, oldest first
+    ///
+    /// `limit`/`offset` page through a long thread instead of loading the whole history at
+    /// once, so the UI can virtualize a 10k+ message conversation; pass `None` for both to get
+    /// every message, as most internal callers (statistics, compaction, analysis) need.
+    pub fn get_messages(
+        &self,
+        conversation_id: i64,
+        limit: Option<i32>,
+        offset: Option<i32>,
+    ) -> SqliteResult<Vec<Message>> {
         let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
-        let mut stmt = conn.prepare(
-            "SELECT id, conversation_id, role, content, created_at, tokens_used, model_used
-             FROM messages
-             WHERE conversation_id = ?1
-             ORDER BY created_at ASC",
-        )?;
 
-        let rows = stmt.query_map([conversation_id], |row| {
+        if let Some(archived_messages) = Self::read_compacted_messages(&conn, conversation_id)? {
+            let offset = offset.unwrap_or(0).max(0) as usize;
+            return Ok(match limit {
+                Some(limit) => archived_messages
+                    .into_iter()
+                    .skip(offset)
+                    .take(limit.max(0) as usize)
+                    .collect(),
+                None => archived_messages.into_iter().skip(offset).collect(),
+            });
+        }
+
+        let query = match limit {
+            Some(_) => {
+                "SELECT id, conversation_id, role, content, created_at, tokens_used, model_used, edited_at, tool_call_id
+                 FROM messages
+                 WHERE conversation_id = ?1
+                 ORDER BY created_at ASC
+                 LIMIT ?2 OFFSET ?3"
+            }
+            None => {
+                "SELECT id, conversation_id, role, content, created_at, tokens_used, model_used, edited_at, tool_call_id
+                 FROM messages
+                 WHERE conversation_id = ?1
+                 ORDER BY created_at ASC"
+            }
+        };
+        let mut stmt = conn.prepare(query)?;
+
+        let map_row = |row: &rusqlite::Row| -> rusqlite::Result<Message> {
             let role_str: String = row.get(2)?;
             let role = match role_str.as_str() {
                 "user" => crate::models::MessageRole::User,
                 "assistant" => crate::models::MessageRole::Assistant,
                 "system" => crate::models::MessageRole::System,
+                "tool" => crate::models::MessageRole::Tool,
                 _ => crate::models::MessageRole::User, // Default fallback
             };
 
@@ -244,200 +657,4495 @@ impl ConversationService {
                     .and_then(|s| s.parse().ok()),
                 model_used: row.get::<_, Option<String>>(6)?.filter(|s| !s.is_empty()),
                 metadata: None,
+                edited_at: row
+                    .get::<_, Option<String>>(7)?
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+                tool_call_id: row.get::<_, Option<String>>(8)?.filter(|s| !s.is_empty()),
             })
-        })?;
+        };
 
         let mut messages = Vec::new();
-        for row in rows {
-            messages.push(row?);
+        match limit {
+            Some(limit) => {
+                let rows = stmt.query_map(params![conversation_id, limit, offset.unwrap_or(0)], map_row)?;
+                for row in rows {
+                    messages.push(row?);
+                }
+            }
+            None => {
+                let rows = stmt.query_map([conversation_id], map_row)?;
+                for row in rows {
+                    messages.push(row?);
+                }
+            }
         }
         Ok(messages)
     }
 
-    /// Delete conversation and all its messages
-    pub fn delete_conversation(&self, id: i64) -> SqliteResult<()> {
-        // Messages will be deleted automatically due to CASCADE
+    /// Count a conversation's messages, for frontend virtualization alongside [`Self::get_messages`]
+    pub fn count_messages(&self, conversation_id: i64) -> SqliteResult<i64> {
         let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
-        conn.execute("DELETE FROM conversations WHERE id = ?1", [id])?;
-        Ok(())
+
+        if let Some(archived_messages) = Self::read_compacted_messages(&conn, conversation_id)? {
+            return Ok(archived_messages.len() as i64);
+        }
+
+        conn.query_row(
+            "SELECT COUNT(*) FROM messages WHERE conversation_id = ?1",
+            [conversation_id],
+            |row| row.get(0),
+        )
     }
 
-    /// Archive/unarchive conversation
-    pub fn set_conversation_archived(&self, id: i64, archived: bool) -> SqliteResult<()> {
+    /// Read and decompress a conversation's messages from cold storage, if it was compacted
+    fn read_compacted_messages(conn: &rusqlite::Connection, conversation_id: i64) -> SqliteResult<Option<Vec<Message>>> {
+        use std::io::Read;
+
+        let compressed: Option<Vec<u8>> = conn
+            .query_row(
+                "SELECT compressed_data FROM conversation_archives WHERE conversation_id = ?1",
+                [conversation_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let Some(compressed) = compressed else {
+            return Ok(None);
+        };
+
+        let mut decoder = flate2::read::GzDecoder::new(compressed.as_slice());
+        let mut json = String::new();
+        decoder
+            .read_to_string(&mut json)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let messages: Vec<Message> = serde_json::from_str(&json)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        Ok(Some(messages))
+    }
+
+    /// Compact an archived conversation's messages into a single compressed blob
+    ///
+    /// Reduces row and index overhead for cold conversations that are unlikely to be edited
+    /// again. Reopening the conversation via [`get_messages`] transparently decompresses the
+    /// blob; only archived conversations may be compacted.
+    pub fn compact_archived_conversation(&self, conversation_id: i64) -> SqliteResult<usize> {
         let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let archived: String = conn.query_row(
+            "SELECT archived FROM conversations WHERE id = ?1",
+            [conversation_id],
+            |row| row.get(0),
+        )?;
+        if archived != "true" {
+            return Err(rusqlite::Error::ToSqlConversionFailure(Box::new(
+                crate::errors::AppError::validation("Only archived conversations can be compacted"),
+            )));
+        }
+
+        let messages = self.get_messages(conversation_id, None, None)?;
+        if messages.is_empty() {
+            return Ok(0);
+        }
+
+        let json = serde_json::to_string(&messages)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        use std::io::Write;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder
+            .write_all(json.as_bytes())
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let compressed = encoder
+            .finish()
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
         conn.execute(
-            "UPDATE conversations SET archived = ?1, updated_at = ?2 WHERE id = ?3",
-            [
-                &archived.to_string(),
-                &Utc::now().to_rfc3339(),
-                &id.to_string(),
-            ],
+            "INSERT INTO conversation_archives (conversation_id, compressed_data, message_count, compressed_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(conversation_id) DO UPDATE SET
+                compressed_data = excluded.compressed_data,
+                message_count = excluded.message_count,
+                compressed_at = excluded.compressed_at",
+            params![conversation_id, compressed, messages.len() as i64, Utc::now().to_rfc3339()],
         )?;
-        Ok(())
+
+        conn.execute(
+            "DELETE FROM messages WHERE conversation_id = ?1",
+            [conversation_id],
+        )?;
+
+        Ok(messages.len())
+    }
+
+    /// A message is "low value" for [`compact_history`](Self::compact_history) if, once
+    /// trimmed of trailing punctuation and case, it is nothing but a short greeting or
+    /// acknowledgement - the kind of exchange that adds no information if the conversation is
+    /// resent as context later
+    fn is_low_value_message(content: &str) -> bool {
+        const LOW_VALUE_PHRASES: &[&str] = &[
+            "hi", "hello", "hey", "thanks", "thank you", "ok", "okay", "k", "got it",
+            "sounds good", "sure", "yes", "no", "cool", "great", "perfect", "understood",
+            "will do", "np", "no problem", "alright", "good morning", "good night",
+        ];
+        let normalized = content
+            .trim()
+            .trim_end_matches(|c: char| c == '.' || c == '!' || c == '?')
+            .to_lowercase();
+        LOW_VALUE_PHRASES.contains(&normalized.as_str())
+    }
+
+    /// Replace runs of old low-value messages (greetings, acknowledged confirmations) with a
+    /// single generated summary message, to keep long-running conversations cheap to resend as
+    /// context without losing substantive content
+    ///
+    /// Runs shorter than two messages are left alone - compacting a single greeting saves
+    /// nothing and would just add noise to the archive. Original messages are preserved in
+    /// `compacted_message_runs` so they can still be inspected later even though they no
+    /// longer appear in `messages`.
+    pub fn compact_history(&self, conversation_id: i64) -> SqliteResult<crate::models::HistoryCompactionReport> {
+        let messages = self.get_messages(conversation_id, None, None)?;
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let mut report = crate::models::HistoryCompactionReport {
+            runs_compacted: 0,
+            messages_compacted: 0,
+            tokens_saved: 0,
+        };
+
+        let mut index = 0;
+        while index < messages.len() {
+            if !Self::is_low_value_message(&messages[index].content) {
+                index += 1;
+                continue;
+            }
+
+            let run_start = index;
+            while index < messages.len() && Self::is_low_value_message(&messages[index].content) {
+                index += 1;
+            }
+            let run = &messages[run_start..index];
+            if run.len() < 2 {
+                continue;
+            }
+
+            let summary_content = format!(
+                "[Compacted {} low-value exchange{}]",
+                run.len(),
+                if run.len() == 1 { "" } else { "s" }
+            );
+            let original_tokens: i64 = run
+                .iter()
+                .map(|m| {
+                    m.tokens_used
+                        .map(|t| t as i64)
+                        .unwrap_or_else(|| m.content.len() as i64 / 4)
+                })
+                .sum();
+            let summary_tokens = summary_content.len() as i64 / 4;
+            let tokens_saved = (original_tokens - summary_tokens).max(0);
+
+            conn.execute(
+                "INSERT INTO messages (conversation_id, role, content, created_at, tokens_used, model_used)
+                 VALUES (?1, 'system', ?2, ?3, ?4, '')",
+                params![conversation_id, summary_content, run[0].created_at.to_rfc3339(), summary_tokens],
+            )?;
+            let summary_message_id = conn.last_insert_rowid();
+
+            let original_json = serde_json::to_string(run)
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+            conn.execute(
+                "INSERT INTO compacted_message_runs (conversation_id, summary_message_id, original_messages, tokens_saved, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![conversation_id, summary_message_id, original_json, tokens_saved, Utc::now().to_rfc3339()],
+            )?;
+
+            for message in run {
+                if let Some(id) = message.id {
+                    conn.execute("DELETE FROM messages WHERE id = ?1", [id])?;
+                }
+            }
+
+            report.runs_compacted += 1;
+            report.messages_compacted += run.len();
+            report.tokens_saved += tokens_saved;
+        }
+
+        Ok(report)
+    }
+
+    /// Most recent summary chunk recorded for `conversation_id`, if any
+    pub fn get_latest_conversation_summary(
+        &self,
+        conversation_id: i64,
+    ) -> SqliteResult<Option<ConversationSummary>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.query_row(
+            "SELECT id, conversation_id, summary, covers_through_message_id, created_at
+             FROM conversation_summaries
+             WHERE conversation_id = ?1
+             ORDER BY id DESC
+             LIMIT 1",
+            [conversation_id],
+            |row| {
+                Ok(ConversationSummary {
+                    id: row.get(0)?,
+                    conversation_id: row.get(1)?,
+                    summary: row.get(2)?,
+                    covers_through_message_id: row.get(3)?,
+                    created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                        .unwrap_or_default()
+                        .with_timezone(&Utc),
+                })
+            },
+        )
+        .optional()
+    }
+
+    /// The old messages `summarize_conversation` should fold into new summary chunks: anything
+    /// after the last summary's cutoff, excluding the most recent [`RECENT_MESSAGE_WINDOW`]
+    /// messages, which are always sent to the provider verbatim instead
+    fn messages_pending_summarization(&self, conversation_id: i64) -> SqliteResult<Vec<Message>> {
+        let covered_through = self
+            .get_latest_conversation_summary(conversation_id)?
+            .map(|s| s.covers_through_message_id)
+            .unwrap_or(0);
+
+        let mut messages = self.get_messages(conversation_id, None, None)?;
+        messages.retain(|m| m.id.map(|id| id > covered_through).unwrap_or(false));
+        if messages.len() > RECENT_MESSAGE_WINDOW {
+            messages.truncate(messages.len() - RECENT_MESSAGE_WINDOW);
+        } else {
+            messages.clear();
+        }
+        Ok(messages)
+    }
+
+    /// Record a new summary chunk covering messages up to and including `covers_through_message_id`
+    fn insert_conversation_summary(
+        &self,
+        conversation_id: i64,
+        summary: &str,
+        covers_through_message_id: i64,
+    ) -> SqliteResult<ConversationSummary> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let created_at = Utc::now();
+        conn.execute(
+            "INSERT INTO conversation_summaries (conversation_id, summary, covers_through_message_id, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![conversation_id, summary, covers_through_message_id, created_at.to_rfc3339()],
+        )?;
+        Ok(ConversationSummary {
+            id: conn.last_insert_rowid(),
+            conversation_id,
+            summary: summary.to_string(),
+            covers_through_message_id,
+            created_at,
+        })
+    }
+
+    /// Fold the oldest pending chunk of [`messages_pending_summarization`] into a new summary
+    /// using `summarizer`, one [`SUMMARY_CHUNK_SIZE`]-sized chunk at a time so a very long
+    /// backlog doesn't overflow the model's own context window in a single call
+    ///
+    /// Returns every chunk summarized in this run; `send_ai_request` only needs the last one.
+    pub async fn summarize_conversation(
+        &self,
+        conversation_id: i64,
+        summarizer: &dyn crate::summarization::Summarizer,
+    ) -> crate::errors::AppResult<Vec<ConversationSummary>> {
+        let pending = self
+            .messages_pending_summarization(conversation_id)
+            .map_err(crate::errors::AppError::from)?;
+
+        let mut summaries = Vec::new();
+        for chunk in pending.chunks(SUMMARY_CHUNK_SIZE) {
+            let Some(covers_through_message_id) = chunk.last().and_then(|m| m.id) else {
+                continue;
+            };
+            let chunk_text = chunk
+                .iter()
+                .map(|m| format!("{:?}: {}", m.role, m.content))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let summary_text = summarizer.summarize(&chunk_text).await?;
+            let summary = self
+                .insert_conversation_summary(conversation_id, &summary_text, covers_through_message_id)
+                .map_err(crate::errors::AppError::from)?;
+            summaries.push(summary);
+        }
+
+        Ok(summaries)
+    }
+
+    /// Get messages added to a conversation after a given message id
+    ///
+    /// Used for delta sync: clients remember the highest message id they've seen and pass
+    /// it back as `since_id` so polling or resume-after-sleep only transfers new messages
+    /// instead of the whole conversation history.
+    pub fn get_messages_since(
+        &self,
+        conversation_id: i64,
+        since_id: i64,
+    ) -> SqliteResult<Vec<Message>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, conversation_id, role, content, created_at, tokens_used, model_used, edited_at, tool_call_id
+             FROM messages
+             WHERE conversation_id = ?1 AND id > ?2
+             ORDER BY id ASC",
+        )?;
+
+        let rows = stmt.query_map(params![conversation_id, since_id], |row| {
+            let role_str: String = row.get(2)?;
+            let role = match role_str.as_str() {
+                "user" => crate::models::MessageRole::User,
+                "assistant" => crate::models::MessageRole::Assistant,
+                "system" => crate::models::MessageRole::System,
+                "tool" => crate::models::MessageRole::Tool,
+                _ => crate::models::MessageRole::User, // Default fallback
+            };
+
+            Ok(Message {
+                id: Some(row.get::<_, i64>(0)?),
+                conversation_id: row.get(1)?,
+                role,
+                content: row.get(3)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                tokens_used: row
+                    .get::<_, Option<String>>(5)?
+                    .and_then(|s| s.parse().ok()),
+                model_used: row.get::<_, Option<String>>(6)?.filter(|s| !s.is_empty()),
+                metadata: None,
+                edited_at: row
+                    .get::<_, Option<String>>(7)?
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+                tool_call_id: row.get::<_, Option<String>>(8)?.filter(|s| !s.is_empty()),
+            })
+        })?;
+
+        let mut messages = Vec::new();
+        for row in rows {
+            messages.push(row?);
+        }
+        Ok(messages)
+    }
+
+    /// Replace a message's content with a regenerated version, recording a word-level diff
+    /// against what it replaced so the UI can highlight the change
+    pub fn regenerate_message(
+        &self,
+        message_id: i64,
+        new_content: String,
+    ) -> SqliteResult<crate::models::MessageRegeneration> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        Self::ensure_message_conversation_not_frozen(&conn, message_id)?;
+
+        let previous_content: String = conn.query_row(
+            "SELECT content FROM messages WHERE id = ?1",
+            [message_id],
+            |row| row.get(0),
+        )?;
+
+        let diff = crate::diff::word_diff(&previous_content, &new_content);
+        let diff_json = serde_json::to_string(&diff)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let created_at = Utc::now();
+
+        conn.execute(
+            "UPDATE messages SET content = ?1 WHERE id = ?2",
+            params![new_content, message_id],
+        )?;
+
+        conn.execute(
+            "INSERT INTO message_regenerations (message_id, previous_content, new_content, diff, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![message_id, previous_content, new_content, diff_json, created_at.to_rfc3339()],
+        )?;
+        let id = conn.last_insert_rowid();
+
+        Ok(crate::models::MessageRegeneration {
+            id: Some(id),
+            message_id,
+            previous_content,
+            new_content,
+            diff,
+            created_at,
+        })
+    }
+
+    /// List every recorded regeneration for a message, oldest first
+    pub fn get_message_regenerations(
+        &self,
+        message_id: i64,
+    ) -> SqliteResult<Vec<crate::models::MessageRegeneration>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, message_id, previous_content, new_content, diff, created_at
+             FROM message_regenerations WHERE message_id = ?1 ORDER BY id ASC",
+        )?;
+
+        let rows = stmt.query_map([message_id], |row| {
+            let diff_json: String = row.get(4)?;
+            let diff = serde_json::from_str(&diff_json).unwrap_or_default();
+            Ok(crate::models::MessageRegeneration {
+                id: Some(row.get(0)?),
+                message_id: row.get(1)?,
+                previous_content: row.get(2)?,
+                new_content: row.get(3)?,
+                diff,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+            })
+        })?;
+
+        let mut regenerations = Vec::new();
+        for row in rows {
+            regenerations.push(row?);
+        }
+        Ok(regenerations)
+    }
+
+    /// Get a single message by id
+    pub fn get_message(&self, id: i64) -> SqliteResult<Option<Message>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, conversation_id, role, content, created_at, tokens_used, model_used, edited_at, tool_call_id
+             FROM messages WHERE id = ?1",
+        )?;
+
+        let mut rows = stmt.query_map([id], |row| {
+            let role_str: String = row.get(2)?;
+            let role = match role_str.as_str() {
+                "user" => crate::models::MessageRole::User,
+                "assistant" => crate::models::MessageRole::Assistant,
+                "system" => crate::models::MessageRole::System,
+                "tool" => crate::models::MessageRole::Tool,
+                _ => crate::models::MessageRole::User,
+            };
+
+            Ok(Message {
+                id: Some(row.get::<_, i64>(0)?),
+                conversation_id: row.get(1)?,
+                role,
+                content: row.get(3)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                tokens_used: row
+                    .get::<_, Option<String>>(5)?
+                    .and_then(|s| s.parse().ok()),
+                model_used: row.get::<_, Option<String>>(6)?.filter(|s| !s.is_empty()),
+                metadata: None,
+                edited_at: row
+                    .get::<_, Option<String>>(7)?
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+                tool_call_id: row.get::<_, Option<String>>(8)?.filter(|s| !s.is_empty()),
+            })
+        })?;
+
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Overwrite a message's content in place and stamp `edited_at`, for user-initiated edits
+    ///
+    /// Unlike [`Self::regenerate_message`], this doesn't keep a diff history - it's meant for
+    /// correcting a message the user wrote themselves, not for tracking AI regenerations.
+    /// Errors if no message exists with the given id.
+    pub fn update_message(&self, id: i64, new_content: String) -> SqliteResult<Message> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        Self::ensure_message_conversation_not_frozen(&conn, id)?;
+        let edited_at = Utc::now();
+
+        conn.execute(
+            "UPDATE messages SET content = ?1, edited_at = ?2 WHERE id = ?3",
+            params![new_content, edited_at.to_rfc3339(), id],
+        )?;
+
+        conn.query_row(
+            "SELECT id, conversation_id, role, content, created_at, tokens_used, model_used, edited_at, tool_call_id
+             FROM messages
+             WHERE id = ?1",
+            [id],
+            |row| {
+                let role_str: String = row.get(2)?;
+                let role = match role_str.as_str() {
+                    "user" => crate::models::MessageRole::User,
+                    "assistant" => crate::models::MessageRole::Assistant,
+                    "system" => crate::models::MessageRole::System,
+                    "tool" => crate::models::MessageRole::Tool,
+                    _ => crate::models::MessageRole::User,
+                };
+
+                Ok(Message {
+                    id: Some(row.get::<_, i64>(0)?),
+                    conversation_id: row.get(1)?,
+                    role,
+                    content: row.get(3)?,
+                    created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                        .unwrap_or_default()
+                        .with_timezone(&Utc),
+                    tokens_used: row
+                        .get::<_, Option<String>>(5)?
+                        .and_then(|s| s.parse().ok()),
+                    model_used: row.get::<_, Option<String>>(6)?.filter(|s| !s.is_empty()),
+                    metadata: None,
+                    edited_at: row
+                        .get::<_, Option<String>>(7)?
+                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                        .map(|dt| dt.with_timezone(&Utc)),
+                    tool_call_id: row.get::<_, Option<String>>(8)?.filter(|s| !s.is_empty()),
+                })
+            },
+        )
+    }
+
+    /// Delete a message. Cascades to its regeneration history via `ON DELETE CASCADE` on
+    /// `message_regenerations.message_id`; a no-op if no message exists with the given id.
+    pub fn delete_message(&self, id: i64) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        Self::ensure_message_conversation_not_frozen(&conn, id)?;
+        conn.execute("DELETE FROM messages WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    /// Pin a message so it's kept in context assembly (see
+    /// [`crate::tokenizer::fit_messages_to_context`]) and surfaced by [`Self::get_pinned_messages`]
+    pub fn pin_message(&self, message_id: i64) -> SqliteResult<()> {
+        self.set_message_flag(message_id, PINNED_FLAG)
+    }
+
+    /// Unpin a previously pinned message; a no-op if it wasn't pinned
+    pub fn unpin_message(&self, message_id: i64) -> SqliteResult<()> {
+        self.clear_message_flag(message_id, PINNED_FLAG)
+    }
+
+    /// Attach a named flag (e.g. `"pinned"`) to a message. Idempotent - setting a flag that's
+    /// already set does nothing.
+    pub fn set_message_flag(&self, message_id: i64, flag: &str) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO message_flags (message_id, flag, created_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(message_id, flag) DO NOTHING",
+            params![message_id, flag, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Remove a named flag from a message; a no-op if it wasn't set
+    pub fn clear_message_flag(&self, message_id: i64, flag: &str) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "DELETE FROM message_flags WHERE message_id = ?1 AND flag = ?2",
+            params![message_id, flag],
+        )?;
+        Ok(())
+    }
+
+    /// Every pinned message in a conversation, oldest first
+    pub fn get_pinned_messages(&self, conversation_id: i64) -> SqliteResult<Vec<Message>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT m.id, m.conversation_id, m.role, m.content, m.created_at, m.tokens_used, m.model_used, m.edited_at, m.tool_call_id
+             FROM messages m
+             JOIN message_flags f ON f.message_id = m.id AND f.flag = ?2
+             WHERE m.conversation_id = ?1
+             ORDER BY m.created_at ASC",
+        )?;
+
+        let messages = stmt
+            .query_map(params![conversation_id, PINNED_FLAG], |row| {
+                let role_str: String = row.get(2)?;
+                let role = match role_str.as_str() {
+                    "user" => crate::models::MessageRole::User,
+                    "assistant" => crate::models::MessageRole::Assistant,
+                    "system" => crate::models::MessageRole::System,
+                    "tool" => crate::models::MessageRole::Tool,
+                    _ => crate::models::MessageRole::User,
+                };
+
+                Ok(Message {
+                    id: Some(row.get::<_, i64>(0)?),
+                    conversation_id: row.get(1)?,
+                    role,
+                    content: row.get(3)?,
+                    created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                        .unwrap_or_default()
+                        .with_timezone(&Utc),
+                    tokens_used: row
+                        .get::<_, Option<String>>(5)?
+                        .and_then(|s| s.parse().ok()),
+                    model_used: row.get::<_, Option<String>>(6)?.filter(|s| !s.is_empty()),
+                    metadata: None,
+                    edited_at: row
+                        .get::<_, Option<String>>(7)?
+                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                        .map(|dt| dt.with_timezone(&Utc)),
+                    tool_call_id: row.get::<_, Option<String>>(8)?.filter(|s| !s.is_empty()),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(messages)
+    }
+
+    /// Fetch the reviewer comments left on a message, oldest first
+    pub fn get_annotations_for_message(
+        &self,
+        message_id: i64,
+    ) -> SqliteResult<Vec<crate::models::MessageAnnotation>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, message_id, author, comment, created_at
+             FROM message_annotations
+             WHERE message_id = ?1
+             ORDER BY created_at ASC",
+        )?;
+
+        let annotations = stmt
+            .query_map([message_id], |row| {
+                Ok(crate::models::MessageAnnotation {
+                    id: Some(row.get(0)?),
+                    message_id: row.get(1)?,
+                    author: row.get(2)?,
+                    comment: row.get(3)?,
+                    created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                        .unwrap_or_default()
+                        .with_timezone(&Utc),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(annotations)
+    }
+
+    /// Persist a single reviewer annotation pulled from a reviewed
+    /// [`crate::review_export::ReviewExport`] bundle
+    fn add_message_annotation(
+        &self,
+        annotation: &crate::review_export::ReviewAnnotation,
+    ) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO message_annotations (message_id, author, comment, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                annotation.message_id,
+                annotation.author,
+                annotation.comment,
+                annotation.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Parse a reviewed bundle and persist every annotation it contains
+    ///
+    /// Returns the number of annotations stored, which may be fewer than the reviewer added -
+    /// see [`crate::review_export::parse_review_annotations`] for which ones get dropped.
+    pub fn import_review_annotations(
+        &self,
+        reviewed_bundle: &str,
+    ) -> crate::errors::AppResult<usize> {
+        let annotations = crate::review_export::parse_review_annotations(reviewed_bundle)?;
+        for annotation in &annotations {
+            self.add_message_annotation(annotation)?;
+        }
+        Ok(annotations.len())
+    }
+
+    /// Compute word/character/token statistics for a conversation
+    ///
+    /// Token counts are estimated from stored `tokens_used` where available and otherwise
+    /// approximated as one token per four characters, matching the rough heuristic used
+    /// elsewhere in the codebase when a provider doesn't report exact usage.
+    pub fn get_conversation_statistics(&self, id: i64) -> SqliteResult<ConversationStatistics> {
+        let messages = self.get_messages(id, None, None)?;
+
+        let mut stats = ConversationStatistics {
+            message_count: messages.len(),
+            user_message_count: 0,
+            assistant_message_count: 0,
+            system_message_count: 0,
+            tool_message_count: 0,
+            word_count: 0,
+            character_count: 0,
+            estimated_tokens: 0,
+            distinct_models: Vec::new(),
+            first_message_at: messages.first().map(|m| m.created_at),
+            last_message_at: messages.last().map(|m| m.created_at),
+            duration_seconds: None,
+        };
+
+        let mut models = std::collections::BTreeSet::new();
+        for message in &messages {
+            match message.role {
+                crate::models::MessageRole::User => stats.user_message_count += 1,
+                crate::models::MessageRole::Assistant => stats.assistant_message_count += 1,
+                crate::models::MessageRole::System => stats.system_message_count += 1,
+                crate::models::MessageRole::Tool => stats.tool_message_count += 1,
+            }
+
+            stats.word_count += message.content.split_whitespace().count();
+            stats.character_count += message.content.chars().count();
+            stats.estimated_tokens += message
+                .tokens_used
+                .map(|t| t as usize)
+                .unwrap_or_else(|| message.content.chars().count() / 4);
+
+            if let Some(model) = &message.model_used {
+                models.insert(model.clone());
+            }
+        }
+        stats.distinct_models = models.into_iter().collect();
+
+        if let (Some(first), Some(last)) = (stats.first_message_at, stats.last_message_at) {
+            stats.duration_seconds = Some((last - first).num_seconds().max(0));
+        }
+
+        Ok(stats)
+    }
+
+    /// Classify a conversation's combined message text by topic and sentiment using keyword
+    /// heuristics, and persist the result for later filtering
+    ///
+    /// Intended to run as a maintenance pass (on demand today; a scheduler could call this
+    /// periodically once one exists) rather than after every message, since sentiment rarely
+    /// changes meaningfully between a handful of new messages.
+    pub fn analyze_conversation(&self, conversation_id: i64) -> SqliteResult<crate::tagging::ConversationAnalysis> {
+        let messages = self.get_messages(conversation_id, None, None)?;
+        let combined_text = messages
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let analysis = crate::tagging::classify_conversation(&combined_text);
+        let tags_json = serde_json::to_string(&analysis.tags)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO conversation_analysis (conversation_id, topic, sentiment, tags, analyzed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(conversation_id) DO UPDATE SET
+                topic = excluded.topic,
+                sentiment = excluded.sentiment,
+                tags = excluded.tags,
+                analyzed_at = excluded.analyzed_at",
+            params![
+                conversation_id,
+                analysis.topic,
+                analysis.sentiment.as_str(),
+                tags_json,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+
+        Ok(analysis)
+    }
+
+    /// Get a conversation's most recently computed topic/sentiment analysis, if any
+    pub fn get_conversation_analysis(&self, conversation_id: i64) -> SqliteResult<Option<crate::tagging::ConversationAnalysis>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.query_row(
+            "SELECT topic, sentiment, tags FROM conversation_analysis WHERE conversation_id = ?1",
+            [conversation_id],
+            |row| {
+                let tags_json: String = row.get(2)?;
+                let tags = serde_json::from_str(&tags_json).unwrap_or_default();
+                let sentiment: String = row.get(1)?;
+                Ok(crate::tagging::ConversationAnalysis {
+                    topic: row.get(0)?,
+                    sentiment: crate::tagging::Sentiment::parse(&sentiment),
+                    tags,
+                })
+            },
+        )
+        .optional()
+    }
+
+    /// Find conversations matching an analyzed sentiment and/or topic, updated no earlier than
+    /// `since` - powers filters like "show frustrated support threads from last month"
+    pub fn filter_by_analysis(
+        &self,
+        sentiment: Option<crate::tagging::Sentiment>,
+        topic: Option<&str>,
+        since: Option<DateTime<Utc>>,
+    ) -> SqliteResult<Vec<Conversation>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let mut where_clauses = Vec::new();
+        let mut bound_params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if let Some(sentiment) = sentiment {
+            where_clauses.push("a.sentiment = ?");
+            bound_params.push(Box::new(sentiment.as_str().to_string()));
+        }
+        if let Some(topic) = topic {
+            where_clauses.push("a.topic = ?");
+            bound_params.push(Box::new(topic.to_string()));
+        }
+        if let Some(since) = since {
+            where_clauses.push("c.updated_at >= ?");
+            bound_params.push(Box::new(since.to_rfc3339()));
+        }
+
+        let where_clause = if where_clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", where_clauses.join(" AND "))
+        };
+
+        let sql = format!(
+            "SELECT c.id, c.uuid, c.title, c.persona_id, c.created_at, c.updated_at, c.archived, c.frozen
+             FROM conversations c
+             INNER JOIN conversation_analysis a ON a.conversation_id = c.id
+             {}
+             ORDER BY c.updated_at DESC",
+            where_clause
+        );
+
+        let param_refs: Vec<&dyn rusqlite::ToSql> = bound_params.iter().map(|p| p.as_ref()).collect();
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(param_refs.as_slice(), |row| {
+            Ok(Conversation {
+                id: Some(row.get::<_, i64>(0)?),
+                uuid: Uuid::parse_str(&row.get::<_, String>(1)?).unwrap_or_default(),
+                title: row.get(2)?,
+                persona_id: row.get::<_, Option<i64>>(3)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                archived: row.get::<_, String>(6)? == "true",
+                metadata: None,
+                last_opened_at: None,
+                frozen: row.get::<_, String>(7)? == "true",
+            })
+        })?;
+
+        let mut conversations = Vec::new();
+        for row in rows {
+            conversations.push(row?);
+        }
+        Ok(conversations)
+    }
+
+    /// Bulk-replace every stored reference to `old_model` with `new_model` in message history,
+    /// returning the number of messages updated. Used to migrate off a model a provider has
+    /// deprecated - see [`crate::model_registry`].
+    pub fn migrate_model_references(&self, old_model: &str, new_model: &str) -> SqliteResult<usize> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "UPDATE messages SET model_used = ?1 WHERE model_used = ?2",
+            params![new_model, old_model],
+        )
+    }
+
+    /// Delete conversation and all its messages
+    pub fn delete_conversation(&self, id: i64) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        Self::ensure_conversation_not_frozen(&conn, id)?;
+        // Messages will be deleted automatically due to CASCADE
+        conn.execute("DELETE FROM conversations WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    /// Move a conversation to the trash by stamping `deleted_at`, without deleting it or its
+    /// messages. Trashed conversations are hidden from [`Self::get_conversations`] and its
+    /// siblings but remain recoverable via [`Self::restore_conversation`] until
+    /// [`Self::purge_trash`] removes them for good.
+    pub fn trash_conversation(&self, id: i64) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        Self::ensure_conversation_not_frozen(&conn, id)?;
+        conn.execute(
+            "UPDATE conversations SET deleted_at = ?1, updated_at = ?1 WHERE id = ?2",
+            params![Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+
+    /// Restore a trashed conversation by clearing its `deleted_at` stamp
+    pub fn restore_conversation(&self, id: i64) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "UPDATE conversations SET deleted_at = NULL, updated_at = ?1 WHERE id = ?2",
+            params![Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+
+    /// Permanently delete every conversation (and its messages, via CASCADE) trashed more than
+    /// `older_than_days` ago. Returns the number of conversations purged.
+    ///
+    /// Invoked both on demand from the trash UI and periodically by [`crate::trash_scheduler`]
+    /// honoring [`crate::database::DatabaseConfig::trash_retention_days`].
+    pub fn purge_trash(&self, older_than_days: i64) -> SqliteResult<usize> {
+        self.db
+            .purge_trash(older_than_days)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+    }
+
+    /// Archive/unarchive conversation
+    pub fn set_conversation_archived(&self, id: i64, archived: bool) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "UPDATE conversations SET archived = ?1, updated_at = ?2 WHERE id = ?3",
+            [
+                &archived.to_string(),
+                &Utc::now().to_rfc3339(),
+                &id.to_string(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Attach a user-assigned tag to a conversation, in [`conversation_tags`][tbl] - a no-op if
+    /// the conversation already carries it
+    ///
+    /// [tbl]: crate::database::DatabaseManager
+    pub fn add_tag(&self, conversation_id: i64, tag: &str) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT OR IGNORE INTO conversation_tags (conversation_id, tag) VALUES (?1, ?2)",
+            params![conversation_id, tag],
+        )?;
+        Ok(())
+    }
+
+    /// Remove a user-assigned tag from a conversation, if present
+    pub fn remove_tag(&self, conversation_id: i64, tag: &str) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "DELETE FROM conversation_tags WHERE conversation_id = ?1 AND tag = ?2",
+            params![conversation_id, tag],
+        )?;
+        Ok(())
+    }
+
+    /// List a conversation's user-assigned tags, alphabetically
+    pub fn get_tags(&self, conversation_id: i64) -> SqliteResult<Vec<String>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT tag FROM conversation_tags WHERE conversation_id = ?1 ORDER BY tag ASC",
+        )?;
+        let rows = stmt.query_map(params![conversation_id], |row| row.get(0))?;
+        let mut tags = Vec::new();
+        for row in rows {
+            tags.push(row?);
+        }
+        Ok(tags)
+    }
+
+    /// Apply `action` to every conversation in `ids` inside a single transaction, so a bulk
+    /// archive/delete/tag operation over hundreds of conversations either lands completely or
+    /// not at all. `on_progress` is called after each conversation is processed, for callers that
+    /// want to report progress as the batch runs - it receives the number of ids processed so
+    /// far.
+    pub fn bulk_update_conversations(
+        &self,
+        ids: &[i64],
+        action: &crate::models::BulkConversationAction,
+        mut on_progress: impl FnMut(usize),
+    ) -> crate::errors::AppResult<()> {
+        use crate::models::BulkConversationAction;
+
+        self.db.with_transaction(|tx| {
+            for (processed, &id) in ids.iter().enumerate() {
+                match action {
+                    BulkConversationAction::Archive => {
+                        tx.execute(
+                            "UPDATE conversations SET archived = 'true', updated_at = ?1 WHERE id = ?2",
+                            params![Utc::now().to_rfc3339(), id],
+                        )
+                    }
+                    BulkConversationAction::Unarchive => {
+                        tx.execute(
+                            "UPDATE conversations SET archived = 'false', updated_at = ?1 WHERE id = ?2",
+                            params![Utc::now().to_rfc3339(), id],
+                        )
+                    }
+                    BulkConversationAction::Delete => {
+                        Self::ensure_conversation_not_frozen(tx, id)?;
+                        tx.execute("DELETE FROM conversations WHERE id = ?1", params![id])
+                    }
+                    BulkConversationAction::Tag { tag } => tx.execute(
+                        "INSERT OR IGNORE INTO conversation_tags (conversation_id, tag) VALUES (?1, ?2)",
+                        params![id, tag],
+                    ),
+                    BulkConversationAction::Untag { tag } => tx.execute(
+                        "DELETE FROM conversation_tags WHERE conversation_id = ?1 AND tag = ?2",
+                        params![id, tag],
+                    ),
+                }
+                .map_err(|e| crate::errors::AppError::database(format!("Failed to apply bulk action to conversation {}: {}", id, e)))?;
+
+                on_progress(processed + 1);
+            }
+            Ok(())
+        })
+    }
+
+    /// Freeze/unfreeze a conversation for compliance retention
+    ///
+    /// While frozen, [`Self::update_message`], [`Self::delete_message`],
+    /// [`Self::regenerate_message`], and [`Self::delete_conversation`] all refuse to act on the
+    /// conversation or its messages. Intended to be set immediately before generating a
+    /// [`crate::compliance_export::ComplianceExport`], so the exported record can't drift from
+    /// what's on disk afterward.
+    pub fn set_conversation_frozen(&self, id: i64, frozen: bool) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "UPDATE conversations SET frozen = ?1, updated_at = ?2 WHERE id = ?3",
+            [
+                &frozen.to_string(),
+                &Utc::now().to_rfc3339(),
+                &id.to_string(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Copy a conversation's messages up to and including `from_message_id` into a new
+    /// conversation linked back to it via `parent_conversation_id`/`forked_from_message_id`, so
+    /// exploring an alternative reply never loses the original thread.
+    pub fn fork_conversation(
+        &self,
+        conversation_id: i64,
+        from_message_id: i64,
+    ) -> crate::errors::AppResult<Conversation> {
+        self.db.with_transaction(|tx| {
+            let (title, persona_id): (String, Option<i64>) = tx
+                .query_row(
+                    "SELECT title, persona_id FROM conversations WHERE id = ?1",
+                    params![conversation_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()
+                .map_err(|e| crate::errors::AppError::database(format!("Failed to load conversation to fork: {}", e)))?
+                .ok_or_else(|| crate::errors::AppError::not_found(format!("Conversation {} not found", conversation_id)))?;
+
+            let cutoff: String = tx
+                .query_row(
+                    "SELECT created_at FROM messages WHERE id = ?1 AND conversation_id = ?2",
+                    params![from_message_id, conversation_id],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| crate::errors::AppError::database(format!("Failed to locate fork point message: {}", e)))?
+                .ok_or_else(|| crate::errors::AppError::not_found(format!("Message {} not found in conversation {}", from_message_id, conversation_id)))?;
+
+            let uuid_str = Uuid::new_v4().to_string();
+            let now = Utc::now().to_rfc3339();
+
+            tx.execute(
+                "INSERT INTO conversations
+                    (uuid, title, persona_id, created_at, updated_at, archived, frozen, parent_conversation_id, forked_from_message_id)
+                 VALUES (?1, ?2, ?3, ?4, ?4, 'false', 'false', ?5, ?6)",
+                params![uuid_str, title, persona_id, now, conversation_id, from_message_id],
+            )
+            .map_err(|e| crate::errors::AppError::database(format!("Failed to create forked conversation: {}", e)))?;
+
+            let new_conversation_id = tx.last_insert_rowid();
+
+            tx.execute(
+                "INSERT INTO messages (conversation_id, role, content, created_at, tokens_used, model_used, edited_at, tool_call_id)
+                 SELECT ?1, role, content, created_at, tokens_used, model_used, edited_at, tool_call_id
+                 FROM messages
+                 WHERE conversation_id = ?2 AND created_at <= ?3
+                 ORDER BY created_at ASC",
+                params![new_conversation_id, conversation_id, cutoff],
+            )
+            .map_err(|e| crate::errors::AppError::database(format!("Failed to copy messages into forked conversation: {}", e)))?;
+
+            Ok(Conversation {
+                id: Some(new_conversation_id),
+                uuid: Uuid::parse_str(&uuid_str).unwrap_or_default(),
+                title,
+                persona_id,
+                created_at: DateTime::parse_from_rfc3339(&now).unwrap_or_default().with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&now).unwrap_or_default().with_timezone(&Utc),
+                archived: false,
+                metadata: None,
+                last_opened_at: None,
+                frozen: false,
+            })
+        })
+    }
+
+    /// Deep-copy a conversation and all of its messages into a new, independent conversation
+    /// with a fresh UUID and fresh message IDs, so editing the copy never touches the original.
+    /// Unlike [`Self::fork_conversation`], the copy has no lineage back to its source.
+    pub fn duplicate_conversation(&self, conversation_id: i64) -> crate::errors::AppResult<Conversation> {
+        self.db.with_transaction(|tx| {
+            let (title, persona_id): (String, Option<i64>) = tx
+                .query_row(
+                    "SELECT title, persona_id FROM conversations WHERE id = ?1",
+                    params![conversation_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()
+                .map_err(|e| crate::errors::AppError::database(format!("Failed to load conversation to duplicate: {}", e)))?
+                .ok_or_else(|| crate::errors::AppError::not_found(format!("Conversation {} not found", conversation_id)))?;
+
+            let new_uuid = Uuid::new_v4().to_string();
+            let now = Utc::now().to_rfc3339();
+            let new_title = format!("{} (Copy)", title);
+
+            tx.execute(
+                "INSERT INTO conversations (uuid, title, persona_id, created_at, updated_at, archived, frozen)
+                 VALUES (?1, ?2, ?3, ?4, ?4, 'false', 'false')",
+                params![new_uuid, new_title, persona_id, now],
+            )
+            .map_err(|e| crate::errors::AppError::database(format!("Failed to create duplicated conversation: {}", e)))?;
+
+            let new_conversation_id = tx.last_insert_rowid();
+
+            let mut stmt = tx
+                .prepare(
+                    "SELECT role, content, created_at, tokens_used, model_used, tool_call_id
+                     FROM messages WHERE conversation_id = ?1 ORDER BY created_at ASC",
+                )
+                .map_err(|e| crate::errors::AppError::database(format!("Failed to read messages to duplicate: {}", e)))?;
+            let messages = stmt
+                .query_map(params![conversation_id], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, Option<i32>>(3)?,
+                        row.get::<_, Option<String>>(4)?,
+                        row.get::<_, Option<String>>(5)?,
+                    ))
+                })
+                .map_err(|e| crate::errors::AppError::database(format!("Failed to read messages to duplicate: {}", e)))?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| crate::errors::AppError::database(format!("Failed to read messages to duplicate: {}", e)))?;
+            drop(stmt);
+
+            for (role, content, created_at, tokens_used, model_used, tool_call_id) in messages {
+                tx.execute(
+                    "INSERT INTO messages (id, conversation_id, role, content, created_at, tokens_used, model_used, tool_call_id)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        Uuid::new_v4().to_string(),
+                        new_conversation_id,
+                        role,
+                        content,
+                        created_at,
+                        tokens_used,
+                        model_used,
+                        tool_call_id
+                    ],
+                )
+                .map_err(|e| crate::errors::AppError::database(format!("Failed to copy message into duplicated conversation: {}", e)))?;
+            }
+
+            Ok(Conversation {
+                id: Some(new_conversation_id),
+                uuid: Uuid::parse_str(&new_uuid).unwrap_or_default(),
+                title: new_title,
+                persona_id,
+                created_at: DateTime::parse_from_rfc3339(&now).unwrap_or_default().with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&now).unwrap_or_default().with_timezone(&Utc),
+                archived: false,
+                metadata: None,
+                last_opened_at: None,
+                frozen: false,
+            })
+        })
+    }
+
+    /// Merge `source_id` into `target_id`: every message from the source is reassigned to the
+    /// target (so [`Self::get_messages`]'s `ORDER BY created_at` interleaves the two histories
+    /// automatically), the conversations' [`crate::tagging::ConversationAnalysis`] tags are
+    /// unioned onto the target, and the now-empty source conversation is deleted. Refuses to run
+    /// if either conversation is frozen.
+    pub fn merge_conversations(&self, source_id: i64, target_id: i64) -> crate::errors::AppResult<()> {
+        if source_id == target_id {
+            return Err(crate::errors::AppError::validation(
+                "Cannot merge a conversation into itself",
+            ));
+        }
+
+        self.db.with_transaction(|tx| {
+            for id in [source_id, target_id] {
+                let frozen: String = tx
+                    .query_row("SELECT frozen FROM conversations WHERE id = ?1", params![id], |row| row.get(0))
+                    .optional()
+                    .map_err(|e| crate::errors::AppError::database(format!("Failed to check conversation {}: {}", id, e)))?
+                    .ok_or_else(|| crate::errors::AppError::not_found(format!("Conversation {} not found", id)))?;
+                if frozen == "true" {
+                    return Err(crate::errors::AppError::validation(format!(
+                        "Conversation {} is frozen for compliance retention and cannot be merged",
+                        id
+                    )));
+                }
+            }
+
+            let load_tags = |tx: &rusqlite::Transaction, id: i64| -> crate::errors::AppResult<Vec<String>> {
+                tx.query_row("SELECT tags FROM conversation_analysis WHERE conversation_id = ?1", params![id], |row| {
+                    row.get::<_, String>(0)
+                })
+                .optional()
+                .map_err(|e| crate::errors::AppError::database(format!("Failed to load tags for conversation {}: {}", id, e)))?
+                .map(|tags_json| serde_json::from_str(&tags_json).unwrap_or_default())
+                .map(Ok)
+                .unwrap_or(Ok(Vec::new()))
+            };
+
+            let mut united_tags = load_tags(tx, target_id)?;
+            for tag in load_tags(tx, source_id)? {
+                if !united_tags.contains(&tag) {
+                    united_tags.push(tag);
+                }
+            }
+
+            tx.execute(
+                "UPDATE messages SET conversation_id = ?1 WHERE conversation_id = ?2",
+                params![target_id, source_id],
+            )
+            .map_err(|e| crate::errors::AppError::database(format!("Failed to move messages during merge: {}", e)))?;
+
+            if !united_tags.is_empty() {
+                let tags_json = serde_json::to_string(&united_tags)
+                    .map_err(|e| crate::errors::AppError::database(format!("Failed to serialize united tags: {}", e)))?;
+                tx.execute(
+                    "INSERT INTO conversation_analysis (conversation_id, topic, sentiment, tags, analyzed_at)
+                     VALUES (?1, '', 'neutral', ?2, ?3)
+                     ON CONFLICT(conversation_id) DO UPDATE SET tags = excluded.tags, analyzed_at = excluded.analyzed_at",
+                    params![target_id, tags_json, Utc::now().to_rfc3339()],
+                )
+                .map_err(|e| crate::errors::AppError::database(format!("Failed to save united tags: {}", e)))?;
+            }
+
+            tx.execute(
+                "UPDATE conversations SET updated_at = ?1 WHERE id = ?2",
+                params![Utc::now().to_rfc3339(), target_id],
+            )
+            .map_err(|e| crate::errors::AppError::database(format!("Failed to touch target conversation: {}", e)))?;
+
+            tx.execute("DELETE FROM conversations WHERE id = ?1", params![source_id])
+                .map_err(|e| crate::errors::AppError::database(format!("Failed to delete merged source conversation: {}", e)))?;
+
+            Ok(())
+        })
+    }
+
+    /// Where a conversation came from, for UI lineage breadcrumbs ("forked from conversation X
+    /// at message Y"). `None` if the conversation was not created via `fork_conversation`.
+    pub fn get_conversation_lineage(&self, id: i64) -> SqliteResult<Option<ConversationLineage>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let lineage = conn
+            .query_row(
+                "SELECT parent_conversation_id, forked_from_message_id FROM conversations WHERE id = ?1",
+                [id],
+                |row| {
+                    Ok((
+                        row.get::<_, Option<i64>>(0)?,
+                        row.get::<_, Option<i64>>(1)?,
+                    ))
+                },
+            )
+            .optional()?
+            .and_then(|(parent_conversation_id, forked_from_message_id)| {
+                parent_conversation_id.map(|parent_conversation_id| ConversationLineage {
+                    parent_conversation_id,
+                    forked_from_message_id,
+                })
+            });
+        Ok(lineage)
+    }
+
+    /// Fetch a conversation's system prompt/model/temperature/max_tokens overrides, if any have
+    /// been set via [`Self::upsert_conversation_settings`]
+    pub fn get_conversation_settings(
+        &self,
+        conversation_id: i64,
+    ) -> SqliteResult<Option<crate::models::ConversationSettings>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.query_row(
+            "SELECT conversation_id, system_prompt, model, temperature, max_tokens, profile_name, updated_at
+             FROM conversation_settings
+             WHERE conversation_id = ?1",
+            [conversation_id],
+            |row| {
+                Ok(crate::models::ConversationSettings {
+                    conversation_id: row.get(0)?,
+                    system_prompt: row.get(1)?,
+                    model: row.get(2)?,
+                    temperature: row.get::<_, Option<f64>>(3)?.map(|t| t as f32),
+                    max_tokens: row.get(4)?,
+                    profile_name: row.get(5)?,
+                    updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(6)?)
+                        .unwrap_or_default()
+                        .with_timezone(&Utc),
+                })
+            },
+        )
+        .optional()
+    }
+
+    /// Set a conversation's system prompt/model/temperature/max_tokens/profile_name overrides,
+    /// replacing any that were set before - pass `None` for a field to clear that override
+    #[allow(clippy::too_many_arguments)]
+    pub fn upsert_conversation_settings(
+        &self,
+        conversation_id: i64,
+        system_prompt: Option<String>,
+        model: Option<String>,
+        temperature: Option<f32>,
+        max_tokens: Option<i32>,
+        profile_name: Option<String>,
+    ) -> SqliteResult<crate::models::ConversationSettings> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let updated_at = Utc::now();
+
+        conn.execute(
+            "INSERT INTO conversation_settings (conversation_id, system_prompt, model, temperature, max_tokens, profile_name, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(conversation_id) DO UPDATE SET
+                system_prompt = excluded.system_prompt,
+                model = excluded.model,
+                temperature = excluded.temperature,
+                max_tokens = excluded.max_tokens,
+                profile_name = excluded.profile_name,
+                updated_at = excluded.updated_at",
+            params![
+                conversation_id,
+                system_prompt,
+                model,
+                temperature.map(|t| t as f64),
+                max_tokens,
+                profile_name,
+                updated_at.to_rfc3339(),
+            ],
+        )?;
+
+        Ok(crate::models::ConversationSettings {
+            conversation_id,
+            system_prompt,
+            model,
+            temperature,
+            max_tokens,
+            profile_name,
+            updated_at,
+        })
+    }
+
+    /// Error out if the given conversation is frozen; a no-op if it isn't (or doesn't exist -
+    /// the caller's own query will surface that).
+    fn ensure_conversation_not_frozen(conn: &Connection, conversation_id: i64) -> SqliteResult<()> {
+        let frozen: String = conn
+            .query_row(
+                "SELECT frozen FROM conversations WHERE id = ?1",
+                [conversation_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .unwrap_or_else(|| "false".to_string());
+
+        if frozen == "true" {
+            return Err(rusqlite::Error::ToSqlConversionFailure(Box::new(
+                crate::errors::AppError::validation(
+                    "Conversation is frozen for compliance retention and cannot be modified",
+                ),
+            )));
+        }
+        Ok(())
+    }
+
+    /// Error out if the conversation owning the given message is frozen
+    fn ensure_message_conversation_not_frozen(conn: &Connection, message_id: i64) -> SqliteResult<()> {
+        let frozen: Option<String> = conn
+            .query_row(
+                "SELECT c.frozen FROM messages m
+                 JOIN conversations c ON c.id = m.conversation_id
+                 WHERE m.id = ?1",
+                [message_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        if frozen.as_deref() == Some("true") {
+            return Err(rusqlite::Error::ToSqlConversionFailure(Box::new(
+                crate::errors::AppError::validation(
+                    "Conversation is frozen for compliance retention and cannot be modified",
+                ),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Persona service - Manages AI character profiles
+pub struct PersonaService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl PersonaService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    /// Create new persona
+    pub fn create_persona(
+        &self,
+        name: String,
+        description: Option<String>,
+        system_prompt: String,
+    ) -> SqliteResult<Persona> {
+        let persona = Persona::new(name, description, system_prompt);
+
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "INSERT INTO personas (name, description, system_prompt, created_at, updated_at, active)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)"
+        )?;
+
+        let description_str = persona.description.as_deref().unwrap_or("");
+        stmt.execute([
+            &persona.name,
+            description_str,
+            &persona.system_prompt,
+            &persona.created_at.to_rfc3339(),
+            &persona.updated_at.to_rfc3339(),
+            &persona.active.to_string(),
+        ])?;
+
+        let id = conn.last_insert_rowid();
+        let mut result = persona;
+        result.id = Some(id);
+        Ok(result)
+    }
+
+    /// Get all active personas
+    pub fn get_personas(&self) -> SqliteResult<Vec<Persona>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, description, system_prompt, created_at, updated_at, active
+             FROM personas
+             WHERE active = 'true'
+             ORDER BY name ASC",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(Persona {
+                id: Some(row.get::<_, i64>(0)?),
+                name: row.get(1)?,
+                description: {
+                    let desc: String = row.get(2)?;
+                    if desc.is_empty() {
+                        None
+                    } else {
+                        Some(desc)
+                    }
+                },
+                system_prompt: row.get(3)?,
+                avatar_path: None,
+                memory_context: None,
+                settings: None,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                active: row.get::<_, String>(6)? == "true",
+            })
+        })?;
+
+        let mut personas = Vec::new();
+        for row in rows {
+            personas.push(row?);
+        }
+        Ok(personas)
+    }
+
+    /// Get persona by ID
+    pub fn get_persona(&self, id: i64) -> SqliteResult<Option<Persona>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, description, system_prompt, created_at, updated_at, active
+             FROM personas
+             WHERE id = ?1",
+        )?;
+
+        let mut rows = stmt.query_map([id], |row| {
+            Ok(Persona {
+                id: Some(row.get::<_, i64>(0)?),
+                name: row.get(1)?,
+                description: {
+                    let desc: String = row.get(2)?;
+                    if desc.is_empty() {
+                        None
+                    } else {
+                        Some(desc)
+                    }
+                },
+                system_prompt: row.get(3)?,
+                avatar_path: None,
+                memory_context: None,
+                settings: None,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                active: row.get::<_, String>(6)? == "true",
+            })
+        })?;
+
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Update persona
+    pub fn update_persona(
+        &self,
+        id: i64,
+        name: Option<String>,
+        description: Option<String>,
+        system_prompt: Option<String>,
+    ) -> SqliteResult<()> {
+        let builder = crate::database::query::UpdateBuilder::new()
+            .set("name", name)
+            .set("description", description)
+            .set("system_prompt", system_prompt);
+
+        if builder.is_empty() {
+            return Ok(());
+        }
+
+        let (set_clause, params) = builder.finish(("updated_at", Utc::now().to_rfc3339()), id);
+        let query = format!("UPDATE personas SET {} WHERE id = ?", set_clause);
+
+        let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(&query, param_refs.as_slice())?;
+        Ok(())
+    }
+
+    /// Delete persona, cleaning up its avatar file (if any) so it doesn't become orphaned
+    pub fn delete_persona(&self, id: i64) -> SqliteResult<()> {
+        if let Ok(Some(avatar_path)) = self.get_persona_avatar(id) {
+            match std::fs::remove_file(&avatar_path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => tracing::warn!("Failed to remove orphaned avatar file: {}", e),
+            }
+        }
+
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute("DELETE FROM personas WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    /// Directory persona avatar images are copied into, inside the app data directory
+    fn avatars_dir() -> crate::errors::AppResult<std::path::PathBuf> {
+        crate::platform::get_app_data_dir()
+            .map(|dir| dir.join("avatars"))
+            .ok_or_else(|| crate::errors::AppError::io("Failed to determine app data directory"))
+    }
+
+    /// Validate and copy an image file into the app-managed avatars directory, associating it
+    /// with `persona_id`. Replaces any avatar previously set for this persona. Returns the
+    /// stored filename (relative to the avatars directory).
+    ///
+    /// There's no image-processing dependency in this tree to decode and re-encode arbitrary
+    /// image formats, so the source file is copied as-is rather than resized - the frontend is
+    /// expected to display it at whatever size it needs via CSS.
+    pub fn set_persona_avatar(&self, persona_id: i64, image_path: &str) -> crate::errors::AppResult<String> {
+        let validator = crate::validation::InputValidator::default();
+        let validated_path = validator.validate_file_path(image_path)?;
+
+        let source = std::path::Path::new(&validated_path);
+        if !source.is_file() {
+            return Err(crate::errors::AppError::validation(format!(
+                "Avatar image not found: {}",
+                validated_path
+            )));
+        }
+
+        let extension = source
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("png");
+
+        let avatars_dir = Self::avatars_dir()?;
+        std::fs::create_dir_all(&avatars_dir)
+            .map_err(|e| crate::errors::AppError::io(format!("Failed to create avatars directory: {}", e)))?;
+
+        let image_size = std::fs::metadata(source)
+            .map_err(|e| crate::errors::AppError::io(format!("Failed to read avatar image metadata: {}", e)))?
+            .len();
+        crate::platform::ensure_disk_space(&avatars_dir, image_size)?;
+
+        if let Some(old_avatar_path) = self.get_persona_avatar(persona_id)? {
+            match std::fs::remove_file(&old_avatar_path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(crate::errors::AppError::io(format!("Failed to remove previous avatar: {}", e))),
+            }
+        }
+
+        let filename = format!("persona_{}.{}", persona_id, extension);
+        let dest = avatars_dir.join(&filename);
+        std::fs::copy(source, &dest)
+            .map_err(|e| crate::errors::AppError::io(format!("Failed to copy avatar image: {}", e)))?;
+
+        let conn = self.db.get_connection()?;
+        conn.execute(
+            "UPDATE personas SET avatar_path = ?1, updated_at = ?2 WHERE id = ?3",
+            params![filename, Utc::now().to_rfc3339(), persona_id],
+        )
+        .map_err(|e| crate::errors::AppError::database(format!("Failed to save avatar path: {}", e)))?;
+
+        Ok(filename)
+    }
+
+    /// Absolute path to a persona's avatar image, if one has been set via `set_persona_avatar`
+    pub fn get_persona_avatar(&self, persona_id: i64) -> crate::errors::AppResult<Option<std::path::PathBuf>> {
+        let conn = self.db.get_connection()?;
+        let avatar_filename: Option<String> = conn
+            .query_row(
+                "SELECT avatar_path FROM personas WHERE id = ?1",
+                [persona_id],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .optional()
+            .map_err(|e| crate::errors::AppError::database(format!("Failed to load avatar path: {}", e)))?
+            .flatten();
+
+        let Some(avatar_filename) = avatar_filename else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self::avatars_dir()?.join(avatar_filename)))
+    }
+
+    /// A persona's free-form settings (model preference, temperature, response style, etc),
+    /// stored as JSON in the `preferences` column. Returns `None` if none have been saved yet,
+    /// or if the stored JSON no longer deserializes (e.g. after an incompatible settings shape
+    /// change) rather than failing the whole lookup.
+    pub fn get_persona_settings(&self, persona_id: i64) -> SqliteResult<Option<crate::models::PersonaSettings>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let preferences: Option<String> = conn
+            .query_row(
+                "SELECT preferences FROM personas WHERE id = ?1",
+                [persona_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+
+        Ok(preferences.and_then(|json| serde_json::from_str(&json).ok()))
+    }
+
+    /// Replace a persona's settings, serialized to JSON in the `preferences` column
+    pub fn set_persona_settings(&self, persona_id: i64, settings: &crate::models::PersonaSettings) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let json = serde_json::to_string(settings).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "UPDATE personas SET preferences = ?1, updated_at = ?2 WHERE id = ?3",
+            params![json, Utc::now().to_rfc3339(), persona_id],
+        )?;
+        Ok(())
+    }
+}
+
+/// How many of a persona's highest-relevance memories [`crate::commands::send_ai_request`]
+/// injects into the system prompt
+pub const PERSONA_MEMORY_INJECTION_LIMIT: i64 = 5;
+
+/// Stores small facts a persona should remember about its user across conversations,
+/// independent of any one conversation's history
+///
+/// `Persona.memory_context` was never populated by anything; this backs it with a normalized
+/// table instead of a loosely-typed JSON blob, matching how the rest of the schema handles
+/// per-entity collections (e.g. `snippets`, `shortcuts`).
+pub struct PersonaMemoryService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl PersonaMemoryService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    fn row_to_memory(row: &rusqlite::Row) -> rusqlite::Result<crate::models::PersonaMemory> {
+        Ok(crate::models::PersonaMemory {
+            id: Some(row.get(0)?),
+            persona_id: row.get(1)?,
+            fact: row.get(2)?,
+            relevance_score: row.get(3)?,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+        })
+    }
+
+    /// Record a new fact about `persona_id`'s user, defaulting to the median relevance score
+    /// (`1.0`) when the caller doesn't have a more specific one to assign yet
+    pub fn append_persona_memory(
+        &self,
+        persona_id: i64,
+        fact: String,
+        relevance_score: Option<f32>,
+    ) -> SqliteResult<crate::models::PersonaMemory> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let created_at = Utc::now();
+        let relevance_score = relevance_score.unwrap_or(1.0);
+
+        conn.execute(
+            "INSERT INTO persona_memories (persona_id, fact, relevance_score, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![persona_id, fact, relevance_score, created_at.to_rfc3339()],
+        )?;
+
+        Ok(crate::models::PersonaMemory {
+            id: Some(conn.last_insert_rowid()),
+            persona_id,
+            fact,
+            relevance_score,
+            created_at,
+        })
+    }
+
+    /// The `limit` highest-relevance memories for `persona_id`, most relevant first (ties
+    /// broken by recency). `limit` of `None` returns every memory.
+    pub fn get_persona_memory(
+        &self,
+        persona_id: i64,
+        limit: Option<i64>,
+    ) -> SqliteResult<Vec<crate::models::PersonaMemory>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, persona_id, fact, relevance_score, created_at FROM persona_memories
+             WHERE persona_id = ?1
+             ORDER BY relevance_score DESC, created_at DESC
+             LIMIT ?2",
+        )?;
+
+        let rows = stmt.query_map(
+            params![persona_id, limit.unwrap_or(-1)],
+            Self::row_to_memory,
+        )?;
+
+        let mut memories = Vec::new();
+        for row in rows {
+            memories.push(row?);
+        }
+        Ok(memories)
+    }
+
+    /// Forget everything remembered about `persona_id`'s user
+    pub fn clear_persona_memory(&self, persona_id: i64) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute("DELETE FROM persona_memories WHERE persona_id = ?1", [persona_id])?;
+        Ok(())
+    }
+}
+
+/// API service - Manages external AI service configurations
+///
+/// The actual API key never touches disk: it is stored in the OS keychain via
+/// [`crate::keychain::KeychainManager`], with only a sentinel marker in the `api_configs.api_key`
+/// column. Rows written before this wiring existed still hold the plaintext key - those are
+/// migrated into the keychain transparently the first time they are read.
+pub struct ApiService {
+    db: std::sync::Arc<DatabaseManager>,
+    keychain: crate::keychain::KeychainManager,
+}
+
+impl ApiService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self {
+            db,
+            keychain: crate::keychain::KeychainManager::new(),
+        }
+    }
+
+    /// Store a named API profile, with the key moved into the OS keychain
+    ///
+    /// `profile_name` identifies this config (e.g. "work-openai", "personal-openai") - a
+    /// provider may have several, unlike the single `provider`-keyed row the table used to
+    /// allow. The keychain entry is keyed by `profile_name` too, so two profiles for the same
+    /// provider never share (and overwrite) one key. The first profile stored for a provider
+    /// becomes its default automatically; re-storing an existing profile preserves whatever
+    /// default flag it already had. `rate_limits`, if set, is also pushed into
+    /// [`crate::ratelimit`]'s registry immediately so it takes effect on the very next request
+    /// without waiting for a `get_api_config` read.
+    pub fn store_api_config(
+        &self,
+        profile_name: String,
+        provider: String,
+        api_key: String,
+        base_url: Option<String>,
+        organization: Option<String>,
+        project: Option<String>,
+        extra_headers: Vec<(String, String)>,
+        rate_limits: Option<crate::models::RateLimits>,
+    ) -> SqliteResult<()> {
+        self.keychain
+            .store_api_key(&profile_name, &api_key)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let extra_headers_json = serde_json::to_string(&extra_headers)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let rate_limits_json = rate_limits
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let existing_is_default: Option<String> = conn
+            .query_row("SELECT is_default FROM api_configs WHERE id = ?1", [&profile_name], |row| row.get(0))
+            .optional()?;
+        let is_default = match existing_is_default {
+            Some(value) => value == "true",
+            None => {
+                let has_other_profile: i64 = conn.query_row(
+                    "SELECT EXISTS(SELECT 1 FROM api_configs WHERE provider = ?1 AND active = 'true')",
+                    [&provider],
+                    |row| row.get(0),
+                )?;
+                has_other_profile == 0
+            }
+        };
+
+        conn.execute(
+            "INSERT OR REPLACE INTO api_configs
+             (id, provider, profile_name, api_key, base_url, organization, project, extra_headers, rate_limits, is_default, created_at, updated_at, active)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![
+                profile_name,
+                provider,
+                profile_name,
+                crate::keychain::KEYCHAIN_MANAGED_MARKER,
+                base_url.unwrap_or_default(),
+                organization,
+                project,
+                extra_headers_json,
+                rate_limits_json,
+                if is_default { "true" } else { "false" },
+                Utc::now().to_rfc3339(),
+                Utc::now().to_rfc3339(),
+                "true",
+            ],
+        )?;
+
+        crate::ratelimit::configure(&provider, rate_limits.as_ref());
+        Ok(())
+    }
+
+    /// Retrieve a provider's default profile, reading the key back from the OS keychain
+    ///
+    /// Resolves to whichever profile [`Self::set_default_profile`] (or the first
+    /// [`Self::store_api_config`] call for this provider) marked as the default - callers that
+    /// only know a provider name, not a specific profile, get that one. Use
+    /// [`Self::get_api_profile`] to look up a specific named profile instead.
+    pub fn get_api_config(&self, provider: &str) -> SqliteResult<Option<crate::models::ApiConfig>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let profile_name: Option<String> = conn
+            .query_row(
+                "SELECT id FROM api_configs WHERE provider = ?1 AND active = 'true' AND is_default = 'true'",
+                [provider],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let Some(profile_name) = profile_name else {
+            return Ok(None);
+        };
+
+        self.get_api_profile(&profile_name)
+    }
+
+    /// Retrieve a specific named API profile, reading the key back from the OS keychain
+    ///
+    /// If the stored row predates profile-aware storage (its `api_key` column holds the
+    /// plaintext key rather than [`crate::keychain::KEYCHAIN_MANAGED_MARKER`]), the key is moved
+    /// into the keychain and the row is rewritten to the marker before returning. Also re-syncs
+    /// [`crate::ratelimit`]'s registry from the stored `rate_limits`, so a profile configured
+    /// before the process started (or on another instance) still gets its limits enforced.
+    pub fn get_api_profile(&self, profile_name: &str) -> SqliteResult<Option<crate::models::ApiConfig>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT provider, api_key, base_url, organization, project, extra_headers, rate_limits, is_default
+             FROM api_configs WHERE id = ?1 AND active = 'true'",
+        )?;
+
+        let row = stmt
+            .query_row([profile_name], |row| {
+                let provider: String = row.get(0)?;
+                let stored_key: String = row.get(1)?;
+                let base_url: Option<String> = {
+                    let url: String = row.get(2)?;
+                    if url.is_empty() {
+                        None
+                    } else {
+                        Some(url)
+                    }
+                };
+                let organization: Option<String> = row.get(3)?;
+                let project: Option<String> = row.get(4)?;
+                let extra_headers_json: Option<String> = row.get(5)?;
+                let extra_headers: Vec<(String, String)> = extra_headers_json
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default();
+                let rate_limits_json: Option<String> = row.get(6)?;
+                let rate_limits: Option<crate::models::RateLimits> = rate_limits_json
+                    .and_then(|json| serde_json::from_str(&json).ok());
+                let is_default: String = row.get(7)?;
+                Ok((provider, stored_key, base_url, organization, project, extra_headers, rate_limits, is_default == "true"))
+            })
+            .optional()?;
+
+        let Some((provider, stored_key, base_url, organization, project, extra_headers, rate_limits, is_default)) = row else {
+            return Ok(None);
+        };
+
+        let api_key = if stored_key == crate::keychain::KEYCHAIN_MANAGED_MARKER {
+            self.keychain
+                .get_api_key(profile_name)
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?
+        } else {
+            self.keychain
+                .store_api_key(profile_name, &stored_key)
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+            conn.execute(
+                "UPDATE api_configs SET api_key = ?1 WHERE id = ?2",
+                params![crate::keychain::KEYCHAIN_MANAGED_MARKER, profile_name],
+            )?;
+            stored_key
+        };
+
+        crate::ratelimit::configure(&provider, rate_limits.as_ref());
+
+        Ok(Some(crate::models::ApiConfig {
+            profile_name: profile_name.to_string(),
+            provider,
+            is_default,
+            api_key,
+            base_url,
+            organization,
+            project,
+            extra_headers,
+            rate_limits,
+        }))
+    }
+
+    /// List stored profiles, without pulling any of their keys out of the keychain
+    ///
+    /// Pass `provider` to list only that provider's profiles, or `None` for every profile
+    /// across every provider (e.g. to populate a profile picker).
+    pub fn list_api_profiles(&self, provider: Option<&str>) -> SqliteResult<Vec<crate::models::ApiProfileSummary>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT profile_name, provider, is_default FROM api_configs
+             WHERE active = 'true' AND (?1 IS NULL OR provider = ?1)
+             ORDER BY provider, profile_name",
+        )?;
+        let rows = stmt.query_map([provider], |row| {
+            let is_default: String = row.get(2)?;
+            Ok(crate::models::ApiProfileSummary {
+                profile_name: row.get(0)?,
+                provider: row.get(1)?,
+                is_default: is_default == "true",
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Make `profile_name` the default profile for its provider, demoting any sibling profile
+    /// that previously held that spot
+    pub fn set_default_profile(&self, profile_name: &str) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let provider: String = conn.query_row(
+            "SELECT provider FROM api_configs WHERE id = ?1 AND active = 'true'",
+            [profile_name],
+            |row| row.get(0),
+        )?;
+
+        conn.execute(
+            "UPDATE api_configs SET is_default = 'false' WHERE provider = ?1",
+            [&provider],
+        )?;
+        conn.execute(
+            "UPDATE api_configs SET is_default = 'true' WHERE id = ?1",
+            [profile_name],
+        )?;
+        Ok(())
+    }
+
+    /// List providers with at least one active stored profile
+    ///
+    /// This only reflects whether credentials are on file, not whether the provider is
+    /// currently reachable - a live reachability check belongs in
+    /// [`crate::commands::check_ai_provider_availability`], which makes a network request and
+    /// is too slow to bundle into an aggregation call.
+    pub fn list_configured_providers(&self) -> SqliteResult<Vec<String>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare("SELECT DISTINCT provider FROM api_configs WHERE active = 'true'")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        rows.collect()
+    }
+
+    /// Delete a named API profile
+    ///
+    /// If the deleted profile was its provider's default and another active profile remains for
+    /// that provider, the most recently updated one is promoted to default so
+    /// [`Self::get_api_config`] doesn't silently stop resolving for that provider.
+    pub fn delete_api_config(&self, profile_name: &str) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let row: Option<(String, String)> = conn
+            .query_row(
+                "SELECT provider, is_default FROM api_configs WHERE id = ?1 AND active = 'true'",
+                [profile_name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((provider, was_default)) = row else {
+            return Ok(());
+        };
+
+        conn.execute(
+            "UPDATE api_configs SET active = 'false', updated_at = ?1 WHERE id = ?2",
+            params![Utc::now().to_rfc3339(), profile_name],
+        )?;
+        let _ = self.keychain.delete_api_key(profile_name);
+
+        if was_default == "true" {
+            let successor: Option<String> = conn
+                .query_row(
+                    "SELECT id FROM api_configs WHERE provider = ?1 AND active = 'true'
+                     ORDER BY updated_at DESC LIMIT 1",
+                    [&provider],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if let Some(successor) = successor {
+                conn.execute("UPDATE api_configs SET is_default = 'true' WHERE id = ?1", [&successor])?;
+            } else {
+                crate::ratelimit::configure(&provider, None);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A single open conversation within a saved session, with its scroll position
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OpenConversationState {
+    pub conversation_id: i64,
+    pub scroll_position: i64,
+}
+
+/// Snapshot of the last active session, used to restore open conversations at startup
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SessionSnapshot {
+    pub open_conversations: Vec<OpenConversationState>,
+    pub auto_restore: bool,
+}
+
+/// Session service - Persists and restores the set of open conversations across app launches
+pub struct SessionService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl SessionService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    /// Persist the current set of open conversations and the auto-restore preference
+    pub fn save_session(
+        &self,
+        open_conversations: &[OpenConversationState],
+        auto_restore: bool,
+    ) -> SqliteResult<()> {
+        let serialized = serde_json::to_string(open_conversations)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO session_state (id, open_conversations, auto_restore, updated_at)
+             VALUES (1, ?1, ?2, ?3)
+             ON CONFLICT(id) DO UPDATE SET
+                open_conversations = excluded.open_conversations,
+                auto_restore = excluded.auto_restore,
+                updated_at = excluded.updated_at",
+            params![serialized, auto_restore, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Retrieve the last saved session, if one exists
+    pub fn get_last_session(&self) -> SqliteResult<Option<SessionSnapshot>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT open_conversations, auto_restore FROM session_state WHERE id = 1",
+        )?;
+
+        let mut rows = stmt.query_map([], |row| {
+            let open_conversations_json: String = row.get(0)?;
+            let auto_restore: bool = row.get(1)?;
+            let open_conversations: Vec<OpenConversationState> =
+                serde_json::from_str(&open_conversations_json).unwrap_or_default();
+            Ok(SessionSnapshot {
+                open_conversations,
+                auto_restore,
+            })
+        })?;
+
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Grimoire service - Manages the local knowledge-base entries stored in `grimoire_entries`
+pub struct GrimoireService {
+    db: std::sync::Arc<DatabaseManager>,
+    keychain: crate::keychain::KeychainManager,
+}
+
+impl GrimoireService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self {
+            db,
+            keychain: crate::keychain::KeychainManager::new(),
+        }
+    }
+
+    /// Load the grimoire entry encryption key, generating one in the OS keychain on first use
+    fn load_grimoire_key(&self) -> SqliteResult<[u8; 32]> {
+        let hex_key = self
+            .keychain
+            .get_or_create_grimoire_encryption_key()
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        crate::grimoire_crypto::key_from_hex(&hex_key)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+    }
+
+    /// Decrypt `content` if `encrypted` is set, else return it unchanged
+    fn decrypt_if_needed(&self, content: String, encrypted: bool) -> SqliteResult<String> {
+        if !encrypted {
+            return Ok(content);
+        }
+        let key = self.load_grimoire_key()?;
+        crate::grimoire_crypto::decrypt(&content, &key)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+    }
+
+    /// Decrypt every entry in `entries` that is marked `encrypted`, in place
+    fn decrypt_entries(&self, entries: Vec<crate::models::GrimoireEntry>) -> SqliteResult<Vec<crate::models::GrimoireEntry>> {
+        entries
+            .into_iter()
+            .map(|mut entry| {
+                entry.content = self.decrypt_if_needed(entry.content, entry.encrypted)?;
+                Ok(entry)
+            })
+            .collect()
+    }
+
+    /// Encrypt a grimoire entry's content at rest with the keychain-managed grimoire key,
+    /// marking it `encrypted`. No-op (returns `Ok(false)`) if the entry doesn't exist or is
+    /// already encrypted.
+    pub fn encrypt_entry(&self, id: &str) -> SqliteResult<bool> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let entry = match Self::query_entry(&conn, id)? {
+            Some(entry) => entry,
+            None => return Ok(false),
+        };
+        if entry.encrypted {
+            return Ok(false);
+        }
+
+        let key = self.load_grimoire_key()?;
+        let ciphertext = crate::grimoire_crypto::encrypt(&entry.content, &key)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "UPDATE grimoire_entries SET content = ?2, encrypted = 'true', updated_at = ?3 WHERE id = ?1",
+            params![id, ciphertext, Utc::now().to_rfc3339()],
+        )?;
+        tracing::info!("Encrypted grimoire entry {} at rest", id);
+        Ok(true)
+    }
+
+    /// Decrypt a grimoire entry's content back to plaintext at rest, clearing `encrypted`.
+    /// No-op (returns `Ok(false)`) if the entry doesn't exist or isn't encrypted.
+    pub fn decrypt_entry(&self, id: &str) -> SqliteResult<bool> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let entry = match Self::query_entry(&conn, id)? {
+            Some(entry) => entry,
+            None => return Ok(false),
+        };
+        if !entry.encrypted {
+            return Ok(false);
+        }
+
+        let key = self.load_grimoire_key()?;
+        let plaintext = crate::grimoire_crypto::decrypt(&entry.content, &key)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "UPDATE grimoire_entries SET content = ?2, encrypted = 'false', updated_at = ?3 WHERE id = ?1",
+            params![id, plaintext, Utc::now().to_rfc3339()],
+        )?;
+        tracing::info!("Decrypted grimoire entry {} at rest", id);
+        Ok(true)
+    }
+
+    /// Create a new grimoire entry and return its id
+    ///
+    /// If `template` is set, `fields` is validated against that template's schema (see
+    /// [`crate::grimoire_templates`]) before the entry is saved - an entry built from a
+    /// template always has structured fields a caller can rely on being well-formed.
+    pub fn create_entry(
+        &self,
+        title: &str,
+        content: &str,
+        category: Option<&str>,
+        tags: Option<&str>,
+        template: Option<&str>,
+        fields: Option<serde_json::Value>,
+    ) -> SqliteResult<String> {
+        let fields_json = match (template, &fields) {
+            (Some(template_name), Some(fields)) => {
+                crate::grimoire_templates::validate_fields(template_name, fields)
+                    .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(crate::errors::AppError::validation(e))))?;
+                Some(
+                    serde_json::to_string(fields)
+                        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?,
+                )
+            }
+            (Some(template_name), None) => {
+                return Err(rusqlite::Error::ToSqlConversionFailure(Box::new(
+                    crate::errors::AppError::validation(format!(
+                        "Template '{}' requires fields",
+                        template_name
+                    )),
+                )));
+            }
+            (None, _) => None,
+        };
+
+        let id = Uuid::new_v4().to_string();
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO grimoire_entries (id, title, content, category, tags, created_at, updated_at, encrypted, template, fields)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6, ?7, ?8, ?9)",
+            params![id, title, content, category, tags, Utc::now().to_rfc3339(), "false", template, fields_json],
+        )?;
+        Ok(id)
+    }
+
+    /// Fetch a grimoire entry's content by id, transparently decrypting it if it was stored
+    /// encrypted (see [`Self::encrypt_entry`])
+    pub fn get_entry_content(&self, id: &str) -> SqliteResult<Option<String>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let row: Option<(String, bool)> = conn
+            .query_row(
+                "SELECT content, encrypted FROM grimoire_entries WHERE id = ?1",
+                [id],
+                |row| Ok((row.get(0)?, row.get::<_, String>(1)? == "true")),
+            )
+            .optional()?;
+
+        row.map(|(content, encrypted)| self.decrypt_if_needed(content, encrypted))
+            .transpose()
+    }
+
+    /// Fetch a grimoire entry by id, recording the access (bumps `accessed_count`, stamps
+    /// `last_accessed`) so the knowledge base can later surface frequently-used entries.
+    /// Content is transparently decrypted if the entry is stored encrypted.
+    pub fn get_entry(&self, id: &str) -> SqliteResult<Option<crate::models::GrimoireEntry>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let entry = Self::query_entry(&conn, id)?;
+        if entry.is_some() {
+            conn.execute(
+                "UPDATE grimoire_entries
+                 SET accessed_count = accessed_count + 1, last_accessed = ?2
+                 WHERE id = ?1",
+                params![id, Utc::now().to_rfc3339()],
+            )?;
+        }
+        entry
+            .map(|entry| {
+                let encrypted = entry.encrypted;
+                let content = self.decrypt_if_needed(entry.content, encrypted)?;
+                Ok(crate::models::GrimoireEntry { content, ..entry })
+            })
+            .transpose()
+    }
+
+    fn query_entry(conn: &Connection, id: &str) -> SqliteResult<Option<crate::models::GrimoireEntry>> {
+        let mut stmt = conn.prepare(
+            "SELECT id, title, content, category, tags, created_at, updated_at,
+                    accessed_count, last_accessed, encrypted, template, fields
+             FROM grimoire_entries
+             WHERE id = ?1",
+        )?;
+
+        let mut rows = stmt.query_map([id], Self::map_row)?;
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+
+    fn map_row(row: &rusqlite::Row) -> rusqlite::Result<crate::models::GrimoireEntry> {
+        Ok(crate::models::GrimoireEntry {
+            id: row.get(0)?,
+            title: row.get(1)?,
+            content: row.get(2)?,
+            category: row.get(3)?,
+            tags: row.get(4)?,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(6)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+            accessed_count: row.get(7)?,
+            last_accessed: row
+                .get::<_, Option<String>>(8)?
+                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
+            encrypted: row.get::<_, String>(9)? == "true",
+            template: row.get(10)?,
+            fields: row
+                .get::<_, Option<String>>(11)?
+                .and_then(|s| serde_json::from_str(&s).ok()),
+        })
+    }
+
+    /// Update a grimoire entry's title, content, category and tags. If the entry is currently
+    /// encrypted, the new content is re-encrypted with the grimoire key so the entry doesn't
+    /// silently revert to plaintext at rest.
+    pub fn update_entry(
+        &self,
+        id: &str,
+        title: &str,
+        content: &str,
+        category: Option<&str>,
+        tags: Option<&str>,
+    ) -> SqliteResult<bool> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let encrypted = Self::query_entry(&conn, id)?.map(|entry| entry.encrypted).unwrap_or(false);
+        let stored_content = if encrypted {
+            let key = self.load_grimoire_key()?;
+            crate::grimoire_crypto::encrypt(content, &key)
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?
+        } else {
+            content.to_string()
+        };
+
+        let updated = conn.execute(
+            "UPDATE grimoire_entries
+             SET title = ?2, content = ?3, category = ?4, tags = ?5, updated_at = ?6
+             WHERE id = ?1",
+            params![id, title, stored_content, category, tags, Utc::now().to_rfc3339()],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Delete a grimoire entry by id
+    pub fn delete_entry(&self, id: &str) -> SqliteResult<bool> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let deleted = conn.execute("DELETE FROM grimoire_entries WHERE id = ?1", [id])?;
+        Ok(deleted > 0)
+    }
+
+    /// List all grimoire entries, most recently updated first
+    pub fn list_entries(&self) -> SqliteResult<Vec<crate::models::GrimoireEntry>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, title, content, category, tags, created_at, updated_at,
+                    accessed_count, last_accessed, encrypted, template, fields
+             FROM grimoire_entries
+             ORDER BY updated_at DESC",
+        )?;
+
+        let rows = stmt.query_map([], Self::map_row)?;
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        self.decrypt_entries(entries)
+    }
+
+    /// Search entries by category, most recently updated first
+    pub fn search_by_category(&self, category: &str) -> SqliteResult<Vec<crate::models::GrimoireEntry>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, title, content, category, tags, created_at, updated_at,
+                    accessed_count, last_accessed, encrypted, template, fields
+             FROM grimoire_entries
+             WHERE category = ?1
+             ORDER BY updated_at DESC",
+        )?;
+
+        let rows = stmt.query_map([category], Self::map_row)?;
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        self.decrypt_entries(entries)
+    }
+
+    /// Search entries whose `tags` column contains `tag` as a substring, most recently updated
+    /// first. `tags` is stored as a single delimited string rather than a normalized table, so
+    /// this is a `LIKE` match rather than an exact one.
+    pub fn search_by_tag(&self, tag: &str) -> SqliteResult<Vec<crate::models::GrimoireEntry>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, title, content, category, tags, created_at, updated_at,
+                    accessed_count, last_accessed, encrypted, template, fields
+             FROM grimoire_entries
+             WHERE tags LIKE ?1
+             ORDER BY updated_at DESC",
+        )?;
+
+        let pattern = format!("%{}%", tag);
+        let rows = stmt.query_map([pattern], Self::map_row)?;
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        self.decrypt_entries(entries)
+    }
+
+    /// Find entry ids built from `template` whose `field` equals `value`, using SQLite's JSON1
+    /// `json_extract` against the stored `fields` column for structured retrieval
+    pub fn find_entries_by_field(
+        &self,
+        template: &str,
+        field: &str,
+        value: &str,
+    ) -> SqliteResult<Vec<String>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let json_path = format!("$.{}", field);
+        let mut stmt = conn.prepare(
+            "SELECT id FROM grimoire_entries
+             WHERE template = ?1 AND json_extract(fields, ?2) = ?3",
+        )?;
+
+        let rows = stmt.query_map(params![template, json_path, value], |row| row.get(0))?;
+        let mut ids = Vec::new();
+        for row in rows {
+            ids.push(row?);
+        }
+        Ok(ids)
+    }
+}
+
+/// Profile service - Manages lightweight local identity profiles for shared machines
+pub struct ProfileService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl ProfileService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    /// Create a new profile
+    pub fn create_profile(
+        &self,
+        name: String,
+        default_persona_id: Option<i64>,
+    ) -> SqliteResult<crate::models::Profile> {
+        let profile = crate::models::Profile::new(name, default_persona_id);
+
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO profiles (name, default_persona_id, created_at) VALUES (?1, ?2, ?3)",
+            params![profile.name, profile.default_persona_id, profile.created_at.to_rfc3339()],
+        )?;
+
+        let mut result = profile;
+        result.id = Some(conn.last_insert_rowid());
+        Ok(result)
+    }
+
+    /// List all profiles, ordered by name
+    pub fn get_profiles(&self) -> SqliteResult<Vec<crate::models::Profile>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, default_persona_id, created_at FROM profiles ORDER BY name ASC",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(crate::models::Profile {
+                id: Some(row.get::<_, i64>(0)?),
+                name: row.get(1)?,
+                default_persona_id: row.get(2)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(3)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+            })
+        })?;
+
+        let mut profiles = Vec::new();
+        for row in rows {
+            profiles.push(row?);
+        }
+        Ok(profiles)
+    }
+}
+
+/// Usage policy service - Enforces optional quiet-hours and daily-cap limits on AI requests
+///
+/// Aimed at self-regulation and shared family machines, not at stopping a determined user -
+/// anyone with app access can change or override the policy via [`set_policy`]/[`set_override`].
+pub struct UsagePolicyService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl UsagePolicyService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    /// Get the current usage policy, or the default (unrestricted) policy if none is set
+    pub fn get_policy(&self) -> SqliteResult<crate::models::UsagePolicy> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT quiet_hours_start, quiet_hours_end, daily_request_cap, override_until
+             FROM usage_policy WHERE id = 1",
+        )?;
+
+        let mut rows = stmt.query_map([], |row| {
+            let override_until: Option<String> = row.get(3)?;
+            Ok(crate::models::UsagePolicy {
+                quiet_hours_start: row.get(0)?,
+                quiet_hours_end: row.get(1)?,
+                daily_request_cap: row.get(2)?,
+                override_until: override_until
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+            })
+        })?;
+
+        match rows.next() {
+            Some(row) => Ok(row?),
+            None => Ok(crate::models::UsagePolicy::default()),
+        }
+    }
+
+    /// Replace the current usage policy
+    pub fn set_policy(&self, policy: &crate::models::UsagePolicy) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO usage_policy (id, quiet_hours_start, quiet_hours_end, daily_request_cap, override_until)
+             VALUES (1, ?1, ?2, ?3, ?4)
+             ON CONFLICT(id) DO UPDATE SET
+                quiet_hours_start = excluded.quiet_hours_start,
+                quiet_hours_end = excluded.quiet_hours_end,
+                daily_request_cap = excluded.daily_request_cap,
+                override_until = excluded.override_until",
+            params![
+                policy.quiet_hours_start,
+                policy.quiet_hours_end,
+                policy.daily_request_cap,
+                policy.override_until.map(|dt| dt.to_rfc3339()),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Temporarily bypass the policy until the given time
+    pub fn set_override(&self, until: DateTime<Utc>) -> SqliteResult<()> {
+        let mut policy = self.get_policy()?;
+        policy.override_until = Some(until);
+        self.set_policy(&policy)
+    }
+
+    fn today_key() -> String {
+        Utc::now().format("%Y-%m-%d").to_string()
+    }
+
+    fn request_count_today(&self, conn: &rusqlite::Connection) -> SqliteResult<u32> {
+        conn.query_row(
+            "SELECT request_count FROM usage_daily_counts WHERE day = ?1",
+            [Self::today_key()],
+            |row| row.get(0),
+        )
+        .or_else(|e| if e == rusqlite::Error::QueryReturnedNoRows { Ok(0) } else { Err(e) })
+    }
+
+    /// Number of AI requests recorded today under the local usage-policy counter
+    pub fn requests_today(&self) -> SqliteResult<u32> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        self.request_count_today(&conn)
+    }
+
+    /// Check the policy against the current time and request count, then record the request
+    ///
+    /// Returns [`crate::errors::AppError::UsageRestricted`] with a friendly message if the
+    /// request falls inside quiet hours or would exceed the daily cap, unless an active
+    /// override is in effect.
+    pub fn check_and_record_request(&self) -> crate::errors::AppResult<()> {
+        let policy = self.get_policy()?;
+
+        let now = Utc::now();
+        let override_active = policy.override_until.is_some_and(|until| now < until);
+
+        if !override_active {
+            if let (Some(start), Some(end)) = (policy.quiet_hours_start, policy.quiet_hours_end) {
+                let hour = now.format("%H").to_string().parse::<u32>().unwrap_or(0);
+                let in_quiet_hours = if start <= end {
+                    hour >= start && hour < end
+                } else {
+                    hour >= start || hour < end
+                };
+                if in_quiet_hours {
+                    return Err(crate::errors::AppError::usage_restricted(format!(
+                        "AI requests are paused between {:02}:00 and {:02}:00 by the current usage policy",
+                        start, end
+                    )));
+                }
+            }
+
+            if let Some(cap) = policy.daily_request_cap {
+                let conn = self.db.get_connection()?;
+                let count = self.request_count_today(&conn)?;
+                if count >= cap {
+                    return Err(crate::errors::AppError::usage_restricted(format!(
+                        "Daily limit of {} AI requests has been reached",
+                        cap
+                    )));
+                }
+            }
+        }
+
+        let conn = self.db.get_connection()?;
+        conn.execute(
+            "INSERT INTO usage_daily_counts (day, request_count) VALUES (?1, 1)
+             ON CONFLICT(day) DO UPDATE SET request_count = request_count + 1",
+            [Self::today_key()],
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Telemetry service - Manages the local-only-mode kill switch and custom redaction patterns
+/// consulted by [`crate::redaction`] before anything is sent to Sentry
+pub struct TelemetryService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl TelemetryService {
+    /// Construct the service and immediately push any persisted settings into
+    /// [`crate::redaction`]'s global state, so they take effect before the first event is sent
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        let service = Self { db };
+        if let Ok(settings) = service.get_settings() {
+            if let Err(e) = crate::redaction::configure(settings.local_only_mode, &settings.custom_redaction_patterns) {
+                tracing::warn!("Failed to apply stored telemetry settings: {}", e);
+            }
+        }
+        service
+    }
+
+    /// Get the current telemetry settings, or the default (Sentry enabled, no custom patterns)
+    /// if none have been saved yet
+    pub fn get_settings(&self) -> SqliteResult<crate::models::TelemetrySettings> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT local_only_mode, custom_redaction_patterns FROM telemetry_settings WHERE id = 1",
+        )?;
+
+        let mut rows = stmt.query_map([], |row| {
+            let patterns_json: Option<String> = row.get(1)?;
+            Ok(crate::models::TelemetrySettings {
+                local_only_mode: row.get::<_, String>(0)? == "true",
+                custom_redaction_patterns: patterns_json
+                    .and_then(|s| serde_json::from_str(&s).ok())
+                    .unwrap_or_default(),
+            })
+        })?;
+
+        match rows.next() {
+            Some(row) => Ok(row?),
+            None => Ok(crate::models::TelemetrySettings::default()),
+        }
+    }
+
+    /// Replace the current telemetry settings, persist them, and immediately apply them to
+    /// [`crate::redaction`]'s global state so local-only mode takes effect without a restart
+    pub fn set_settings(&self, settings: &crate::models::TelemetrySettings) -> SqliteResult<()> {
+        let patterns_json = serde_json::to_string(&settings.custom_redaction_patterns)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO telemetry_settings (id, local_only_mode, custom_redaction_patterns)
+             VALUES (1, ?1, ?2)
+             ON CONFLICT(id) DO UPDATE SET
+                local_only_mode = excluded.local_only_mode,
+                custom_redaction_patterns = excluded.custom_redaction_patterns",
+            params![settings.local_only_mode.to_string(), patterns_json],
+        )?;
+
+        crate::redaction::configure(settings.local_only_mode, &settings.custom_redaction_patterns)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(crate::errors::AppError::validation(e))))?;
+
+        tracing::info!(
+            "Updated telemetry settings: local_only_mode={}, custom_redaction_patterns={}",
+            settings.local_only_mode,
+            settings.custom_redaction_patterns.len()
+        );
+        Ok(())
+    }
+}
+
+/// Reliability service - Tracks app session start/end markers locally, for users who disable
+/// Sentry and still want the diagnostics bundle to show whether the app is crashing on their
+/// machine.
+pub struct ReliabilityService {
+    db: std::sync::Arc<DatabaseManager>,
+    /// The row id of the session [`record_session_start`] opened, if this process has started
+    /// one. `None` before the first call, or if that call failed.
+    current_session_id: std::sync::Mutex<Option<i64>>,
+}
+
+impl ReliabilityService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self {
+            db,
+            current_session_id: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Record the start of a new session, first marking any session left open by a previous run
+    /// (`ended_at IS NULL`) as a crash - a clean shutdown always closes its own session before
+    /// the process exits, so an open row can only mean the process died without running that
+    /// code. Remembers the new session's id so [`record_clean_shutdown`] can close it later.
+    pub fn record_session_start(&self) -> SqliteResult<i64> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let now = Utc::now().to_rfc3339();
+
+        conn.execute(
+            "UPDATE app_sessions SET ended_at = ?1, clean_shutdown = 'false' WHERE ended_at IS NULL",
+            [&now],
+        )?;
+
+        conn.execute(
+            "INSERT INTO app_sessions (app_version, started_at, ended_at, clean_shutdown)
+             VALUES (?1, ?2, NULL, NULL)",
+            params![env!("CARGO_PKG_VERSION"), now],
+        )?;
+
+        let session_id = conn.last_insert_rowid();
+        *self.current_session_id.lock().unwrap() = Some(session_id);
+        Ok(session_id)
+    }
+
+    /// Mark this process's session as having shut down cleanly. Called from the shutdown
+    /// coordinator; a no-op if [`record_session_start`] was never called or failed.
+    pub fn record_clean_shutdown(&self) -> SqliteResult<()> {
+        let Some(session_id) = *self.current_session_id.lock().unwrap() else {
+            return Ok(());
+        };
+
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "UPDATE app_sessions SET ended_at = ?1, clean_shutdown = 'true' WHERE id = ?2",
+            params![Utc::now().to_rfc3339(), session_id],
+        )?;
+        Ok(())
+    }
+
+    /// Summarize every recorded session, for a reliability panel in diagnostics
+    pub fn get_report(&self) -> SqliteResult<crate::models::ReliabilityReport> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let total_sessions: i64 =
+            conn.query_row("SELECT COUNT(*) FROM app_sessions", [], |row| row.get(0))?;
+        let clean_shutdowns: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM app_sessions WHERE clean_shutdown = 'true'",
+            [],
+            |row| row.get(0),
+        )?;
+        let crashed_shutdowns: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM app_sessions WHERE clean_shutdown = 'false'",
+            [],
+            |row| row.get(0),
+        )?;
+        let last_session_started_at = conn
+            .query_row(
+                "SELECT started_at FROM app_sessions ORDER BY started_at DESC LIMIT 1",
+                [],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+
+        Ok(crate::models::ReliabilityReport {
+            total_sessions,
+            clean_shutdowns,
+            crashed_shutdowns,
+            last_session_started_at,
+        })
+    }
+}
+
+/// Snippet service - Manages user-defined text abbreviations and expands them on demand
+pub struct SnippetService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl SnippetService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    /// Create or update a snippet for the given trigger
+    pub fn upsert_snippet(&self, trigger: String, expansion: String) -> SqliteResult<crate::models::Snippet> {
+        let snippet = crate::models::Snippet::new(trigger, expansion);
+        let id = Uuid::new_v4().to_string();
+
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO snippets (id, trigger, expansion, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(trigger) DO UPDATE SET
+                expansion = excluded.expansion,
+                updated_at = excluded.updated_at",
+            params![id, snippet.trigger, snippet.expansion, snippet.created_at.to_rfc3339(), snippet.updated_at.to_rfc3339()],
+        )?;
+
+        let mut result = snippet;
+        result.id = Some(id);
+        Ok(result)
+    }
+
+    /// List all snippets, ordered by trigger
+    pub fn get_snippets(&self) -> SqliteResult<Vec<crate::models::Snippet>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, trigger, expansion, created_at, updated_at FROM snippets ORDER BY trigger ASC",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(crate::models::Snippet {
+                id: Some(row.get(0)?),
+                trigger: row.get(1)?,
+                expansion: row.get(2)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(3)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+            })
+        })?;
+
+        let mut snippets = Vec::new();
+        for row in rows {
+            snippets.push(row?);
+        }
+        Ok(snippets)
+    }
+
+    /// Delete a snippet by trigger
+    pub fn delete_snippet(&self, trigger: &str) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute("DELETE FROM snippets WHERE trigger = ?1", [trigger])?;
+        Ok(())
+    }
+
+    /// Expand a snippet by trigger, substituting built-in variables and locating the cursor marker
+    pub fn expand_snippet(&self, trigger: &str) -> SqliteResult<Option<crate::models::ExpandedSnippet>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let expansion: Option<String> = conn
+            .query_row(
+                "SELECT expansion FROM snippets WHERE trigger = ?1",
+                [trigger],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let Some(expansion) = expansion else {
+            return Ok(None);
+        };
+
+        let now = Utc::now();
+        let rendered = expansion
+            .replace("{{date}}", &now.format("%Y-%m-%d").to_string())
+            .replace("{{time}}", &now.format("%H:%M").to_string());
+
+        let cursor_offset = rendered.find("{{cursor}}");
+        let text = rendered.replace("{{cursor}}", "");
+
+        Ok(Some(crate::models::ExpandedSnippet { text, cursor_offset }))
+    }
+}
+
+/// Attachment service - Copies files referenced by messages into app-managed storage
+///
+/// Files are content-addressed: the stored filename is the SHA-256 hash of the bytes (plus the
+/// original extension), so two attachments with identical content share one file on disk no
+/// matter how many `attachments` rows reference it. [`Self::remove_attachment`] only deletes the
+/// file once the last referencing row is gone.
+pub struct AttachmentService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl AttachmentService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    fn attachments_dir() -> crate::errors::AppResult<std::path::PathBuf> {
+        crate::platform::get_app_data_dir()
+            .map(|dir| dir.join("attachments"))
+            .ok_or_else(|| crate::errors::AppError::io("Failed to determine app data directory"))
+    }
+
+    /// Validate and copy `file_path` into app-managed attachment storage, associating it with
+    /// `message_id`. If a file with identical content is already stored, the existing copy is
+    /// reused instead of writing a duplicate.
+    pub fn add_attachment(
+        &self,
+        message_id: i64,
+        file_path: &str,
+    ) -> crate::errors::AppResult<crate::models::MessageAttachment> {
+        let validator = crate::validation::InputValidator::default();
+        let validated_path = validator.validate_file_path(file_path)?;
+
+        let source = std::path::Path::new(&validated_path);
+        if !source.is_file() {
+            return Err(crate::errors::AppError::validation(format!(
+                "Attachment file not found: {}",
+                validated_path
+            )));
+        }
+
+        let bytes = std::fs::read(source)
+            .map_err(|e| crate::errors::AppError::io(format!("Failed to read attachment file: {}", e)))?;
+        let size_bytes = bytes.len() as i64;
+
+        let content_hash = ring::digest::digest(&ring::digest::SHA256, &bytes)
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+
+        let extension = source
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        let filename = source
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("attachment")
+            .to_string();
+
+        let attachments_dir = Self::attachments_dir()?;
+        std::fs::create_dir_all(&attachments_dir)
+            .map_err(|e| crate::errors::AppError::io(format!("Failed to create attachments directory: {}", e)))?;
+
+        let stored_filename = if extension.is_empty() {
+            content_hash.clone()
+        } else {
+            format!("{}.{}", content_hash, extension)
+        };
+        let dest = attachments_dir.join(&stored_filename);
+
+        if !dest.exists() {
+            crate::platform::ensure_disk_space(&attachments_dir, size_bytes as u64)?;
+            std::fs::write(&dest, &bytes)
+                .map_err(|e| crate::errors::AppError::io(format!("Failed to store attachment: {}", e)))?;
+        }
+
+        let id = Uuid::new_v4().to_string();
+        let conn = self.db.get_connection()?;
+        conn.execute(
+            "INSERT INTO attachments (id, message_id, filename, file_type, size_bytes, file_path, content_hash, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                id,
+                message_id,
+                filename,
+                extension,
+                size_bytes,
+                stored_filename,
+                content_hash,
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+
+        Ok(crate::models::MessageAttachment {
+            id,
+            filename,
+            file_type: extension,
+            size_bytes,
+            file_path: stored_filename,
+        })
+    }
+
+    /// List the attachments recorded for a message, oldest first
+    pub fn get_attachments(
+        &self,
+        message_id: i64,
+    ) -> crate::errors::AppResult<Vec<crate::models::MessageAttachment>> {
+        let conn = self.db.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, filename, file_type, size_bytes, file_path
+             FROM attachments
+             WHERE message_id = ?1
+             ORDER BY created_at ASC",
+        )?;
+
+        let attachments = stmt
+            .query_map([message_id], |row| {
+                Ok(crate::models::MessageAttachment {
+                    id: row.get(0)?,
+                    filename: row.get(1)?,
+                    file_type: row.get(2)?,
+                    size_bytes: row.get(3)?,
+                    file_path: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(attachments)
+    }
+
+    /// Remove an attachment record. The underlying file is only deleted once no other
+    /// attachment row still references the same content hash; a no-op if `id` doesn't exist.
+    pub fn remove_attachment(&self, id: &str) -> crate::errors::AppResult<()> {
+        let conn = self.db.get_connection()?;
+
+        let row = conn
+            .query_row(
+                "SELECT file_path, content_hash FROM attachments WHERE id = ?1",
+                [id],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()?;
+
+        let Some((file_path, content_hash)) = row else {
+            return Ok(());
+        };
+
+        conn.execute("DELETE FROM attachments WHERE id = ?1", [id])?;
+
+        let still_referenced: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM attachments WHERE content_hash = ?1",
+            [&content_hash],
+            |row| row.get(0),
+        )?;
+
+        if still_referenced == 0 {
+            let dest = Self::attachments_dir()?.join(&file_path);
+            match std::fs::remove_file(&dest) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(crate::errors::AppError::io(format!(
+                        "Failed to remove attachment file: {}",
+                        e
+                    )))
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read the raw bytes of a stored attachment
+    ///
+    /// Used by exports (e.g. [`crate::export_formats::conversation_to_html`]'s `data:` URI
+    /// embedding) that need the file content itself rather than just the stored path.
+    pub fn read_attachment_bytes(
+        &self,
+        attachment: &crate::models::MessageAttachment,
+    ) -> crate::errors::AppResult<Vec<u8>> {
+        let path = Self::attachments_dir()?.join(&attachment.file_path);
+        std::fs::read(&path)
+            .map_err(|e| crate::errors::AppError::io(format!("Failed to read attachment file: {}", e)))
+    }
+}
+
+/// Manages registered [`crate::webhooks::Webhook`]s and matches them against new assistant
+/// messages - see `crate::webhooks::notify_assistant_message`, which is fired from
+/// `crate::commands::add_message`.
+pub struct WebhookService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl WebhookService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    fn scope_str(scope: crate::webhooks::WebhookScope) -> &'static str {
+        match scope {
+            crate::webhooks::WebhookScope::Conversation => "conversation",
+            crate::webhooks::WebhookScope::Tag => "tag",
+        }
+    }
+
+    fn parse_row(row: &rusqlite::Row) -> rusqlite::Result<crate::webhooks::Webhook> {
+        let scope: String = row.get(1)?;
+        let scope = match scope.as_str() {
+            "tag" => crate::webhooks::WebhookScope::Tag,
+            _ => crate::webhooks::WebhookScope::Conversation,
+        };
+        let active: String = row.get(5)?;
+        let created_at: String = row.get(6)?;
+
+        Ok(crate::webhooks::Webhook {
+            id: row.get(0)?,
+            scope,
+            scope_value: row.get(2)?,
+            url: row.get(3)?,
+            secret: row.get(4)?,
+            active: active == "true",
+            created_at: created_at
+                .parse()
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+
+    /// Register a new webhook, returning it with a freshly assigned id
+    pub fn register_webhook(
+        &self,
+        scope: crate::webhooks::WebhookScope,
+        scope_value: String,
+        url: String,
+        secret: String,
+    ) -> crate::errors::AppResult<crate::webhooks::Webhook> {
+        let id = Uuid::new_v4().to_string();
+        let created_at = Utc::now();
+
+        let conn = self.db.get_connection()?;
+        conn.execute(
+            "INSERT INTO webhooks (id, scope, scope_value, url, secret, active, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, 'true', ?6)",
+            params![
+                id,
+                Self::scope_str(scope),
+                scope_value,
+                url,
+                secret,
+                created_at.to_rfc3339(),
+            ],
+        )?;
+
+        Ok(crate::webhooks::Webhook {
+            id,
+            scope,
+            scope_value,
+            url,
+            secret,
+            active: true,
+            created_at,
+        })
+    }
+
+    /// List all registered webhooks, newest first
+    pub fn list_webhooks(&self) -> crate::errors::AppResult<Vec<crate::webhooks::Webhook>> {
+        let conn = self.db.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, scope, scope_value, url, secret, active, created_at
+             FROM webhooks
+             ORDER BY created_at DESC",
+        )?;
+
+        let webhooks = stmt
+            .query_map([], Self::parse_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(webhooks)
+    }
+
+    /// Delete a webhook registration; a no-op if `id` doesn't exist
+    pub fn delete_webhook(&self, id: &str) -> crate::errors::AppResult<()> {
+        let conn = self.db.get_connection()?;
+        conn.execute("DELETE FROM webhooks WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    /// Find active webhooks registered directly for `conversation_id`, or for any tag in `tags`
+    pub fn find_matching(
+        &self,
+        conversation_id: i64,
+        tags: &[String],
+    ) -> crate::errors::AppResult<Vec<crate::webhooks::Webhook>> {
+        let conn = self.db.get_connection()?;
+
+        let mut matched = conn
+            .prepare(
+                "SELECT id, scope, scope_value, url, secret, active, created_at
+                 FROM webhooks
+                 WHERE active = 'true' AND scope = 'conversation' AND scope_value = ?1",
+            )?
+            .query_map([conversation_id.to_string()], Self::parse_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if !tags.is_empty() {
+            let mut tag_stmt = conn.prepare(
+                "SELECT id, scope, scope_value, url, secret, active, created_at
+                 FROM webhooks
+                 WHERE active = 'true' AND scope = 'tag' AND scope_value = ?1",
+            )?;
+            for tag in tags {
+                let rows = tag_stmt
+                    .query_map([tag], Self::parse_row)?
+                    .collect::<Result<Vec<_>, _>>()?;
+                for webhook in rows {
+                    if !matched.iter().any(|w: &crate::webhooks::Webhook| w.id == webhook.id) {
+                        matched.push(webhook);
+                    }
+                }
+            }
+        }
+
+        Ok(matched)
+    }
+}
+
+/// Holds open [`crate::mcp::McpConnection`]s to Grimoire MCP servers, keyed by server path, for
+/// reuse across `list_grimoire_tools` / `invoke_grimoire_tool` calls after `connect_grimoire`
+pub struct McpClientService {
+    connections: tokio::sync::Mutex<std::collections::HashMap<String, crate::mcp::McpConnection>>,
+}
+
+impl Default for McpClientService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl McpClientService {
+    pub fn new() -> Self {
+        Self {
+            connections: tokio::sync::Mutex::new(std::collections::HashMap::new()),
+        }
+    }
+
+    /// Connect to the MCP server at `server_path`, replacing any existing connection for it
+    pub async fn connect(
+        &self,
+        server_path: &str,
+        configuration: &crate::models::GrimoireConfiguration,
+    ) -> crate::errors::AppResult<()> {
+        let connection = crate::mcp::McpConnection::connect(server_path, configuration).await?;
+        self.connections.lock().await.insert(server_path.to_string(), connection);
+        Ok(())
+    }
+
+    /// Drop the connection to `server_path`, if any
+    pub async fn disconnect(&self, server_path: &str) {
+        self.connections.lock().await.remove(server_path);
+    }
+
+    pub async fn list_tools(&self, server_path: &str) -> crate::errors::AppResult<Vec<crate::mcp::McpTool>> {
+        let mut connections = self.connections.lock().await;
+        let connection = connections.get_mut(server_path).ok_or_else(|| {
+            crate::errors::AppError::not_found(format!(
+                "No open MCP connection for '{}'; call connect_grimoire first",
+                server_path
+            ))
+        })?;
+        connection.list_tools().await
+    }
+
+    pub async fn invoke_tool(
+        &self,
+        server_path: &str,
+        tool_name: &str,
+        arguments: serde_json::Value,
+    ) -> crate::errors::AppResult<serde_json::Value> {
+        let mut connections = self.connections.lock().await;
+        let connection = connections.get_mut(server_path).ok_or_else(|| {
+            crate::errors::AppError::not_found(format!(
+                "No open MCP connection for '{}'; call connect_grimoire first",
+                server_path
+            ))
+        })?;
+        connection.call_tool(tool_name, arguments).await
+    }
+}
+
+/// Manages the lifecycle of the optional read-only HTTP snapshot server - see
+/// `crate::snapshot_server`. At most one instance runs at a time; starting a new one while
+/// another is running stops the old one first.
+pub struct SnapshotServerService {
+    db: std::sync::Arc<DatabaseManager>,
+    handle: std::sync::Mutex<Option<crate::snapshot_server::SnapshotServerHandle>>,
+}
+
+impl SnapshotServerService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self {
+            db,
+            handle: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Stop any running server, then bind and start a new one with `config`
+    pub fn start(&self, config: crate::snapshot_server::SnapshotServerConfig) -> crate::errors::AppResult<()> {
+        self.stop();
+        let new_handle = crate::snapshot_server::spawn(self.db.clone(), config)?;
+        *self.handle.lock().unwrap() = Some(new_handle);
+        Ok(())
+    }
+
+    /// Stop the running server, if any; a no-op otherwise
+    pub fn stop(&self) {
+        if let Some(handle) = self.handle.lock().unwrap().take() {
+            handle.stop();
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.handle.lock().unwrap().is_some()
+    }
+}
+
+/// Read-aloud service - Splits conversations/grimoire entries into chunks for background TTS
+///
+/// Chunking and job/progress tracking live here; actual speech synthesis is not wired up yet
+/// (no TTS engine is integrated into this crate), so chunks are created in `pending` status
+/// and a future synthesis worker is expected to call [`mark_chunk_complete`].
+pub struct ReadAloudService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl ReadAloudService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    /// Create a job from pre-split chunk texts and return the new job id
+    fn create_job(&self, source_type: &str, source_id: &str, chunks: Vec<String>) -> SqliteResult<String> {
+        let job_id = Uuid::new_v4().to_string();
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        conn.execute(
+            "INSERT INTO read_aloud_jobs (id, source_type, source_id) VALUES (?1, ?2, ?3)",
+            params![job_id, source_type, source_id],
+        )?;
+
+        for (sequence, text) in chunks.into_iter().enumerate() {
+            conn.execute(
+                "INSERT INTO read_aloud_chunks (id, job_id, sequence, text) VALUES (?1, ?2, ?3, ?4)",
+                params![Uuid::new_v4().to_string(), job_id, sequence as i32, text],
+            )?;
+        }
+
+        Ok(job_id)
+    }
+
+    /// Queue a conversation for read-aloud, one chunk per message
+    pub fn enqueue_conversation(&self, conversation_id: i64, messages: &[Message]) -> SqliteResult<String> {
+        let chunks = messages.iter().map(|m| m.content.clone()).collect();
+        self.create_job("conversation", &conversation_id.to_string(), chunks)
+    }
+
+    /// Queue a single grimoire entry for read-aloud as one chunk
+    pub fn enqueue_grimoire_entry(&self, entry_id: &str, content: &str) -> SqliteResult<String> {
+        self.create_job("grimoire", entry_id, vec![content.to_string()])
+    }
+
+    /// Get playback progress and per-chunk status for a job, in sequential order
+    pub fn get_progress(&self, job_id: &str) -> SqliteResult<crate::models::ReadAloudProgress> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, sequence, text, status, audio_path
+             FROM read_aloud_chunks WHERE job_id = ?1 ORDER BY sequence ASC",
+        )?;
+
+        let rows = stmt.query_map([job_id], |row| {
+            let status_str: String = row.get(3)?;
+            let status = match status_str.as_str() {
+                "processing" => crate::models::ReadAloudChunkStatus::Processing,
+                "complete" => crate::models::ReadAloudChunkStatus::Complete,
+                "failed" => crate::models::ReadAloudChunkStatus::Failed,
+                _ => crate::models::ReadAloudChunkStatus::Pending,
+            };
+            Ok(crate::models::ReadAloudChunk {
+                id: row.get(0)?,
+                sequence: row.get(1)?,
+                text: row.get(2)?,
+                status,
+                audio_path: row.get(4)?,
+            })
+        })?;
+
+        let mut chunks = Vec::new();
+        for row in rows {
+            chunks.push(row?);
+        }
+
+        let completed_count = chunks
+            .iter()
+            .filter(|c| c.status == crate::models::ReadAloudChunkStatus::Complete)
+            .count();
+        let total_count = chunks.len();
+
+        Ok(crate::models::ReadAloudProgress {
+            job_id: job_id.to_string(),
+            chunks,
+            completed_count,
+            total_count,
+        })
+    }
+
+    /// Mark a chunk as successfully synthesized, recording the path to its audio file
+    pub fn mark_chunk_complete(&self, chunk_id: &str, audio_path: &str) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "UPDATE read_aloud_chunks SET status = 'complete', audio_path = ?1 WHERE id = ?2",
+            params![audio_path, chunk_id],
+        )?;
+        Ok(())
+    }
+
+    /// List jobs with at least one chunk still pending or processing, for a dashboard view
+    pub fn list_in_progress(&self) -> SqliteResult<Vec<crate::models::ReadAloudProgress>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT job_id FROM read_aloud_chunks WHERE status IN ('pending', 'processing')",
+        )?;
+        let job_ids = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<SqliteResult<Vec<_>>>()?;
+        drop(stmt);
+        drop(conn);
+
+        job_ids.iter().map(|job_id| self.get_progress(job_id)).collect()
+    }
+}
+
+/// Embedding index service - Tracks bulk re-embedding jobs and the vector index they populate
+///
+/// There is no local embedding model wired into this crate yet, so this service does not
+/// compute vectors itself; it only provides the job bookkeeping and atomic index swap an
+/// external embedding worker needs so switching models never leaves the index half-upgraded.
+pub struct EmbeddingService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl EmbeddingService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    /// Start a rebuild of the embedding index under `model`, sized to the current message count
+    pub fn start_rebuild(&self, model: &str) -> SqliteResult<String> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let total_items: i32 = conn.query_row("SELECT COUNT(*) FROM messages", [], |row| row.get(0))?;
+
+        let job_id = Uuid::new_v4().to_string();
+        conn.execute(
+            "INSERT INTO embedding_jobs (id, model, total_items) VALUES (?1, ?2, ?3)",
+            params![job_id, model, total_items],
+        )?;
+        Ok(job_id)
+    }
+
+    /// Record one freshly-computed vector as part of an in-progress rebuild job
+    ///
+    /// Written with `active = FALSE` - it isn't visible to lookups until [`Self::finalize_rebuild`]
+    /// swaps it in.
+    pub fn record_embedding(
+        &self,
+        job_id: &str,
+        content_type: &str,
+        content_id: &str,
+        model: &str,
+        vector: &[f32],
+    ) -> SqliteResult<()> {
+        let vector_json = serde_json::to_string(vector)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO embedding_index (id, job_id, model, content_type, content_id, vector, active)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'false')",
+            params![Uuid::new_v4().to_string(), job_id, model, content_type, content_id, vector_json],
+        )?;
+        conn.execute(
+            "UPDATE embedding_jobs SET processed_items = processed_items + 1 WHERE id = ?1",
+            [job_id],
+        )?;
+        Ok(())
+    }
+
+    /// Get progress for a rebuild job
+    pub fn get_job_progress(&self, job_id: &str) -> SqliteResult<Option<crate::models::EmbeddingJobProgress>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.query_row(
+            "SELECT id, model, status, total_items, processed_items FROM embedding_jobs WHERE id = ?1",
+            [job_id],
+            |row| {
+                let status_str: String = row.get(2)?;
+                let status = match status_str.as_str() {
+                    "complete" => crate::models::EmbeddingJobStatus::Complete,
+                    "failed" => crate::models::EmbeddingJobStatus::Failed,
+                    _ => crate::models::EmbeddingJobStatus::InProgress,
+                };
+                Ok(crate::models::EmbeddingJobProgress {
+                    job_id: row.get(0)?,
+                    model: row.get(1)?,
+                    status,
+                    total_items: row.get(3)?,
+                    processed_items: row.get(4)?,
+                })
+            },
+        )
+        .optional()
+    }
+
+    /// List rebuild jobs that haven't finished yet, for a dashboard view
+    pub fn list_in_progress(&self) -> SqliteResult<Vec<crate::models::EmbeddingJobProgress>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, model, status, total_items, processed_items
+             FROM embedding_jobs WHERE status = 'in_progress'",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(crate::models::EmbeddingJobProgress {
+                job_id: row.get(0)?,
+                model: row.get(1)?,
+                status: crate::models::EmbeddingJobStatus::InProgress,
+                total_items: row.get(3)?,
+                processed_items: row.get(4)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Atomically swap the active index over to this job's freshly-built vectors
+    ///
+    /// Deactivates the previous index rows and activates this job's rows in one transaction, so
+    /// a lookup in flight always sees either the fully-old or fully-new index, never a mix.
+    pub fn finalize_rebuild(&self, job_id: &str) -> crate::errors::AppResult<()> {
+        self.db.with_transaction(|tx| {
+            let model: String = tx
+                .query_row("SELECT model FROM embedding_jobs WHERE id = ?1", [job_id], |row| row.get(0))
+                .map_err(|e| crate::errors::AppError::database(format!("Rebuild job {} not found: {}", job_id, e)))?;
+
+            tx.execute(
+                "UPDATE embedding_index SET active = 'false' WHERE model = ?1 AND active = 'true'",
+                [&model],
+            )
+            .map_err(|e| crate::errors::AppError::database(format!("Failed to retire previous index: {}", e)))?;
+
+            tx.execute(
+                "UPDATE embedding_index SET active = 'true' WHERE job_id = ?1",
+                [job_id],
+            )
+            .map_err(|e| crate::errors::AppError::database(format!("Failed to activate new index: {}", e)))?;
+
+            tx.execute(
+                "UPDATE embedding_jobs SET status = 'complete', completed_at = ?2 WHERE id = ?1",
+                params![job_id, Utc::now().to_rfc3339()],
+            )
+            .map_err(|e| crate::errors::AppError::database(format!("Failed to mark rebuild job complete: {}", e)))?;
+
+            Ok(())
+        })
+    }
+
+    /// Store (or overwrite) the semantic search vector for a single message
+    pub fn store_message_embedding(&self, message_id: i64, model: &str, vector: &[f32]) -> SqliteResult<()> {
+        let vector_json = serde_json::to_string(vector)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO message_embeddings (message_id, model, vector) VALUES (?1, ?2, ?3)
+             ON CONFLICT(message_id) DO UPDATE SET model = excluded.model, vector = excluded.vector,
+                created_at = CURRENT_TIMESTAMP",
+            params![message_id, model, vector_json],
+        )?;
+        Ok(())
+    }
+
+    /// Rank every embedded message by cosine similarity to `query_vector`, most similar first
+    ///
+    /// A full scan over `message_embeddings`, scored in Rust rather than in SQL - there is no
+    /// vector index, so this is only expected to scale to a single user's local message history.
+    pub fn semantic_search(&self, query_vector: &[f32], top_k: usize) -> SqliteResult<Vec<(i64, f32)>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare("SELECT message_id, vector FROM message_embeddings")?;
+        let rows = stmt.query_map([], |row| {
+            let message_id: i64 = row.get(0)?;
+            let vector_json: String = row.get(1)?;
+            Ok((message_id, vector_json))
+        })?;
+
+        let mut scored = Vec::new();
+        for row in rows {
+            let (message_id, vector_json) = row?;
+            let vector: Vec<f32> = serde_json::from_str(&vector_json)
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+            scored.push((message_id, crate::embeddings::cosine_similarity(query_vector, &vector)));
+        }
+
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(top_k);
+        Ok(scored)
+    }
+}
+
+/// Slash command service - CRUD and argument substitution for user-defined slash commands
+pub struct SlashCommandService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl SlashCommandService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    /// Create or update a slash command by name
+    pub fn upsert_command(
+        &self,
+        name: String,
+        prompt_template: String,
+        default_model: Option<String>,
+    ) -> SqliteResult<crate::models::SlashCommand> {
+        let command = crate::models::SlashCommand::new(name, prompt_template, default_model);
+        let id = Uuid::new_v4().to_string();
+
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO slash_commands (id, name, prompt_template, default_model, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(name) DO UPDATE SET
+                prompt_template = excluded.prompt_template,
+                default_model = excluded.default_model,
+                updated_at = excluded.updated_at",
+            params![
+                id,
+                command.name,
+                command.prompt_template,
+                command.default_model,
+                command.created_at.to_rfc3339(),
+                command.updated_at.to_rfc3339(),
+            ],
+        )?;
+
+        let mut result = command;
+        result.id = Some(id);
+        Ok(result)
+    }
+
+    /// Bulk-replace every slash command's `default_model` of `old_model` with `new_model`,
+    /// returning the number of commands updated
+    pub fn migrate_model_references(&self, old_model: &str, new_model: &str) -> SqliteResult<usize> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "UPDATE slash_commands SET default_model = ?1 WHERE default_model = ?2",
+            params![new_model, old_model],
+        )
+    }
+
+    /// List all slash commands, ordered by name
+    pub fn get_commands(&self) -> SqliteResult<Vec<crate::models::SlashCommand>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, prompt_template, default_model, created_at, updated_at
+             FROM slash_commands ORDER BY name ASC",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(crate::models::SlashCommand {
+                id: Some(row.get(0)?),
+                name: row.get(1)?,
+                prompt_template: row.get(2)?,
+                default_model: row.get(3)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+            })
+        })?;
+
+        let mut commands = Vec::new();
+        for row in rows {
+            commands.push(row?);
+        }
+        Ok(commands)
+    }
+
+    /// Delete a slash command by name
+    pub fn delete_command(&self, name: &str) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute("DELETE FROM slash_commands WHERE name = ?1", [name])?;
+        Ok(())
+    }
+
+    /// Look up a slash command by name
+    pub fn get_command(&self, name: &str) -> SqliteResult<Option<crate::models::SlashCommand>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.query_row(
+            "SELECT id, name, prompt_template, default_model, created_at, updated_at
+             FROM slash_commands WHERE name = ?1",
+            [name],
+            |row| {
+                Ok(crate::models::SlashCommand {
+                    id: Some(row.get(0)?),
+                    name: row.get(1)?,
+                    prompt_template: row.get(2)?,
+                    default_model: row.get(3)?,
+                    created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                        .unwrap_or_default()
+                        .with_timezone(&Utc),
+                    updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                        .unwrap_or_default()
+                        .with_timezone(&Utc),
+                })
+            },
+        )
+        .optional()
+    }
+
+    /// Substitute `{{args}}` and positional `{{argN}}` placeholders in a prompt template
+    pub fn render_template(template: &str, args: &[String]) -> String {
+        let mut rendered = template.replace("{{args}}", &args.join(" "));
+        for (i, arg) in args.iter().enumerate() {
+            rendered = rendered.replace(&format!("{{{{arg{}}}}}", i + 1), arg);
+        }
+        rendered
+    }
+
+    /// Parse a raw `/name arg1 arg2` input into its command name and arguments
+    pub fn parse_input(input: &str) -> Option<(String, Vec<String>)> {
+        let input = input.trim().strip_prefix('/')?;
+        let mut parts = input.split_whitespace();
+        let name = parts.next()?.to_string();
+        let args = parts.map(|s| s.to_string()).collect();
+        Some((name, args))
+    }
+}
+
+/// CRUD for user-defined OS-level global shortcut bindings, persisted for
+/// [`crate::shortcuts`] to restore on the next launch
+pub struct ShortcutService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl ShortcutService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    /// Bind `action` to `accelerator`, replacing any accelerator it was previously bound to
+    pub fn upsert_shortcut(
+        &self,
+        action: String,
+        accelerator: String,
+    ) -> SqliteResult<crate::models::Shortcut> {
+        let shortcut = crate::models::Shortcut::new(action, accelerator);
+
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO shortcuts (action, accelerator, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(action) DO UPDATE SET
+                accelerator = excluded.accelerator,
+                updated_at = excluded.updated_at",
+            params![
+                shortcut.action,
+                shortcut.accelerator,
+                shortcut.created_at.to_rfc3339(),
+                shortcut.updated_at.to_rfc3339(),
+            ],
+        )?;
+
+        Ok(shortcut)
+    }
+
+    /// List all bound shortcuts, ordered by action
+    pub fn get_shortcuts(&self) -> SqliteResult<Vec<crate::models::Shortcut>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT action, accelerator, created_at, updated_at FROM shortcuts ORDER BY action ASC",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(crate::models::Shortcut {
+                action: row.get(0)?,
+                accelerator: row.get(1)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(2)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(3)?)
+                    .unwrap_or_default()
+                    .with_timezone(&Utc),
+            })
+        })?;
+
+        let mut shortcuts = Vec::new();
+        for row in rows {
+            shortcuts.push(row?);
+        }
+        Ok(shortcuts)
+    }
+
+    /// Look up the shortcut bound to `action`, if any
+    pub fn get_shortcut(&self, action: &str) -> SqliteResult<Option<crate::models::Shortcut>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.query_row(
+            "SELECT action, accelerator, created_at, updated_at FROM shortcuts WHERE action = ?1",
+            [action],
+            |row| {
+                Ok(crate::models::Shortcut {
+                    action: row.get(0)?,
+                    accelerator: row.get(1)?,
+                    created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(2)?)
+                        .unwrap_or_default()
+                        .with_timezone(&Utc),
+                    updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(3)?)
+                        .unwrap_or_default()
+                        .with_timezone(&Utc),
+                })
+            },
+        )
+        .optional()
+    }
+
+    /// Unbind `action`'s shortcut
+    pub fn delete_shortcut(&self, action: &str) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute("DELETE FROM shortcuts WHERE action = ?1", [action])?;
+        Ok(())
+    }
+}
+
+/// Tracks [`crate::commands::import_conversation_export_streaming`] runs so a partial failure can
+/// be resumed instead of re-importing a large export file from the start
+pub struct ImportJobService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl ImportJobService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    fn row_to_job(row: &rusqlite::Row) -> rusqlite::Result<crate::models::ImportJob> {
+        let status = match row.get::<_, String>(2)?.as_str() {
+            "completed" => crate::models::ImportJobStatus::Completed,
+            "failed" => crate::models::ImportJobStatus::Failed,
+            _ => crate::models::ImportJobStatus::Running,
+        };
+
+        Ok(crate::models::ImportJob {
+            id: row.get(0)?,
+            source_path: row.get(1)?,
+            status,
+            conversations_imported: row.get::<_, i64>(3)? as usize,
+            messages_imported: row.get::<_, i64>(4)? as usize,
+            error: row.get(5)?,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(6)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(7)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+        })
+    }
+
+    /// Start tracking a new streaming import of `source_path`
+    pub fn create_job(&self, source_path: &str) -> SqliteResult<crate::models::ImportJob> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        conn.execute(
+            "INSERT INTO import_jobs (id, source_path, status, created_at, updated_at)
+             VALUES (?1, ?2, 'running', ?3, ?3)",
+            params![id, source_path, now],
+        )?;
+
+        self.get_job(&id)?.ok_or_else(|| rusqlite::Error::QueryReturnedNoRows)
+    }
+
+    /// Look up a job by id, for resuming a previous run
+    pub fn get_job(&self, id: &str) -> SqliteResult<Option<crate::models::ImportJob>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.query_row(
+            "SELECT id, source_path, status, conversations_imported, messages_imported, error, created_at, updated_at
+             FROM import_jobs WHERE id = ?1",
+            [id],
+            Self::row_to_job,
+        )
+        .optional()
+    }
+
+    /// Record how many conversations/messages have been committed so far
+    pub fn update_progress(&self, id: &str, conversations_imported: usize, messages_imported: usize) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "UPDATE import_jobs SET conversations_imported = ?1, messages_imported = ?2, updated_at = ?3 WHERE id = ?4",
+            params![conversations_imported as i64, messages_imported as i64, Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+
+    /// Mark a job as having finished successfully
+    pub fn mark_completed(&self, id: &str) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "UPDATE import_jobs SET status = 'completed', updated_at = ?1 WHERE id = ?2",
+            params![Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+
+    /// Mark a job as failed, recording `error` so the next resume attempt's caller can show why
+    /// the previous one stopped
+    pub fn mark_failed(&self, id: &str, error: &str) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "UPDATE import_jobs SET status = 'failed', error = ?1, updated_at = ?2 WHERE id = ?3",
+            params![error, Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+}
+
+/// Append-only record of sensitive operations (API config changes, exports, key rotations,
+/// deletions, restores) for users treating this app as a privacy-focused vault who want to see
+/// what's touched it. Deliberately offers no update or delete method onto `audit_log` - see
+/// [`crate::database::DatabaseManager::create_audit_log_table`].
+pub struct AuditLogService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl AuditLogService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    fn row_to_entry(row: &rusqlite::Row) -> rusqlite::Result<crate::models::AuditLogEntry> {
+        Ok(crate::models::AuditLogEntry {
+            id: row.get(0)?,
+            actor: row.get(1)?,
+            action: row.get(2)?,
+            details: row.get(3)?,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+        })
+    }
+
+    /// Append one entry. Never fails the caller's own operation on an audit-log write error -
+    /// callers are expected to log and continue, not abort a delete/export/rotation because the
+    /// audit trail for it couldn't be written.
+    pub fn record(&self, actor: &str, action: &str, details: Option<&str>) -> SqliteResult<()> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO audit_log (actor, action, details, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![actor, action, details, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Page through the audit log, newest first, narrowed by whichever of `filter`'s fields are
+    /// set. `limit` defaults to 100 and is capped at 1000 so an unbounded query can't be used to
+    /// pull the whole table in one request.
+    pub fn query(&self, filter: &crate::models::AuditLogFilter) -> SqliteResult<Vec<crate::models::AuditLogEntry>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let mut where_clauses = Vec::new();
+        let mut bound_params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if let Some(action) = &filter.action {
+            where_clauses.push("action = ?");
+            bound_params.push(Box::new(action.clone()));
+        }
+        if let Some(actor) = &filter.actor {
+            where_clauses.push("actor = ?");
+            bound_params.push(Box::new(actor.clone()));
+        }
+        if let Some(since) = filter.since {
+            where_clauses.push("created_at >= ?");
+            bound_params.push(Box::new(since.to_rfc3339()));
+        }
+        if let Some(until) = filter.until {
+            where_clauses.push("created_at <= ?");
+            bound_params.push(Box::new(until.to_rfc3339()));
+        }
+
+        let where_clause = if where_clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", where_clauses.join(" AND "))
+        };
+
+        let limit = filter.limit.unwrap_or(100).clamp(1, 1000);
+        let offset = filter.offset.unwrap_or(0).max(0);
+        bound_params.push(Box::new(limit));
+        bound_params.push(Box::new(offset));
+
+        let sql = format!(
+            "SELECT id, actor, action, details, created_at
+             FROM audit_log
+             {}
+             ORDER BY created_at DESC
+             LIMIT ? OFFSET ?",
+            where_clause
+        );
+
+        let param_refs: Vec<&dyn rusqlite::ToSql> = bound_params.iter().map(|p| p.as_ref()).collect();
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(param_refs.as_slice(), Self::row_to_entry)?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        Ok(entries)
+    }
+}
+
+/// Prompt template service - CRUD and named-variable substitution for reusable prompts
+pub struct PromptTemplateService {
+    db: std::sync::Arc<DatabaseManager>,
+}
+
+impl PromptTemplateService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    fn row_to_template(row: &rusqlite::Row) -> rusqlite::Result<crate::models::PromptTemplate> {
+        Ok(crate::models::PromptTemplate {
+            id: Some(row.get(0)?),
+            name: row.get(1)?,
+            category: row.get(2)?,
+            template: row.get(3)?,
+            favorite: row.get::<_, String>(4)? == "true",
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(6)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+        })
+    }
+
+    /// Create a new prompt template
+    pub fn create_template(
+        &self,
+        name: String,
+        category: Option<String>,
+        template: String,
+        favorite: bool,
+    ) -> SqliteResult<crate::models::PromptTemplate> {
+        let prompt_template = crate::models::PromptTemplate::new(name, category, template, favorite);
+
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO prompt_templates (name, category, template, favorite, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                prompt_template.name,
+                prompt_template.category,
+                prompt_template.template,
+                prompt_template.favorite.to_string(),
+                prompt_template.created_at.to_rfc3339(),
+                prompt_template.updated_at.to_rfc3339(),
+            ],
+        )?;
+
+        let mut result = prompt_template;
+        result.id = Some(conn.last_insert_rowid());
+        Ok(result)
+    }
+
+    /// List all prompt templates, favorites first, then alphabetically by name
+    pub fn list_templates(&self) -> SqliteResult<Vec<crate::models::PromptTemplate>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, category, template, favorite, created_at, updated_at
+             FROM prompt_templates
+             ORDER BY favorite DESC, name ASC",
+        )?;
+
+        let rows = stmt.query_map([], Self::row_to_template)?;
+        let mut templates = Vec::new();
+        for row in rows {
+            templates.push(row?);
+        }
+        Ok(templates)
+    }
+
+    /// Look up a prompt template by id
+    pub fn get_template(&self, id: i64) -> SqliteResult<Option<crate::models::PromptTemplate>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.query_row(
+            "SELECT id, name, category, template, favorite, created_at, updated_at
+             FROM prompt_templates WHERE id = ?1",
+            [id],
+            Self::row_to_template,
+        )
+        .optional()
+    }
+
+    /// Extract the `{{variable}}` placeholder names referenced by a template, in the order they
+    /// first appear, without duplicates
+    fn extract_variables(template: &str) -> Vec<String> {
+        let placeholder = regex::Regex::new(r"\{\{\s*([a-zA-Z_][a-zA-Z0-9_]*)\s*\}\}").unwrap();
+        let mut names = Vec::new();
+        for capture in placeholder.captures_iter(template) {
+            let name = capture[1].to_string();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// Substitute a template's named `{{variable}}` placeholders with `variables`
+    ///
+    /// Unlike [`SlashCommandService::render_template`]'s positional, best-effort substitution,
+    /// every placeholder referenced by `template` must have a supplied value - an unrendered
+    /// `{{variable}}` would otherwise leak into the prompt sent to a provider unnoticed.
+    pub fn render_template(
+        template: &str,
+        variables: &std::collections::HashMap<String, String>,
+    ) -> crate::errors::AppResult<String> {
+        let mut rendered = template.to_string();
+        for name in Self::extract_variables(template) {
+            let value = variables.get(&name).ok_or_else(|| {
+                crate::errors::AppError::validation(format!(
+                    "Missing value for template variable '{{{{{}}}}}'",
+                    name
+                ))
+            })?;
+            rendered = rendered.replace(&format!("{{{{{}}}}}", name), value);
+        }
+        Ok(rendered)
+    }
+
+    /// Look up a prompt template by id and render it with `variables`
+    pub fn render_by_id(
+        &self,
+        id: i64,
+        variables: &std::collections::HashMap<String, String>,
+    ) -> crate::errors::AppResult<String> {
+        let template = self
+            .get_template(id)?
+            .ok_or_else(|| crate::errors::AppError::not_found(format!("Prompt template {} not found", id)))?;
+        Self::render_template(&template.template, variables)
     }
 }
 
-/// Persona service - Manages AI character profiles
-pub struct PersonaService {
+/// Records token usage and estimated cost for every [`crate::commands::send_ai_provider_request`]
+/// call, for the frontend analytics dashboard. A distinct concern from [`UsagePolicyService`],
+/// which only enforces quiet-hours/daily-request limits and keeps no cost or token history.
+pub struct UsageAnalyticsService {
     db: std::sync::Arc<DatabaseManager>,
 }
 
-impl PersonaService {
+impl UsageAnalyticsService {
     pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
         Self { db }
     }
 
-    /// Create new persona
-    pub fn create_persona(
+    fn row_to_record(row: &rusqlite::Row) -> rusqlite::Result<crate::models::UsageRecord> {
+        Ok(crate::models::UsageRecord {
+            id: row.get(0)?,
+            conversation_id: row.get(1)?,
+            provider: row.get(2)?,
+            model: row.get(3)?,
+            prompt_tokens: row.get(4)?,
+            completion_tokens: row.get(5)?,
+            total_tokens: row.get(6)?,
+            latency_ms: row.get(7)?,
+            estimated_cost_usd: row.get(8)?,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(9)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+            status: row.get(10)?,
+        })
+    }
+
+    /// Record one completed provider request; `estimated_cost_usd` is `None` when
+    /// [`crate::pricing`] has no price for this (provider, model) pair
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_usage(
         &self,
-        name: String,
-        description: Option<String>,
-        system_prompt: String,
-    ) -> SqliteResult<Persona> {
-        let persona = Persona::new(name, description, system_prompt);
+        conversation_id: Option<i64>,
+        provider: &str,
+        model: &str,
+        prompt_tokens: i64,
+        completion_tokens: i64,
+        total_tokens: i64,
+        latency_ms: i64,
+        estimated_cost_usd: Option<f64>,
+    ) -> SqliteResult<crate::models::UsageRecord> {
+        self.record_usage_with_status(
+            conversation_id,
+            provider,
+            model,
+            prompt_tokens,
+            completion_tokens,
+            total_tokens,
+            latency_ms,
+            estimated_cost_usd,
+            "completed",
+        )
+    }
+
+    /// Record one provider request that was cancelled mid-stream via
+    /// [`crate::commands::cancel_ai_request`], before any usage/cost could be measured
+    pub fn record_cancelled_usage(
+        &self,
+        conversation_id: Option<i64>,
+        provider: &str,
+        model: &str,
+        latency_ms: i64,
+    ) -> SqliteResult<crate::models::UsageRecord> {
+        self.record_usage_with_status(conversation_id, provider, model, 0, 0, 0, latency_ms, None, "cancelled")
+    }
 
+    #[allow(clippy::too_many_arguments)]
+    fn record_usage_with_status(
+        &self,
+        conversation_id: Option<i64>,
+        provider: &str,
+        model: &str,
+        prompt_tokens: i64,
+        completion_tokens: i64,
+        total_tokens: i64,
+        latency_ms: i64,
+        estimated_cost_usd: Option<f64>,
+        status: &str,
+    ) -> SqliteResult<crate::models::UsageRecord> {
         let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
-        let mut stmt = conn.prepare(
-            "INSERT INTO personas (name, description, system_prompt, created_at, updated_at, active)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)"
+        let created_at = Utc::now();
+        conn.execute(
+            "INSERT INTO usage_records (
+                conversation_id, provider, model, prompt_tokens, completion_tokens,
+                total_tokens, latency_ms, estimated_cost_usd, created_at, status
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                conversation_id,
+                provider,
+                model,
+                prompt_tokens,
+                completion_tokens,
+                total_tokens,
+                latency_ms,
+                estimated_cost_usd,
+                created_at.to_rfc3339(),
+                status,
+            ],
         )?;
-
-        let description_str = persona.description.as_deref().unwrap_or("");
-        stmt.execute([
-            &persona.name,
-            description_str,
-            &persona.system_prompt,
-            &persona.created_at.to_rfc3339(),
-            &persona.updated_at.to_rfc3339(),
-            &persona.active.to_string(),
-        ])?;
-
-        let id = conn.last_insert_rowid();
-        let mut result = persona;
-        result.id = Some(id);
-        Ok(result)
+        Ok(crate::models::UsageRecord {
+            id: conn.last_insert_rowid(),
+            conversation_id,
+            provider: provider.to_string(),
+            model: model.to_string(),
+            prompt_tokens,
+            completion_tokens,
+            total_tokens,
+            latency_ms,
+            estimated_cost_usd,
+            created_at,
+            status: status.to_string(),
+        })
     }
 
-    /// Get all active personas
-    pub fn get_personas(&self) -> SqliteResult<Vec<Persona>> {
+    /// Aggregate usage recorded in the last `lookback_days` days (0 = today only), broken down by
+    /// provider/model
+    pub fn get_usage_summary(&self, lookback_days: i64) -> SqliteResult<crate::models::UsageSummary> {
+        let since = (Utc::now() - chrono::Duration::days(lookback_days))
+            .format("%Y-%m-%dT00:00:00+00:00")
+            .to_string();
+
         let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
         let mut stmt = conn.prepare(
-            "SELECT id, name, description, system_prompt, created_at, updated_at, active
-             FROM personas
-             WHERE active = 'true'
-             ORDER BY name ASC",
+            "SELECT provider, model, COUNT(*), SUM(total_tokens), SUM(COALESCE(estimated_cost_usd, 0))
+             FROM usage_records
+             WHERE created_at >= ?1
+             GROUP BY provider, model
+             ORDER BY provider, model",
         )?;
-
-        let rows = stmt.query_map([], |row| {
-            Ok(Persona {
-                id: Some(row.get::<_, i64>(0)?),
-                name: row.get(1)?,
-                description: {
-                    let desc: String = row.get(2)?;
-                    if desc.is_empty() {
-                        None
-                    } else {
-                        Some(desc)
-                    }
-                },
-                system_prompt: row.get(3)?,
-                avatar_path: None,
-                memory_context: None,
-                settings: None,
-                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
-                    .unwrap_or_default()
-                    .with_timezone(&Utc),
-                updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
-                    .unwrap_or_default()
-                    .with_timezone(&Utc),
-                active: row.get::<_, String>(6)? == "true",
+        let rows = stmt.query_map([&since], |row| {
+            Ok(crate::models::ProviderUsageTotals {
+                provider: row.get(0)?,
+                model: row.get(1)?,
+                requests: row.get(2)?,
+                total_tokens: row.get(3)?,
+                estimated_cost_usd: row.get(4)?,
             })
         })?;
 
-        let mut personas = Vec::new();
+        let mut by_provider = Vec::new();
         for row in rows {
-            personas.push(row?);
+            by_provider.push(row?);
         }
-        Ok(personas)
+
+        Ok(crate::models::UsageSummary {
+            total_requests: by_provider.iter().map(|p| p.requests).sum(),
+            total_tokens: by_provider.iter().map(|p| p.total_tokens).sum(),
+            total_estimated_cost_usd: by_provider.iter().map(|p| p.estimated_cost_usd).sum(),
+            by_provider,
+        })
     }
 
-    /// Get persona by ID
-    pub fn get_persona(&self, id: i64) -> SqliteResult<Option<Persona>> {
+    /// Every usage record attributed to `conversation_id`, oldest first
+    pub fn get_usage_by_conversation(&self, conversation_id: i64) -> SqliteResult<Vec<crate::models::UsageRecord>> {
         let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
         let mut stmt = conn.prepare(
-            "SELECT id, name, description, system_prompt, created_at, updated_at, active
-             FROM personas
-             WHERE id = ?1",
+            "SELECT id, conversation_id, provider, model, prompt_tokens, completion_tokens,
+                    total_tokens, latency_ms, estimated_cost_usd, created_at, status
+             FROM usage_records
+             WHERE conversation_id = ?1
+             ORDER BY id ASC",
         )?;
+        let rows = stmt.query_map([conversation_id], Self::row_to_record)?;
+        let mut records = Vec::new();
+        for row in rows {
+            records.push(row?);
+        }
+        Ok(records)
+    }
+}
 
-        let mut rows = stmt.query_map([id], |row| {
-            Ok(Persona {
-                id: Some(row.get::<_, i64>(0)?),
-                name: row.get(1)?,
-                description: {
-                    let desc: String = row.get(2)?;
-                    if desc.is_empty() {
-                        None
-                    } else {
-                        Some(desc)
-                    }
-                },
-                system_prompt: row.get(3)?,
-                avatar_path: None,
-                memory_context: None,
-                settings: None,
-                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
-                    .unwrap_or_default()
-                    .with_timezone(&Utc),
-                updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
-                    .unwrap_or_default()
-                    .with_timezone(&Utc),
-                active: row.get::<_, String>(6)? == "true",
-            })
-        })?;
+/// Project service - CRUD for development projects and their linked conversations
+///
+/// Projects group conversations around a piece of work (e.g. a repository) so
+/// `get_project_context` can assemble everything relevant to that work for a provider prompt,
+/// without the caller having to know which conversations belong to it.
+pub struct ProjectService {
+    db: std::sync::Arc<DatabaseManager>,
+}
 
-        match rows.next() {
-            Some(row) => Ok(Some(row?)),
-            None => Ok(None),
+impl ProjectService {
+    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
+        Self { db }
+    }
+
+    fn status_to_str(status: &crate::models::ProjectStatus) -> &'static str {
+        match status {
+            crate::models::ProjectStatus::Active => "active",
+            crate::models::ProjectStatus::Paused => "paused",
+            crate::models::ProjectStatus::Completed => "completed",
+            crate::models::ProjectStatus::Archived => "archived",
         }
     }
 
-    /// Update persona
-    pub fn update_persona(
+    fn status_from_str(status: &str) -> crate::models::ProjectStatus {
+        match status {
+            "paused" => crate::models::ProjectStatus::Paused,
+            "completed" => crate::models::ProjectStatus::Completed,
+            "archived" => crate::models::ProjectStatus::Archived,
+            _ => crate::models::ProjectStatus::Active,
+        }
+    }
+
+    fn row_to_project(row: &rusqlite::Row) -> rusqlite::Result<crate::models::Project> {
+        Ok(crate::models::Project {
+            id: Some(row.get(0)?),
+            name: row.get(1)?,
+            description: row.get(2)?,
+            repository_url: row.get(3)?,
+            status: Self::status_from_str(&row.get::<_, String>(4)?),
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(6)?)
+                .unwrap_or_default()
+                .with_timezone(&Utc),
+            // `metadata` is always loaded separately, matching `ConversationService::get_conversation`
+            metadata: None,
+        })
+    }
+
+    /// Create a new project
+    pub fn create_project(
         &self,
-        id: i64,
+        name: String,
+        description: Option<String>,
+        repository_url: Option<String>,
+    ) -> SqliteResult<crate::models::Project> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            "INSERT INTO projects (id, name, description, repository_url, status, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, 'active', ?5, ?5)",
+            params![id, name, description, repository_url, now.to_rfc3339()],
+        )?;
+
+        Ok(crate::models::Project {
+            id: Some(id),
+            name,
+            description,
+            repository_url,
+            status: crate::models::ProjectStatus::Active,
+            created_at: now,
+            updated_at: now,
+            metadata: None,
+        })
+    }
+
+    /// Update a project's name, description, repository URL, and/or status. Fields left `None`
+    /// are left unchanged.
+    pub fn update_project(
+        &self,
+        id: &str,
         name: Option<String>,
         description: Option<String>,
-        system_prompt: Option<String>,
+        repository_url: Option<String>,
+        status: Option<crate::models::ProjectStatus>,
     ) -> SqliteResult<()> {
-        let mut query_parts = Vec::new();
-        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
-
-        if let Some(name) = name {
-            query_parts.push("name = ?");
-            params.push(Box::new(name));
-        }
-        if let Some(description) = description {
-            query_parts.push("description = ?");
-            params.push(Box::new(description));
-        }
-        if let Some(system_prompt) = system_prompt {
-            query_parts.push("system_prompt = ?");
-            params.push(Box::new(system_prompt));
-        }
+        let builder = crate::database::query::UpdateBuilder::new()
+            .set("name", name)
+            .set("description", description)
+            .set("repository_url", repository_url)
+            .set("status", status.map(|status| Self::status_to_str(&status)));
 
-        if query_parts.is_empty() {
+        if builder.is_empty() {
             return Ok(());
         }
 
-        query_parts.push("updated_at = ?");
-        params.push(Box::new(Utc::now().to_rfc3339()));
-        params.push(Box::new(id));
-
-        let query = format!(
-            "UPDATE personas SET {} WHERE id = ?",
-            query_parts.join(", ")
-        );
+        let (set_clause, params) = builder.finish(("updated_at", Utc::now().to_rfc3339()), id.to_string());
+        let query = format!("UPDATE projects SET {} WHERE id = ?", set_clause);
 
         let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
         let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
@@ -445,90 +5153,73 @@ impl PersonaService {
         Ok(())
     }
 
-    /// Delete persona
-    pub fn delete_persona(&self, id: i64) -> SqliteResult<()> {
+    /// List all projects, most recently updated first
+    pub fn list_projects(&self) -> SqliteResult<Vec<crate::models::Project>> {
         let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
-        conn.execute("DELETE FROM personas WHERE id = ?1", [id])?;
-        Ok(())
-    }
-}
+        let mut stmt = conn.prepare(
+            "SELECT id, name, description, repository_url, status, created_at, updated_at
+             FROM projects
+             ORDER BY updated_at DESC",
+        )?;
 
-/// API service - Manages external AI service configurations
-pub struct ApiService {
-    db: std::sync::Arc<DatabaseManager>,
-}
+        let rows = stmt.query_map([], Self::row_to_project)?;
+        let mut projects = Vec::new();
+        for row in rows {
+            projects.push(row?);
+        }
+        Ok(projects)
+    }
 
-impl ApiService {
-    pub fn new(db: std::sync::Arc<DatabaseManager>) -> Self {
-        Self { db }
+    /// Look up a project by id
+    pub fn get_project(&self, id: &str) -> SqliteResult<Option<crate::models::Project>> {
+        let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.query_row(
+            "SELECT id, name, description, repository_url, status, created_at, updated_at
+             FROM projects WHERE id = ?1",
+            [id],
+            Self::row_to_project,
+        )
+        .optional()
     }
 
-    /// Store API configuration (encrypt sensitive data)
-    pub fn store_api_config(
-        &self,
-        provider: String,
-        api_key: String,
-        base_url: Option<String>,
-    ) -> SqliteResult<()> {
-        // TODO: Implement proper encryption for API keys
-        let encrypted_key = api_key; // Placeholder - implement actual encryption
+    /// Mark a project archived, same as `update_project` with `status: Archived`
+    pub fn archive_project(&self, id: &str) -> SqliteResult<()> {
+        self.update_project(id, None, None, None, Some(crate::models::ProjectStatus::Archived))
+    }
 
+    /// Attach a conversation to a project; a no-op if already linked
+    pub fn link_conversation(&self, project_id: &str, conversation_id: i64) -> SqliteResult<()> {
         let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
         conn.execute(
-            "INSERT OR REPLACE INTO api_configs
-             (id, provider, api_key, base_url, created_at, updated_at, active)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            [
-                &provider,
-                &provider,
-                &encrypted_key,
-                &base_url.unwrap_or_default(),
-                &Utc::now().to_rfc3339(),
-                &Utc::now().to_rfc3339(),
-                "true",
-            ],
+            "INSERT OR IGNORE INTO conversation_projects (conversation_id, project_id, created_at)
+             VALUES (?1, ?2, ?3)",
+            params![conversation_id, project_id, Utc::now().to_rfc3339()],
         )?;
         Ok(())
     }
 
-    /// Retrieve API configuration (decrypt sensitive data)
-    pub fn get_api_config(&self, provider: &str) -> SqliteResult<Option<(String, Option<String>)>> {
+    /// Detach a conversation from a project
+    pub fn unlink_conversation(&self, project_id: &str, conversation_id: i64) -> SqliteResult<()> {
         let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
-        let mut stmt = conn.prepare(
-            "SELECT api_key, base_url FROM api_configs WHERE provider = ?1 AND active = 'true'",
+        conn.execute(
+            "DELETE FROM conversation_projects WHERE project_id = ?1 AND conversation_id = ?2",
+            params![project_id, conversation_id],
         )?;
-
-        let mut rows = stmt.query_map([provider], |row| {
-            let encrypted_key: String = row.get(0)?;
-            let base_url: Option<String> = {
-                let url: String = row.get(1)?;
-                if url.is_empty() {
-                    None
-                } else {
-                    Some(url)
-                }
-            };
-
-            // TODO: Implement proper decryption for API keys
-            let decrypted_key = encrypted_key; // Placeholder
-
-            Ok((decrypted_key, base_url))
-        })?;
-
-        match rows.next() {
-            Some(row) => Ok(Some(row?)),
-            None => Ok(None),
-        }
+        Ok(())
     }
 
-    /// Delete API configuration
-    pub fn delete_api_config(&self, provider: &str) -> SqliteResult<()> {
+    /// Ids of every conversation linked to `project_id`
+    pub fn linked_conversation_ids(&self, project_id: &str) -> SqliteResult<Vec<i64>> {
         let conn = self.db.get_connection().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
-        conn.execute(
-            "UPDATE api_configs SET active = 'false', updated_at = ?1 WHERE provider = ?2",
-            [&Utc::now().to_rfc3339(), provider],
+        let mut stmt = conn.prepare(
+            "SELECT conversation_id FROM conversation_projects WHERE project_id = ?1 ORDER BY created_at ASC",
         )?;
-        Ok(())
+        let rows = stmt.query_map([project_id], |row| row.get(0))?;
+        let mut ids = Vec::new();
+        for row in rows {
+            ids.push(row?);
+        }
+        Ok(ids)
     }
 }
 
@@ -536,7 +5227,31 @@ impl ApiService {
 pub struct Services {
     pub conversations: ConversationService,
     pub personas: PersonaService,
+    pub persona_memories: PersonaMemoryService,
     pub apis: ApiService,
+    pub sessions: SessionService,
+    pub grimoire: GrimoireService,
+    pub profiles: ProfileService,
+    pub usage_policy: UsagePolicyService,
+    pub snippets: SnippetService,
+    pub read_aloud: ReadAloudService,
+    pub slash_commands: SlashCommandService,
+    pub shortcuts: ShortcutService,
+    pub import_jobs: ImportJobService,
+    pub audit_log: AuditLogService,
+    pub prompt_templates: PromptTemplateService,
+    pub attachments: AttachmentService,
+    pub webhooks: WebhookService,
+    pub snapshot_server: SnapshotServerService,
+    pub mcp_clients: McpClientService,
+    pub embeddings: EmbeddingService,
+    pub reliability: ReliabilityService,
+    pub usage_analytics: UsageAnalyticsService,
+    pub projects: ProjectService,
+    pub telemetry: TelemetryService,
+    /// Shared query result cache, primarily used by read-heavy lookups and by
+    /// the `trim_caches` / `get_memory_report` commands for low-RAM environments
+    pub query_cache: QueryCache,
 }
 
 impl Services {
@@ -544,7 +5259,62 @@ impl Services {
         Self {
             conversations: ConversationService::new(db.clone()),
             personas: PersonaService::new(db.clone()),
-            apis: ApiService::new(db),
+            persona_memories: PersonaMemoryService::new(db.clone()),
+            apis: ApiService::new(db.clone()),
+            sessions: SessionService::new(db.clone()),
+            grimoire: GrimoireService::new(db.clone()),
+            profiles: ProfileService::new(db.clone()),
+            usage_policy: UsagePolicyService::new(db.clone()),
+            snippets: SnippetService::new(db.clone()),
+            read_aloud: ReadAloudService::new(db.clone()),
+            slash_commands: SlashCommandService::new(db.clone()),
+            shortcuts: ShortcutService::new(db.clone()),
+            import_jobs: ImportJobService::new(db.clone()),
+            audit_log: AuditLogService::new(db.clone()),
+            prompt_templates: PromptTemplateService::new(db.clone()),
+            attachments: AttachmentService::new(db.clone()),
+            webhooks: WebhookService::new(db.clone()),
+            snapshot_server: SnapshotServerService::new(db.clone()),
+            mcp_clients: McpClientService::new(),
+            embeddings: EmbeddingService::new(db.clone()),
+            reliability: ReliabilityService::new(db.clone()),
+            usage_analytics: UsageAnalyticsService::new(db.clone()),
+            projects: ProjectService::new(db.clone()),
+            telemetry: TelemetryService::new(db),
+            query_cache: QueryCache::new(DEFAULT_QUERY_CACHE_TTL_SECONDS),
+        }
+    }
+
+    /// Evict cached message pages and summaries for conversations outside the `keep_recent`
+    /// most-recently-opened window, keyed as `conversation:{id}:messages` and
+    /// `conversation:{id}:summary` in `query_cache`. Called on conversation open so the cache
+    /// stays bounded by how many threads a user is actually switching between, not by the size
+    /// of the whole library.
+    pub fn evict_stale_conversation_cache(&self, keep_recent: i32) -> SqliteResult<()> {
+        for id in self.conversations.conversation_ids_beyond_recency_window(keep_recent)? {
+            self.query_cache.invalidate(&format!("conversation:{}:messages", id));
+            self.query_cache.invalidate(&format!("conversation:{}:summary", id));
+        }
+        Ok(())
+    }
+
+    /// `evict_stale_conversation_cache` using the default cache window, called whenever a
+    /// conversation is opened
+    pub fn evict_stale_conversation_cache_default(&self) -> SqliteResult<()> {
+        self.evict_stale_conversation_cache(DEFAULT_CACHED_CONVERSATION_WINDOW)
+    }
+
+    /// Flush in-memory caches and checkpoint the WAL ahead of process exit
+    ///
+    /// Called by the shutdown coordinator; errors are logged rather than propagated
+    /// since there is nothing left upstream to handle them once the app is closing.
+    pub fn shutdown(&self) {
+        self.query_cache.clear();
+        if let Err(e) = self.reliability.record_clean_shutdown() {
+            tracing::warn!("⚠️ Failed to record clean shutdown: {}", e);
+        }
+        if let Err(e) = self.conversations.db.checkpoint_wal() {
+            tracing::warn!("⚠️ Failed to checkpoint WAL during shutdown: {}", e);
         }
     }
 }
@@ -811,4 +5581,139 @@ mod tests {
         assert_eq!(conversations[1].title, "Second");
         assert_eq!(conversations[2].title, "First");
     }
+
+    fn setup_grimoire_test_environment() -> (GrimoireService, TempDir) {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+        let db_manager = DatabaseManager::new_in_memory().expect("Failed to create test database");
+        let service = GrimoireService::new(Arc::new(db_manager));
+        (service, temp_dir)
+    }
+
+    #[test]
+    fn test_grimoire_get_entry_bumps_accessed_count() {
+        let (service, _temp_dir) = setup_grimoire_test_environment();
+        let id = service
+            .create_entry("Spell", "content", None, None, None, None)
+            .unwrap();
+
+        let entry = service.get_entry(&id).unwrap().unwrap();
+        assert_eq!(entry.accessed_count, 1);
+        assert!(entry.last_accessed.is_some());
+
+        let entry = service.get_entry(&id).unwrap().unwrap();
+        assert_eq!(entry.accessed_count, 2);
+    }
+
+    #[test]
+    fn test_grimoire_update_entry_reencrypts_when_already_encrypted() {
+        let (service, _temp_dir) = setup_grimoire_test_environment();
+        let id = service
+            .create_entry("Spell", "original content", None, None, None, None)
+            .unwrap();
+
+        assert!(service.encrypt_entry(&id).unwrap());
+
+        let updated = service
+            .update_entry(&id, "Spell", "updated content", None, None)
+            .unwrap();
+        assert!(updated);
+
+        // The row is still marked encrypted, and its ciphertext isn't the plaintext we wrote
+        let entry = service.get_entry(&id).unwrap().unwrap();
+        assert!(entry.encrypted);
+        assert_eq!(entry.content, "updated content");
+
+        // Decrypting back to plaintext at rest should round-trip to the new content too
+        assert!(service.decrypt_entry(&id).unwrap());
+        assert_eq!(service.get_entry_content(&id).unwrap().unwrap(), "updated content");
+    }
+
+    #[test]
+    fn test_grimoire_list_and_search_round_trip_decryption() {
+        let (service, _temp_dir) = setup_grimoire_test_environment();
+        let plain_id = service
+            .create_entry("Plain", "plain content", Some("lore"), Some("alpha"), None, None)
+            .unwrap();
+        let encrypted_id = service
+            .create_entry("Secret", "secret content", Some("lore"), Some("beta"), None, None)
+            .unwrap();
+        assert!(service.encrypt_entry(&encrypted_id).unwrap());
+
+        let listed = service.list_entries().unwrap();
+        assert_eq!(listed.len(), 2);
+        for entry in &listed {
+            if entry.id == plain_id {
+                assert_eq!(entry.content, "plain content");
+            } else if entry.id == encrypted_id {
+                assert_eq!(entry.content, "secret content");
+            }
+        }
+
+        let by_category = service.search_by_category("lore").unwrap();
+        assert_eq!(by_category.len(), 2);
+        assert!(by_category.iter().any(|e| e.content == "secret content"));
+
+        let by_tag = service.search_by_tag("beta").unwrap();
+        assert_eq!(by_tag.len(), 1);
+        assert_eq!(by_tag[0].content, "secret content");
+    }
+
+    #[test]
+    fn test_grimoire_delete_entry() {
+        let (service, _temp_dir) = setup_grimoire_test_environment();
+        let id = service
+            .create_entry("Spell", "content", None, None, None, None)
+            .unwrap();
+
+        assert!(service.delete_entry(&id).unwrap());
+        assert!(!service.delete_entry(&id).unwrap());
+        assert!(service.get_entry(&id).unwrap().is_none());
+    }
+
+    fn setup_webhook_test_environment() -> (WebhookService, TempDir) {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+        let db_manager = DatabaseManager::new_in_memory().expect("Failed to create test database");
+        let service = WebhookService::new(Arc::new(db_manager));
+        (service, temp_dir)
+    }
+
+    #[test]
+    fn test_find_matching_unions_conversation_and_tag_scoped_webhooks() {
+        let (service, _temp_dir) = setup_webhook_test_environment();
+
+        let by_conversation = service
+            .register_webhook(
+                crate::webhooks::WebhookScope::Conversation,
+                "1".to_string(),
+                "https://example.com/conversation-hook".to_string(),
+                "secret-a".to_string(),
+            )
+            .unwrap();
+        let by_tag = service
+            .register_webhook(
+                crate::webhooks::WebhookScope::Tag,
+                "urgent".to_string(),
+                "https://example.com/tag-hook".to_string(),
+                "secret-b".to_string(),
+            )
+            .unwrap();
+        // Never matches conversation 1 or tag "urgent" - confirms find_matching doesn't return
+        // every webhook unconditionally
+        service
+            .register_webhook(
+                crate::webhooks::WebhookScope::Tag,
+                "unrelated".to_string(),
+                "https://example.com/other-hook".to_string(),
+                "secret-c".to_string(),
+            )
+            .unwrap();
+
+        // Passing "urgent" twice shouldn't duplicate the tag-scoped webhook in the result
+        let tags = vec!["urgent".to_string(), "urgent".to_string()];
+        let matched = service.find_matching(1, &tags).unwrap();
+
+        assert_eq!(matched.len(), 2);
+        assert!(matched.iter().any(|w| w.id == by_conversation.id));
+        assert!(matched.iter().any(|w| w.id == by_tag.id));
+    }
 }
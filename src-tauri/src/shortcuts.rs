@@ -0,0 +1,96 @@
+//! OS-level global keyboard shortcuts, dispatched to named actions
+//!
+//! Bindings are persisted in the `shortcuts` table via
+//! [`crate::services::ShortcutService`] and re-registered with Tauri's
+//! [`tauri::GlobalShortcutManager`] on every launch by [`register_all`], so a
+//! user's custom bindings survive a restart. [`dispatch_action`] is what
+//! actually runs when a registered accelerator fires - it's also called
+//! directly by `main.rs`'s menu/tray handling where a command round-trip
+//! isn't needed.
+
+use tauri::{AppHandle, GlobalShortcutManager, Manager, WindowBuilder, WindowUrl};
+
+/// Opens a new conversation, dispatched by emitting the same event the system tray's
+/// "New Conversation" menu item already emits for the frontend to react to
+pub const NEW_CONVERSATION_ACTION: &str = "new_conversation";
+
+/// Shows or hides the quick capture mini window
+pub const TOGGLE_QUICK_CAPTURE_ACTION: &str = "toggle_quick_capture";
+
+const QUICK_CAPTURE_WINDOW_LABEL: &str = "quick_capture";
+
+/// Bindings registered the first time the app launches with an empty `shortcuts` table
+pub fn default_shortcuts() -> Vec<(&'static str, &'static str)> {
+    vec![
+        (NEW_CONVERSATION_ACTION, "CmdOrCtrl+Shift+N"),
+        (TOGGLE_QUICK_CAPTURE_ACTION, "CmdOrCtrl+Shift+Space"),
+    ]
+}
+
+/// Register every persisted shortcut with the OS, logging (rather than failing startup on) any
+/// accelerator that's already claimed by another application
+pub fn register_all(app: &AppHandle, shortcuts: &[crate::models::Shortcut]) {
+    let mut manager = app.global_shortcut_manager();
+    for shortcut in shortcuts {
+        let action = shortcut.action.clone();
+        let app_handle = app.clone();
+        if let Err(e) = manager.register(&shortcut.accelerator, move || {
+            dispatch_action(&app_handle, &action);
+        }) {
+            tracing::error!(
+                "⚠️ Failed to register shortcut '{}' for action '{}': {}",
+                shortcut.accelerator,
+                shortcut.action,
+                e
+            );
+        }
+    }
+}
+
+/// Run the action bound to a shortcut that just fired
+pub fn dispatch_action(app: &AppHandle, action: &str) {
+    match action {
+        NEW_CONVERSATION_ACTION => {
+            if let Some(window) = app.get_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            let _ = app.emit_all("tray-new-conversation", ());
+        }
+        TOGGLE_QUICK_CAPTURE_ACTION => toggle_quick_capture_window(app),
+        _ => {
+            // User-defined action with no built-in behavior - let the frontend decide what to do
+            let _ = app.emit_all("shortcut-triggered", action);
+        }
+    }
+}
+
+/// Show the quick capture mini window if it's hidden, hide it if it's visible, creating it on
+/// first use
+pub fn toggle_quick_capture_window(app: &AppHandle) {
+    if let Some(window) = app.get_window(QUICK_CAPTURE_WINDOW_LABEL) {
+        let is_visible = window.is_visible().unwrap_or(false);
+        if is_visible {
+            let _ = window.hide();
+        } else {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+        return;
+    }
+
+    if let Err(e) = WindowBuilder::new(
+        app,
+        QUICK_CAPTURE_WINDOW_LABEL,
+        WindowUrl::App("quick-capture".into()),
+    )
+    .title("Quick Capture")
+    .inner_size(420.0, 180.0)
+    .resizable(false)
+    .always_on_top(true)
+    .center()
+    .build()
+    {
+        tracing::error!("⚠️ Failed to open quick capture window: {}", e);
+    }
+}
@@ -0,0 +1,256 @@
+//! Optional read-only HTTP server that exposes a fixed set of conversations as sanitized HTML,
+//! so they can be read from another device (e.g. a tablet) on the same network without syncing
+//! anything off the machine.
+//!
+//! Hand-rolls minimal HTTP/1.1 `GET` handling directly on `tokio::net::TcpListener` rather than
+//! pulling in a web framework - this app has no existing HTTP server dependency, and a handful
+//! of read-only pages don't warrant one (the same reasoning that keeps archive export on
+//! `flate2` instead of a full `zip` crate - see `crate::library_archive`). Every request must
+//! carry the configured token as a `token` query parameter or it's rejected with 403; binding to
+//! a LAN address rather than `127.0.0.1` is the caller's choice via `bind_addr`, not this
+//! module's concern.
+
+use crate::database::DatabaseManager;
+use crate::errors::{AppError, AppResult};
+use crate::services::ConversationService;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+
+/// Which conversations are exposed, where to listen, and the token required to view them
+#[derive(Debug, Clone)]
+pub struct SnapshotServerConfig {
+    pub bind_addr: String,
+    pub port: u16,
+    pub token: String,
+    pub conversation_ids: Vec<i64>,
+}
+
+/// Handle returned by [`spawn`]; call `stop()` to shut the server down. Dropping it without
+/// calling `stop()` leaves the server running for the rest of the process's lifetime.
+pub struct SnapshotServerHandle {
+    stop: oneshot::Sender<()>,
+}
+
+impl SnapshotServerHandle {
+    pub fn stop(self) {
+        let _ = self.stop.send(());
+    }
+}
+
+/// Bind `config.bind_addr:config.port` and start serving in a background task
+pub fn spawn(db: Arc<DatabaseManager>, config: SnapshotServerConfig) -> AppResult<SnapshotServerHandle> {
+    let addr = format!("{}:{}", config.bind_addr, config.port);
+    let std_listener = std::net::TcpListener::bind(&addr)
+        .map_err(|e| AppError::io(format!("Failed to bind snapshot server to {}: {}", addr, e)))?;
+    std_listener
+        .set_nonblocking(true)
+        .map_err(|e| AppError::io(format!("Failed to configure snapshot server listener: {}", e)))?;
+    let listener = TcpListener::from_std(std_listener)
+        .map_err(|e| AppError::io(format!("Failed to attach snapshot server listener to the async runtime: {}", e)))?;
+
+    let (stop_tx, mut stop_rx) = oneshot::channel();
+    let config = Arc::new(config);
+
+    tokio::spawn(async move {
+        tracing::info!("📖 Snapshot server listening on {}", addr);
+        loop {
+            tokio::select! {
+                _ = &mut stop_rx => {
+                    tracing::info!("📖 Snapshot server stopped");
+                    break;
+                }
+                accepted = listener.accept() => {
+                    match accepted {
+                        Ok((stream, _)) => {
+                            let db = db.clone();
+                            let config = config.clone();
+                            tokio::spawn(async move {
+                                if let Err(e) = handle_connection(stream, db, &config).await {
+                                    tracing::warn!("Snapshot server request failed: {}", e);
+                                }
+                            });
+                        }
+                        Err(e) => tracing::warn!("Snapshot server accept failed: {}", e),
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(SnapshotServerHandle { stop: stop_tx })
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    db: Arc<DatabaseManager>,
+    config: &SnapshotServerConfig,
+) -> std::io::Result<()> {
+    let mut buf = [0u8; 8192];
+    let n = stream.read(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let request_line = request.lines().next().unwrap_or("");
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let target = parts.next().unwrap_or("/");
+
+    if method != "GET" {
+        return write_response(&mut stream, 405, "Method Not Allowed", "Only GET is supported").await;
+    }
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let token_matches = query_param(query, "token")
+        .map(|token| constant_time_eq(token.as_bytes(), config.token.as_bytes()))
+        .unwrap_or(false);
+
+    if !token_matches {
+        return write_response(&mut stream, 403, "Forbidden", "Missing or invalid token").await;
+    }
+
+    let conversations = ConversationService::new(db);
+
+    let page = if path == "/" {
+        Ok(render_index(&conversations, config))
+    } else if let Some(id_str) = path.strip_prefix("/conversation/") {
+        match id_str.parse::<i64>() {
+            Ok(id) if config.conversation_ids.contains(&id) => render_conversation(&conversations, id),
+            Ok(_) => Err(AppError::not_found("That conversation is not part of this snapshot")),
+            Err(_) => Err(AppError::validation("Invalid conversation id")),
+        }
+    } else {
+        Err(AppError::not_found("Not found"))
+    };
+
+    match page {
+        Ok(html) => write_response(&mut stream, 200, "OK", &html).await,
+        Err(e) => write_response(&mut stream, 404, "Not Found", &format!("<p>{}</p>", escape_html(&e.to_string()))).await,
+    }
+}
+
+fn render_index(conversations: &ConversationService, config: &SnapshotServerConfig) -> String {
+    let mut items = String::new();
+    for &id in &config.conversation_ids {
+        let title = conversations
+            .get_conversation(id)
+            .ok()
+            .flatten()
+            .map(|c| c.title)
+            .unwrap_or_else(|| format!("Conversation {}", id));
+        items.push_str(&format!(
+            "<li><a href=\"/conversation/{}?token={}\">{}</a></li>\n",
+            id,
+            escape_html(&config.token),
+            escape_html(&title)
+        ));
+    }
+
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Forbidden Library snapshot</title></head>\
+         <body><h1>Shared conversations</h1><ul>{}</ul></body></html>",
+        items
+    )
+}
+
+fn render_conversation(conversations: &ConversationService, conversation_id: i64) -> AppResult<String> {
+    let conversation = conversations
+        .get_conversation(conversation_id)?
+        .ok_or_else(|| AppError::not_found(format!("Conversation {} not found", conversation_id)))?;
+    let messages = conversations.get_messages(conversation_id, None, None)?;
+
+    let mut body = String::new();
+    for message in &messages {
+        body.push_str(&format!(
+            "<div class=\"message {:?}\"><strong>{:?}</strong><pre>{}</pre></div>\n",
+            message.role,
+            message.role,
+            escape_html(&message.content)
+        ));
+    }
+
+    Ok(format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{}</title></head>\
+         <body><h1>{}</h1>{}</body></html>",
+        escape_html(&conversation.title),
+        escape_html(&conversation.title),
+        body
+    ))
+}
+
+/// Escape the five HTML special characters; this module's only defense against an injected
+/// message turning into executable markup in a browser, since nothing else in this codebase
+/// renders message content as HTML
+fn escape_html(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Percent-decode a single query-string value; `+` is treated as a literal space per
+/// `application/x-www-form-urlencoded`
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 3;
+                    }
+                    None => {
+                        decoded.push(bytes[i]);
+                        i += 1;
+                    }
+                }
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn query_param(query: &str, key: &str) -> Option<String> {
+    query.split('&').find_map(|pair| {
+        let (k, v) = pair.split_once('=')?;
+        (k == key).then(|| percent_decode(v))
+    })
+}
+
+/// Constant-time byte comparison for the `token` query parameter, so an attacker probing this
+/// server over the network can't use response-timing differences to recover the configured token
+/// one byte at a time, the same class of bug `conversation_share.rs` fixed for snapshot
+/// signatures via `ring::hmac::verify` - plain equality-checking short-circuits on the first
+/// mismatched byte.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+async fn write_response(stream: &mut TcpStream, status: u16, reason: &str, body: &str) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await
+}
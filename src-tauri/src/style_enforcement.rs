@@ -0,0 +1,117 @@
+//! Enforcement pass for persona response style constraints
+//!
+//! Only `ResponseStyle::Concise` and `ResponseStyle::Formal` are enforceable today - other
+//! styles are intentionally free-form, so [`check_style`] always reports them as compliant.
+
+use crate::models::{ResponseStyle, StyleEnforcementConfig};
+
+/// A single way a response failed a persona's configured style constraints
+#[derive(Debug, Clone, PartialEq)]
+pub enum StyleViolation {
+    TooLong { limit: usize, actual: usize },
+    BannedPhrase(String),
+}
+
+/// Check `response` against `config`, if `style` is one this feature enforces
+pub fn check_style(
+    response: &str,
+    style: &ResponseStyle,
+    config: &StyleEnforcementConfig,
+) -> Vec<StyleViolation> {
+    let mut violations = Vec::new();
+
+    if !config.enabled || !matches!(style, ResponseStyle::Concise | ResponseStyle::Formal) {
+        return violations;
+    }
+
+    if let Some(limit) = config.max_chars {
+        let actual = response.chars().count();
+        if actual > limit {
+            violations.push(StyleViolation::TooLong { limit, actual });
+        }
+    }
+
+    let lower = response.to_lowercase();
+    for phrase in &config.banned_phrases {
+        if lower.contains(&phrase.to_lowercase()) {
+            violations.push(StyleViolation::BannedPhrase(phrase.clone()));
+        }
+    }
+
+    violations
+}
+
+/// Build the one-time revision instruction sent back to the model, describing exactly which
+/// constraints the previous response broke
+pub fn build_revision_prompt(violations: &[StyleViolation]) -> String {
+    let mut lines = vec!["Your previous response did not follow the required style:".to_string()];
+    for violation in violations {
+        match violation {
+            StyleViolation::TooLong { limit, actual } => lines.push(format!(
+                "- It was {} characters long, exceeding the {} character limit. Shorten it.",
+                actual, limit
+            )),
+            StyleViolation::BannedPhrase(phrase) => lines.push(format!(
+                "- It used the banned phrase \"{}\". Remove it and rephrase.",
+                phrase
+            )),
+        }
+    }
+    lines.push("Please revise your response to follow these constraints.".to_string());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled_config() -> StyleEnforcementConfig {
+        StyleEnforcementConfig {
+            enabled: true,
+            max_chars: Some(20),
+            banned_phrases: vec!["as an ai".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_conversational_style_is_never_enforced() {
+        let violations = check_style(
+            "this response is definitely longer than twenty characters",
+            &ResponseStyle::Conversational,
+            &enabled_config(),
+        );
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_disabled_config_skips_checks() {
+        let config = StyleEnforcementConfig {
+            enabled: false,
+            ..enabled_config()
+        };
+        let violations = check_style("way too long for the configured limit", &ResponseStyle::Concise, &config);
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_detects_length_and_banned_phrase() {
+        let violations = check_style(
+            "As an AI, I must say this response is far too long",
+            &ResponseStyle::Formal,
+            &enabled_config(),
+        );
+        assert_eq!(violations.len(), 2);
+        assert!(violations
+            .iter()
+            .any(|v| matches!(v, StyleViolation::TooLong { .. })));
+        assert!(violations
+            .iter()
+            .any(|v| matches!(v, StyleViolation::BannedPhrase(p) if p == "as an ai")));
+    }
+
+    #[test]
+    fn test_compliant_response_has_no_violations() {
+        let violations = check_style("Short and formal.", &ResponseStyle::Concise, &enabled_config());
+        assert!(violations.is_empty());
+    }
+}
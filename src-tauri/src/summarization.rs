@@ -0,0 +1,190 @@
+//! Pluggable summarization strategies
+//!
+//! Conversation titling and the weekly digest both boil down to "give me a short summary of
+//! this text". [`ExtractiveSummarizer`] answers that locally and instantly by picking
+//! representative sentences out of the original text - no provider call, no spend, no network -
+//! while [`AbstractiveSummarizer`] asks the configured model to write fresh prose. Both
+//! implement [`Summarizer`] so a caller can either name a strategy explicitly or fall back to
+//! [`default_strategy_for`]'s size-based pick, and a future strategy only needs a new
+//! `Summarizer` impl, not changes to every call site.
+
+use crate::ai_providers::{AIProvider, AIRequest, ChatMessage};
+use crate::errors::AppResult;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Content shorter than this is summarized extractively by default; beyond it, picking
+/// existing sentences tends to read as a grab-bag of unrelated fragments, so the default
+/// switches to the model-backed abstractive strategy instead.
+const EXTRACTIVE_DEFAULT_MAX_CHARS: usize = 2_000;
+
+/// A strategy for condensing `content` into a short summary
+#[async_trait]
+pub trait Summarizer: Send + Sync {
+    /// Human-readable strategy name, for logging and for surfacing the choice to the frontend
+    fn name(&self) -> &'static str;
+
+    /// Summarize `content`, returning plain text
+    async fn summarize(&self, content: &str) -> AppResult<String>;
+}
+
+/// Fast local summarizer that extracts representative sentences instead of generating new text
+///
+/// Picks the first sentence (usually states the topic) plus the longest remaining sentence
+/// (usually the most information-dense one), up to `max_sentences`, kept in their original
+/// order. Cheap enough to run as part of a larger operation without a network round-trip, and
+/// the sensible default for content too short for a model call to be worth its cost.
+pub struct ExtractiveSummarizer {
+    pub max_sentences: usize,
+}
+
+impl Default for ExtractiveSummarizer {
+    fn default() -> Self {
+        Self { max_sentences: 2 }
+    }
+}
+
+#[async_trait]
+impl Summarizer for ExtractiveSummarizer {
+    fn name(&self) -> &'static str {
+        "extractive"
+    }
+
+    async fn summarize(&self, content: &str) -> AppResult<String> {
+        let sentences: Vec<&str> = content
+            .split(['.', '!', '?'])
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        if sentences.is_empty() {
+            return Ok(String::new());
+        }
+        if sentences.len() <= self.max_sentences.max(1) {
+            return Ok(format!("{}.", sentences.join(". ")));
+        }
+
+        let mut chosen_indices = vec![0];
+        if let Some((longest_index, _)) = sentences
+            .iter()
+            .enumerate()
+            .skip(1)
+            .max_by_key(|(_, sentence)| sentence.len())
+        {
+            chosen_indices.push(longest_index);
+        }
+        chosen_indices.sort_unstable();
+        chosen_indices.dedup();
+        chosen_indices.truncate(self.max_sentences.max(1));
+
+        let summary = chosen_indices
+            .into_iter()
+            .map(|i| sentences[i])
+            .collect::<Vec<_>>()
+            .join(". ");
+        Ok(format!("{}.", summary))
+    }
+}
+
+/// Summarizer that hands `content` to the configured AI provider/model and returns its reply
+///
+/// Produces genuinely new, coherent prose at the cost of a network round-trip (and provider
+/// spend) - use it where an extractive pick of existing sentences would lose too much.
+pub struct AbstractiveSummarizer {
+    provider: AIProvider,
+    model: String,
+    instruction: String,
+}
+
+impl AbstractiveSummarizer {
+    /// Build an abstractive summarizer with the repo's default "summarize concisely"
+    /// instruction; use [`Self::with_instruction`] to tailor it (e.g. for title generation).
+    pub fn new(provider: AIProvider, model: impl Into<String>) -> Self {
+        Self {
+            provider,
+            model: model.into(),
+            instruction: "Summarize the following text concisely.".to_string(),
+        }
+    }
+
+    pub fn with_instruction(mut self, instruction: impl Into<String>) -> Self {
+        self.instruction = instruction.into();
+        self
+    }
+}
+
+#[async_trait]
+impl Summarizer for AbstractiveSummarizer {
+    fn name(&self) -> &'static str {
+        "abstractive"
+    }
+
+    async fn summarize(&self, content: &str) -> AppResult<String> {
+        let request = AIRequest {
+            model: self.model.clone(),
+            messages: vec![ChatMessage {
+                role: "user".to_string(),
+                content: format!("{}\n\n{}", self.instruction, content),
+                tool_call_id: None,
+                pinned: false,
+            }],
+            temperature: None,
+            max_tokens: None,
+            stream: false,
+            tools: Vec::new(),
+        };
+
+        let response = self.provider.send_request(request).await?;
+        Ok(response.content.trim().to_string())
+    }
+}
+
+/// Strategy selector exposed across the command layer, so the frontend can request a specific
+/// strategy or leave it unset to get [`default_strategy_for`]'s size-based pick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummarizationStrategy {
+    Extractive,
+    Abstractive,
+}
+
+/// Default strategy for a piece of content, based on its length
+///
+/// Short content is summarized locally for free; longer content goes to the model, where a
+/// sentence-extraction pick would struggle to stay coherent.
+pub fn default_strategy_for(content: &str) -> SummarizationStrategy {
+    if content.len() <= EXTRACTIVE_DEFAULT_MAX_CHARS {
+        SummarizationStrategy::Extractive
+    } else {
+        SummarizationStrategy::Abstractive
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_extractive_summarizer_keeps_short_content_whole() {
+        let summarizer = ExtractiveSummarizer::default();
+        let summary = summarizer.summarize("Just one sentence").await.unwrap();
+        assert_eq!(summary, "Just one sentence.");
+    }
+
+    #[tokio::test]
+    async fn test_extractive_summarizer_picks_first_and_longest_sentence() {
+        let summarizer = ExtractiveSummarizer::default();
+        let content = "Short intro. A much longer and more information dense middle sentence here. Ok.";
+        let summary = summarizer.summarize(content).await.unwrap();
+        assert!(summary.starts_with("Short intro."));
+        assert!(summary.contains("information dense middle sentence"));
+        assert!(!summary.contains("Ok."));
+    }
+
+    #[test]
+    fn test_default_strategy_for_switches_on_length() {
+        assert_eq!(default_strategy_for("short"), SummarizationStrategy::Extractive);
+        let long_content = "x".repeat(EXTRACTIVE_DEFAULT_MAX_CHARS + 1);
+        assert_eq!(default_strategy_for(&long_content), SummarizationStrategy::Abstractive);
+    }
+}
@@ -0,0 +1,126 @@
+//! Keyword-heuristic sentiment and topic classification for conversations
+//!
+//! This stands in for a local classifier model: good enough to power filters like "show
+//! frustrated support threads from last month" without requiring a model download or network
+//! call, and cheap enough to run synchronously as part of a maintenance pass.
+
+use serde::{Deserialize, Serialize};
+
+/// Overall tone detected across a conversation's messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sentiment {
+    Positive,
+    Neutral,
+    Negative,
+    Frustrated,
+}
+
+impl Sentiment {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Sentiment::Positive => "positive",
+            Sentiment::Neutral => "neutral",
+            Sentiment::Negative => "negative",
+            Sentiment::Frustrated => "frustrated",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "positive" => Sentiment::Positive,
+            "negative" => Sentiment::Negative,
+            "frustrated" => Sentiment::Frustrated,
+            _ => Sentiment::Neutral,
+        }
+    }
+}
+
+/// Result of classifying a conversation's combined message text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationAnalysis {
+    pub topic: String,
+    pub sentiment: Sentiment,
+    pub tags: Vec<String>,
+}
+
+const TOPIC_KEYWORDS: &[(&str, &[&str])] = &[
+    ("billing", &["invoice", "charge", "refund", "billing", "payment", "subscription"]),
+    ("technical support", &["error", "bug", "crash", "broken", "doesn't work", "not working", "exception"]),
+    ("account", &["password", "login", "account", "sign in", "locked out"]),
+    ("feature request", &["feature request", "would be nice", "could you add", "suggestion"]),
+];
+
+const FRUSTRATED_KEYWORDS: &[&str] = &[
+    "frustrat", "angry", "furious", "ridiculous", "unacceptable", "fed up", "sick of", "useless",
+];
+const NEGATIVE_KEYWORDS: &[&str] = &[
+    "problem", "issue", "bad", "terrible", "disappointed", "not working", "doesn't work", "broken",
+];
+const POSITIVE_KEYWORDS: &[&str] = &[
+    "thanks", "thank you", "great", "awesome", "love it", "perfect", "works well", "appreciate",
+];
+
+/// Classify the combined text of a conversation's messages into a topic, sentiment, and tags
+pub fn classify_conversation(text: &str) -> ConversationAnalysis {
+    let lower = text.to_lowercase();
+
+    let sentiment = if FRUSTRATED_KEYWORDS.iter().any(|k| lower.contains(k)) {
+        Sentiment::Frustrated
+    } else if NEGATIVE_KEYWORDS.iter().any(|k| lower.contains(k)) {
+        Sentiment::Negative
+    } else if POSITIVE_KEYWORDS.iter().any(|k| lower.contains(k)) {
+        Sentiment::Positive
+    } else {
+        Sentiment::Neutral
+    };
+
+    let topic = TOPIC_KEYWORDS
+        .iter()
+        .find(|(_, keywords)| keywords.iter().any(|k| lower.contains(k)))
+        .map(|(topic, _)| topic.to_string())
+        .unwrap_or_else(|| "general".to_string());
+
+    let tags = vec![topic.clone(), sentiment.as_str().to_string()];
+
+    ConversationAnalysis {
+        topic,
+        sentiment,
+        tags,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classifies_frustrated_technical_support() {
+        let analysis = classify_conversation(
+            "This is so frustrating, the app keeps crashing with an error every time I log in",
+        );
+        assert_eq!(analysis.sentiment, Sentiment::Frustrated);
+        assert_eq!(analysis.topic, "technical support");
+    }
+
+    #[test]
+    fn test_classifies_positive_general() {
+        let analysis = classify_conversation("Thanks so much, that works perfectly!");
+        assert_eq!(analysis.sentiment, Sentiment::Positive);
+        assert_eq!(analysis.topic, "general");
+    }
+
+    #[test]
+    fn test_classifies_billing_negative() {
+        let analysis = classify_conversation("There's a problem with my latest invoice, the charge is wrong");
+        assert_eq!(analysis.sentiment, Sentiment::Negative);
+        assert_eq!(analysis.topic, "billing");
+    }
+
+    #[test]
+    fn test_sentiment_round_trip_through_string() {
+        for sentiment in [Sentiment::Positive, Sentiment::Neutral, Sentiment::Negative, Sentiment::Frustrated] {
+            assert_eq!(Sentiment::parse(sentiment.as_str()), sentiment);
+        }
+    }
+}
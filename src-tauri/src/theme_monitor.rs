@@ -0,0 +1,58 @@
+//! Background poller for the OS desktop theme (light/dark)
+//!
+//! [`crate::platform::is_dark_mode`] shells out to the OS (`gsettings`, `defaults read`, or a
+//! registry query) on every call, which is too slow to have the frontend poll directly. Mirrors
+//! [`crate::provider_monitor::ProviderMonitor`]'s shape: poll on an interval, cache the last
+//! known value, and only emit `theme-changed` when it actually flips.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::Manager;
+
+/// How often the monitor re-checks the OS theme
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Emitted when the OS theme flips from light to dark or vice versa
+#[derive(Debug, Clone, Serialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct ThemeChangedEvent {
+    pub dark: bool,
+}
+
+/// Polls [`crate::platform::is_dark_mode`] on [`POLL_INTERVAL`] and emits `theme-changed` when it
+/// changes from its last known value
+pub struct ThemeMonitor {
+    dark: AtomicBool,
+}
+
+impl ThemeMonitor {
+    pub fn new() -> Self {
+        Self { dark: AtomicBool::new(crate::platform::is_dark_mode()) }
+    }
+
+    /// Spawn the background poll loop as a tokio task. Returns immediately; runs for the
+    /// lifetime of the process.
+    pub fn spawn(self: Arc<Self>, app_handle: tauri::AppHandle) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                let dark = crate::platform::is_dark_mode();
+                let changed = self.dark.swap(dark, Ordering::Relaxed) != dark;
+                if changed {
+                    let _ = app_handle.emit_all("theme-changed", ThemeChangedEvent { dark });
+                }
+            }
+        });
+    }
+}
+
+impl Default for ThemeMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,196 @@
+//! Approximate token counting and context-window fitting
+//!
+//! This isn't a real BPE tokenizer (no `tiktoken`-equivalent crate is vendored), so counts are
+//! an approximation based on character length. That's good enough to decide whether a prompt
+//! needs truncating before it's sent and to estimate `tokens_used` for providers (Ollama) that
+//! don't report it - it is not precise enough for billing.
+
+use crate::ai_providers::ChatMessage;
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+const MODEL_CONTEXT_WINDOWS_JSON: &str = include_str!("model_context_windows.json");
+
+/// Context window used for models not found in `model_context_windows.json`
+const DEFAULT_CONTEXT_WINDOW: u32 = 8192;
+
+/// Tokens reserved out of the context window for the model's own reply
+const DEFAULT_RESPONSE_RESERVE: u32 = 1024;
+
+/// Rough characters-per-token ratio for English-language prose, shared by most modern BPE
+/// tokenizers (OpenAI, Anthropic, and Gemini all land close to this)
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Fixed per-message overhead (role marker, separators) added on top of content length
+const TOKENS_PER_MESSAGE_OVERHEAD: u32 = 4;
+
+#[derive(Debug, Deserialize)]
+struct ModelContextWindow {
+    model: String,
+    context_window: u32,
+}
+
+static CONTEXT_WINDOWS: OnceLock<Vec<ModelContextWindow>> = OnceLock::new();
+
+fn context_windows() -> &'static [ModelContextWindow] {
+    CONTEXT_WINDOWS
+        .get_or_init(|| {
+            serde_json::from_str(MODEL_CONTEXT_WINDOWS_JSON)
+                .expect("bundled model_context_windows.json must be valid")
+        })
+        .as_slice()
+}
+
+/// Estimate the number of tokens in a string, by character count
+pub fn estimate_tokens(text: &str) -> u32 {
+    ((text.len() + CHARS_PER_TOKEN - 1) / CHARS_PER_TOKEN) as u32
+}
+
+/// Estimate the total prompt token count for a list of chat messages
+pub fn estimate_prompt_tokens(messages: &[ChatMessage]) -> u32 {
+    messages
+        .iter()
+        .map(|m| estimate_tokens(&m.content) + TOKENS_PER_MESSAGE_OVERHEAD)
+        .sum()
+}
+
+/// Look up a model's context window, matching by prefix against the bundled registry
+/// (e.g. "gpt-4o-2024-08-06" matches the "gpt-4o" entry), falling back to
+/// `DEFAULT_CONTEXT_WINDOW` for unrecognized models
+pub fn context_window_for_model(model: &str) -> u32 {
+    context_windows()
+        .iter()
+        .filter(|entry| model.starts_with(&entry.model))
+        // Prefer the longest (most specific) matching prefix, e.g. "gpt-4o" over "gpt-4"
+        .max_by_key(|entry| entry.model.len())
+        .map(|entry| entry.context_window)
+        .unwrap_or(DEFAULT_CONTEXT_WINDOW)
+}
+
+/// Drop the oldest non-system messages until the remaining prompt fits within `model`'s context
+/// window, minus a reserve for the model's own reply. Always keeps every system message and at
+/// least the most recent user message, even if that alone exceeds the budget. Messages with
+/// `pinned: true` (see [`ChatMessage::pinned`]) are preferred over unpinned ones and are only
+/// dropped once no unpinned message is left to drop instead.
+///
+/// Returns the (possibly truncated) messages and whether any truncation happened.
+pub fn fit_messages_to_context(messages: Vec<ChatMessage>, model: &str) -> (Vec<ChatMessage>, bool) {
+    let budget = context_window_for_model(model).saturating_sub(DEFAULT_RESPONSE_RESERVE);
+
+    if estimate_prompt_tokens(&messages) <= budget {
+        return (messages, false);
+    }
+
+    let (system_messages, mut rest): (Vec<ChatMessage>, Vec<ChatMessage>) =
+        messages.into_iter().partition(|m| m.role == "system");
+
+    let system_tokens = estimate_prompt_tokens(&system_messages);
+    let rest_budget = budget.saturating_sub(system_tokens);
+
+    // Drop oldest-first, but never drop the last remaining message so there's always something
+    // to send. If even that single message doesn't fit, send it anyway - truncating mid-message
+    // would corrupt the conversation in ways a shorter history wouldn't.
+    while rest.len() > 1 && estimate_prompt_tokens(&rest) > rest_budget {
+        let drop_at = rest.iter().position(|m| !m.pinned).unwrap_or(0);
+        rest.remove(drop_at);
+    }
+
+    let mut fitted = system_messages;
+    fitted.extend(rest);
+    (fitted, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens_scales_with_length() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+
+    #[test]
+    fn test_known_model_context_window() {
+        assert_eq!(context_window_for_model("gpt-4o-2024-08-06"), 128000);
+        assert_eq!(context_window_for_model("claude-3-5-sonnet-20241022"), 200000);
+    }
+
+    #[test]
+    fn test_unknown_model_falls_back_to_default() {
+        assert_eq!(context_window_for_model("some-future-model"), DEFAULT_CONTEXT_WINDOW);
+    }
+
+    #[test]
+    fn test_fit_messages_no_truncation_needed() {
+        let messages = vec![ChatMessage {
+            role: "user".to_string(),
+            content: "hello".to_string(),
+            tool_call_id: None,
+            pinned: false,
+        }];
+        let (fitted, truncated) = fit_messages_to_context(messages.clone(), "gpt-4o");
+        assert!(!truncated);
+        assert_eq!(fitted.len(), messages.len());
+    }
+
+    #[test]
+    fn test_fit_messages_drops_oldest_first_but_keeps_system_and_latest() {
+        let mut messages = vec![ChatMessage {
+            role: "system".to_string(),
+            content: "You are a helpful assistant.".to_string(),
+            tool_call_id: None,
+            pinned: false,
+        }];
+        for i in 0..10_000 {
+            messages.push(ChatMessage {
+                role: "user".to_string(),
+                content: format!("padding message {}", i),
+                tool_call_id: None,
+                pinned: false,
+            });
+        }
+        messages.push(ChatMessage {
+            role: "user".to_string(),
+            content: "final question".to_string(),
+            tool_call_id: None,
+            pinned: false,
+        });
+
+        let (fitted, truncated) = fit_messages_to_context(messages, "gpt-3.5-turbo");
+        assert!(truncated);
+        assert_eq!(fitted.first().unwrap().role, "system");
+        assert_eq!(fitted.last().unwrap().content, "final question");
+        assert!(fitted.len() < 10_002);
+    }
+
+    #[test]
+    fn test_fit_messages_keeps_pinned_message_over_newer_unpinned_ones() {
+        let mut messages = vec![ChatMessage {
+            role: "user".to_string(),
+            content: "the important answer".to_string(),
+            tool_call_id: None,
+            pinned: true,
+        }];
+        for i in 0..10_000 {
+            messages.push(ChatMessage {
+                role: "user".to_string(),
+                content: format!("padding message {}", i),
+                tool_call_id: None,
+                pinned: false,
+            });
+        }
+        messages.push(ChatMessage {
+            role: "user".to_string(),
+            content: "final question".to_string(),
+            tool_call_id: None,
+            pinned: false,
+        });
+
+        let (fitted, truncated) = fit_messages_to_context(messages, "gpt-3.5-turbo");
+        assert!(truncated);
+        assert!(fitted.iter().any(|m| m.content == "the important answer"));
+        assert_eq!(fitted.last().unwrap().content, "final question");
+    }
+}
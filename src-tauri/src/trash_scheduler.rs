@@ -0,0 +1,38 @@
+//! Background scheduler that periodically purges conversations trashed longer than
+//! [`crate::database::DatabaseConfig::trash_retention_days`].
+//!
+//! Spawned once from `main.rs`'s `setup()` closure, alongside [`crate::backup_scheduler`] and
+//! [`crate::maintenance_scheduler`]. Runs for the lifetime of the process and is not expected to
+//! be stopped before shutdown.
+
+use crate::database::DatabaseManager;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often the scheduler wakes up to purge expired trash
+const PURGE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Spawn the scheduler as a background tokio task. Returns immediately.
+pub fn spawn(db: Arc<DatabaseManager>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PURGE_INTERVAL);
+        // The first tick fires immediately; skip it so a fresh launch doesn't pay for a purge
+        // pass before anything has had a chance to sit in the trash.
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            run_once(&db);
+        }
+    });
+}
+
+fn run_once(db: &DatabaseManager) {
+    match db.purge_trash(db.config().trash_retention_days) {
+        Ok(purged) if purged > 0 => {
+            tracing::info!("🗑️ Purged {} conversation(s) beyond trash retention", purged)
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("⚠️ Scheduled trash purge failed: {}", e),
+    }
+}
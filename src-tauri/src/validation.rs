@@ -429,6 +429,42 @@ impl InputValidator {
         Ok(trimmed.to_string())
     }
 
+    /// Validate a user-supplied HTTP header name/value pair before it is attached to
+    /// outgoing provider requests, rejecting anything that could smuggle extra headers or
+    /// split the request (CR/LF injection) or contain non-ASCII/control characters.
+    pub fn validate_http_header(&self, name: &str, value: &str) -> AppResult<(String, String)> {
+        let name = name.trim();
+        let value = value.trim();
+
+        if name.is_empty() {
+            return Err(AppError::validation("Header name cannot be empty"));
+        }
+
+        let name_regex = Regex::new(r"^[a-zA-Z0-9!#$%&'*+\-.^_`|~]+$").unwrap();
+        if !name_regex.is_match(name) {
+            return Err(AppError::validation(format!(
+                "Header name '{}' contains invalid characters",
+                name
+            )));
+        }
+
+        if value.contains('\r') || value.contains('\n') || value.bytes().any(|b| b < 0x20 && b != b'\t') {
+            return Err(AppError::validation(format!(
+                "Header value for '{}' contains invalid control characters",
+                name
+            )));
+        }
+
+        if !value.is_ascii() {
+            return Err(AppError::validation(format!(
+                "Header value for '{}' must be ASCII",
+                name
+            )));
+        }
+
+        Ok((name.to_string(), value.to_string()))
+    }
+
     /// Validate email format (for contact information)
     pub fn validate_email(&self, email: &str) -> AppResult<String> {
         let trimmed = email.trim();
@@ -0,0 +1,320 @@
+//! Outbound webhook notifications fired when a new assistant message arrives, so a long-running
+//! prompt can ping a phone (e.g. via ntfy) or trigger a downstream automation.
+//!
+//! Payloads are signed with HMAC-SHA256 over the raw JSON body (the `X-Signature` header, as a
+//! hex digest) using `ring::hmac`, the same crate [`crate::compliance_export`] already uses for
+//! its hash chain, rather than pulling in a dedicated HMAC crate - so a receiver can confirm a
+//! webhook actually came from this app and wasn't forged or altered in transit.
+
+use crate::ai_providers::RetryPolicy;
+use crate::errors::{AppError, AppResult};
+use crate::models::Message;
+use chrono::{DateTime, Utc};
+use ring::hmac;
+use std::sync::Arc;
+
+/// Header carrying the hex-encoded HMAC-SHA256 signature of the request body
+pub const SIGNATURE_HEADER: &str = "X-Signature";
+
+/// Retry behavior for webhook delivery - more persistent than
+/// [`RetryPolicy::default`]'s single attempt, since a missed notification can't be recovered by
+/// the user retrying the way a failed AI request can
+const WEBHOOK_RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_attempts: 3,
+    initial_backoff_ms: 1_000,
+    max_backoff_ms: 10_000,
+};
+
+/// What a webhook is registered against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookScope {
+    /// Fires for assistant messages in one specific conversation
+    Conversation,
+    /// Fires for assistant messages in any conversation carrying this tag - see
+    /// `ConversationService::analyze_conversation`
+    Tag,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Webhook {
+    pub id: String,
+    pub scope: WebhookScope,
+    /// A conversation id (as a string) when `scope` is `Conversation`, or a tag name when it's `Tag`
+    pub scope_value: String,
+    pub url: String,
+    /// Shared secret used to HMAC-sign delivered payloads; never returned to the frontend after
+    /// registration - see `crate::commands::list_webhooks`
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body POSTed to a webhook when a new assistant message arrives
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WebhookPayload {
+    pub conversation_id: i64,
+    pub message_id: i64,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Hex-encoded HMAC-SHA256 of `body` using `secret` as the key
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::sign(&key, body)
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Constant-time check that `signature` (hex-encoded, as produced by [`sign_payload`]) is the
+/// correct HMAC-SHA256 of `body` under `secret` - callers that need to verify a signature they
+/// didn't just compute themselves should use this instead of comparing two [`sign_payload`]
+/// outputs with `==`/`!=`, which leaks timing information about where the strings first differ
+pub fn verify_payload(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Ok(signature_bytes) = hex_decode(signature) else {
+        return false;
+    };
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::verify(&key, body, &signature_bytes).is_ok()
+}
+
+fn hex_decode(hex: &str) -> Result<Vec<u8>, ()> {
+    if hex.len() % 2 != 0 {
+        return Err(());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| ()))
+        .collect()
+}
+
+/// POST `payload` to `url`, signed with `secret`, retrying per `policy` on connect/timeout
+/// errors and 429/5xx responses. Other 4xx responses (bad secret, dead endpoint, malformed
+/// body) are permanent failures - they return immediately without burning the rest of
+/// `policy.max_attempts` on a request that will never succeed.
+async fn deliver(url: &str, secret: &str, payload: &WebhookPayload, policy: &RetryPolicy) -> AppResult<()> {
+    let body = serde_json::to_vec(payload)
+        .map_err(|e| AppError::validation(format!("Failed to serialize webhook payload: {}", e)))?;
+    let signature = sign_payload(secret, &body);
+
+    let client = reqwest::Client::new();
+    let attempts = policy.max_attempts.max(1);
+    let mut backoff_ms = policy.initial_backoff_ms;
+    let mut last_error = String::new();
+    let mut made = 0;
+
+    for attempt in 1..=attempts {
+        made = attempt;
+        let result = client
+            .post(url)
+            .header(SIGNATURE_HEADER, &signature)
+            .header("Content-Type", "application/json")
+            .body(body.clone())
+            .send()
+            .await;
+
+        let retryable;
+        match result {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) => {
+                let status = response.status();
+                retryable = status.as_u16() == 429 || status.is_server_error();
+                last_error = format!("webhook responded with status {}", status);
+            }
+            Err(e) => {
+                retryable = true;
+                last_error = e.to_string();
+            }
+        }
+
+        if !retryable {
+            break;
+        }
+
+        if attempt < attempts {
+            tokio::time::sleep(std::time::Duration::from_millis(backoff_ms)).await;
+            backoff_ms = (backoff_ms * 2).min(policy.max_backoff_ms);
+        }
+    }
+
+    Err(AppError::api(format!(
+        "Failed to deliver webhook to {} after {} attempt(s): {}",
+        url, made, last_error
+    )))
+}
+
+/// Find webhooks registered for `conversation_id` directly, or for any of its analyzed tags, and
+/// deliver `message` to each
+///
+/// Delivery failures are logged and otherwise swallowed - this is a best-effort notification
+/// side channel, not something that should fail the message that triggered it. Intended to be
+/// called from a spawned task (see `crate::commands::add_message`) rather than awaited inline.
+pub async fn notify_assistant_message(db: &Arc<crate::database::DatabaseManager>, conversation_id: i64, message: &Message) {
+    let webhook_service = crate::services::WebhookService::new(db.clone());
+    let conversation_service = crate::services::ConversationService::new(db.clone());
+
+    let tags = conversation_service
+        .get_conversation_analysis(conversation_id)
+        .ok()
+        .flatten()
+        .map(|analysis| analysis.tags)
+        .unwrap_or_default();
+
+    let webhooks = match webhook_service.find_matching(conversation_id, &tags) {
+        Ok(webhooks) => webhooks,
+        Err(e) => {
+            tracing::warn!("Failed to look up webhooks for conversation {}: {}", conversation_id, e);
+            return;
+        }
+    };
+
+    if webhooks.is_empty() {
+        return;
+    }
+
+    let payload = WebhookPayload {
+        conversation_id,
+        message_id: message.id.unwrap_or_default(),
+        content: message.content.clone(),
+        created_at: message.created_at,
+    };
+
+    for webhook in webhooks {
+        if let Err(e) = deliver(&webhook.url, &webhook.secret, &payload, &WEBHOOK_RETRY_POLICY).await {
+            tracing::warn!("Webhook {} delivery failed: {}", webhook.id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_sign_payload_is_deterministic() {
+        let signature_a = sign_payload("secret", b"hello");
+        let signature_b = sign_payload("secret", b"hello");
+        assert_eq!(signature_a, signature_b);
+        assert_ne!(signature_a, sign_payload("other-secret", b"hello"));
+        assert_ne!(signature_a, sign_payload("secret", b"goodbye"));
+    }
+
+    #[test]
+    fn test_verify_payload_accepts_a_matching_signature() {
+        let signature = sign_payload("secret", b"hello");
+        assert!(verify_payload("secret", b"hello", &signature));
+    }
+
+    #[test]
+    fn test_verify_payload_rejects_tampered_body_or_secret() {
+        let signature = sign_payload("secret", b"hello");
+        assert!(!verify_payload("secret", b"goodbye", &signature));
+        assert!(!verify_payload("other-secret", b"hello", &signature));
+    }
+
+    #[test]
+    fn test_verify_payload_rejects_malformed_signature() {
+        assert!(!verify_payload("secret", b"hello", "not-hex-at-all"));
+        assert!(!verify_payload("secret", b"hello", "abc"));
+    }
+
+    /// Serves `statuses.len()` sequential HTTP responses on an ephemeral local port, one per
+    /// accepted connection, then stops. Returns the server's base URL and a counter of how many
+    /// requests it actually received, so a caller can confirm a retrying client made more than
+    /// one attempt instead of just trusting the end-to-end result.
+    fn spawn_mock_server(statuses: Vec<u16>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind mock server");
+        let addr = listener.local_addr().expect("Failed to read mock server address");
+        let received = Arc::new(AtomicUsize::new(0));
+        let received_clone = received.clone();
+
+        std::thread::spawn(move || {
+            for status in statuses {
+                let Ok((mut stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf);
+                received_clone.fetch_add(1, Ordering::SeqCst);
+
+                let reason = if status == 200 { "OK" } else { "Error" };
+                let response = format!(
+                    "HTTP/1.1 {} {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status, reason
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        (format!("http://{}", addr), received)
+    }
+
+    #[tokio::test]
+    async fn test_deliver_retries_on_5xx_then_succeeds() {
+        let (url, received) = spawn_mock_server(vec![503, 200]);
+        let payload = WebhookPayload {
+            conversation_id: 1,
+            message_id: 1,
+            content: "hi".to_string(),
+            created_at: Utc::now(),
+        };
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            initial_backoff_ms: 10,
+            max_backoff_ms: 20,
+        };
+
+        let result = deliver(&url, "secret", &payload, &policy).await;
+        assert!(result.is_ok(), "expected delivery to succeed after retrying: {:?}", result);
+        assert_eq!(received.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_deliver_gives_up_after_max_attempts() {
+        let (url, received) = spawn_mock_server(vec![429, 429]);
+        let payload = WebhookPayload {
+            conversation_id: 1,
+            message_id: 1,
+            content: "hi".to_string(),
+            created_at: Utc::now(),
+        };
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            initial_backoff_ms: 10,
+            max_backoff_ms: 20,
+        };
+
+        let result = deliver(&url, "secret", &payload, &policy).await;
+        assert!(result.is_err());
+        assert_eq!(received.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_deliver_fails_fast_on_permanent_4xx() {
+        // Only one response queued - if deliver retried this, the second request would hang
+        // waiting for a connection the mock server never accepts, and the test would time out.
+        let (url, received) = spawn_mock_server(vec![401]);
+        let payload = WebhookPayload {
+            conversation_id: 1,
+            message_id: 1,
+            content: "hi".to_string(),
+            created_at: Utc::now(),
+        };
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            initial_backoff_ms: 10,
+            max_backoff_ms: 20,
+        };
+
+        let result = deliver(&url, "secret", &payload, &policy).await;
+        assert!(result.is_err());
+        assert_eq!(received.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,131 @@
+//! Detachable per-conversation windows
+//!
+//! Each window opened by [`crate::commands::open_conversation_window`] gets its own OS window
+//! showing a single conversation, labeled `conversation_{id}` (or `conversation_{id}_{n}` if
+//! that conversation is already popped out more than once). A process-wide registry maps each
+//! such label back to its conversation id - not stored on the `Window` itself, since Tauri has
+//! no typed per-window state slot - so events about a conversation can be routed only to the
+//! window(s) actually showing it instead of broadcast to every window.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Manager, WindowBuilder, WindowUrl};
+
+fn registry() -> &'static Mutex<HashMap<String, i64>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<String, i64>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// One open conversation window, as reported by [`crate::commands::list_windows`]
+#[derive(Debug, Clone, serde::Serialize, ts_rs::TS)]
+#[ts(export, export_to = "../src/lib/types/generated/")]
+pub struct WindowInfo {
+    pub label: String,
+    pub conversation_id: i64,
+}
+
+/// Open a new window showing `conversation_id`, or focus its existing window if it's already
+/// open. Returns the window's label.
+pub fn open_conversation_window(app: &AppHandle, conversation_id: i64) -> Result<String, String> {
+    let mut registry = registry().lock().unwrap();
+
+    if let Some((label, _)) = registry.iter().find(|(_, id)| **id == conversation_id) {
+        let label = label.clone();
+        if let Some(window) = app.get_window(&label) {
+            let _ = window.show();
+            let _ = window.set_focus();
+            return Ok(label);
+        }
+        // The window was closed without its close handler clearing the registry (e.g. the
+        // process was killed) - fall through and open a fresh one under a new label.
+        registry.remove(&label);
+    }
+
+    let label = unique_label(&registry, conversation_id);
+    WindowBuilder::new(
+        app,
+        &label,
+        WindowUrl::App(format!("conversation/{}", conversation_id).into()),
+    )
+    .title(format!("Conversation #{}", conversation_id))
+    .inner_size(900.0, 700.0)
+    .build()
+    .map_err(|e| format!("Failed to open conversation window: {}", e))?;
+
+    registry.insert(label.clone(), conversation_id);
+
+    let closed_label = label.clone();
+    if let Some(window) = app.get_window(&label) {
+        window.on_window_event(move |event| {
+            if let tauri::WindowEvent::Destroyed = event {
+                registry().lock().unwrap().remove(&closed_label);
+            }
+        });
+    }
+
+    Ok(label)
+}
+
+/// `conversation_{id}`, or `conversation_{id}_{n}` for the first `n` not already taken
+fn unique_label(registry: &HashMap<String, i64>, conversation_id: i64) -> String {
+    let base = format!("conversation_{}", conversation_id);
+    if !registry.contains_key(&base) {
+        return base;
+    }
+    (2..).map(|n| format!("{}_{}", base, n)).find(|label| !registry.contains_key(label)).unwrap()
+}
+
+/// List every currently open conversation window
+pub fn list_windows() -> Vec<WindowInfo> {
+    registry()
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(label, conversation_id)| WindowInfo {
+            label: label.clone(),
+            conversation_id: *conversation_id,
+        })
+        .collect()
+}
+
+/// Bring a window to the front by label
+pub fn focus_window(app: &AppHandle, label: &str) -> Result<(), String> {
+    let window = app
+        .get_window(label)
+        .ok_or_else(|| format!("No window with label '{}'", label))?;
+    window
+        .show()
+        .map_err(|e| format!("Failed to show window '{}': {}", label, e))?;
+    window
+        .set_focus()
+        .map_err(|e| format!("Failed to focus window '{}': {}", label, e))
+}
+
+/// Emit `event` only to windows currently showing `conversation_id`, falling back to every
+/// window if none of them have their own (e.g. it's only open in the main window, which isn't
+/// tracked in the registry)
+pub fn emit_to_conversation<S: serde::Serialize + Clone>(
+    app: &AppHandle,
+    conversation_id: i64,
+    event: &str,
+    payload: S,
+) {
+    let labels: Vec<String> = registry()
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(_, id)| **id == conversation_id)
+        .map(|(label, _)| label.clone())
+        .collect();
+
+    if labels.is_empty() {
+        let _ = app.emit_all(event, payload);
+        return;
+    }
+
+    for label in labels {
+        if let Some(window) = app.get_window(&label) {
+            let _ = window.emit(event, payload.clone());
+        }
+    }
+}